@@ -1,144 +1,331 @@
 //! API authentication and authorization
 
 use axum::{
-    extract::{Request, State},
-    http::header,
+    extract::{FromRequestParts, RawPathParams, Request, State},
+    http::{header, request::Parts},
     middleware::Next,
     response::Response,
 };
-use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
 use crate::error::ApiError;
+use crate::session;
 use crate::state::AppState;
+use crate::token_cache::GitHubIdentity;
+use greport_core::{GitHubApi, OctocrabClient};
+use greport_db::hash_api_key;
 
-/// Hash an API key for storage/lookup
-pub fn hash_api_key(key: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(key.as_bytes());
-    hex::encode(hasher.finalize())
+tokio::task_local! {
+    /// GitHub client using the caller's own token, set for the duration of a
+    /// request when `use_caller_token` is enabled
+    pub static CALLER_CLIENT: Arc<dyn GitHubApi>;
 }
 
 /// Authenticated user info extracted from request
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct AuthInfo {
     pub owner: String,
     pub scopes: Vec<String>,
     pub rate_limit: i32,
+    /// Login of a passthrough token or a (non-admin) login session; set
+    /// when the caller is limited to its own account and organizations
+    pub github_login: Option<String>,
+    /// Organization memberships, for verified passthrough tokens and login
+    /// sessions
+    pub github_orgs: Vec<String>,
+    /// API key ID, when authenticated with a key
+    pub key_id: Option<uuid::Uuid>,
 }
 
-/// Authentication middleware
-/// Extracts and validates API key from Authorization header
-#[allow(dead_code)]
-pub async fn auth_middleware(
-    State(state): State<AppState>,
-    mut request: Request,
-    next: Next,
-) -> Result<Response, ApiError> {
-    // Get Authorization header
-    let auth_header = request
+impl AuthInfo {
+    /// Check whether the key grants `scope`
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|s| scope.granted_by(s))
+    }
+
+    /// Organizations the key is restricted to (`org:<name>` scopes).
+    ///
+    /// An empty list means the key is not restricted.
+    pub fn allowed_orgs(&self) -> Vec<&str> {
+        self.scopes
+            .iter()
+            .filter_map(|s| s.strip_prefix("org:"))
+            .collect()
+    }
+
+    /// Whether a passthrough caller's GitHub account is `owner` or belongs to
+    /// it. Always true for API keys.
+    pub fn can_see_owner(&self, owner: &str) -> bool {
+        match self.github_login {
+            Some(ref login) => {
+                login.eq_ignore_ascii_case(owner)
+                    || self
+                        .github_orgs
+                        .iter()
+                        .any(|o| o.eq_ignore_ascii_case(owner))
+            }
+            None => true,
+        }
+    }
+}
+
+/// Caller of a route, for routes that span organizations (`/repos`,
+/// `/orgs`, `/aggregate/*`) and so filter their results instead of being
+/// checked against an `{owner}` path parameter
+#[derive(Debug, Clone, Default)]
+pub struct Caller(pub Option<Arc<AuthInfo>>);
+
+impl Caller {
+    /// Whether the caller may see data belonging to `owner`.
+    ///
+    /// Synced data was fetched with the server's credentials, so passthrough
+    /// callers only see their own account and organizations.
+    pub fn can_see_owner(&self, owner: &str) -> bool {
+        self.0.as_ref().is_none_or(|auth| auth.can_see_owner(owner))
+    }
+
+    /// Whether the caller may see the repository `owner/name`
+    pub fn can_see_repo(&self, full_name: &str) -> bool {
+        let owner = full_name.split('/').next().unwrap_or_default();
+        self.can_see_owner(owner)
+    }
+
+    /// Whether results depend on who is asking (a passthrough caller)
+    pub fn is_filtered(&self) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(|auth| auth.github_login.is_some())
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Caller {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(parts.extensions.get::<Arc<AuthInfo>>().cloned()))
+    }
+}
+
+/// Access level required by a route.
+///
+/// `admin` (or `*`) grants every scope and `sync` also grants `read`.
+/// Keys may additionally carry `org:<name>` scopes, which limit them to
+/// routes for those organizations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Read reports and data
+    Read,
+    /// Trigger syncs and manage tracked repositories
+    Sync,
+    /// Manage API keys
+    Admin,
+}
+
+impl Scope {
+    /// Scope name as stored on API keys
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Sync => "sync",
+            Scope::Admin => "admin",
+        }
+    }
+
+    fn granted_by(&self, scope: &str) -> bool {
+        match scope {
+            "*" | "admin" => true,
+            "sync" => matches!(self, Scope::Read | Scope::Sync),
+            "read" => *self == Scope::Read,
+            _ => false,
+        }
+    }
+}
+
+/// Check if a bearer token looks like a GitHub token (passthrough mode)
+fn is_github_token(token: &str) -> bool {
+    token.starts_with("ghp_") || token.starts_with("gho_") || token.starts_with("github_pat_")
+}
+
+/// Get the bearer token from the Authorization header, falling back to the
+/// login session cookie
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
         .headers()
         .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok());
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .or_else(|| session::cookie_value(request.headers(), session::SESSION_COOKIE))
+}
 
-    let token = match auth_header {
-        Some(h) if h.starts_with("Bearer ") => &h[7..],
-        _ => {
+/// Verify a GitHub token against `/user`, consulting the verified-token cache
+async fn verify_github_token(
+    state: &AppState,
+    token: &str,
+) -> Result<Arc<GitHubIdentity>, ApiError> {
+    let token_hash = hash_api_key(token);
+    if let Some(cached) = state.token_cache.get(&token_hash).await {
+        return cached.ok_or(ApiError::Unauthorized);
+    }
+
+    let client = OctocrabClient::new(token, state.registry.default_base_url())
+        .map_err(|_| ApiError::Unauthorized)?;
+    let user = match client.authenticated_user().await {
+        Ok(user) => user,
+        Err(greport_core::Error::InvalidToken) => {
+            state.token_cache.insert(token_hash, None).await;
             return Err(ApiError::Unauthorized);
         }
+        Err(e) => {
+            tracing::warn!("Could not verify GitHub token: {}", e);
+            return Err(ApiError::Internal(
+                "Authentication service unavailable".to_string(),
+            ));
+        }
     };
+    let orgs = client.authenticated_user_orgs().await.unwrap_or_else(|e| {
+        tracing::debug!(login = %user.login, "Could not list organizations: {}", e);
+        Vec::new()
+    });
+
+    tracing::debug!(login = %user.login, orgs = orgs.len(), "Verified GitHub token");
+    let identity = Arc::new(GitHubIdentity {
+        login: user.login,
+        orgs,
+        client: Arc::new(client),
+    });
+    state
+        .token_cache
+        .insert(token_hash, Some(identity.clone()))
+        .await;
+    Ok(identity)
+}
 
-    // Check if it's a GitHub token (passthrough mode)
-    if token.starts_with("ghp_") || token.starts_with("gho_") || token.starts_with("github_pat_") {
-        // GitHub token - allow through without DB lookup
+/// Resolve a bearer token to auth info.
+///
+/// GitHub tokens are verified with GitHub and login sessions by their
+/// signature; both fail with `Unauthorized` when rejected. API keys are looked up in the database; `Ok(None)` means the
+/// key is unknown or there is no database.
+async fn resolve_token(
+    state: &AppState,
+    token: &str,
+) -> Result<Option<(AuthInfo, Option<Arc<GitHubIdentity>>)>, ApiError> {
+    if is_github_token(token) {
+        let identity = verify_github_token(state, token).await?;
         let auth_info = AuthInfo {
-            owner: "github_user".to_string(),
+            owner: identity.login.clone(),
             scopes: vec!["read".to_string()],
             rate_limit: 60,
+            github_login: Some(identity.login.clone()),
+            github_orgs: identity.orgs.clone(),
+            key_id: None,
         };
-        request.extensions_mut().insert(Arc::new(auth_info));
-        return Ok(next.run(request).await);
+        return Ok(Some((auth_info, Some(identity))));
+    }
+
+    if let Some(ref oauth) = state.oauth {
+        if session::is_session_token(token) {
+            let claims = oauth.sessions.verify(token).ok_or(ApiError::Unauthorized)?;
+            // Sessions only see the orgs recorded at login; admins see all
+            let is_admin = claims.scopes.iter().any(|s| s == "admin");
+            let auth_info = AuthInfo {
+                github_login: (!is_admin).then(|| claims.sub.clone()),
+                owner: claims.sub,
+                scopes: claims.scopes,
+                rate_limit: state.config.rate_limit_per_minute as i32,
+                github_orgs: claims.orgs,
+                key_id: None,
+            };
+            return Ok(Some((auth_info, None)));
+        }
     }
 
-    // Hash the API key and look it up
+    let Some(ref pool) = state.db else {
+        return Ok(None);
+    };
     let key_hash = hash_api_key(token);
+    match greport_db::queries::get_api_key_by_hash(pool, &key_hash).await {
+        Ok(Some(api_key)) => {
+            // Update last used timestamp
+            let _ = greport_db::queries::update_api_key_last_used(pool, api_key.id).await;
+            let auth_info = AuthInfo {
+                owner: api_key.owner,
+                scopes: api_key.scopes,
+                rate_limit: api_key.rate_limit,
+                github_login: None,
+                github_orgs: Vec::new(),
+                key_id: Some(api_key.id),
+            };
+            Ok(Some((auth_info, None)))
+        }
+        Ok(None) => Ok(None),
+        Err(e) => {
+            tracing::error!("Database error during auth: {:?}", e);
+            Err(ApiError::Internal(
+                "Authentication service unavailable".to_string(),
+            ))
+        }
+    }
+}
 
-    // Only validate if we have a database connection
-    if let Some(ref pool) = state.db {
-        match greport_db::queries::get_api_key_by_hash(pool, &key_hash).await {
-            Ok(Some(api_key)) => {
-                // Update last used timestamp
-                let _ = greport_db::queries::update_api_key_last_used(pool, api_key.id).await;
-
-                let auth_info = AuthInfo {
-                    owner: api_key.owner,
-                    scopes: api_key.scopes,
-                    rate_limit: api_key.rate_limit,
-                };
-                request.extensions_mut().insert(Arc::new(auth_info));
-            }
-            Ok(None) => {
-                return Err(ApiError::Unauthorized);
-            }
-            Err(e) => {
-                tracing::error!("Database error during auth: {:?}", e);
-                return Err(ApiError::Internal(
-                    "Authentication service unavailable".to_string(),
-                ));
-            }
+/// Attach auth info to the request and run it, with the caller's GitHub
+/// client in scope when `use_caller_token` is enabled
+async fn run_authenticated(
+    state: &AppState,
+    mut request: Request,
+    next: Next,
+    auth_info: AuthInfo,
+    identity: Option<Arc<GitHubIdentity>>,
+) -> Response {
+    request.extensions_mut().insert(Arc::new(auth_info));
+    match identity {
+        Some(identity) if state.config.use_caller_token => {
+            CALLER_CLIENT
+                .scope(
+                    identity.client.clone() as Arc<dyn GitHubApi>,
+                    next.run(request),
+                )
+                .await
         }
-    } else {
-        // No database - reject API keys (only GitHub tokens allowed)
-        return Err(ApiError::Unauthorized);
+        _ => next.run(request).await,
     }
+}
 
-    Ok(next.run(request).await)
+/// Authentication middleware
+/// Extracts and validates API key from Authorization header
+#[allow(dead_code)]
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let token = bearer_token(&request).ok_or(ApiError::Unauthorized)?;
+    let (auth_info, identity) = resolve_token(&state, token)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+
+    Ok(run_authenticated(&state, request, next, auth_info, identity).await)
 }
 
 /// Optional auth middleware - allows unauthenticated requests but extracts auth info if present
+///
+/// GitHub tokens that fail verification are rejected rather than treated as
+/// anonymous.
 pub async fn optional_auth_middleware(
     State(state): State<AppState>,
-    mut request: Request,
+    request: Request,
     next: Next,
-) -> Response {
-    // Get Authorization header
-    let auth_header = request
-        .headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok());
-
-    if let Some(h) = auth_header {
-        if let Some(token) = h.strip_prefix("Bearer ") {
-            // Check if it's a GitHub token
-            if token.starts_with("ghp_")
-                || token.starts_with("gho_")
-                || token.starts_with("github_pat_")
-            {
-                let auth_info = AuthInfo {
-                    owner: "github_user".to_string(),
-                    scopes: vec!["read".to_string()],
-                    rate_limit: 60,
-                };
-                request.extensions_mut().insert(Arc::new(auth_info));
-            } else if let Some(ref pool) = state.db {
-                let key_hash = hash_api_key(token);
-                if let Ok(Some(api_key)) =
-                    greport_db::queries::get_api_key_by_hash(pool, &key_hash).await
-                {
-                    let _ = greport_db::queries::update_api_key_last_used(pool, api_key.id).await;
-                    let auth_info = AuthInfo {
-                        owner: api_key.owner,
-                        scopes: api_key.scopes,
-                        rate_limit: api_key.rate_limit,
-                    };
-                    request.extensions_mut().insert(Arc::new(auth_info));
-                }
-            }
-        }
-    }
+) -> Result<Response, ApiError> {
+    let resolved = match bearer_token(&request) {
+        Some(token) => resolve_token(&state, token).await?,
+        None => None,
+    };
 
-    next.run(request).await
+    Ok(match resolved {
+        Some((auth_info, identity)) => {
+            run_authenticated(&state, request, next, auth_info, identity).await
+        }
+        None => next.run(request).await,
+    })
 }
 
 /// Extract auth info from request extensions
@@ -147,11 +334,183 @@ pub fn get_auth_info(request: &Request) -> Option<Arc<AuthInfo>> {
     request.extensions().get::<Arc<AuthInfo>>().cloned()
 }
 
-/// Check if user has required scope
-#[allow(dead_code)]
-pub fn has_scope(auth_info: &AuthInfo, required_scope: &str) -> bool {
-    auth_info
-        .scopes
+/// Decide whether a caller may use a route.
+///
+/// Anonymous callers are allowed `read` unless `require_auth` is set, and
+/// never `sync` or `admin`. `org` is the organization in the route path;
+/// routes without one span all organizations and need an unrestricted key.
+pub fn check_access(
+    auth_info: Option<&AuthInfo>,
+    scope: Scope,
+    org: Option<&str>,
+    require_auth: bool,
+) -> Result<(), ApiError> {
+    let Some(auth_info) = auth_info else {
+        if require_auth || scope != Scope::Read {
+            return Err(ApiError::Unauthorized);
+        }
+        return Ok(());
+    };
+
+    if !auth_info.has_scope(scope) {
+        return Err(ApiError::Forbidden(format!(
+            "Missing required scope '{}'",
+            scope.as_str()
+        )));
+    }
+
+    let allowed = auth_info.allowed_orgs();
+    if allowed.is_empty() {
+        return Ok(());
+    }
+    match org {
+        Some(org) if allowed.iter().any(|a| a.eq_ignore_ascii_case(org)) => Ok(()),
+        Some(org) => Err(ApiError::Forbidden(format!(
+            "Missing required scope 'org:{}'",
+            org
+        ))),
+        None => Err(ApiError::Forbidden(
+            "Route spans all organizations; key is restricted by org: scopes".to_string(),
+        )),
+    }
+}
+
+async fn authorize(
+    state: &AppState,
+    scope: Scope,
+    params: &RawPathParams,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let org = params
         .iter()
-        .any(|s| s == required_scope || s == "*")
+        .find(|(name, _)| *name == "owner" || *name == "org")
+        .map(|(_, value)| value);
+    let auth_info = request.extensions().get::<Arc<AuthInfo>>();
+    check_access(
+        auth_info.map(|a| a.as_ref()),
+        scope,
+        org,
+        state.config.require_auth,
+    )?;
+
+    // Passthrough callers only see their own orgs, even with
+    // `use_caller_token`: database-backed routes answer from synced data
+    // that GitHub never checks against the caller's token.
+    if let (Some(auth_info), Some(org)) = (auth_info, org) {
+        if !auth_info.can_see_owner(org) {
+            return Err(ApiError::Forbidden(format!(
+                "User '{}' is not a member of '{}'",
+                auth_info.github_login.as_deref().unwrap_or_default(),
+                org
+            )));
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+/// Route layer requiring the `read` scope
+pub async fn require_read(
+    State(state): State<AppState>,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    authorize(&state, Scope::Read, &params, request, next).await
+}
+
+/// Route layer requiring the `sync` scope
+pub async fn require_sync(
+    State(state): State<AppState>,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    authorize(&state, Scope::Sync, &params, request, next).await
+}
+
+/// Route layer requiring the `admin` scope
+pub async fn require_admin(
+    State(state): State<AppState>,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    authorize(&state, Scope::Admin, &params, request, next).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(scopes: &[&str]) -> AuthInfo {
+        AuthInfo {
+            owner: "test".to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            rate_limit: 60,
+            github_login: None,
+            github_orgs: Vec::new(),
+            key_id: None,
+        }
+    }
+
+    #[test]
+    fn test_scope_hierarchy() {
+        assert!(auth(&["read"]).has_scope(Scope::Read));
+        assert!(!auth(&["read"]).has_scope(Scope::Sync));
+        assert!(auth(&["sync"]).has_scope(Scope::Read));
+        assert!(!auth(&["sync"]).has_scope(Scope::Admin));
+        assert!(auth(&["admin"]).has_scope(Scope::Sync));
+        assert!(auth(&["*"]).has_scope(Scope::Admin));
+        assert!(!auth(&["org:acme"]).has_scope(Scope::Read));
+    }
+
+    #[test]
+    fn test_check_access_anonymous() {
+        assert!(check_access(None, Scope::Read, None, false).is_ok());
+        assert!(matches!(
+            check_access(None, Scope::Sync, Some("acme"), false),
+            Err(ApiError::Unauthorized)
+        ));
+        assert!(matches!(
+            check_access(None, Scope::Admin, None, false),
+            Err(ApiError::Unauthorized)
+        ));
+        assert!(matches!(
+            check_access(None, Scope::Read, None, true),
+            Err(ApiError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn test_check_access_org_restriction() {
+        let key = auth(&["read", "org:acme"]);
+        assert!(check_access(Some(&key), Scope::Read, Some("ACME"), true).is_ok());
+
+        match check_access(Some(&key), Scope::Read, Some("other"), true) {
+            Err(ApiError::Forbidden(msg)) => assert!(msg.contains("org:other")),
+            other => panic!("expected forbidden, got {:?}", other),
+        }
+        assert!(matches!(
+            check_access(Some(&key), Scope::Read, None, true),
+            Err(ApiError::Forbidden(_))
+        ));
+
+        match check_access(Some(&key), Scope::Sync, Some("acme"), true) {
+            Err(ApiError::Forbidden(msg)) => assert!(msg.contains("'sync'")),
+            other => panic!("expected forbidden, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_passthrough_owner_visibility() {
+        let mut info = auth(&["read"]);
+        assert!(info.can_see_owner("anyone"));
+
+        info.github_login = Some("octocat".to_string());
+        info.github_orgs = vec!["Acme".to_string()];
+        assert!(info.can_see_owner("octocat"));
+        assert!(info.can_see_owner("acme"));
+        assert!(!info.can_see_owner("other"));
+    }
 }
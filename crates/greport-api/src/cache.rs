@@ -0,0 +1,250 @@
+//! Response cache for read routes
+//!
+//! Successful `GET` responses are stored in the `cache_metadata` table,
+//! keyed by route, normalized query and sync generation. Repository routes
+//! use the repository's generation and other routes the global one, so a
+//! re-sync changes the key and stale entries simply expire. Every response
+//! carries an `ETag`, and a matching `If-None-Match` gets `304 Not Modified`.
+
+use axum::{
+    body::Body,
+    extract::{RawPathParams, Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use greport_db::DbPool;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::auth::{AuthInfo, CALLER_CLIENT};
+use crate::state::AppState;
+
+/// Largest response body that is cached
+const MAX_CACHED_BODY: usize = 8 * 1024 * 1024;
+
+/// `cache_metadata.data_type` for cached responses
+const DATA_TYPE: &str = "response";
+
+/// Header reporting whether the response came from the cache
+const X_CACHE: &str = "x-cache";
+
+/// Cache key for a request: path, sorted query pairs and sync generation
+pub fn cache_key(path: &str, query: Option<&str>, generation: i64) -> String {
+    let mut pairs: Vec<&str> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .collect();
+    pairs.sort_unstable();
+
+    let canonical = format!("{}?{}#{}", path, pairs.join("&"), generation);
+    format!("{}:{}", DATA_TYPE, hex::encode(Sha256::digest(canonical)))
+}
+
+/// Strong entity tag for a response body
+pub fn etag_for(body: &[u8]) -> String {
+    let digest = hex::encode(Sha256::digest(body));
+    format!("\"{}\"", &digest[..32])
+}
+
+/// Whether `If-None-Match` matches the entity tag
+fn not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .map(|t| t.trim().trim_start_matches("W/"))
+                .any(|t| t == etag || t == "*")
+        })
+}
+
+/// Build the response for a body, honouring `If-None-Match`
+fn respond(
+    request_headers: &HeaderMap,
+    mut parts: axum::http::response::Parts,
+    body: Body,
+    etag: &str,
+    ttl: u64,
+    cache_status: &'static str,
+) -> Response {
+    let headers = &mut parts.headers;
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&format!("private, max-age={}", ttl)) {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+    headers.insert(X_CACHE, HeaderValue::from_static(cache_status));
+
+    if not_modified(request_headers, etag) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        headers.remove(header::CONTENT_LENGTH);
+        return Response::from_parts(parts, Body::empty());
+    }
+    Response::from_parts(parts, body)
+}
+
+/// Sync generation for the request's repository (or the global one)
+async fn generation(pool: &DbPool, params: &RawPathParams) -> Option<i64> {
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    };
+    let full_name = match (param("owner"), param("repo")) {
+        (Some(owner), Some(repo)) => Some(format!("{}/{}", owner, repo)),
+        _ => None,
+    };
+
+    greport_db::queries::get_sync_generation(pool, full_name.as_deref())
+        .await
+        .map_err(|e| tracing::warn!("Could not read sync generation: {}", e))
+        .ok()
+}
+
+/// Route layer serving cached `GET` responses
+pub async fn response_cache(
+    State(state): State<AppState>,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let ttl = state.config.cache_ttl_seconds;
+    let request_headers = request.headers().clone();
+
+    // Responses fetched with a caller's own GitHub token are not shared, nor
+    // are routes spanning organizations, which passthrough callers only see
+    // filtered to their own orgs
+    let spans_orgs = !params
+        .iter()
+        .any(|(name, _)| name == "owner" || name == "org");
+    let passthrough = request
+        .extensions()
+        .get::<Arc<AuthInfo>>()
+        .is_some_and(|auth| auth.github_login.is_some());
+    let shared = ttl > 0 && CALLER_CLIENT.try_with(|_| ()).is_err() && !(spans_orgs && passthrough);
+    let pool = state.db.as_ref().filter(|_| shared);
+
+    let key = match pool {
+        Some(pool) => generation(pool, &params)
+            .await
+            .map(|generation| cache_key(request.uri().path(), request.uri().query(), generation)),
+        None => None,
+    };
+
+    if let (Some(pool), Some(key)) = (pool, key.as_deref()) {
+        match greport_db::queries::get_cache_entry(pool, key).await {
+            Ok(Some(entry)) => {
+                if let (Some(etag), Some(body)) = (entry.etag, entry.body) {
+                    let (parts, body) = ([(header::CONTENT_TYPE, "application/json")], body)
+                        .into_response()
+                        .into_parts();
+                    return respond(&request_headers, parts, body, &etag, ttl, "HIT");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Response cache lookup failed: {}", e),
+        }
+    }
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_CACHED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Could not buffer response body: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let etag = etag_for(&bytes);
+
+    if let (Some(pool), Some(key)) = (pool, key.as_deref()) {
+        if let Ok(text) = std::str::from_utf8(&bytes) {
+            if let Err(e) = greport_db::queries::set_cached_response(
+                pool, key, DATA_TYPE, ttl as i64, &etag, text,
+            )
+            .await
+            {
+                tracing::warn!("Could not store cached response: {}", e);
+            }
+        }
+    }
+
+    let cache_status = if key.is_some() { "MISS" } else { "BYPASS" };
+    respond(
+        &request_headers,
+        parts,
+        Body::from(bytes),
+        &etag,
+        ttl,
+        cache_status,
+    )
+}
+
+/// Start a background task to delete expired cache entries
+pub fn start_cleanup_task(pool: DbPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(600));
+        loop {
+            interval.tick().await;
+            match greport_db::queries::cleanup_expired_cache(&pool).await {
+                Ok(0) => {}
+                Ok(deleted) => tracing::debug!(deleted, "Removed expired cache entries"),
+                Err(e) => tracing::warn!("Cache cleanup failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_normalizes_query() {
+        let a = cache_key("/aggregate/velocity", Some("period=week&last=12"), 3);
+        let b = cache_key("/aggregate/velocity", Some("last=12&period=week"), 3);
+        assert_eq!(a, b);
+        assert!(a.starts_with("response:"));
+        assert!(a.len() <= 512);
+
+        assert_ne!(
+            a,
+            cache_key("/aggregate/velocity", Some("period=week&last=12"), 4)
+        );
+        assert_ne!(
+            a,
+            cache_key("/aggregate/calendar", Some("period=week&last=12"), 3)
+        );
+        assert_eq!(
+            cache_key("/repos", None, 0),
+            cache_key("/repos", Some(""), 0)
+        );
+    }
+
+    #[test]
+    fn test_not_modified() {
+        let etag = etag_for(b"{}");
+        let mut headers = HeaderMap::new();
+        assert!(!not_modified(&headers, &etag));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"other\", W/{}", etag)).unwrap(),
+        );
+        assert!(not_modified(&headers, &etag));
+    }
+}
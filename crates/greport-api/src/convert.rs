@@ -3,9 +3,13 @@
 //! Reads from PostgreSQL and converts flat DB rows into rich core domain models.
 
 use greport_core::models::{
-    Issue, IssueState, Label, Milestone, MilestoneState, PullRequest, PullState, Release, User,
+    FieldValue, Issue, IssueState, Label, Milestone, MilestoneState, Project, ProjectField,
+    ProjectFieldType, ProjectFieldValue, ProjectItem, ProjectItemContent, PullRequest, PullState,
+    Release, User,
+};
+use greport_db::models::{
+    IssueRow, MilestoneRow, ProjectFieldRow, ProjectItemRow, ProjectRow, PullRequestRow, ReleaseRow,
 };
-use greport_db::models::{IssueRow, MilestoneRow, PullRequestRow, ReleaseRow};
 use greport_db::DbPool;
 
 /// Look up the internal DB id for a repository by owner/repo.
@@ -47,6 +51,15 @@ fn user_from_db_with_base(login: &str, id: i64, web_base: &str) -> User {
         login: login.to_string(),
         avatar_url: format!("https://avatars.githubusercontent.com/u/{}", id),
         html_url: format!("{}/{}", web_base, login),
+        is_bot: false,
+    }
+}
+
+/// A row's author, carrying the bot flag recorded at sync
+fn author_from_db(login: &str, id: i64, is_bot: bool) -> User {
+    User {
+        is_bot,
+        ..user_from_db(login, id)
     }
 }
 
@@ -71,7 +84,7 @@ pub async fn issues_from_db(
     Ok(issues)
 }
 
-async fn issue_row_to_model(pool: &DbPool, row: IssueRow) -> Result<Issue, sqlx::Error> {
+pub async fn issue_row_to_model(pool: &DbPool, row: IssueRow) -> Result<Issue, sqlx::Error> {
     // Fetch labels
     let label_rows = greport_db::queries::get_issue_labels(pool, row.id).await?;
     let labels: Vec<Label> = label_rows
@@ -118,7 +131,7 @@ async fn issue_row_to_model(pool: &DbPool, row: IssueRow) -> Result<Issue, sqlx:
         labels,
         assignees,
         milestone,
-        author: user_from_db(&row.author_login, row.author_id),
+        author: author_from_db(&row.author_login, row.author_id, row.author_is_bot),
         comments_count: row.comments_count as u32,
         created_at: row.created_at,
         updated_at: row.updated_at,
@@ -144,7 +157,7 @@ pub async fn pulls_from_db(
     Ok(pulls)
 }
 
-fn pull_row_to_model(row: PullRequestRow) -> PullRequest {
+pub fn pull_row_to_model(row: PullRequestRow) -> PullRequest {
     let state = match row.state.as_str() {
         "closed" => PullState::Closed,
         _ => PullState::Open,
@@ -158,7 +171,7 @@ fn pull_row_to_model(row: PullRequestRow) -> PullRequest {
         body: row.body,
         state,
         draft: row.draft,
-        author: user_from_db(&row.author_login, row.author_id),
+        author: author_from_db(&row.author_login, row.author_id, row.author_is_bot),
         labels: vec![],
         milestone: None,
         head_ref: row.head_ref,
@@ -190,7 +203,7 @@ pub async fn releases_from_db(
     Ok(releases)
 }
 
-fn release_row_to_model(row: ReleaseRow) -> Release {
+pub fn release_row_to_model(row: ReleaseRow) -> Release {
     Release {
         id: row.id,
         tag_name: row.tag_name,
@@ -198,7 +211,7 @@ fn release_row_to_model(row: ReleaseRow) -> Release {
         body: row.body,
         draft: row.draft,
         prerelease: row.prerelease,
-        author: user_from_db(&row.author_login, row.author_id),
+        author: author_from_db(&row.author_login, row.author_id, row.author_is_bot),
         created_at: row.created_at,
         published_at: row.published_at,
     }
@@ -219,7 +232,7 @@ pub async fn milestones_from_db(
     Ok(milestones)
 }
 
-fn milestone_row_to_model(row: MilestoneRow) -> Milestone {
+pub fn milestone_row_to_model(row: MilestoneRow) -> Milestone {
     let state = match row.state.as_str() {
         "closed" => MilestoneState::Closed,
         _ => MilestoneState::Open,
@@ -238,3 +251,120 @@ fn milestone_row_to_model(row: MilestoneRow) -> Milestone {
         closed_at: row.closed_at,
     }
 }
+
+// ---------------------------------------------------------------------------
+// Projects
+// ---------------------------------------------------------------------------
+
+/// Load a synced project with its field definitions as a core Project model.
+pub async fn project_from_db(pool: &DbPool, row: ProjectRow) -> Result<Project, sqlx::Error> {
+    let field_rows = greport_db::queries::list_project_fields(pool, &row.node_id).await?;
+    let fields: Vec<ProjectField> = field_rows.into_iter().map(field_row_to_model).collect();
+
+    Ok(Project {
+        node_id: row.node_id,
+        number: row.number as u64,
+        title: row.title,
+        description: row.description,
+        url: row.url,
+        closed: row.closed,
+        owner: row.owner,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        fields,
+        total_items: row.total_items as u32,
+    })
+}
+
+/// Fetch all synced items of a project and convert to core ProjectItem models.
+pub async fn project_items_from_db(
+    pool: &DbPool,
+    project_id: &str,
+) -> Result<Vec<ProjectItem>, sqlx::Error> {
+    let rows =
+        greport_db::queries::list_project_items(pool, project_id, None, None, None, None).await?;
+
+    let items: Vec<ProjectItem> = rows.into_iter().map(item_row_to_model).collect();
+    Ok(items)
+}
+
+fn field_row_to_model(row: ProjectFieldRow) -> ProjectField {
+    let config = row.config_json.unwrap_or(serde_json::Value::Null);
+    let field_type = match row.field_type.as_str() {
+        "text" => ProjectFieldType::Text,
+        "number" => ProjectFieldType::Number,
+        "date" => ProjectFieldType::Date,
+        "single_select" => ProjectFieldType::SingleSelect {
+            options: serde_json::from_value(config).unwrap_or_default(),
+        },
+        "iteration" => ProjectFieldType::Iteration {
+            iterations: serde_json::from_value(config).unwrap_or_default(),
+        },
+        _ => ProjectFieldType::BuiltIn,
+    };
+
+    ProjectField {
+        node_id: row.node_id,
+        name: row.name,
+        field_type,
+    }
+}
+
+pub fn item_row_to_model(row: ProjectItemRow) -> ProjectItem {
+    let content_json = row.content_json.unwrap_or(serde_json::Value::Null);
+    let json_field = |key: &str| content_json.get(key).cloned().unwrap_or_default();
+
+    let content = match row.content_type.as_str() {
+        "issue" => ProjectItemContent::Issue {
+            number: row.content_number.unwrap_or(0) as u64,
+            title: row.content_title,
+            state: row.content_state.unwrap_or_default(),
+            url: row.content_url.unwrap_or_default(),
+            repository: row.content_repository.unwrap_or_default(),
+            assignees: serde_json::from_value(json_field("assignees")).unwrap_or_default(),
+            labels: serde_json::from_value(json_field("labels")).unwrap_or_default(),
+        },
+        "pull_request" => ProjectItemContent::PullRequest {
+            number: row.content_number.unwrap_or(0) as u64,
+            title: row.content_title,
+            state: row.content_state.unwrap_or_default(),
+            url: row.content_url.unwrap_or_default(),
+            repository: row.content_repository.unwrap_or_default(),
+            merged: json_field("merged").as_bool().unwrap_or(false),
+            author: json_field("author")
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        },
+        _ => ProjectItemContent::DraftIssue {
+            title: row.content_title,
+            body: json_field("body").as_str().map(String::from),
+            assignees: serde_json::from_value(json_field("assignees")).unwrap_or_default(),
+        },
+    };
+
+    // Field values are stored as {"field_name": ..., "type": ..., ...} objects,
+    // which match the tagged FieldValue representation.
+    let field_values = row
+        .field_values_json
+        .and_then(|v| match v {
+            serde_json::Value::Array(values) => Some(values),
+            _ => None,
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| {
+            let field_name = v.get("field_name")?.as_str()?.to_string();
+            let value: FieldValue = serde_json::from_value(v).ok()?;
+            Some(ProjectFieldValue { field_name, value })
+        })
+        .collect();
+
+    ProjectItem {
+        node_id: row.node_id,
+        content,
+        field_values,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
@@ -5,7 +5,8 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde::Serialize;
+
+pub use greport_types::{ErrorBody, ErrorResponse};
 
 /// API error type
 #[derive(Debug)]
@@ -17,6 +18,8 @@ pub enum ApiError {
     BadRequest(String),
     /// Unauthorized
     Unauthorized,
+    /// Authenticated but not allowed
+    Forbidden(String),
     /// Rate limited
     RateLimited,
     /// Internal server error
@@ -25,24 +28,13 @@ pub enum ApiError {
     GitHub(greport_core::Error),
 }
 
-/// Error response body
-#[derive(Serialize)]
-struct ErrorResponse {
-    error: ErrorBody,
-}
-
-#[derive(Serialize)]
-struct ErrorBody {
-    code: String,
-    message: String,
-}
-
 impl std::fmt::Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ApiError::NotFound(msg) => write!(f, "{msg}"),
             ApiError::BadRequest(msg) => write!(f, "{msg}"),
             ApiError::Unauthorized => write!(f, "Invalid or missing authentication"),
+            ApiError::Forbidden(msg) => write!(f, "{msg}"),
             ApiError::RateLimited => write!(f, "Rate limit exceeded"),
             ApiError::Internal(msg) => write!(f, "{msg}"),
             ApiError::GitHub(e) => write!(f, "{}", friendly_github_message(e)),
@@ -50,26 +42,38 @@ impl std::fmt::Display for ApiError {
     }
 }
 
+impl ApiError {
+    /// Machine-readable error code, as sent in error responses
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::BadRequest(_) => "BAD_REQUEST",
+            ApiError::Unauthorized => "UNAUTHORIZED",
+            ApiError::Forbidden(_) => "FORBIDDEN",
+            ApiError::RateLimited => "RATE_LIMITED",
+            ApiError::Internal(_) => "INTERNAL_ERROR",
+            ApiError::GitHub(_) => "GITHUB_ERROR",
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, code, message) = match self {
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND", msg),
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg),
+        let code = self.code();
+        let (status, message) = match self {
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             ApiError::Unauthorized => (
                 StatusCode::UNAUTHORIZED,
-                "UNAUTHORIZED",
                 "Invalid or missing authentication".into(),
             ),
-            ApiError::RateLimited => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "RATE_LIMITED",
-                "Rate limit exceeded".into(),
-            ),
-            ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", msg),
+            ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            ApiError::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded".into()),
+            ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             ApiError::GitHub(e) => {
                 tracing::error!("GitHub API error: {:?}", e);
                 let message = friendly_github_message(&e);
-                (StatusCode::BAD_GATEWAY, "GITHUB_ERROR", message)
+                (StatusCode::BAD_GATEWAY, message)
             }
         };
 
@@ -107,7 +111,11 @@ fn friendly_github_message(e: &greport_core::Error) -> String {
 
 impl From<greport_core::Error> for ApiError {
     fn from(err: greport_core::Error) -> Self {
-        ApiError::GitHub(err)
+        match err {
+            // The server's own `max_items` cap, not something GitHub did
+            greport_core::Error::Truncated { .. } => ApiError::Internal(err.to_string()),
+            err => ApiError::GitHub(err),
+        }
     }
 }
 
@@ -117,6 +125,19 @@ impl From<anyhow::Error> for ApiError {
     }
 }
 
+impl From<greport_db::DbError> for ApiError {
+    fn from(err: greport_db::DbError) -> Self {
+        match err {
+            greport_db::DbError::InvalidInput(msg) => ApiError::BadRequest(msg),
+            greport_db::DbError::NotFound(msg) => ApiError::NotFound(msg),
+            other => {
+                tracing::error!("Database error: {:?}", other);
+                ApiError::Internal(other.to_string())
+            }
+        }
+    }
+}
+
 impl From<sqlx::Error> for ApiError {
     fn from(err: sqlx::Error) -> Self {
         tracing::error!("Database error: {:?}", err);
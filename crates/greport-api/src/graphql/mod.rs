@@ -0,0 +1,252 @@
+//! GraphQL query API over the synced data
+//!
+//! `POST /api/graphql` answers queries for repositories, issues, pull
+//! requests, releases, milestones, projects and their metrics from the
+//! database, so a dashboard page can fetch everything it shows in one
+//! request. `GET /api/graphql` serves GraphQL Playground.
+//!
+//! Queries are limited in depth and complexity (see [`ApiConfig`]); list
+//! fields count their page size against the complexity limit. Access follows
+//! the REST read routes: the caller needs the `read` scope, and `org:` scoped
+//! keys only see their organizations.
+
+mod query;
+mod types;
+
+use std::sync::Arc;
+
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, ErrorExtensions, Schema};
+use axum::{extract::State, response::Html, Extension, Json};
+use greport_db::DbPool;
+
+use crate::auth::{check_access, AuthInfo, Scope};
+use crate::error::{ApiError, ErrorResponse};
+use crate::state::{ApiConfig, AppState};
+
+pub use query::QueryRoot;
+
+/// Executable GraphQL schema
+pub type GreportSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema for `state`, applying its depth and complexity limits
+pub fn build_schema(state: &AppState) -> GreportSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state.clone())
+        .limit_depth(state.config.graphql_max_depth)
+        .limit_complexity(state.config.graphql_max_complexity)
+        .finish()
+}
+
+impl ErrorExtensions for ApiError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(self.to_string()).extend_with(|_, e| e.set("code", self.code()))
+    }
+}
+
+/// Convert a handler error into a GraphQL error carrying the REST error code
+pub(crate) fn gql_error(err: impl Into<ApiError>) -> async_graphql::Error {
+    err.into().extend()
+}
+
+/// Database pool, or an error when the server runs without one
+pub(crate) fn pool<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a DbPool> {
+    ctx.data_unchecked::<AppState>()
+        .db
+        .as_ref()
+        .ok_or_else(|| gql_error(ApiError::BadRequest("Database required for GraphQL".into())))
+}
+
+pub(crate) fn config<'a>(ctx: &Context<'a>) -> &'a ApiConfig {
+    &ctx.data_unchecked::<AppState>().config
+}
+
+/// Check that the caller may read data belonging to `owner`
+pub(crate) fn require_owner(ctx: &Context<'_>, owner: &str) -> async_graphql::Result<()> {
+    let auth = ctx.data_opt::<Arc<AuthInfo>>().map(Arc::as_ref);
+    let config = config(ctx);
+    check_access(auth, Scope::Read, Some(owner), config.require_auth).map_err(gql_error)?;
+    // Data is served with the server's credentials, so passthrough callers
+    // and login sessions only see their own orgs
+    if let Some(auth) = auth {
+        if !auth.can_see_owner(owner) {
+            return Err(gql_error(ApiError::Forbidden(format!(
+                "User '{}' is not a member of '{}'",
+                auth.github_login.as_deref().unwrap_or_default(),
+                owner
+            ))));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the caller may read data belonging to `owner`
+pub(crate) fn can_read_owner(ctx: &Context<'_>, owner: &str) -> bool {
+    require_owner(ctx, owner).is_ok()
+}
+
+/// POST /api/graphql - Execute a GraphQL query
+#[utoipa::path(
+    post,
+    path = "/api/graphql",
+    tag = "graphql",
+    request_body(
+        content = Object,
+        description = "GraphQL request: `query`, optional `operationName` and `variables`",
+        content_type = "application/json"
+    ),
+    responses(
+        (status = 200, description = "GraphQL response with `data` and/or `errors`", content_type = "application/json"),
+        (status = "4XX", description = "Not authorized", body = ErrorResponse)
+    ),
+)]
+pub async fn graphql(
+    State(state): State<AppState>,
+    Extension(schema): Extension<GreportSchema>,
+    auth: Option<Extension<Arc<AuthInfo>>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<Json<async_graphql::Response>, ApiError> {
+    let auth = auth.map(|Extension(auth)| auth);
+    match auth.as_deref() {
+        None if state.config.require_auth => return Err(ApiError::Unauthorized),
+        Some(auth) if !auth.has_scope(Scope::Read) => {
+            return Err(ApiError::Forbidden(format!(
+                "Missing required scope '{}'",
+                Scope::Read.as_str()
+            )))
+        }
+        _ => {}
+    }
+
+    let request = match auth {
+        Some(auth) => request.data(auth),
+        None => request,
+    };
+    Ok(Json(schema.execute(request).await))
+}
+
+/// GET /api/graphql - GraphQL Playground
+#[utoipa::path(
+    get,
+    path = "/api/graphql",
+    tag = "graphql",
+    responses((status = 200, description = "GraphQL Playground", content_type = "text/html")),
+    security(()),
+)]
+pub async fn playground() -> Html<String> {
+    Html(playground_source(GraphQLPlaygroundConfig::new(
+        "/api/graphql",
+    )))
+}
+
+/// SDL of the schema, for clients that generate types from it
+pub fn sdl() -> String {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .finish()
+        .sdl()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use greport_core::{GitHubClientRegistry, OctocrabClient};
+    use serde_json::Value;
+
+    fn schema(config: ApiConfig) -> GreportSchema {
+        let client = OctocrabClient::new("test-token", None).unwrap();
+        build_schema(&AppState::new(
+            GitHubClientRegistry::with_default(client, None),
+            config,
+            None,
+        ))
+    }
+
+    /// `data` and error messages of a response
+    fn response_data(response: async_graphql::Response) -> (Value, Vec<String>) {
+        let errors = response.errors.iter().map(|e| e.message.clone()).collect();
+        (response.data.into_json().unwrap(), errors)
+    }
+
+    fn key(scopes: &[&str]) -> Arc<AuthInfo> {
+        Arc::new(AuthInfo {
+            owner: "test".to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            rate_limit: 100,
+            github_login: None,
+            github_orgs: Vec::new(),
+            key_id: None,
+        })
+    }
+
+    #[test]
+    fn test_sdl_lists_types() {
+        let sdl = sdl();
+        for name in [
+            "type Repository",
+            "type Issue",
+            "type PullRequest",
+            "type Release",
+            "type Milestone",
+            "type Project",
+            "type IssueMetrics",
+            "type PullRequestMetrics",
+            "type IssueConnection",
+            "input IssueFilter",
+            "enum PullRequestState",
+        ] {
+            assert!(sdl.contains(name), "missing {}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_depth_limit() {
+        let schema = schema(ApiConfig {
+            graphql_max_depth: 3,
+            ..Default::default()
+        });
+        let query =
+            r#"{ repository(owner: "a", name: "b") { issues { nodes { milestone { title } } } } }"#;
+        let (_, errors) = response_data(schema.execute(query).await);
+        assert!(errors[0].contains("nested too deep"), "{:?}", errors);
+    }
+
+    #[tokio::test]
+    async fn test_complexity_counts_page_size() {
+        let schema = schema(ApiConfig {
+            graphql_max_complexity: 300,
+            ..Default::default()
+        });
+        let query = |first: u32| {
+            format!(
+                r#"{{ repository(owner: "a", name: "b") {{ issues(first: {}) {{ nodes {{ number title state }} }} }} }}"#,
+                first
+            )
+        };
+
+        let (_, errors) = response_data(schema.execute(query(100)).await);
+        assert!(errors[0].contains("too complex"), "{:?}", errors);
+
+        // Within the limit the query runs and fails for want of a database
+        let (_, errors) = response_data(schema.execute(query(10)).await);
+        assert!(errors[0].contains("Database required"), "{:?}", errors);
+    }
+
+    #[tokio::test]
+    async fn test_org_restricted_keys() {
+        let schema = schema(ApiConfig::default());
+        let query = r#"{ repository(owner: "acme", name: "app") { name } }"#;
+
+        let request = async_graphql::Request::new(query).data(key(&["read", "org:other"]));
+        let response = schema.execute(request).await;
+        assert_eq!(
+            response.errors[0].extensions.as_ref().unwrap().get("code"),
+            Some(&async_graphql::Value::from("FORBIDDEN"))
+        );
+        assert!(response.errors[0].message.contains("org:acme"));
+
+        // Access is checked before the database is needed
+        let request = async_graphql::Request::new(query).data(key(&["read", "org:acme"]));
+        let (_, errors) = response_data(schema.execute(request).await);
+        assert!(errors[0].contains("Database required"), "{:?}", errors);
+    }
+}
@@ -0,0 +1,79 @@
+//! GraphQL query root
+
+use async_graphql::{Context, Object};
+use greport_db::queries;
+
+use super::types::{Project, Repository};
+use super::{can_read_owner, gql_error, pool, require_owner};
+
+/// Entry points of the schema
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Tracked repositories the caller can read, optionally for one owner
+    async fn repositories(
+        &self,
+        ctx: &Context<'_>,
+        owner: Option<String>,
+    ) -> async_graphql::Result<Vec<Repository>> {
+        if let Some(ref owner) = owner {
+            require_owner(ctx, owner)?;
+        }
+        let rows = queries::list_repositories(pool(ctx)?)
+            .await
+            .map_err(gql_error)?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| {
+                owner
+                    .as_deref()
+                    .is_none_or(|o| r.owner.eq_ignore_ascii_case(o))
+            })
+            .filter(|r| can_read_owner(ctx, &r.owner))
+            .map(Repository)
+            .collect())
+    }
+
+    /// A tracked repository
+    async fn repository(
+        &self,
+        ctx: &Context<'_>,
+        owner: String,
+        name: String,
+    ) -> async_graphql::Result<Option<Repository>> {
+        require_owner(ctx, &owner)?;
+        let row = queries::get_repository_by_name(pool(ctx)?, &format!("{}/{}", owner, name))
+            .await
+            .map_err(gql_error)?;
+        Ok(row.map(Repository))
+    }
+
+    /// Synced projects of an organization
+    async fn projects(
+        &self,
+        ctx: &Context<'_>,
+        owner: String,
+        #[graphql(default)] include_closed: bool,
+    ) -> async_graphql::Result<Vec<Project>> {
+        require_owner(ctx, &owner)?;
+        let rows = queries::list_projects(pool(ctx)?, &owner, include_closed)
+            .await
+            .map_err(gql_error)?;
+        Ok(rows.into_iter().map(Project).collect())
+    }
+
+    /// A synced project
+    async fn project(
+        &self,
+        ctx: &Context<'_>,
+        owner: String,
+        number: i64,
+    ) -> async_graphql::Result<Option<Project>> {
+        require_owner(ctx, &owner)?;
+        let row = queries::get_project(pool(ctx)?, &owner, number)
+            .await
+            .map_err(gql_error)?;
+        Ok(row.map(Project))
+    }
+}
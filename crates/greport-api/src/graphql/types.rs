@@ -0,0 +1,1091 @@
+//! GraphQL object and input types
+//!
+//! Objects wrap database rows and resolve related data (labels, milestones,
+//! nested lists, metrics) only when a query selects it.
+
+use std::collections::HashMap;
+
+use async_graphql::{Context, Enum, InputObject, Object, OutputType, SimpleObject};
+use chrono::{DateTime, Utc};
+use greport_core::metrics::{
+    IssueMetricsCalculator, ProjectMetricsCalculator, PullMetricsCalculator,
+};
+use greport_db::models::{
+    IssueRow, MilestoneRow, ProjectFieldRow, ProjectItemRow, ProjectRow, PullRequestRow,
+    ReleaseRow, RepositoryRow,
+};
+use greport_db::queries;
+use serde_json::Value;
+
+use super::{config, gql_error, pool};
+use crate::convert;
+use crate::error::ApiError;
+
+/// Page size when a list field is not given `first`
+pub const DEFAULT_PAGE_SIZE: i32 = 30;
+
+/// Complexity of a list field: its selection, once per requested item
+pub fn page_complexity(first: i32, child_complexity: usize) -> usize {
+    (first.max(1) as usize).saturating_mul(child_complexity)
+}
+
+// =============================================================================
+// Pagination
+// =============================================================================
+
+/// Offset and size of a requested page
+#[derive(Debug, Clone, Copy)]
+pub struct Page {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Page {
+    /// Page for `first`/`offset` arguments, capped at the maximum page size
+    pub fn new(ctx: &Context<'_>, first: i32, offset: i32) -> async_graphql::Result<Self> {
+        if first < 0 || offset < 0 {
+            return Err(gql_error(ApiError::BadRequest(
+                "first and offset must not be negative".into(),
+            )));
+        }
+        Ok(Self {
+            limit: (first as i64).min(config(ctx).max_page_size as i64),
+            offset: offset as i64,
+        })
+    }
+}
+
+/// Position of a page within the full list
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PageInfo {
+    /// Offset of the first node
+    pub offset: i64,
+    /// Whether nodes follow this page
+    pub has_next_page: bool,
+    /// Whether nodes precede this page
+    pub has_previous_page: bool,
+}
+
+/// A page of a list with its total size
+#[derive(SimpleObject)]
+#[graphql(concrete(name = "IssueConnection", params(Issue)))]
+#[graphql(concrete(name = "PullRequestConnection", params(PullRequest)))]
+#[graphql(concrete(name = "ReleaseConnection", params(Release)))]
+#[graphql(concrete(name = "ProjectItemConnection", params(ProjectItem)))]
+pub struct Connection<T: OutputType> {
+    /// Number of nodes matching the query across all pages
+    pub total_count: i64,
+    pub nodes: Vec<T>,
+    pub page_info: PageInfo,
+}
+
+impl<T: OutputType> Connection<T> {
+    pub fn new(nodes: Vec<T>, total_count: i64, page: Page) -> Self {
+        let page_info = PageInfo {
+            offset: page.offset,
+            has_next_page: page.offset + (nodes.len() as i64) < total_count,
+            has_previous_page: page.offset > 0,
+        };
+        Self {
+            total_count,
+            nodes,
+            page_info,
+        }
+    }
+}
+
+// =============================================================================
+// Enums and filters
+// =============================================================================
+
+/// Open or closed, for issues and milestones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum State {
+    Open,
+    Closed,
+}
+
+impl State {
+    fn from_db(state: &str) -> Self {
+        match state {
+            "closed" => State::Closed,
+            _ => State::Open,
+        }
+    }
+
+    fn as_db(self) -> &'static str {
+        match self {
+            State::Open => "open",
+            State::Closed => "closed",
+        }
+    }
+}
+
+/// Pull request state; merged pull requests are not reported as closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum PullRequestState {
+    Open,
+    Closed,
+    Merged,
+}
+
+/// Issue filters; all given conditions must match
+#[derive(Debug, Clone, Default, InputObject)]
+#[graphql(name = "IssueFilter")]
+pub struct IssueFilterInput {
+    pub state: Option<State>,
+    /// Labels the issue must all carry (case-insensitive)
+    pub labels: Option<Vec<String>>,
+    /// Assignee login
+    pub assignee: Option<String>,
+    /// Author login
+    pub author: Option<String>,
+    /// Milestone number
+    pub milestone: Option<i64>,
+    /// Only issues updated at or after this time
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+impl IssueFilterInput {
+    /// Database filter for a repository, resolving the milestone number
+    async fn to_db(
+        &self,
+        ctx: &Context<'_>,
+        repository_id: i64,
+    ) -> async_graphql::Result<Option<greport_db::models::IssueFilter>> {
+        let milestone_id = match self.milestone {
+            Some(number) => match milestone_by_number(ctx, repository_id, number).await? {
+                Some(milestone) => Some(milestone.id),
+                // No such milestone: nothing can match
+                None => return Ok(None),
+            },
+            None => None,
+        };
+        Ok(Some(greport_db::models::IssueFilter {
+            state: self.state.map(|s| s.as_db().to_string()),
+            milestone_id,
+            labels: self.labels.clone().unwrap_or_default(),
+            assignee: self.assignee.clone(),
+            author: self.author.clone(),
+            updated_since: self.updated_since,
+        }))
+    }
+}
+
+/// Pull request filters; all given conditions must match
+#[derive(Debug, Clone, Default, InputObject)]
+#[graphql(name = "PullRequestFilter")]
+pub struct PullRequestFilterInput {
+    pub state: Option<PullRequestState>,
+    pub draft: Option<bool>,
+    /// Author login
+    pub author: Option<String>,
+    /// Base branch
+    pub base_ref: Option<String>,
+    /// Only pull requests updated at or after this time
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+impl PullRequestFilterInput {
+    fn to_db(&self) -> greport_db::models::PullRequestFilter {
+        let (state, merged) = match self.state {
+            Some(PullRequestState::Open) => (Some("open"), None),
+            Some(PullRequestState::Closed) => (Some("closed"), Some(false)),
+            Some(PullRequestState::Merged) => (None, Some(true)),
+            None => (None, None),
+        };
+        greport_db::models::PullRequestFilter {
+            state: state.map(String::from),
+            merged,
+            draft: self.draft,
+            author: self.author.clone(),
+            base_ref: self.base_ref.clone(),
+            milestone_id: None,
+            updated_since: self.updated_since,
+        }
+    }
+}
+
+async fn milestone_by_number(
+    ctx: &Context<'_>,
+    repository_id: i64,
+    number: i64,
+) -> async_graphql::Result<Option<MilestoneRow>> {
+    let milestones = queries::list_milestones(pool(ctx)?, repository_id, None)
+        .await
+        .map_err(gql_error)?;
+    Ok(milestones.into_iter().find(|m| m.number == number))
+}
+
+/// A page of issues matching `filter`
+async fn issue_page(
+    ctx: &Context<'_>,
+    repository_id: i64,
+    filter: &IssueFilterInput,
+    page: Page,
+) -> async_graphql::Result<Connection<Issue>> {
+    let Some(filter) = filter.to_db(ctx, repository_id).await? else {
+        return Ok(Connection::new(Vec::new(), 0, page));
+    };
+    let pool = pool(ctx)?;
+    let total = queries::count_issues(pool, repository_id, &filter)
+        .await
+        .map_err(gql_error)?;
+    let rows = queries::search_issues(pool, repository_id, &filter, page.limit, page.offset)
+        .await
+        .map_err(gql_error)?;
+    Ok(Connection::new(
+        rows.into_iter().map(Issue).collect(),
+        total,
+        page,
+    ))
+}
+
+/// Key/count pairs, largest count first
+fn counts(map: HashMap<String, usize>) -> Vec<Count> {
+    let mut counts: Vec<Count> = map
+        .into_iter()
+        .map(|(key, count)| Count {
+            key,
+            count: count as i64,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    counts
+}
+
+// =============================================================================
+// Repositories
+// =============================================================================
+
+/// A tracked repository
+pub struct Repository(pub RepositoryRow);
+
+#[Object]
+impl Repository {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn owner(&self) -> &str {
+        &self.0.owner
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn full_name(&self) -> &str {
+        &self.0.full_name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn private(&self) -> bool {
+        self.0.private
+    }
+
+    async fn default_branch(&self) -> &str {
+        &self.0.default_branch
+    }
+
+    /// When the repository metadata was last synced
+    async fn synced_at(&self) -> DateTime<Utc> {
+        self.0.synced_at
+    }
+
+    /// Issue, pull request and release counts
+    async fn stats(&self, ctx: &Context<'_>) -> async_graphql::Result<RepositoryStats> {
+        let stats = queries::get_repository_stats(pool(ctx)?, self.0.id)
+            .await
+            .map_err(gql_error)?;
+        Ok(RepositoryStats {
+            open_issues: stats.open_issues,
+            closed_issues: stats.closed_issues,
+            open_pull_requests: stats.open_prs,
+            merged_pull_requests: stats.merged_prs,
+            closed_pull_requests: stats.closed_prs,
+            releases: stats.releases,
+        })
+    }
+
+    /// Issues, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn issues(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: IssueFilterInput,
+        #[graphql(default_with = "DEFAULT_PAGE_SIZE")] first: i32,
+        #[graphql(default)] offset: i32,
+    ) -> async_graphql::Result<Connection<Issue>> {
+        issue_page(ctx, self.0.id, &filter, Page::new(ctx, first, offset)?).await
+    }
+
+    async fn issue(&self, ctx: &Context<'_>, number: i64) -> async_graphql::Result<Option<Issue>> {
+        let row = queries::get_issue_by_number(pool(ctx)?, self.0.id, number)
+            .await
+            .map_err(gql_error)?;
+        Ok(row.map(Issue))
+    }
+
+    /// Pull requests, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn pull_requests(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: PullRequestFilterInput,
+        #[graphql(default_with = "DEFAULT_PAGE_SIZE")] first: i32,
+        #[graphql(default)] offset: i32,
+    ) -> async_graphql::Result<Connection<PullRequest>> {
+        let page = Page::new(ctx, first, offset)?;
+        let filter = filter.to_db();
+        let pool = pool(ctx)?;
+        let total = queries::count_pull_requests(pool, self.0.id, &filter)
+            .await
+            .map_err(gql_error)?;
+        let rows = queries::search_pull_requests(pool, self.0.id, &filter, page.limit, page.offset)
+            .await
+            .map_err(gql_error)?;
+        Ok(Connection::new(
+            rows.into_iter().map(PullRequest).collect(),
+            total,
+            page,
+        ))
+    }
+
+    async fn pull_request(
+        &self,
+        ctx: &Context<'_>,
+        number: i64,
+    ) -> async_graphql::Result<Option<PullRequest>> {
+        let row = queries::get_pull_request_by_number(pool(ctx)?, self.0.id, number)
+            .await
+            .map_err(gql_error)?;
+        Ok(row.map(PullRequest))
+    }
+
+    /// Releases, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn releases(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = true)] include_prereleases: bool,
+        #[graphql(default_with = "DEFAULT_PAGE_SIZE")] first: i32,
+        #[graphql(default)] offset: i32,
+    ) -> async_graphql::Result<Connection<Release>> {
+        let page = Page::new(ctx, first, offset)?;
+        let pool = pool(ctx)?;
+        let total = queries::count_releases(pool, self.0.id, include_prereleases)
+            .await
+            .map_err(gql_error)?;
+        let rows = queries::search_releases(
+            pool,
+            self.0.id,
+            include_prereleases,
+            page.limit,
+            page.offset,
+        )
+        .await
+        .map_err(gql_error)?;
+        Ok(Connection::new(
+            rows.into_iter().map(Release).collect(),
+            total,
+            page,
+        ))
+    }
+
+    async fn release(
+        &self,
+        ctx: &Context<'_>,
+        tag: String,
+    ) -> async_graphql::Result<Option<Release>> {
+        let row = queries::get_release_by_tag(pool(ctx)?, self.0.id, &tag)
+            .await
+            .map_err(gql_error)?;
+        Ok(row.map(Release))
+    }
+
+    /// Milestones by number
+    async fn milestones(
+        &self,
+        ctx: &Context<'_>,
+        state: Option<State>,
+    ) -> async_graphql::Result<Vec<Milestone>> {
+        let rows = queries::list_milestones(pool(ctx)?, self.0.id, state.map(State::as_db))
+            .await
+            .map_err(gql_error)?;
+        Ok(rows.into_iter().map(Milestone).collect())
+    }
+
+    async fn milestone(
+        &self,
+        ctx: &Context<'_>,
+        number: i64,
+    ) -> async_graphql::Result<Option<Milestone>> {
+        Ok(milestone_by_number(ctx, self.0.id, number)
+            .await?
+            .map(Milestone))
+    }
+
+    /// Metrics over the issues matching `filter`
+    async fn issue_metrics(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: IssueFilterInput,
+        #[graphql(default = 30)] stale_days: i64,
+    ) -> async_graphql::Result<IssueMetrics> {
+        let issues = match filter.to_db(ctx, self.0.id).await? {
+            Some(filter) => {
+                let pool = pool(ctx)?;
+                let rows = queries::search_issues(pool, self.0.id, &filter, i64::MAX, 0)
+                    .await
+                    .map_err(gql_error)?;
+                let mut issues = Vec::with_capacity(rows.len());
+                for row in rows {
+                    issues.push(
+                        convert::issue_row_to_model(pool, row)
+                            .await
+                            .map_err(gql_error)?,
+                    );
+                }
+                issues
+            }
+            None => Vec::new(),
+        };
+        Ok(IssueMetricsCalculator::new(stale_days)
+            .with_bot_filter(config(ctx).bot_filter(&self.0.owner))
+            .calculate(&issues)
+            .into())
+    }
+
+    /// Metrics over the pull requests matching `filter`
+    async fn pull_request_metrics(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: PullRequestFilterInput,
+    ) -> async_graphql::Result<PullRequestMetrics> {
+        let rows =
+            queries::search_pull_requests(pool(ctx)?, self.0.id, &filter.to_db(), i64::MAX, 0)
+                .await
+                .map_err(gql_error)?;
+        let pulls: Vec<_> = rows.into_iter().map(convert::pull_row_to_model).collect();
+        let bots = config(ctx).bot_filter(&self.0.owner);
+        Ok(PullMetricsCalculator::calculate_with_bots(&pulls, &bots).into())
+    }
+}
+
+/// Issue, pull request and release counts for a repository
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RepositoryStats {
+    pub open_issues: i64,
+    pub closed_issues: i64,
+    pub open_pull_requests: i64,
+    pub merged_pull_requests: i64,
+    /// Closed without merging
+    pub closed_pull_requests: i64,
+    pub releases: i64,
+}
+
+// =============================================================================
+// Issues, pull requests, releases, milestones
+// =============================================================================
+
+/// A label on an issue
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Label {
+    pub name: String,
+    /// Hex color without `#`
+    pub color: Option<String>,
+}
+
+pub struct Issue(pub IssueRow);
+
+#[Object]
+impl Issue {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn number(&self) -> i64 {
+        self.0.number
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn body(&self) -> Option<&str> {
+        self.0.body.as_deref()
+    }
+
+    async fn state(&self) -> State {
+        State::from_db(&self.0.state)
+    }
+
+    /// Author login
+    async fn author(&self) -> &str {
+        &self.0.author_login
+    }
+
+    async fn comments_count(&self) -> i32 {
+        self.0.comments_count
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn closed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.closed_at
+    }
+
+    /// Login of the user who closed the issue
+    async fn closed_by(&self) -> Option<&str> {
+        self.0.closed_by_login.as_deref()
+    }
+
+    async fn labels(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Label>> {
+        let rows = queries::get_issue_labels(pool(ctx)?, self.0.id)
+            .await
+            .map_err(gql_error)?;
+        Ok(rows
+            .into_iter()
+            .map(|l| Label {
+                name: l.label_name,
+                color: l.label_color,
+            })
+            .collect())
+    }
+
+    /// Assignee logins
+    async fn assignees(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<String>> {
+        let rows = queries::get_issue_assignees(pool(ctx)?, self.0.id)
+            .await
+            .map_err(gql_error)?;
+        Ok(rows.into_iter().map(|a| a.user_login).collect())
+    }
+
+    async fn milestone(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Milestone>> {
+        milestone_by_id(ctx, self.0.milestone_id).await
+    }
+}
+
+pub struct PullRequest(pub PullRequestRow);
+
+#[Object]
+impl PullRequest {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn number(&self) -> i64 {
+        self.0.number
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn body(&self) -> Option<&str> {
+        self.0.body.as_deref()
+    }
+
+    async fn state(&self) -> PullRequestState {
+        match (self.0.merged, self.0.state.as_str()) {
+            (true, _) => PullRequestState::Merged,
+            (false, "closed") => PullRequestState::Closed,
+            _ => PullRequestState::Open,
+        }
+    }
+
+    async fn draft(&self) -> bool {
+        self.0.draft
+    }
+
+    /// Author login
+    async fn author(&self) -> &str {
+        &self.0.author_login
+    }
+
+    async fn head_ref(&self) -> &str {
+        &self.0.head_ref
+    }
+
+    async fn base_ref(&self) -> &str {
+        &self.0.base_ref
+    }
+
+    async fn merged_at(&self) -> Option<DateTime<Utc>> {
+        self.0.merged_at
+    }
+
+    async fn additions(&self) -> i32 {
+        self.0.additions
+    }
+
+    async fn deletions(&self) -> i32 {
+        self.0.deletions
+    }
+
+    async fn changed_files(&self) -> i32 {
+        self.0.changed_files
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn closed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.closed_at
+    }
+
+    async fn milestone(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Milestone>> {
+        milestone_by_id(ctx, self.0.milestone_id).await
+    }
+}
+
+pub struct Release(pub ReleaseRow);
+
+#[Object]
+impl Release {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn tag_name(&self) -> &str {
+        &self.0.tag_name
+    }
+
+    async fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    async fn body(&self) -> Option<&str> {
+        self.0.body.as_deref()
+    }
+
+    async fn draft(&self) -> bool {
+        self.0.draft
+    }
+
+    async fn prerelease(&self) -> bool {
+        self.0.prerelease
+    }
+
+    /// Author login
+    async fn author(&self) -> &str {
+        &self.0.author_login
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn published_at(&self) -> Option<DateTime<Utc>> {
+        self.0.published_at
+    }
+}
+
+async fn milestone_by_id(
+    ctx: &Context<'_>,
+    id: Option<i64>,
+) -> async_graphql::Result<Option<Milestone>> {
+    let Some(id) = id else {
+        return Ok(None);
+    };
+    let row = queries::get_milestone(pool(ctx)?, id)
+        .await
+        .map_err(gql_error)?;
+    Ok(row.map(Milestone))
+}
+
+pub struct Milestone(pub MilestoneRow);
+
+#[Object]
+impl Milestone {
+    async fn number(&self) -> i64 {
+        self.0.number
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn state(&self) -> State {
+        State::from_db(&self.0.state)
+    }
+
+    async fn open_issues(&self) -> i32 {
+        self.0.open_issues
+    }
+
+    async fn closed_issues(&self) -> i32 {
+        self.0.closed_issues
+    }
+
+    /// Closed issues as a percentage of all issues (0 when empty)
+    async fn progress_percent(&self) -> f64 {
+        let total = self.0.open_issues + self.0.closed_issues;
+        if total == 0 {
+            0.0
+        } else {
+            self.0.closed_issues as f64 / total as f64 * 100.0
+        }
+    }
+
+    async fn due_on(&self) -> Option<DateTime<Utc>> {
+        self.0.due_on
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn closed_at(&self) -> Option<DateTime<Utc>> {
+        self.0.closed_at
+    }
+
+    /// Issues in this milestone, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn issues(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: IssueFilterInput,
+        #[graphql(default_with = "DEFAULT_PAGE_SIZE")] first: i32,
+        #[graphql(default)] offset: i32,
+    ) -> async_graphql::Result<Connection<Issue>> {
+        let filter = IssueFilterInput {
+            milestone: Some(self.0.number),
+            ..filter
+        };
+        issue_page(
+            ctx,
+            self.0.repository_id,
+            &filter,
+            Page::new(ctx, first, offset)?,
+        )
+        .await
+    }
+}
+
+// =============================================================================
+// Metrics
+// =============================================================================
+
+/// Number of items sharing a key
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Count {
+    pub key: String,
+    pub count: i64,
+}
+
+/// Issues whose age falls in a range of days
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AgeBucket {
+    pub label: String,
+    pub min_days: i64,
+    /// Exclusive; null for the open-ended bucket
+    pub max_days: Option<i64>,
+    pub count: i64,
+}
+
+/// Aggregated issue metrics
+#[derive(Debug, Clone, SimpleObject)]
+pub struct IssueMetrics {
+    pub total: i64,
+    pub open: i64,
+    pub closed: i64,
+    pub avg_time_to_close_hours: Option<f64>,
+    pub median_time_to_close_hours: Option<f64>,
+    pub by_label: Vec<Count>,
+    /// Issues per assignee ("Unassigned" for none)
+    pub by_assignee: Vec<Count>,
+    pub by_milestone: Vec<Count>,
+    pub age_distribution: Vec<AgeBucket>,
+    /// Open issues not updated within the stale threshold
+    pub stale_count: i64,
+}
+
+impl From<greport_core::metrics::IssueMetrics> for IssueMetrics {
+    fn from(m: greport_core::metrics::IssueMetrics) -> Self {
+        Self {
+            total: m.total as i64,
+            open: m.open as i64,
+            closed: m.closed as i64,
+            avg_time_to_close_hours: m.avg_time_to_close_hours,
+            median_time_to_close_hours: m.median_time_to_close_hours,
+            by_label: counts(m.by_label),
+            by_assignee: counts(m.by_assignee),
+            by_milestone: counts(m.by_milestone),
+            age_distribution: m
+                .age_distribution
+                .buckets
+                .into_iter()
+                .map(|b| AgeBucket {
+                    label: b.label,
+                    min_days: b.min_days,
+                    max_days: b.max_days,
+                    count: b.count as i64,
+                })
+                .collect(),
+            stale_count: m.stale_count as i64,
+        }
+    }
+}
+
+/// Aggregated pull request metrics
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PullRequestMetrics {
+    pub total: i64,
+    pub open: i64,
+    pub merged: i64,
+    pub closed_unmerged: i64,
+    pub avg_time_to_merge_hours: Option<f64>,
+    pub median_time_to_merge_hours: Option<f64>,
+    /// Pull requests per size category (XS to XL)
+    pub by_size: Vec<Count>,
+    pub by_author: Vec<Count>,
+    pub by_base_branch: Vec<Count>,
+    pub draft_count: i64,
+}
+
+impl From<greport_core::metrics::PullMetrics> for PullRequestMetrics {
+    fn from(m: greport_core::metrics::PullMetrics) -> Self {
+        Self {
+            total: m.total as i64,
+            open: m.open as i64,
+            merged: m.merged as i64,
+            closed_unmerged: m.closed_unmerged as i64,
+            avg_time_to_merge_hours: m.avg_time_to_merge_hours,
+            median_time_to_merge_hours: m.median_time_to_merge_hours,
+            by_size: counts(m.by_size),
+            by_author: counts(m.by_author),
+            by_base_branch: counts(m.by_base_branch),
+            draft_count: m.draft_count as i64,
+        }
+    }
+}
+
+// =============================================================================
+// Projects
+// =============================================================================
+
+/// A synced GitHub Projects V2 board
+pub struct Project(pub ProjectRow);
+
+#[Object]
+impl Project {
+    async fn number(&self) -> i64 {
+        self.0.number
+    }
+
+    async fn owner(&self) -> &str {
+        &self.0.owner
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn url(&self) -> &str {
+        &self.0.url
+    }
+
+    async fn closed(&self) -> bool {
+        self.0.closed
+    }
+
+    async fn total_items(&self) -> i32 {
+        self.0.total_items
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn synced_at(&self) -> DateTime<Utc> {
+        self.0.synced_at
+    }
+
+    /// Field definitions by name
+    async fn fields(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ProjectField>> {
+        let rows = queries::list_project_fields(pool(ctx)?, &self.0.node_id)
+            .await
+            .map_err(gql_error)?;
+        Ok(rows.into_iter().map(ProjectField).collect())
+    }
+
+    /// Items, most recently updated first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "issue, pull_request or draft_issue")] content_type: Option<String>,
+        #[graphql(desc = "State of the linked issue or pull request")] state: Option<String>,
+        #[graphql(default_with = "DEFAULT_PAGE_SIZE")] first: i32,
+        #[graphql(default)] offset: i32,
+    ) -> async_graphql::Result<Connection<ProjectItem>> {
+        let page = Page::new(ctx, first, offset)?;
+        let pool = pool(ctx)?;
+        let total = queries::count_project_items(
+            pool,
+            &self.0.node_id,
+            content_type.as_deref(),
+            state.as_deref(),
+        )
+        .await
+        .map_err(gql_error)?;
+        let rows = queries::list_project_items(
+            pool,
+            &self.0.node_id,
+            content_type.as_deref(),
+            state.as_deref(),
+            Some(page.limit),
+            Some(page.offset),
+        )
+        .await
+        .map_err(gql_error)?;
+        Ok(Connection::new(
+            rows.into_iter().map(ProjectItem).collect(),
+            total,
+            page,
+        ))
+    }
+
+    /// Board metrics, grouping status by `statusField`
+    async fn metrics(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = "Status")] status_field: String,
+    ) -> async_graphql::Result<ProjectMetrics> {
+        let items = convert::project_items_from_db(pool(ctx)?, &self.0.node_id)
+            .await
+            .map_err(gql_error)?;
+        Ok(ProjectMetricsCalculator::calculate(&items, &status_field).into())
+    }
+}
+
+pub struct ProjectField(pub ProjectFieldRow);
+
+#[Object]
+impl ProjectField {
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// text, number, date, single_select, iteration or a built-in type
+    async fn field_type(&self) -> &str {
+        &self.0.field_type
+    }
+
+    /// Options of a single-select field or iterations of an iteration field
+    async fn config(&self) -> Option<async_graphql::Json<Value>> {
+        self.0.config_json.clone().map(async_graphql::Json)
+    }
+}
+
+pub struct ProjectItem(pub ProjectItemRow);
+
+#[Object]
+impl ProjectItem {
+    async fn node_id(&self) -> &str {
+        &self.0.node_id
+    }
+
+    /// issue, pull_request or draft_issue
+    async fn content_type(&self) -> &str {
+        &self.0.content_type
+    }
+
+    /// Issue or pull request number
+    async fn number(&self) -> Option<i64> {
+        self.0.content_number
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.content_title
+    }
+
+    async fn state(&self) -> Option<&str> {
+        self.0.content_state.as_deref()
+    }
+
+    async fn url(&self) -> Option<&str> {
+        self.0.content_url.as_deref()
+    }
+
+    /// Repository (owner/name) of a linked issue or pull request
+    async fn repository(&self) -> Option<&str> {
+        self.0.content_repository.as_deref()
+    }
+
+    /// Field values as stored at sync time
+    async fn field_values(&self) -> Option<async_graphql::Json<Value>> {
+        self.0.field_values_json.clone().map(async_graphql::Json)
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+}
+
+/// Aggregated metrics for the items on a project board
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectMetrics {
+    pub total_items: i64,
+    /// Linked issues and pull requests that are closed or merged
+    pub closed_items: i64,
+    /// Items per status value ("No Status" when unset)
+    pub by_status: Vec<Count>,
+    pub by_content_type: Vec<Count>,
+    pub by_assignee: Vec<Count>,
+    pub by_iteration: Vec<Count>,
+}
+
+impl From<greport_core::metrics::ProjectMetrics> for ProjectMetrics {
+    fn from(m: greport_core::metrics::ProjectMetrics) -> Self {
+        let counts = |counts: Vec<greport_core::metrics::FieldCount>| {
+            counts
+                .into_iter()
+                .map(|c| Count {
+                    key: c.value,
+                    count: c.count as i64,
+                })
+                .collect()
+        };
+        Self {
+            total_items: m.total_items as i64,
+            closed_items: m.closed_items as i64,
+            by_status: counts(m.by_status),
+            by_content_type: counts(m.by_content_type),
+            by_assignee: counts(m.by_assignee),
+            by_iteration: counts(m.by_iteration),
+        }
+    }
+}
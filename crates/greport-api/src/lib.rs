@@ -0,0 +1,734 @@
+//! greport API server library
+//!
+//! The server binary is a thin wrapper around [`build_router`]; exposing the
+//! router and state here lets integration tests drive every route against a
+//! mock GitHub client.
+
+pub mod auth;
+pub mod cache;
+mod convert;
+pub mod error;
+pub mod graphql;
+pub mod oauth;
+pub mod openapi;
+pub mod rate_limit;
+pub mod response;
+pub mod routes;
+pub mod session;
+pub mod state;
+pub mod sync;
+pub mod token_cache;
+
+use axum::{middleware, Extension, Router};
+use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
+
+pub use state::{ApiConfig, AppState};
+
+/// Build the API router with all routes and middleware
+pub fn build_router(state: AppState) -> Router {
+    // Read-only report routes
+    let read_routes = Router::new()
+        // Issues
+        .route(
+            "/repos/{owner}/{repo}/issues",
+            axum::routing::get(routes::issues::list_issues),
+        )
+        .route(
+            "/repos/{owner}/{repo}/issues/metrics",
+            axum::routing::get(routes::issues::get_metrics),
+        )
+        .route(
+            "/repos/{owner}/{repo}/issues/velocity",
+            axum::routing::get(routes::issues::get_velocity),
+        )
+        .route(
+            "/repos/{owner}/{repo}/issues/burndown",
+            axum::routing::get(routes::issues::get_burndown),
+        )
+        .route(
+            "/repos/{owner}/{repo}/issues/stale",
+            axum::routing::get(routes::issues::get_stale),
+        )
+        .route(
+            "/repos/{owner}/{repo}/issues/epics",
+            axum::routing::get(routes::issues::get_epics),
+        )
+        // Pull Requests
+        .route(
+            "/repos/{owner}/{repo}/pulls",
+            axum::routing::get(routes::pulls::list_pulls),
+        )
+        .route(
+            "/repos/{owner}/{repo}/pulls/metrics",
+            axum::routing::get(routes::pulls::get_metrics),
+        )
+        // Releases
+        .route(
+            "/repos/{owner}/{repo}/releases",
+            axum::routing::get(routes::releases::list_releases),
+        )
+        .route(
+            "/repos/{owner}/{repo}/releases/notes",
+            axum::routing::get(routes::releases::get_notes),
+        )
+        .route(
+            "/repos/{owner}/{repo}/milestones/{milestone}/progress",
+            axum::routing::get(routes::releases::get_progress),
+        )
+        // Contributors
+        .route(
+            "/repos/{owner}/{repo}/contributors",
+            axum::routing::get(routes::contrib::list_contributors),
+        )
+        // Calendar
+        .route(
+            "/repos/{owner}/{repo}/calendar",
+            axum::routing::get(routes::calendar::get_calendar),
+        )
+        // Release Plan
+        .route(
+            "/repos/{owner}/{repo}/release-plan",
+            axum::routing::get(routes::release_plan::get_release_plan),
+        )
+        // SLA
+        .route(
+            "/repos/{owner}/{repo}/sla",
+            axum::routing::get(routes::sla::get_sla_report),
+        )
+        // Projects
+        .route(
+            "/orgs/{org}/projects",
+            axum::routing::get(routes::projects::list_projects),
+        )
+        .route(
+            "/orgs/{org}/projects/{number}",
+            axum::routing::get(routes::projects::get_project),
+        )
+        .route(
+            "/orgs/{org}/projects/{number}/items",
+            axum::routing::get(routes::projects::list_project_items),
+        )
+        .route(
+            "/orgs/{org}/projects/{number}/metrics",
+            axum::routing::get(routes::projects::get_project_metrics),
+        )
+        .route(
+            "/orgs/{org}/projects/{number}/iterations",
+            axum::routing::get(routes::projects::get_project_iterations),
+        )
+        // Repository management
+        .route("/repos", axum::routing::get(routes::repos::list_repos))
+        // Organizations
+        .route("/orgs", axum::routing::get(routes::orgs::list_orgs))
+        .route(
+            "/orgs/{org}/repos",
+            axum::routing::get(routes::orgs::list_org_repos),
+        )
+        // Aggregate lists
+        .route(
+            "/aggregate/issues",
+            axum::routing::get(routes::aggregate::aggregate_issues_list),
+        )
+        .route(
+            "/aggregate/pulls",
+            axum::routing::get(routes::aggregate::aggregate_pulls_list),
+        )
+        // Aggregate metrics
+        .route(
+            "/aggregate/issues/metrics",
+            axum::routing::get(routes::aggregate::aggregate_issue_metrics),
+        )
+        .route(
+            "/aggregate/pulls/metrics",
+            axum::routing::get(routes::aggregate::aggregate_pull_metrics),
+        )
+        .route(
+            "/aggregate/contributors",
+            axum::routing::get(routes::aggregate::aggregate_contributors),
+        )
+        .route(
+            "/aggregate/velocity",
+            axum::routing::get(routes::aggregate::aggregate_velocity),
+        )
+        .route(
+            "/aggregate/projects",
+            axum::routing::get(routes::projects::aggregate_projects),
+        )
+        // Cross-org aggregate
+        .route(
+            "/aggregate/orgs/issues",
+            axum::routing::get(routes::aggregate::aggregate_org_issues),
+        )
+        .route(
+            "/aggregate/orgs/pulls",
+            axum::routing::get(routes::aggregate::aggregate_org_pulls),
+        )
+        .route(
+            "/aggregate/calendar",
+            axum::routing::get(routes::calendar::get_aggregate_calendar),
+        )
+        .route(
+            "/aggregate/release-plan",
+            axum::routing::get(routes::release_plan::get_aggregate_release_plan),
+        )
+        // Layers run bottom-up: authorize first, then serve from cache
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            cache::response_cache,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_read,
+        ));
+
+    // Routes that call out to GitHub and write to the database
+    let sync_routes = Router::new()
+        .route(
+            "/repos/{owner}/{repo}/sync",
+            axum::routing::post(routes::sync::sync_repo),
+        )
+        .route("/repos", axum::routing::post(routes::repos::add_repo))
+        .route(
+            "/repos/{owner}/{repo}",
+            axum::routing::delete(routes::repos::remove_repo),
+        )
+        // Batch sync (all tracked repos)
+        .route("/sync", axum::routing::post(routes::batch::batch_sync))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_sync,
+        ));
+
+    // API key management
+    let admin_routes = Router::new()
+        .route(
+            "/admin/api-keys",
+            axum::routing::get(routes::admin::list_api_keys).post(routes::admin::create_api_key),
+        )
+        .route(
+            "/admin/api-keys/{id}",
+            axum::routing::delete(routes::admin::revoke_api_key),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_admin,
+        ));
+
+    // Login (public; `/auth/me` reports whoever is authenticated)
+    let login_routes = Router::new()
+        .route("/auth/login", axum::routing::get(routes::login::login))
+        .route(
+            "/auth/callback",
+            axum::routing::get(routes::login::callback),
+        )
+        .route("/auth/logout", axum::routing::post(routes::login::logout))
+        .route("/auth/me", axum::routing::get(routes::login::me));
+
+    // API reference (public)
+    let docs_routes = Router::new()
+        .route("/openapi.json", axum::routing::get(openapi::openapi_json))
+        .route("/docs", axum::routing::get(openapi::docs));
+
+    // API v1 routes with middleware
+    let api_v1 = Router::new()
+        .merge(docs_routes)
+        .merge(login_routes)
+        .merge(read_routes)
+        .merge(sync_routes)
+        .merge(admin_routes)
+        // Apply middleware in reverse order (last added runs first)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::optional_auth_middleware,
+        ));
+
+    // Sessions are cookies, so the dashboard origin must be allowed to send
+    // credentials; without login any origin may call the API
+    let cors = match state
+        .oauth
+        .as_ref()
+        .and_then(|o| o.dashboard_url.as_deref())
+    {
+        Some(dashboard_url) => dashboard_cors(dashboard_url),
+        None => CorsLayer::permissive(),
+    };
+
+    // GraphQL over the synced data; scopes are checked per organization as
+    // fields resolve
+    let graphql_routes = Router::new()
+        .route(
+            "/api/graphql",
+            axum::routing::get(graphql::playground).post(graphql::graphql),
+        )
+        .layer(Extension(graphql::build_schema(&state)))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::optional_auth_middleware,
+        ));
+
+    Router::new()
+        // Health check (no auth or rate limiting)
+        .route("/health", axum::routing::get(routes::health::health_check))
+        // API v1
+        .nest("/api/v1", api_v1)
+        .merge(graphql_routes)
+        // Global middleware
+        .layer(TraceLayer::new_for_http())
+        .layer(CompressionLayer::new())
+        .layer(cors)
+        .with_state(state)
+}
+
+/// CORS policy allowing credentialed requests from the dashboard only
+fn dashboard_cors(dashboard_url: &str) -> CorsLayer {
+    use axum::http::{header, HeaderValue, Method};
+
+    let origin = dashboard_url
+        .split('/')
+        .take(3)
+        .collect::<Vec<_>>()
+        .join("/");
+    match HeaderValue::from_str(&origin) {
+        Ok(origin) => CorsLayer::new()
+            .allow_origin(origin)
+            .allow_credentials(true)
+            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]),
+        Err(_) => {
+            tracing::warn!(dashboard_url, "Invalid dashboard URL; CORS disabled");
+            CorsLayer::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthInfo, Scope};
+    use crate::state::ApiConfig;
+    use crate::token_cache::{GitHubIdentity, TokenCache};
+    use axum::extract::Request;
+    use axum::http::{Method, StatusCode};
+    use axum::middleware::Next;
+    use axum::response::Response;
+    use axum_test::TestServer;
+    use greport_core::client::Provider;
+    use greport_core::{GitHubClientRegistry, OctocrabClient, OrgEntry};
+    use std::sync::Arc;
+
+    /// Every protected route with the scope it requires
+    const ROUTES: &[(&str, &str, Scope)] = &[
+        ("GET", "/api/v1/repos/acme/app/issues", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/issues/metrics", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/issues/velocity", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/issues/burndown", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/issues/stale", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/issues/epics", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/pulls", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/pulls/metrics", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/releases", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/releases/notes", Scope::Read),
+        (
+            "GET",
+            "/api/v1/repos/acme/app/milestones/v1/progress",
+            Scope::Read,
+        ),
+        ("GET", "/api/v1/repos/acme/app/contributors", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/calendar", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/release-plan", Scope::Read),
+        ("GET", "/api/v1/repos/acme/app/sla", Scope::Read),
+        ("GET", "/api/v1/orgs/acme/projects", Scope::Read),
+        ("GET", "/api/v1/orgs/acme/projects/1", Scope::Read),
+        ("GET", "/api/v1/orgs/acme/projects/1/items", Scope::Read),
+        ("GET", "/api/v1/orgs/acme/projects/1/metrics", Scope::Read),
+        (
+            "GET",
+            "/api/v1/orgs/acme/projects/1/iterations",
+            Scope::Read,
+        ),
+        ("GET", "/api/v1/repos", Scope::Read),
+        ("GET", "/api/v1/orgs", Scope::Read),
+        ("GET", "/api/v1/orgs/acme/repos", Scope::Read),
+        ("GET", "/api/v1/aggregate/issues", Scope::Read),
+        ("GET", "/api/v1/aggregate/pulls", Scope::Read),
+        ("GET", "/api/v1/aggregate/issues/metrics", Scope::Read),
+        ("GET", "/api/v1/aggregate/pulls/metrics", Scope::Read),
+        ("GET", "/api/v1/aggregate/contributors", Scope::Read),
+        ("GET", "/api/v1/aggregate/velocity", Scope::Read),
+        ("GET", "/api/v1/aggregate/projects", Scope::Read),
+        ("GET", "/api/v1/aggregate/orgs/issues", Scope::Read),
+        ("GET", "/api/v1/aggregate/orgs/pulls", Scope::Read),
+        ("GET", "/api/v1/aggregate/calendar", Scope::Read),
+        ("GET", "/api/v1/aggregate/release-plan", Scope::Read),
+        ("POST", "/api/v1/repos/acme/app/sync", Scope::Sync),
+        ("POST", "/api/v1/repos", Scope::Sync),
+        ("DELETE", "/api/v1/repos/acme/app", Scope::Sync),
+        ("POST", "/api/v1/sync", Scope::Sync),
+        ("GET", "/api/v1/admin/api-keys", Scope::Admin),
+        ("POST", "/api/v1/admin/api-keys", Scope::Admin),
+        (
+            "DELETE",
+            "/api/v1/admin/api-keys/00000000-0000-0000-0000-000000000000",
+            Scope::Admin,
+        ),
+    ];
+
+    fn test_state(require_auth: bool) -> AppState {
+        let client = OctocrabClient::new("test-token", None).unwrap();
+        AppState::new(
+            GitHubClientRegistry::with_default(client, None),
+            ApiConfig {
+                require_auth,
+                rate_limit_per_minute: 10_000,
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    fn serve(state: AppState) -> TestServer {
+        let router = build_router(state).layer(middleware::from_fn(inject_test_key));
+        TestServer::new(router).unwrap()
+    }
+
+    fn test_server(require_auth: bool) -> TestServer {
+        serve(test_state(require_auth))
+    }
+
+    /// Stand-in for a database-backed key: scopes come from a test header
+    async fn inject_test_key(mut request: Request, next: Next) -> Response {
+        let scopes = request
+            .headers()
+            .get("x-test-scopes")
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.split(',').map(String::from).collect::<Vec<_>>());
+        if let Some(scopes) = scopes {
+            request.extensions_mut().insert(Arc::new(AuthInfo {
+                owner: "test".to_string(),
+                scopes,
+                rate_limit: 10_000,
+                github_login: None,
+                github_orgs: Vec::new(),
+                key_id: None,
+            }));
+        }
+        next.run(request).await
+    }
+
+    async fn status(
+        server: &TestServer,
+        method: &str,
+        path: &str,
+        scopes: Option<&str>,
+    ) -> (StatusCode, String) {
+        let mut request = server.method(Method::from_bytes(method.as_bytes()).unwrap(), path);
+        if let Some(scopes) = scopes {
+            request = request.add_header("x-test-scopes", scopes);
+        }
+        let response = request.await;
+        (response.status_code(), response.text())
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let server = test_server(true);
+        let (code, _) = status(&server, "GET", "/health", None).await;
+        assert_eq!(code, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_routes_reject_missing_scope() {
+        let server = test_server(false);
+        for (method, path, scope) in ROUTES {
+            let granted = match scope {
+                Scope::Read => "org:acme",
+                Scope::Sync => "read",
+                Scope::Admin => "sync",
+            };
+            let (code, body) = status(&server, method, path, Some(granted)).await;
+            assert_eq!(code, StatusCode::FORBIDDEN, "{} {}", method, path);
+            assert!(
+                body.contains(&format!("'{}'", scope.as_str())),
+                "{} {}: {}",
+                method,
+                path,
+                body
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_enforce_org_restriction() {
+        let server = test_server(false);
+        for (method, path, _) in ROUTES {
+            let (code, body) = status(&server, method, path, Some("admin,org:other")).await;
+            assert_eq!(code, StatusCode::FORBIDDEN, "{} {}", method, path);
+            if path.contains("/acme") {
+                assert!(body.contains("org:acme"), "{} {}: {}", method, path, body);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_require_auth() {
+        let server = test_server(true);
+        for (method, path, _) in ROUTES {
+            let (code, _) = status(&server, method, path, None).await;
+            assert_eq!(code, StatusCode::UNAUTHORIZED, "{} {}", method, path);
+        }
+
+        // Sync and admin routes never allow anonymous access
+        let server = test_server(false);
+        for (method, path, scope) in ROUTES {
+            let (code, _) = status(&server, method, path, None).await;
+            if *scope == Scope::Read {
+                assert_ne!(code, StatusCode::UNAUTHORIZED, "{} {}", method, path);
+            } else {
+                assert_eq!(code, StatusCode::UNAUTHORIZED, "{} {}", method, path);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_allow_granted_scope() {
+        // Without a database these handlers stop at a 400, past the scope check
+        let server = test_server(true);
+        let cases = [
+            ("GET", "/api/v1/repos", "read"),
+            ("GET", "/api/v1/orgs/acme/projects", "read,org:acme"),
+            ("DELETE", "/api/v1/repos/acme/app", "sync"),
+            ("GET", "/api/v1/admin/api-keys", "admin"),
+        ];
+        for (method, path, scopes) in cases {
+            let (code, body) = status(&server, method, path, Some(scopes)).await;
+            assert_eq!(
+                code,
+                StatusCode::BAD_REQUEST,
+                "{} {}: {}",
+                method,
+                path,
+                body
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_tokens_are_verified() {
+        let cache = TokenCache::default();
+        cache
+            .insert(greport_db::hash_api_key("ghp_bogus"), None)
+            .await;
+        let identity = Arc::new(GitHubIdentity {
+            login: "octocat".to_string(),
+            orgs: vec!["acme".to_string()],
+            client: Arc::new(OctocrabClient::new("ghp_valid", None).unwrap()),
+        });
+        cache
+            .insert(greport_db::hash_api_key("ghp_valid"), Some(identity))
+            .await;
+        let org = |name: &str| OrgEntry {
+            name: name.to_string(),
+            base_url: None,
+            repo_count: 0,
+            repo_names: Vec::new(),
+            has_token: true,
+            provider: Provider::GitHub,
+        };
+        let client = || OctocrabClient::new("test-token", None).unwrap();
+        let registry = GitHubClientRegistry::with_default(client(), None)
+            .with_org(org("acme"), client())
+            .with_org(org("other"), client());
+        let server = serve(AppState {
+            token_cache: Arc::new(cache),
+            ..AppState::new(
+                registry,
+                ApiConfig {
+                    rate_limit_per_minute: 10_000,
+                    ..Default::default()
+                },
+                None,
+            )
+        });
+
+        let get = |path: &'static str, token: &'static str| {
+            server
+                .get(path)
+                .add_header("authorization", format!("Bearer {}", token))
+        };
+
+        // A rejected token is not downgraded to anonymous access
+        let response = get("/api/v1/repos", "ghp_bogus").await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+        let response = get("/api/v1/orgs/acme/projects", "ghp_valid").await;
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+        let response = get("/api/v1/orgs/other/projects", "ghp_valid").await;
+        assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+        assert!(response.text().contains("octocat"));
+
+        // Cross-org listings only show the caller's orgs
+        let response = get("/api/v1/orgs", "ghp_valid").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.header("x-cache"), "BYPASS");
+        let names: Vec<String> = response.json::<serde_json::Value>()["data"]["orgs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|org| org["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["acme"]);
+    }
+
+    /// Log in against a mock OIDC provider and use the session cookie
+    #[tokio::test]
+    async fn test_oidc_login_flow() {
+        use wiremock::matchers::{body_string_contains, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let idp = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("code=good-code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "idp-token",
+                "token_type": "Bearer"
+            })))
+            .mount(&idp)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/userinfo"))
+            .and(header("authorization", "Bearer idp-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sub": "1234",
+                "preferred_username": "alice",
+                "groups": ["acme", "staff"]
+            })))
+            .mount(&idp)
+            .await;
+
+        let config = greport_core::Config {
+            oauth: greport_core::config::OAuthConfig {
+                provider: Some("oidc".to_string()),
+                client_id: Some("greport".to_string()),
+                client_secret: Some("client-secret".to_string()),
+                authorize_url: Some(format!("{}/authorize", idp.uri())),
+                token_url: Some(format!("{}/token", idp.uri())),
+                userinfo_url: Some(format!("{}/userinfo", idp.uri())),
+                redirect_url: Some("http://localhost:9423/api/v1/auth/callback".to_string()),
+                session_secret: Some("0123456789abcdef0123456789abcdef".to_string()),
+                allowed_orgs: vec!["acme".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let oauth = crate::oauth::OAuthSettings::from_config(&config)
+            .unwrap()
+            .unwrap();
+        let server = serve(AppState {
+            oauth: Some(Arc::new(oauth)),
+            ..test_state(true)
+        });
+
+        // Login redirects to the provider with a CSRF state cookie
+        let response = server.get("/api/v1/auth/login").await;
+        assert_eq!(response.status_code(), StatusCode::SEE_OTHER);
+        let location = response.header("location").to_str().unwrap().to_string();
+        assert!(location.starts_with(&format!("{}/authorize", idp.uri())));
+        let csrf = location.split("state=").nth(1).unwrap().to_string();
+        let state_cookie = format!("greport_oauth_state={}", csrf);
+
+        // A mismatched state is rejected
+        let response = server
+            .get("/api/v1/auth/callback?code=good-code&state=forged")
+            .add_header("cookie", state_cookie.clone())
+            .await;
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+        // The callback exchanges the code and sets the session cookie
+        let response = server
+            .get(&format!(
+                "/api/v1/auth/callback?code=good-code&state={}",
+                csrf
+            ))
+            .add_header("cookie", state_cookie)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::SEE_OTHER);
+        let session_cookie = response
+            .iter_headers_by_name("set-cookie")
+            .filter_map(|h| h.to_str().ok())
+            .find(|c| c.starts_with("greport_session="))
+            .and_then(|c| c.split(';').next())
+            .unwrap()
+            .to_string();
+        let token = session_cookie
+            .trim_start_matches("greport_session=")
+            .to_string();
+
+        let response = server
+            .get("/api/v1/auth/me")
+            .add_header("cookie", session_cookie.clone())
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let me: serde_json::Value = response.json();
+        assert_eq!(me["data"]["login"], "alice");
+        assert_eq!(
+            me["data"]["scopes"],
+            serde_json::json!(["read", "org:acme"])
+        );
+
+        // The session works as a bearer token and is limited to its orgs
+        let bearer = format!("Bearer {}", token);
+        let response = server
+            .get("/api/v1/orgs/acme/projects")
+            .add_header("authorization", bearer.clone())
+            .await;
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+        let response = server
+            .get("/api/v1/orgs/other/projects")
+            .add_header("authorization", bearer)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+        // A tampered session is rejected
+        let response = server
+            .get("/api/v1/auth/me")
+            .add_header("cookie", format!("{}x", session_cookie))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_read_routes_support_etags() {
+        let server = test_server(false);
+
+        let response = server.get("/api/v1/orgs").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let etag = response.header("etag").to_str().unwrap().to_string();
+        assert_eq!(response.header("cache-control"), "private, max-age=300");
+        // No database: served fresh, not stored
+        assert_eq!(response.header("x-cache"), "BYPASS");
+
+        let response = server
+            .get("/api/v1/orgs")
+            .add_header("if-none-match", etag.clone())
+            .await;
+        assert_eq!(response.status_code(), StatusCode::NOT_MODIFIED);
+        assert!(response.text().is_empty());
+        assert_eq!(response.header("etag"), etag.as_str());
+
+        let response = server
+            .get("/api/v1/orgs")
+            .add_header("if-none-match", "\"stale\"")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+}
@@ -1,23 +1,12 @@
 //! greport API Server
 
-mod auth;
-mod convert;
-mod error;
-mod rate_limit;
-mod response;
-mod routes;
-mod state;
-mod sync;
-
-use axum::{middleware, Router};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::signal;
-use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use rate_limit::start_cleanup_task;
-use state::AppState;
+use greport_api::rate_limit::start_cleanup_task;
+use greport_api::{build_router, cache, token_cache, AppState};
 
 #[tokio::main]
 async fn main() -> std::process::ExitCode {
@@ -109,6 +98,10 @@ async fn run() -> anyhow::Result<()> {
 
     // Start rate limiter cleanup task
     start_cleanup_task(Arc::clone(&state.rate_limiter));
+    token_cache::start_cleanup_task(Arc::clone(&state.token_cache));
+    if let Some(ref pool) = state.db {
+        cache::start_cleanup_task(pool.clone());
+    }
 
     // Build router with state
     let app = build_router(state);
@@ -131,180 +124,6 @@ async fn run() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn build_router(state: AppState) -> Router {
-    // API v1 routes with middleware
-    let api_v1 = Router::new()
-        // Issues
-        .route(
-            "/repos/{owner}/{repo}/issues",
-            axum::routing::get(routes::issues::list_issues),
-        )
-        .route(
-            "/repos/{owner}/{repo}/issues/metrics",
-            axum::routing::get(routes::issues::get_metrics),
-        )
-        .route(
-            "/repos/{owner}/{repo}/issues/velocity",
-            axum::routing::get(routes::issues::get_velocity),
-        )
-        .route(
-            "/repos/{owner}/{repo}/issues/burndown",
-            axum::routing::get(routes::issues::get_burndown),
-        )
-        .route(
-            "/repos/{owner}/{repo}/issues/stale",
-            axum::routing::get(routes::issues::get_stale),
-        )
-        // Pull Requests
-        .route(
-            "/repos/{owner}/{repo}/pulls",
-            axum::routing::get(routes::pulls::list_pulls),
-        )
-        .route(
-            "/repos/{owner}/{repo}/pulls/metrics",
-            axum::routing::get(routes::pulls::get_metrics),
-        )
-        // Releases
-        .route(
-            "/repos/{owner}/{repo}/releases",
-            axum::routing::get(routes::releases::list_releases),
-        )
-        .route(
-            "/repos/{owner}/{repo}/releases/notes",
-            axum::routing::get(routes::releases::get_notes),
-        )
-        .route(
-            "/repos/{owner}/{repo}/milestones/{milestone}/progress",
-            axum::routing::get(routes::releases::get_progress),
-        )
-        // Contributors
-        .route(
-            "/repos/{owner}/{repo}/contributors",
-            axum::routing::get(routes::contrib::list_contributors),
-        )
-        // Calendar
-        .route(
-            "/repos/{owner}/{repo}/calendar",
-            axum::routing::get(routes::calendar::get_calendar),
-        )
-        // Release Plan
-        .route(
-            "/repos/{owner}/{repo}/release-plan",
-            axum::routing::get(routes::release_plan::get_release_plan),
-        )
-        // SLA
-        .route(
-            "/repos/{owner}/{repo}/sla",
-            axum::routing::get(routes::sla::get_sla_report),
-        )
-        // Projects
-        .route(
-            "/orgs/{org}/projects",
-            axum::routing::get(routes::projects::list_projects),
-        )
-        .route(
-            "/orgs/{org}/projects/{number}",
-            axum::routing::get(routes::projects::get_project),
-        )
-        .route(
-            "/orgs/{org}/projects/{number}/items",
-            axum::routing::get(routes::projects::list_project_items),
-        )
-        .route(
-            "/orgs/{org}/projects/{number}/metrics",
-            axum::routing::get(routes::projects::get_project_metrics),
-        )
-        // Sync
-        .route(
-            "/repos/{owner}/{repo}/sync",
-            axum::routing::post(routes::sync::sync_repo),
-        )
-        // Repository management
-        .route(
-            "/repos",
-            axum::routing::get(routes::repos::list_repos).post(routes::repos::add_repo),
-        )
-        .route(
-            "/repos/{owner}/{repo}",
-            axum::routing::delete(routes::repos::remove_repo),
-        )
-        // Batch sync (all tracked repos)
-        .route("/sync", axum::routing::post(routes::batch::batch_sync))
-        // Organizations
-        .route("/orgs", axum::routing::get(routes::orgs::list_orgs))
-        .route(
-            "/orgs/{org}/repos",
-            axum::routing::get(routes::orgs::list_org_repos),
-        )
-        // Aggregate lists
-        .route(
-            "/aggregate/issues",
-            axum::routing::get(routes::aggregate::aggregate_issues_list),
-        )
-        .route(
-            "/aggregate/pulls",
-            axum::routing::get(routes::aggregate::aggregate_pulls_list),
-        )
-        // Aggregate metrics
-        .route(
-            "/aggregate/issues/metrics",
-            axum::routing::get(routes::aggregate::aggregate_issue_metrics),
-        )
-        .route(
-            "/aggregate/pulls/metrics",
-            axum::routing::get(routes::aggregate::aggregate_pull_metrics),
-        )
-        .route(
-            "/aggregate/contributors",
-            axum::routing::get(routes::aggregate::aggregate_contributors),
-        )
-        .route(
-            "/aggregate/velocity",
-            axum::routing::get(routes::aggregate::aggregate_velocity),
-        )
-        .route(
-            "/aggregate/projects",
-            axum::routing::get(routes::projects::aggregate_projects),
-        )
-        // Cross-org aggregate
-        .route(
-            "/aggregate/orgs/issues",
-            axum::routing::get(routes::aggregate::aggregate_org_issues),
-        )
-        .route(
-            "/aggregate/orgs/pulls",
-            axum::routing::get(routes::aggregate::aggregate_org_pulls),
-        )
-        .route(
-            "/aggregate/calendar",
-            axum::routing::get(routes::calendar::get_aggregate_calendar),
-        )
-        .route(
-            "/aggregate/release-plan",
-            axum::routing::get(routes::release_plan::get_aggregate_release_plan),
-        )
-        // Apply middleware in reverse order (last added runs first)
-        .layer(middleware::from_fn_with_state(
-            state.clone(),
-            rate_limit::rate_limit_middleware,
-        ))
-        .layer(middleware::from_fn_with_state(
-            state.clone(),
-            auth::optional_auth_middleware,
-        ));
-
-    Router::new()
-        // Health check (no auth or rate limiting)
-        .route("/health", axum::routing::get(routes::health::health_check))
-        // API v1
-        .nest("/api/v1", api_v1)
-        // Global middleware
-        .layer(TraceLayer::new_for_http())
-        .layer(CompressionLayer::new())
-        .layer(CorsLayer::permissive())
-        .with_state(state)
-}
-
 /// Shutdown signal handler for graceful shutdown
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -333,14 +152,3 @@ async fn shutdown_signal() {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    #[tokio::test]
-    async fn test_health_check() {
-        // This test would need a mock state
-        // Verifies the test infrastructure works
-        let health_endpoint = "/health";
-        assert_eq!(health_endpoint, "/health");
-    }
-}
@@ -0,0 +1,420 @@
+//! OAuth2/OIDC login provider
+//!
+//! Supports a GitHub OAuth App or a generic OIDC provider. After the code
+//! exchange the user's login and organizations are mapped to API scopes and
+//! a signed session is issued (see [`crate::session`]).
+
+use chrono::Duration;
+use greport_core::config::OAuthConfig;
+use greport_core::Config;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::ApiError;
+use crate::session::SessionKeys;
+
+const GITHUB_AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
+const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Identity provider flavour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// GitHub OAuth App; orgs come from `/user/orgs`
+    GitHub,
+    /// Generic OIDC provider; orgs come from a userinfo claim
+    Oidc,
+}
+
+/// User identity returned by the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginIdentity {
+    pub login: String,
+    pub orgs: Vec<String>,
+}
+
+/// Resolved login settings
+#[derive(Debug)]
+pub struct OAuthSettings {
+    pub provider: Provider,
+    client_id: String,
+    client_secret: String,
+    authorize_url: reqwest::Url,
+    token_url: String,
+    userinfo_url: String,
+    /// GitHub only: endpoint listing the user's organizations
+    orgs_url: Option<String>,
+    redirect_url: String,
+    scopes: Vec<String>,
+    org_claim: String,
+    /// Where to send the browser after login
+    pub dashboard_url: Option<String>,
+    allowed_orgs: Vec<String>,
+    default_scopes: Vec<String>,
+    org_scopes: Vec<(String, Vec<String>)>,
+    admin_users: Vec<String>,
+    /// Session signing keys
+    pub sessions: SessionKeys,
+    /// Mark cookies `Secure` (when the redirect URL is https)
+    pub secure_cookies: bool,
+    http: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OrgLogin {
+    login: String,
+}
+
+impl OAuthSettings {
+    /// Resolve settings from the `[oauth]` config section.
+    ///
+    /// Returns `Ok(None)` when login is not configured (no client ID).
+    pub fn from_config(config: &Config) -> anyhow::Result<Option<Self>> {
+        let oauth: &OAuthConfig = &config.oauth;
+        let Some(client_id) = oauth.client_id.clone() else {
+            return Ok(None);
+        };
+        let client_secret = config.oauth_client_secret()?.ok_or_else(|| {
+            anyhow::anyhow!("OAuth login needs a client secret (OAUTH_CLIENT_SECRET)")
+        })?;
+        let session_secret = config.session_secret()?.ok_or_else(|| {
+            anyhow::anyhow!("OAuth login needs a session secret (SESSION_SECRET)")
+        })?;
+        if session_secret.len() < 32 {
+            anyhow::bail!("Session secret must be at least 32 characters");
+        }
+        let redirect_url = oauth
+            .redirect_url
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("OAuth login needs a redirect_url"))?;
+        // Without it, any account on the provider could read every org
+        if oauth.allowed_orgs.is_empty() {
+            anyhow::bail!(
+                "OAuth login needs allowed_orgs: the organizations (or OIDC groups) whose members may log in"
+            );
+        }
+
+        let provider = match oauth.provider.as_deref().unwrap_or("github") {
+            "github" => Provider::GitHub,
+            "oidc" => Provider::Oidc,
+            other => anyhow::bail!("Unknown OAuth provider '{}': use github or oidc", other),
+        };
+
+        let (authorize_url, token_url, userinfo_url, orgs_url, scopes) = match provider {
+            Provider::GitHub => {
+                let api = config
+                    .github
+                    .base_url
+                    .as_deref()
+                    .unwrap_or(GITHUB_API_URL)
+                    .trim_end_matches('/');
+                (
+                    oauth
+                        .authorize_url
+                        .clone()
+                        .unwrap_or_else(|| GITHUB_AUTHORIZE_URL.to_string()),
+                    oauth
+                        .token_url
+                        .clone()
+                        .unwrap_or_else(|| GITHUB_TOKEN_URL.to_string()),
+                    oauth
+                        .userinfo_url
+                        .clone()
+                        .unwrap_or_else(|| format!("{api}/user")),
+                    Some(format!("{api}/user/orgs")),
+                    vec!["read:user".to_string(), "read:org".to_string()],
+                )
+            }
+            Provider::Oidc => {
+                let required = |value: &Option<String>, name: &str| {
+                    value
+                        .clone()
+                        .ok_or_else(|| anyhow::anyhow!("OIDC login needs {}", name))
+                };
+                (
+                    required(&oauth.authorize_url, "authorize_url")?,
+                    required(&oauth.token_url, "token_url")?,
+                    required(&oauth.userinfo_url, "userinfo_url")?,
+                    None,
+                    vec![
+                        "openid".to_string(),
+                        "profile".to_string(),
+                        "email".to_string(),
+                    ],
+                )
+            }
+        };
+
+        let authorize_url = reqwest::Url::parse(&authorize_url)
+            .map_err(|e| anyhow::anyhow!("Invalid authorize_url '{}': {}", authorize_url, e))?;
+
+        let ttl_hours = oauth.session_ttl_hours.unwrap_or(12);
+        if ttl_hours <= 0 {
+            anyhow::bail!("session_ttl_hours must be positive");
+        }
+
+        Ok(Some(Self {
+            provider,
+            client_id,
+            client_secret,
+            authorize_url,
+            token_url,
+            userinfo_url,
+            orgs_url,
+            secure_cookies: redirect_url.starts_with("https://"),
+            redirect_url,
+            scopes: oauth.scopes.clone().unwrap_or(scopes),
+            org_claim: oauth
+                .org_claim
+                .clone()
+                .unwrap_or_else(|| "groups".to_string()),
+            dashboard_url: oauth.dashboard_url.clone(),
+            allowed_orgs: oauth.allowed_orgs.clone(),
+            default_scopes: oauth
+                .default_scopes
+                .clone()
+                .unwrap_or_else(|| vec!["read".to_string()]),
+            org_scopes: oauth
+                .org_scopes
+                .iter()
+                .map(|(org, scopes)| (org.clone(), scopes.clone()))
+                .collect(),
+            admin_users: oauth.admin_users.clone(),
+            sessions: SessionKeys::new(session_secret.as_bytes(), Duration::hours(ttl_hours)),
+            http: reqwest::Client::builder()
+                .user_agent(concat!("greport/", env!("CARGO_PKG_VERSION")))
+                .timeout(std::time::Duration::from_secs(15))
+                .build()?,
+        }))
+    }
+
+    /// Provider URL to send the browser to, carrying the CSRF `state`
+    pub fn authorize_redirect(&self, state: &str) -> String {
+        let mut url = self.authorize_url.clone();
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_url)
+            .append_pair("scope", &self.scopes.join(" "))
+            .append_pair("state", state);
+        url.into()
+    }
+
+    /// Exchange an authorization code for a provider access token
+    pub async fn exchange_code(&self, code: &str) -> Result<String, ApiError> {
+        let response = self
+            .http
+            .post(&self.token_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &self.redirect_url),
+                ("client_id", &self.client_id),
+                ("client_secret", &self.client_secret),
+            ])
+            .send()
+            .await
+            .map_err(provider_unreachable)?;
+
+        let status = response.status();
+        let body: TokenResponse = response.json().await.map_err(provider_unreachable)?;
+        match body.access_token {
+            Some(token) if status.is_success() => Ok(token),
+            _ => {
+                let reason = body
+                    .error_description
+                    .or(body.error)
+                    .unwrap_or_else(|| status.to_string());
+                tracing::warn!("OAuth code exchange failed: {}", reason);
+                Err(ApiError::Unauthorized)
+            }
+        }
+    }
+
+    /// Look up the user's login and organizations with a provider token
+    pub async fn fetch_identity(&self, access_token: &str) -> Result<LoginIdentity, ApiError> {
+        let userinfo: Value = self.get_json(&self.userinfo_url, access_token).await?;
+
+        let login = ["login", "preferred_username", "email", "sub"]
+            .iter()
+            .find_map(|claim| userinfo.get(*claim).and_then(Value::as_str))
+            .ok_or_else(|| ApiError::Internal("Provider returned no user login".to_string()))?
+            .to_string();
+
+        let orgs = match (self.provider, &self.orgs_url) {
+            (Provider::GitHub, Some(orgs_url)) => {
+                let orgs: Vec<OrgLogin> = self.get_json(orgs_url, access_token).await?;
+                orgs.into_iter().map(|o| o.login).collect()
+            }
+            _ => match userinfo.get(&self.org_claim) {
+                Some(Value::Array(values)) => values
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(String::from)
+                    .collect(),
+                Some(Value::String(value)) => vec![value.clone()],
+                _ => Vec::new(),
+            },
+        };
+
+        Ok(LoginIdentity { login, orgs })
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<T, ApiError> {
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(access_token)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(provider_unreachable)?;
+        if !response.status().is_success() {
+            tracing::warn!(url, status = %response.status(), "OAuth provider request failed");
+            return Err(ApiError::Unauthorized);
+        }
+        response.json().await.map_err(provider_unreachable)
+    }
+
+    /// Map a provider identity to the orgs and scopes recorded on its
+    /// session.
+    ///
+    /// Users in `admin_users` get `admin`. Other users must belong to one of
+    /// `allowed_orgs`, and get `default_scopes` plus `org_scopes` for orgs
+    /// they belong to, restricted to their allowed orgs with `org:` scopes.
+    pub fn grant(&self, identity: &LoginIdentity) -> Result<(Vec<String>, Vec<String>), ApiError> {
+        let member_of = |org: &str| identity.orgs.iter().any(|o| o.eq_ignore_ascii_case(org));
+
+        if self
+            .admin_users
+            .iter()
+            .any(|u| u.eq_ignore_ascii_case(&identity.login))
+        {
+            return Ok((identity.orgs.clone(), vec!["admin".to_string()]));
+        }
+
+        let orgs: Vec<String> = self
+            .allowed_orgs
+            .iter()
+            .filter(|org| member_of(org))
+            .cloned()
+            .collect();
+        if orgs.is_empty() {
+            return Err(ApiError::Forbidden(format!(
+                "User '{}' is not a member of an allowed organization",
+                identity.login
+            )));
+        }
+
+        let mut scopes = self.default_scopes.clone();
+        for (org, extra) in &self.org_scopes {
+            if member_of(org) {
+                scopes.extend(extra.iter().cloned());
+            }
+        }
+        scopes.extend(orgs.iter().map(|org| format!("org:{org}")));
+        let mut seen = std::collections::HashSet::new();
+        scopes.retain(|s| seen.insert(s.clone()));
+
+        Ok((orgs, scopes))
+    }
+}
+
+fn provider_unreachable(e: reqwest::Error) -> ApiError {
+    tracing::warn!("OAuth provider request failed: {}", e);
+    ApiError::Internal("Login provider unavailable".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn oauth_config() -> OAuthConfig {
+        OAuthConfig {
+            client_id: Some("greport".to_string()),
+            client_secret: Some("secret".to_string()),
+            session_secret: Some("0123456789abcdef0123456789abcdef".to_string()),
+            redirect_url: Some("http://localhost:9423/api/v1/auth/callback".to_string()),
+            allowed_orgs: vec!["acme".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn settings(oauth: OAuthConfig) -> OAuthSettings {
+        let config = Config {
+            oauth,
+            ..Default::default()
+        };
+        OAuthSettings::from_config(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_oauth_disabled_without_client_id() {
+        let config = Config::default();
+        assert!(OAuthSettings::from_config(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_oauth_requires_allowed_orgs() {
+        let config = Config {
+            oauth: OAuthConfig {
+                allowed_orgs: Vec::new(),
+                ..oauth_config()
+            },
+            ..Default::default()
+        };
+        let err = OAuthSettings::from_config(&config).err().unwrap();
+        assert!(err.to_string().contains("allowed_orgs"));
+    }
+
+    #[test]
+    fn test_authorize_redirect() {
+        let settings = settings(oauth_config());
+        assert_eq!(settings.provider, Provider::GitHub);
+
+        let url = settings.authorize_redirect("xyz");
+        assert!(url.starts_with(GITHUB_AUTHORIZE_URL));
+        assert!(url.contains("client_id=greport"));
+        assert!(url.contains("state=xyz"));
+        assert!(url.contains("scope=read%3Auser+read%3Aorg"));
+    }
+
+    #[test]
+    fn test_grant_scopes() {
+        let settings = settings(OAuthConfig {
+            allowed_orgs: vec!["acme".to_string(), "globex".to_string()],
+            admin_users: vec!["root".to_string()],
+            org_scopes: HashMap::from([("acme".to_string(), vec!["sync".to_string()])]),
+            ..oauth_config()
+        });
+        let user = |login: &str, orgs: &[&str]| LoginIdentity {
+            login: login.to_string(),
+            orgs: orgs.iter().map(|o| o.to_string()).collect(),
+        };
+
+        let (orgs, scopes) = settings.grant(&user("alice", &["ACME", "other"])).unwrap();
+        assert_eq!(orgs, vec!["acme"]);
+        assert_eq!(scopes, vec!["read", "sync", "org:acme"]);
+
+        let (_, scopes) = settings.grant(&user("root", &[])).unwrap();
+        assert_eq!(scopes, vec!["admin"]);
+
+        assert!(matches!(
+            settings.grant(&user("mallory", &["other"])),
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+}
@@ -0,0 +1,261 @@
+//! OpenAPI document and API reference page
+//!
+//! The document is generated from the `#[utoipa::path]` attributes on the
+//! route handlers and the schemas derived on their request and response
+//! types. Every route registered in `build_router` must be listed in
+//! [`ApiDoc`]; a test fails otherwise.
+
+use axum::{response::Html, Json};
+use utoipa::openapi::security::{
+    ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+};
+use utoipa::{Modify, OpenApi};
+
+use crate::error::ErrorResponse;
+use crate::graphql;
+use crate::routes;
+use crate::session::SESSION_COOKIE;
+
+/// OpenAPI document for the REST API
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "greport API",
+        description = "GitHub reporting and analytics. Responses are wrapped in `data`; \
+                       paginated lists add `meta`."
+    ),
+    paths(
+        routes::health::health_check,
+        openapi_json,
+        docs,
+        routes::login::login,
+        routes::login::callback,
+        routes::login::logout,
+        routes::login::me,
+        routes::issues::list_issues,
+        routes::issues::get_metrics,
+        routes::issues::get_velocity,
+        routes::issues::get_burndown,
+        routes::issues::get_stale,
+        routes::issues::get_epics,
+        routes::pulls::list_pulls,
+        routes::pulls::get_metrics,
+        routes::releases::list_releases,
+        routes::releases::get_notes,
+        routes::releases::get_progress,
+        routes::contrib::list_contributors,
+        routes::calendar::get_calendar,
+        routes::release_plan::get_release_plan,
+        routes::sla::get_sla_report,
+        routes::projects::list_projects,
+        routes::projects::get_project,
+        routes::projects::list_project_items,
+        routes::projects::get_project_metrics,
+        routes::projects::get_project_iterations,
+        routes::repos::list_repos,
+        routes::orgs::list_orgs,
+        routes::orgs::list_org_repos,
+        routes::aggregate::aggregate_issues_list,
+        routes::aggregate::aggregate_pulls_list,
+        routes::aggregate::aggregate_issue_metrics,
+        routes::aggregate::aggregate_pull_metrics,
+        routes::aggregate::aggregate_contributors,
+        routes::aggregate::aggregate_velocity,
+        routes::projects::aggregate_projects,
+        routes::aggregate::aggregate_org_issues,
+        routes::aggregate::aggregate_org_pulls,
+        routes::calendar::get_aggregate_calendar,
+        routes::release_plan::get_aggregate_release_plan,
+        routes::sync::sync_repo,
+        routes::repos::add_repo,
+        routes::repos::remove_repo,
+        routes::batch::batch_sync,
+        routes::admin::list_api_keys,
+        routes::admin::create_api_key,
+        routes::admin::revoke_api_key,
+        graphql::graphql,
+        graphql::playground,
+    ),
+    components(schemas(ErrorResponse)),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "issues", description = "Issue lists, metrics and reports for a repository"),
+        (name = "pulls", description = "Pull request lists and metrics for a repository"),
+        (name = "releases", description = "Releases, release notes and milestone progress"),
+        (name = "contributors", description = "Contributor statistics"),
+        (name = "calendar", description = "Calendar of issue, PR and release events"),
+        (name = "release-plan", description = "Upcoming and recent releases"),
+        (name = "sla", description = "SLA compliance"),
+        (name = "projects", description = "GitHub Projects V2 (synced to the database)"),
+        (name = "repositories", description = "Tracked repositories"),
+        (name = "organizations", description = "Configured organizations"),
+        (name = "aggregate", description = "Reports across all tracked repositories"),
+        (name = "sync", description = "Sync GitHub data into the database (sync scope)"),
+        (name = "admin", description = "API key management (admin scope)"),
+        (name = "auth", description = "OAuth/OIDC login sessions"),
+        (name = "graphql", description = "GraphQL queries over the synced data"),
+        (name = "health", description = "Liveness"),
+        (name = "docs", description = "This document"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the bearer and session-cookie schemes and requires either by
+/// default; public routes opt out with `security(())`
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("API key, GitHub token (passthrough) or session token"))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "session",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(SESSION_COOKIE))),
+        );
+        openapi.security = Some(vec![
+            SecurityRequirement::new("bearer", Vec::<String>::new()),
+            SecurityRequirement::new("session", Vec::<String>::new()),
+        ]);
+    }
+}
+
+/// GET /api/v1/openapi.json - OpenAPI document
+#[utoipa::path(
+    get,
+    path = "/api/v1/openapi.json",
+    tag = "docs",
+    responses((status = 200, description = "OpenAPI 3 document", content_type = "application/json")),
+    security(()),
+)]
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// GET /api/v1/docs - API reference (Redoc)
+#[utoipa::path(
+    get,
+    path = "/api/v1/docs",
+    tag = "docs",
+    responses((status = 200, description = "API reference page", content_type = "text/html")),
+    security(()),
+)]
+pub async fn docs() -> Html<&'static str> {
+    Html(DOCS_PAGE)
+}
+
+/// Redoc page rendering the document served next to it
+const DOCS_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>greport API</title>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+  </head>
+  <body>
+    <redoc spec-url="openapi.json"></redoc>
+    <script src="https://cdn.redoc.ly/redoc/latest/bundles/redoc.standalone.js"></script>
+  </body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// `(method, path)` for every route registered in `build_router`, read
+    /// from its source
+    fn registered_routes() -> BTreeSet<(String, String)> {
+        let source = include_str!("lib.rs");
+        let start = source.find("pub fn build_router").unwrap();
+        let end = source[start..].find("\n}\n").unwrap() + start;
+        let body = &source[start..end];
+
+        let mut routes = BTreeSet::new();
+        for (offset, _) in body.match_indices(".route(") {
+            let call = &body[offset + ".route(".len()..];
+            let mut depth = 1;
+            let len = call
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .map(|(i, _)| i)
+                .unwrap();
+            let call = &call[..len];
+
+            let path = call.split('"').nth(1).unwrap();
+            let path = if path == "/health" || path.starts_with("/api/") {
+                path.to_string()
+            } else {
+                format!("/api/v1{}", path)
+            };
+            for method in ["get", "post", "put", "patch", "delete"] {
+                let routed = call.contains(&format!("routing::{}(", method))
+                    || call.contains(&format!(".{}(", method));
+                if routed {
+                    routes.insert((method.to_string(), path.clone()));
+                }
+            }
+        }
+        routes
+    }
+
+    /// `(method, path)` for every operation in the document
+    fn documented_routes() -> BTreeSet<(String, String)> {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut routes = BTreeSet::new();
+        for (path, item) in doc["paths"].as_object().unwrap() {
+            for method in item.as_object().unwrap().keys() {
+                routes.insert((method.clone(), path.clone()));
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let registered = registered_routes();
+        let documented = documented_routes();
+        assert!(registered.len() > 40, "route scan found {:?}", registered);
+
+        let undocumented: Vec<_> = registered.difference(&documented).collect();
+        assert!(
+            undocumented.is_empty(),
+            "routes missing from ApiDoc: {:?}",
+            undocumented
+        );
+        let unrouted: Vec<_> = documented.difference(&registered).collect();
+        assert!(
+            unrouted.is_empty(),
+            "ApiDoc entries with no route: {:?}",
+            unrouted
+        );
+    }
+
+    #[test]
+    fn test_document_references_resolve() {
+        let doc = serde_json::to_string(&ApiDoc::openapi()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&doc).unwrap();
+        let schemas = value["components"]["schemas"].as_object().unwrap();
+
+        for (offset, _) in doc.match_indices("#/components/schemas/") {
+            let rest = &doc[offset + "#/components/schemas/".len()..];
+            let name = &rest[..rest.find('"').unwrap()];
+            assert!(schemas.contains_key(name), "missing schema {}", name);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Rate limiting middleware
 
+use async_trait::async_trait;
 use axum::{
     extract::{ConnectInfo, Request, State},
     http::StatusCode,
@@ -7,6 +8,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use greport_db::DbPool;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -17,6 +19,53 @@ use tokio::sync::RwLock;
 use crate::auth::AuthInfo;
 use crate::state::AppState;
 
+/// Storage for rate limit counters
+#[async_trait]
+pub trait RateLimitBackend: Send + Sync + std::fmt::Debug {
+    /// Count a request for `client_id` and decide whether it is allowed
+    async fn check(&self, client_id: &str, limit: u32) -> RateLimitResult;
+
+    /// Drop expired counters
+    async fn cleanup(&self);
+}
+
+/// Rate limiter applying per-client limits through a backend
+#[derive(Debug)]
+pub struct RateLimiter {
+    backend: Arc<dyn RateLimitBackend>,
+    /// Default requests per minute
+    default_limit: u32,
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter with process-local counters
+    pub fn new(default_limit: u32) -> Self {
+        Self::with_backend(default_limit, Arc::new(MemoryBackend::new(WINDOW)))
+    }
+
+    /// Create a rate limiter using the given backend
+    pub fn with_backend(default_limit: u32, backend: Arc<dyn RateLimitBackend>) -> Self {
+        Self {
+            backend,
+            default_limit,
+        }
+    }
+
+    /// Check if a request should be allowed
+    pub async fn check(&self, client_id: &str, limit: Option<u32>) -> RateLimitResult {
+        let limit = limit.unwrap_or(self.default_limit);
+        self.backend.check(client_id, limit).await
+    }
+
+    /// Clean up old entries
+    pub async fn cleanup(&self) {
+        self.backend.cleanup().await;
+    }
+}
+
+/// Rate limit window length
+const WINDOW: Duration = Duration::from_secs(60);
+
 /// Rate limit state for a single client
 #[derive(Debug, Clone)]
 struct RateLimitEntry {
@@ -26,30 +75,28 @@ struct RateLimitEntry {
     window_start: Instant,
 }
 
-/// Rate limiter state
-#[derive(Debug, Default)]
-pub struct RateLimiter {
+/// Fixed-window counters kept in process memory (the default)
+#[derive(Debug)]
+pub struct MemoryBackend {
     /// Map of client identifier to rate limit state
     entries: RwLock<HashMap<String, RateLimitEntry>>,
-    /// Default requests per minute
-    default_limit: u32,
     /// Window duration
     window: Duration,
 }
 
-impl RateLimiter {
-    /// Create a new rate limiter
-    pub fn new(default_limit: u32) -> Self {
+impl MemoryBackend {
+    /// Create an in-memory backend
+    pub fn new(window: Duration) -> Self {
         Self {
             entries: RwLock::new(HashMap::new()),
-            default_limit,
-            window: Duration::from_secs(60),
+            window,
         }
     }
+}
 
-    /// Check if a request should be allowed
-    pub async fn check(&self, client_id: &str, limit: Option<u32>) -> RateLimitResult {
-        let limit = limit.unwrap_or(self.default_limit);
+#[async_trait]
+impl RateLimitBackend for MemoryBackend {
+    async fn check(&self, client_id: &str, limit: u32) -> RateLimitResult {
         let now = Instant::now();
 
         let mut entries = self.entries.write().await;
@@ -90,14 +137,138 @@ impl RateLimiter {
         }
     }
 
-    /// Clean up old entries
-    pub async fn cleanup(&self) {
+    async fn cleanup(&self) {
         let now = Instant::now();
         let mut entries = self.entries.write().await;
         entries.retain(|_, v| now.duration_since(v.window_start) < self.window * 2);
     }
 }
 
+/// Sliding-window counters in PostgreSQL, shared by all replicas.
+///
+/// Each client has a counter per fixed window; the request rate is
+/// estimated from the current window plus the overlapping share of the
+/// previous one. If the database is unreachable, requests are counted in
+/// process memory instead.
+#[derive(Debug)]
+pub struct PostgresBackend {
+    pool: DbPool,
+    window: Duration,
+    fallback: MemoryBackend,
+}
+
+impl PostgresBackend {
+    /// Create a backend using the API's database pool
+    pub fn new(pool: DbPool, window: Duration) -> Self {
+        Self {
+            pool,
+            window,
+            fallback: MemoryBackend::new(window),
+        }
+    }
+}
+
+/// Decide a sliding-window request from the current and previous window
+/// counts (the current count includes this request)
+fn sliding_window(
+    current: u32,
+    previous: u32,
+    elapsed_secs: f64,
+    window_secs: f64,
+    limit: u32,
+) -> RateLimitResult {
+    let elapsed = elapsed_secs.clamp(0.0, window_secs);
+    let previous_weight = 1.0 - elapsed / window_secs;
+    let estimate = f64::from(previous) * previous_weight + f64::from(current);
+    let reset_in_secs = (window_secs - elapsed).ceil() as u32;
+
+    if estimate > f64::from(limit) {
+        return RateLimitResult {
+            allowed: false,
+            limit,
+            remaining: 0,
+            reset_in_secs,
+        };
+    }
+    RateLimitResult {
+        allowed: true,
+        limit,
+        remaining: (f64::from(limit) - estimate).floor() as u32,
+        reset_in_secs,
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for PostgresBackend {
+    async fn check(&self, client_id: &str, limit: u32) -> RateLimitResult {
+        let window_secs = self.window.as_secs();
+        let counts =
+            match greport_db::queries::rate_limit_hit(&self.pool, client_id, window_secs).await {
+                Ok(counts) => counts,
+                Err(e) => {
+                    tracing::warn!("Rate limit store unavailable, using local counters: {}", e);
+                    return self.fallback.check(client_id, limit).await;
+                }
+            };
+
+        let result = sliding_window(
+            counts.current_count.max(0) as u32,
+            counts.previous_count.max(0) as u32,
+            counts.elapsed_seconds,
+            window_secs as f64,
+            limit,
+        );
+        if !result.allowed {
+            // Rejected requests do not use up the client's allowance
+            if let Err(e) =
+                greport_db::queries::rate_limit_release(&self.pool, client_id, counts.window_start)
+                    .await
+            {
+                tracing::warn!("Could not release rate limit count: {}", e);
+            }
+        }
+        result
+    }
+
+    async fn cleanup(&self) {
+        if let Err(e) =
+            greport_db::queries::cleanup_rate_limit_counters(&self.pool, self.window.as_secs())
+                .await
+        {
+            tracing::warn!("Rate limit cleanup failed: {}", e);
+        }
+        self.fallback.cleanup().await;
+    }
+}
+
+/// Build the rate limiter selected by configuration.
+///
+/// Fails on a backend other than `memory` or `postgres`.
+pub fn from_config(
+    backend: &str,
+    default_limit: u32,
+    db: Option<&DbPool>,
+) -> anyhow::Result<RateLimiter> {
+    Ok(match (backend, db) {
+        ("postgres", Some(pool)) => {
+            tracing::info!("Using PostgreSQL rate limit counters");
+            RateLimiter::with_backend(
+                default_limit,
+                Arc::new(PostgresBackend::new(pool.clone(), WINDOW)),
+            )
+        }
+        ("postgres", None) => {
+            tracing::warn!("PostgreSQL rate limiting needs a database; using in-memory counters");
+            RateLimiter::new(default_limit)
+        }
+        ("memory", _) => RateLimiter::new(default_limit),
+        (other, _) => anyhow::bail!(
+            "Invalid rate_limit_backend '{}' (expected 'memory' or 'postgres')",
+            other
+        ),
+    })
+}
+
 /// Result of a rate limit check
 #[derive(Debug, Clone)]
 pub struct RateLimitResult {
@@ -182,9 +353,12 @@ pub async fn rate_limit_middleware(
 
 /// Get client identifier from request
 fn get_client_id(request: &Request) -> String {
-    // First try to get from auth info
+    // First try to get from auth info; each API key has its own allowance
     if let Some(auth) = request.extensions().get::<Arc<AuthInfo>>() {
-        return format!("user:{}", auth.owner);
+        return match auth.key_id {
+            Some(id) => format!("key:{}", id),
+            None => format!("user:{}", auth.owner),
+        };
     }
 
     // Then try X-Forwarded-For header
@@ -226,3 +400,41 @@ pub fn start_cleanup_task(rate_limiter: Arc<RateLimiter>) {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_backend_fixed_window() {
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.check("a", None).await.allowed);
+        let second = limiter.check("a", None).await;
+        assert!(second.allowed);
+        assert_eq!(second.remaining, 0);
+        assert!(!limiter.check("a", None).await.allowed);
+
+        // Limits are per client, and per-key limits override the default
+        assert!(limiter.check("b", Some(5)).await.allowed);
+    }
+
+    #[test]
+    fn test_from_config_rejects_unknown_backend() {
+        assert!(from_config("memory", 60, None).is_ok());
+        let err = from_config("postgress", 60, None).err().unwrap();
+        assert!(err.to_string().contains("postgress"));
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        // Half way through the window half of the previous window counts
+        let result = sliding_window(5, 10, 30.0, 60.0, 10);
+        assert!(result.allowed);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(result.reset_in_secs, 30);
+
+        assert!(!sliding_window(6, 10, 30.0, 60.0, 10).allowed);
+        assert!(sliding_window(6, 10, 45.0, 60.0, 10).allowed);
+        assert!(sliding_window(1, 0, 0.0, 60.0, 1).allowed);
+    }
+}
@@ -0,0 +1,141 @@
+//! Admin route handlers (API key management)
+
+use axum::{
+    extract::{Json as AxumJson, Path, Query, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::AuthInfo;
+use crate::error::{ApiError, ErrorResponse};
+use crate::response::ApiResponse;
+use crate::state::AppState;
+use greport_db::ApiKeyRow;
+
+pub use greport_types::admin::{
+    ApiKeySummary, CreateApiKeyRequest, CreatedApiKey, ListApiKeysQuery,
+};
+
+fn key_summary(row: ApiKeyRow) -> ApiKeySummary {
+    ApiKeySummary {
+        id: row.id,
+        name: row.name,
+        owner: row.owner,
+        scopes: row.scopes,
+        rate_limit: row.rate_limit,
+        created_at: row.created_at,
+        expires_at: row.expires_at,
+        last_used_at: row.last_used_at,
+        revoked: row.revoked,
+    }
+}
+
+/// POST /api/v1/admin/api-keys - Create an API key
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/api-keys",
+    tag = "admin",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CreatedApiKey>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    auth: Option<Extension<Arc<AuthInfo>>>,
+    AxumJson(body): AxumJson<CreateApiKeyRequest>,
+) -> Result<Json<ApiResponse<CreatedApiKey>>, ApiError> {
+    let pool = state
+        .db
+        .as_ref()
+        .ok_or_else(|| ApiError::BadRequest("Database required".into()))?;
+
+    let owner = body
+        .owner
+        .or_else(|| auth.map(|Extension(a)| a.owner.clone()))
+        .unwrap_or_default();
+    let (key, input) = greport_db::keys::new_api_key(
+        &body.name,
+        &owner,
+        body.scopes.unwrap_or_else(|| vec!["read".to_string()]),
+        body.rate_limit
+            .unwrap_or(state.config.rate_limit_per_minute as i32),
+        body.expires_at,
+    )?;
+
+    let row = greport_db::queries::create_api_key(pool, &input).await?;
+    tracing::info!(id = %row.id, name = %row.name, owner = %row.owner, "Created API key");
+
+    Ok(Json(ApiResponse::ok(CreatedApiKey {
+        key,
+        info: key_summary(row),
+    })))
+}
+
+/// GET /api/v1/admin/api-keys - List API keys
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/api-keys",
+    tag = "admin",
+    params(
+        ListApiKeysQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ApiKeySummary>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    Query(query): Query<ListApiKeysQuery>,
+) -> Result<Json<ApiResponse<Vec<ApiKeySummary>>>, ApiError> {
+    let pool = state
+        .db
+        .as_ref()
+        .ok_or_else(|| ApiError::BadRequest("Database required".into()))?;
+
+    let include_revoked = query.include_revoked.unwrap_or(false);
+    let rows = greport_db::queries::list_all_api_keys(pool, include_revoked).await?;
+    let keys = rows
+        .into_iter()
+        .filter(|k| query.owner.as_ref().is_none_or(|o| &k.owner == o))
+        .map(key_summary)
+        .collect();
+
+    Ok(Json(ApiResponse::ok(keys)))
+}
+
+/// DELETE /api/v1/admin/api-keys/{id} - Revoke an API key
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/api-keys/{id}",
+    tag = "admin",
+    params(
+        ("id" = uuid::Uuid, Path, description = "API key ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ApiKeySummary>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ApiKeySummary>>, ApiError> {
+    let pool = state
+        .db
+        .as_ref()
+        .ok_or_else(|| ApiError::BadRequest("Database required".into()))?;
+
+    if !greport_db::queries::revoke_api_key(pool, id).await? {
+        return Err(ApiError::NotFound(format!("API key not found: {}", id)));
+    }
+    let row = greport_db::queries::get_api_key(pool, id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("API key not found: {}", id)))?;
+    tracing::info!(id = %row.id, name = %row.name, "Revoked API key");
+
+    Ok(Json(ApiResponse::ok(key_summary(row))))
+}
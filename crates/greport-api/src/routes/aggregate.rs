@@ -5,147 +5,26 @@ use axum::{
     Json,
 };
 use chrono::Utc;
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::auth::Caller;
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::{ApiResponse, PaginatedResponse};
 use crate::state::AppState;
+use greport_core::identities::Identities;
 use greport_core::metrics::{
     IssueMetricsCalculator, Period, PullMetricsCalculator, VelocityCalculator,
 };
 use greport_core::models::{Issue, PullRequest};
 
-// ---------------------------------------------------------------------------
-// Types
-// ---------------------------------------------------------------------------
-
-#[derive(Serialize)]
-pub struct RepoIssueMetrics {
-    pub repository: String,
-    pub total: usize,
-    pub open: usize,
-    pub closed: usize,
-    pub avg_time_to_close_hours: Option<f64>,
-    pub stale_count: usize,
-}
-
-#[derive(Serialize)]
-pub struct IssueMetricsTotals {
-    pub total: usize,
-    pub open: usize,
-    pub closed: usize,
-    pub avg_time_to_close_hours: Option<f64>,
-    pub stale_count: usize,
-}
-
-#[derive(Serialize)]
-pub struct AgeBucketResponse {
-    pub label: String,
-    pub min_days: i64,
-    pub max_days: Option<i64>,
-    pub count: usize,
-}
-
-#[derive(Serialize)]
-pub struct AggregateIssueMetrics {
-    pub by_repository: Vec<RepoIssueMetrics>,
-    pub totals: IssueMetricsTotals,
-    pub by_label: HashMap<String, usize>,
-    pub by_assignee: HashMap<String, usize>,
-    pub age_distribution: Vec<AgeBucketResponse>,
-}
-
-#[derive(Serialize)]
-pub struct RepoPullMetrics {
-    pub repository: String,
-    pub total: usize,
-    pub open: usize,
-    pub merged: usize,
-    pub avg_time_to_merge_hours: Option<f64>,
-}
-
-#[derive(Serialize)]
-pub struct PullMetricsTotals {
-    pub total: usize,
-    pub open: usize,
-    pub merged: usize,
-    pub avg_time_to_merge_hours: Option<f64>,
-}
-
-#[derive(Serialize)]
-pub struct AggregatePullMetrics {
-    pub by_repository: Vec<RepoPullMetrics>,
-    pub totals: PullMetricsTotals,
-    pub by_size: HashMap<String, usize>,
-    pub by_author: HashMap<String, usize>,
-}
-
-#[derive(Serialize)]
-pub struct AggregateContributorStats {
-    pub login: String,
-    pub repositories: Vec<String>,
-    pub total_issues_created: usize,
-    pub total_prs_created: usize,
-    pub total_prs_merged: usize,
-}
-
-#[derive(Serialize)]
-pub struct RepoVelocityEntry {
-    pub repository: String,
-    pub avg_opened: f64,
-    pub avg_closed: f64,
-}
-
-#[derive(Serialize)]
-pub struct AggregateVelocityMetrics {
-    pub period: String,
-    pub by_repository: Vec<RepoVelocityEntry>,
-    pub combined_avg_opened: f64,
-    pub combined_avg_closed: f64,
-    pub trend: String,
-}
-
-#[derive(Deserialize)]
-pub struct IssueMetricsQuery {
-    state: Option<String>,
-    days: Option<i64>,
-}
-
-#[derive(Deserialize)]
-pub struct PullMetricsQuery {
-    state: Option<String>,
-    days: Option<i64>,
-}
-
-#[derive(Deserialize)]
-pub struct AggregateListQuery {
-    state: Option<String>,
-    days: Option<i64>,
-    page: Option<u32>,
-    per_page: Option<u32>,
-}
-
-#[derive(Serialize)]
-pub struct AggregateIssueItem {
-    pub repository: String,
-    #[serde(flatten)]
-    pub issue: Issue,
-}
-
-#[derive(Serialize)]
-pub struct AggregatePullItem {
-    pub repository: String,
-    #[serde(flatten)]
-    pub pull: PullRequest,
-}
-
-#[derive(Deserialize)]
-pub struct VelocityQuery {
-    period: Option<String>,
-    last: Option<usize>,
-}
+pub use greport_types::aggregate::{
+    AgeBucketResponse, AggregateContributorStats, AggregateIssueItem, AggregateIssueMetrics,
+    AggregateListQuery, AggregatePullItem, AggregatePullMetrics, AggregateVelocityMetrics,
+    IssueMetricsQuery, IssueMetricsTotals, OrgAggregateIssueItem, OrgAggregatePullItem,
+    PullMetricsQuery, PullMetricsTotals, RepoIssueMetrics, RepoPullMetrics, RepoVelocityEntry,
+    VelocityQuery,
+};
 
 // ---------------------------------------------------------------------------
 // Helper: get all synced repos
@@ -156,7 +35,14 @@ struct RepoData {
     db_id: i64,
 }
 
-async fn get_synced_repos(state: &AppState) -> Result<Vec<RepoData>, ApiError> {
+impl RepoData {
+    fn owner(&self) -> &str {
+        self.full_name.split('/').next().unwrap_or_default()
+    }
+}
+
+/// Tracked repositories with synced data that `caller` may see
+async fn get_synced_repos(state: &AppState, caller: &Caller) -> Result<Vec<RepoData>, ApiError> {
     let pool = state
         .db
         .as_ref()
@@ -166,6 +52,9 @@ async fn get_synced_repos(state: &AppState) -> Result<Vec<RepoData>, ApiError> {
     let mut repos = Vec::new();
 
     for tracked_repo in tracked {
+        if !caller.can_see_repo(&tracked_repo.full_name) {
+            continue;
+        }
         let parts: Vec<&str> = tracked_repo.full_name.splitn(2, '/').collect();
         if parts.len() != 2 {
             continue;
@@ -186,8 +75,21 @@ async fn get_synced_repos(state: &AppState) -> Result<Vec<RepoData>, ApiError> {
 // ---------------------------------------------------------------------------
 
 /// GET /api/v1/aggregate/issues/metrics
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/issues/metrics",
+    tag = "aggregate",
+    params(
+        IssueMetricsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AggregateIssueMetrics>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_issue_metrics(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<IssueMetricsQuery>,
 ) -> Result<Json<ApiResponse<AggregateIssueMetrics>>, ApiError> {
     let pool = state
@@ -195,7 +97,7 @@ pub async fn aggregate_issue_metrics(
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate metrics".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let state_filter = query.state.as_deref();
     let days_filter = query.days;
 
@@ -211,7 +113,8 @@ pub async fn aggregate_issue_metrics(
         let issues = convert::issues_from_db(pool, repo.db_id, None, None).await?;
         let filtered: Vec<Issue> = filter_issues(issues, state_filter, days_filter);
 
-        let calc = IssueMetricsCalculator::default();
+        let bots = state.config.bot_filter(repo.owner());
+        let calc = IssueMetricsCalculator::default().with_bot_filter(bots.clone());
         let metrics = calc.calculate(&filtered);
 
         total_total += metrics.total;
@@ -234,7 +137,7 @@ pub async fn aggregate_issue_metrics(
             stale_count: metrics.stale_count,
         });
 
-        all_issues.extend(filtered);
+        all_issues.extend(filtered.into_iter().filter(|i| !bots.is_bot(&i.author)));
     }
 
     let avg_close = if all_close_hours.is_empty() {
@@ -313,8 +216,21 @@ fn filter_pulls(
 }
 
 /// GET /api/v1/aggregate/issues
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/issues",
+    tag = "aggregate",
+    params(
+        AggregateListQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<AggregateIssueItem>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_issues_list(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<AggregateListQuery>,
 ) -> Result<Json<PaginatedResponse<AggregateIssueItem>>, ApiError> {
     let pool = state
@@ -322,7 +238,7 @@ pub async fn aggregate_issues_list(
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate list".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let state_filter = query.state.as_deref();
     let days_filter = query.days;
     let mut all_items: Vec<AggregateIssueItem> = Vec::new();
@@ -339,7 +255,7 @@ pub async fn aggregate_issues_list(
     }
 
     // Sort newest first
-    all_items.sort_by(|a, b| b.issue.created_at.cmp(&a.issue.created_at));
+    all_items.sort_by_key(|e| std::cmp::Reverse(e.issue.created_at));
 
     let total = all_items.len() as u32;
     Ok(Json(PaginatedResponse::new(
@@ -351,8 +267,21 @@ pub async fn aggregate_issues_list(
 }
 
 /// GET /api/v1/aggregate/pulls
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/pulls",
+    tag = "aggregate",
+    params(
+        AggregateListQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<AggregatePullItem>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_pulls_list(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<AggregateListQuery>,
 ) -> Result<Json<PaginatedResponse<AggregatePullItem>>, ApiError> {
     let pool = state
@@ -360,7 +289,7 @@ pub async fn aggregate_pulls_list(
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate list".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let state_filter = query.state.as_deref();
     let days_filter = query.days;
     let mut all_items: Vec<AggregatePullItem> = Vec::new();
@@ -377,7 +306,7 @@ pub async fn aggregate_pulls_list(
     }
 
     // Sort newest first
-    all_items.sort_by(|a, b| b.pull.created_at.cmp(&a.pull.created_at));
+    all_items.sort_by_key(|e| std::cmp::Reverse(e.pull.created_at));
 
     let total = all_items.len() as u32;
     Ok(Json(PaginatedResponse::new(
@@ -389,30 +318,27 @@ pub async fn aggregate_pulls_list(
 }
 
 // ---------------------------------------------------------------------------
-// Cross-org aggregate types
+// Cross-org aggregates
 // ---------------------------------------------------------------------------
 
-#[derive(Serialize)]
-pub struct OrgAggregateIssueItem {
-    pub organization: String,
-    pub repository: String,
-    #[serde(flatten)]
-    pub issue: Issue,
-}
-
-#[derive(Serialize)]
-pub struct OrgAggregatePullItem {
-    pub organization: String,
-    pub repository: String,
-    #[serde(flatten)]
-    pub pull: PullRequest,
-}
-
 /// GET /api/v1/aggregate/orgs/issues
 ///
 /// Cross-org aggregation: lists issues across all synced repos with org field.
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/orgs/issues",
+    tag = "aggregate",
+    params(
+        AggregateListQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<OrgAggregateIssueItem>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_org_issues(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<AggregateListQuery>,
 ) -> Result<Json<PaginatedResponse<OrgAggregateIssueItem>>, ApiError> {
     let pool = state
@@ -420,7 +346,7 @@ pub async fn aggregate_org_issues(
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate list".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let state_filter = query.state.as_deref();
     let days_filter = query.days;
     let mut all_items: Vec<OrgAggregateIssueItem> = Vec::new();
@@ -439,7 +365,7 @@ pub async fn aggregate_org_issues(
     }
 
     // Sort newest first
-    all_items.sort_by(|a, b| b.issue.created_at.cmp(&a.issue.created_at));
+    all_items.sort_by_key(|e| std::cmp::Reverse(e.issue.created_at));
 
     let total = all_items.len() as u32;
     Ok(Json(PaginatedResponse::new(
@@ -453,8 +379,21 @@ pub async fn aggregate_org_issues(
 /// GET /api/v1/aggregate/orgs/pulls
 ///
 /// Cross-org aggregation: lists pull requests across all synced repos with org field.
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/orgs/pulls",
+    tag = "aggregate",
+    params(
+        AggregateListQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<OrgAggregatePullItem>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_org_pulls(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<AggregateListQuery>,
 ) -> Result<Json<PaginatedResponse<OrgAggregatePullItem>>, ApiError> {
     let pool = state
@@ -462,7 +401,7 @@ pub async fn aggregate_org_pulls(
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate list".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let state_filter = query.state.as_deref();
     let days_filter = query.days;
     let mut all_items: Vec<OrgAggregatePullItem> = Vec::new();
@@ -481,7 +420,7 @@ pub async fn aggregate_org_pulls(
     }
 
     // Sort newest first
-    all_items.sort_by(|a, b| b.pull.created_at.cmp(&a.pull.created_at));
+    all_items.sort_by_key(|e| std::cmp::Reverse(e.pull.created_at));
 
     let total = all_items.len() as u32;
     Ok(Json(PaginatedResponse::new(
@@ -493,8 +432,21 @@ pub async fn aggregate_org_pulls(
 }
 
 /// GET /api/v1/aggregate/pulls/metrics
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/pulls/metrics",
+    tag = "aggregate",
+    params(
+        PullMetricsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AggregatePullMetrics>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_pull_metrics(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<PullMetricsQuery>,
 ) -> Result<Json<ApiResponse<AggregatePullMetrics>>, ApiError> {
     let pool = state
@@ -502,7 +454,7 @@ pub async fn aggregate_pull_metrics(
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate metrics".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let state_filter = query.state.as_deref();
     let days_filter = query.days;
 
@@ -517,7 +469,8 @@ pub async fn aggregate_pull_metrics(
         let pulls = convert::pulls_from_db(pool, repo.db_id, None, None).await?;
         let filtered = filter_pulls(pulls, state_filter, days_filter);
 
-        let metrics = PullMetricsCalculator::calculate(&filtered);
+        let bots = state.config.bot_filter(repo.owner());
+        let metrics = PullMetricsCalculator::calculate_with_bots(&filtered, &bots);
 
         total_total += metrics.total;
         total_open += metrics.open;
@@ -537,7 +490,7 @@ pub async fn aggregate_pull_metrics(
             avg_time_to_merge_hours: metrics.avg_time_to_merge_hours,
         });
 
-        all_pulls.extend(filtered);
+        all_pulls.extend(filtered.into_iter().filter(|p| !bots.is_bot(&p.author)));
     }
 
     let avg_merge = if all_merge_hours.is_empty() {
@@ -563,78 +516,122 @@ pub async fn aggregate_pull_metrics(
 }
 
 /// GET /api/v1/aggregate/contributors
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/contributors",
+    tag = "aggregate",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<AggregateContributorStats>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_contributors(
     State(state): State<AppState>,
+    caller: Caller,
 ) -> Result<Json<ApiResponse<Vec<AggregateContributorStats>>>, ApiError> {
     let pool = state
         .db
         .as_ref()
         .ok_or_else(|| ApiError::BadRequest("Database required for aggregate metrics".into()))?;
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
 
     struct ContribAccum {
         repos: Vec<String>,
         issues_created: usize,
         prs_created: usize,
         prs_merged: usize,
+        bot: bool,
+        logins: Vec<String>,
     }
 
+    // Key on the person, so logins mapped to one person (across orgs and
+    // accounts) roll up together
+    let identities = &state.config.identities;
     let mut contributors: HashMap<String, ContribAccum> = HashMap::new();
+    fn entry_for<'a>(
+        contributors: &'a mut HashMap<String, ContribAccum>,
+        identities: &Identities,
+        login: &str,
+        bot: bool,
+        repo: &str,
+    ) -> &'a mut ContribAccum {
+        let entry = contributors
+            .entry(identities.canonical(login).to_string())
+            .or_insert_with(|| ContribAccum {
+                repos: vec![],
+                issues_created: 0,
+                prs_created: 0,
+                prs_merged: 0,
+                bot,
+                logins: vec![],
+            });
+        if identities.person(login).is_some() && !entry.logins.iter().any(|l| l == login) {
+            entry.logins.push(login.to_string());
+        }
+        if !entry.repos.iter().any(|r| r == repo) {
+            entry.repos.push(repo.to_string());
+        }
+        entry
+    }
 
     for repo in &repos {
+        let bots = state.config.bot_filter(repo.owner());
         let issues = convert::issues_from_db(pool, repo.db_id, None, None).await?;
         for issue in &issues {
-            let entry = contributors
-                .entry(issue.author.login.clone())
-                .or_insert_with(|| ContribAccum {
-                    repos: vec![],
-                    issues_created: 0,
-                    prs_created: 0,
-                    prs_merged: 0,
-                });
-            entry.issues_created += 1;
-            if !entry.repos.contains(&repo.full_name) {
-                entry.repos.push(repo.full_name.clone());
+            let is_bot = bots.is_bot(&issue.author);
+            if is_bot && !bots.separate() {
+                continue;
             }
+            entry_for(
+                &mut contributors,
+                identities,
+                &issue.author.login,
+                is_bot,
+                &repo.full_name,
+            )
+            .issues_created += 1;
         }
 
         let pulls = convert::pulls_from_db(pool, repo.db_id, None, None).await?;
         for pr in &pulls {
-            let entry = contributors
-                .entry(pr.author.login.clone())
-                .or_insert_with(|| ContribAccum {
-                    repos: vec![],
-                    issues_created: 0,
-                    prs_created: 0,
-                    prs_merged: 0,
-                });
+            let is_bot = bots.is_bot(&pr.author);
+            if is_bot && !bots.separate() {
+                continue;
+            }
+            let entry = entry_for(
+                &mut contributors,
+                identities,
+                &pr.author.login,
+                is_bot,
+                &repo.full_name,
+            );
             entry.prs_created += 1;
             if pr.merged {
                 entry.prs_merged += 1;
             }
-            if !entry.repos.contains(&repo.full_name) {
-                entry.repos.push(repo.full_name.clone());
-            }
         }
     }
 
     let mut result: Vec<AggregateContributorStats> = contributors
         .into_iter()
         .map(|(login, accum)| AggregateContributorStats {
+            team: identities.team(&login).map(str::to_string),
             login,
             repositories: accum.repos,
             total_issues_created: accum.issues_created,
             total_prs_created: accum.prs_created,
             total_prs_merged: accum.prs_merged,
+            bot: accum.bot,
+            logins: accum.logins,
         })
         .collect();
 
-    // Sort by total activity (issues + PRs)
+    // Sort people first, then by total activity (issues + PRs)
     result.sort_by(|a, b| {
         let a_total = a.total_issues_created + a.total_prs_created;
         let b_total = b.total_issues_created + b.total_prs_created;
-        b_total.cmp(&a_total)
+        a.bot.cmp(&b.bot).then(b_total.cmp(&a_total))
     });
 
     // Limit to top 30
@@ -644,8 +641,21 @@ pub async fn aggregate_contributors(
 }
 
 /// GET /api/v1/aggregate/velocity
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/velocity",
+    tag = "aggregate",
+    params(
+        VelocityQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AggregateVelocityMetrics>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_velocity(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<VelocityQuery>,
 ) -> Result<Json<ApiResponse<AggregateVelocityMetrics>>, ApiError> {
     let pool = state
@@ -661,7 +671,7 @@ pub async fn aggregate_velocity(
         _ => Period::Week,
     };
 
-    let repos = get_synced_repos(&state).await?;
+    let repos = get_synced_repos(&state, &caller).await?;
     let mut by_repository = Vec::new();
     let mut all_issues: Vec<Issue> = Vec::new();
 
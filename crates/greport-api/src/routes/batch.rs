@@ -2,52 +2,25 @@
 
 use axum::{extract::State, Json};
 use chrono::Utc;
-use serde::Serialize;
+use greport_core::client::RateResource;
 
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
 use crate::sync;
 
-/// Result of syncing a single repository within a batch
-#[derive(Serialize)]
-pub struct RepoSyncResult {
-    pub repository: String,
-    pub success: bool,
-    pub issues_synced: Option<usize>,
-    pub pulls_synced: Option<usize>,
-    pub releases_synced: Option<usize>,
-    pub milestones_synced: Option<usize>,
-    pub error: Option<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub warnings: Vec<String>,
-}
-
-/// Result of syncing projects for one organization within a batch
-#[derive(Serialize)]
-pub struct OrgProjectSyncResult {
-    pub organization: String,
-    pub success: bool,
-    pub projects_synced: Option<usize>,
-    pub items_synced: Option<usize>,
-    pub error: Option<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub warnings: Vec<String>,
-}
-
-/// Result of a batch sync operation
-#[derive(Serialize)]
-pub struct BatchSyncResult {
-    pub results: Vec<RepoSyncResult>,
-    pub total_repos: usize,
-    pub successful: usize,
-    pub failed: usize,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub project_results: Vec<OrgProjectSyncResult>,
-    pub synced_at: String,
-}
+pub use greport_types::batch::{BatchSyncResult, OrgProjectSyncResult, RepoSyncResult};
 
 /// POST /api/v1/sync - Batch sync all tracked repositories
+#[utoipa::path(
+    post,
+    path = "/api/v1/sync",
+    tag = "sync",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<BatchSyncResult>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn batch_sync(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<BatchSyncResult>>, ApiError> {
@@ -117,6 +90,34 @@ pub async fn batch_sync(
             }
         };
 
+        // The client would wait for the reset; skip the repo instead of
+        // holding the request open until then
+        if let Some(until) = client
+            .rate_budget()
+            .and_then(|b| b.resume_at(RateResource::Core))
+        {
+            tracing::warn!(
+                repo = %tracked_repo.full_name,
+                resume_at = %until,
+                "GitHub rate limit nearly exhausted, skipping repo"
+            );
+            results.push(RepoSyncResult {
+                repository: tracked_repo.full_name.clone(),
+                success: false,
+                issues_synced: None,
+                pulls_synced: None,
+                releases_synced: None,
+                milestones_synced: None,
+                error: Some(format!(
+                    "GitHub rate limit nearly exhausted until {}",
+                    until.to_rfc3339()
+                )),
+                warnings: vec![],
+            });
+            failed += 1;
+            continue;
+        }
+
         match sync::sync_repository(pool, client.as_ref(), owner, repo).await {
             Ok(result) => {
                 results.push(RepoSyncResult {
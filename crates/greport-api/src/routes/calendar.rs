@@ -5,11 +5,11 @@ use axum::{
     Json,
 };
 use chrono::{Datelike, NaiveDate, TimeZone, Utc};
-use serde::Deserialize;
 use std::collections::HashMap;
 
+use crate::auth::Caller;
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
 use greport_core::models::{
@@ -17,12 +17,7 @@ use greport_core::models::{
     MilestoneState, PullRequest, Release,
 };
 
-#[derive(Deserialize)]
-pub struct CalendarQuery {
-    start_date: Option<String>,
-    end_date: Option<String>,
-    types: Option<String>,
-}
+pub use greport_types::calendar::CalendarQuery;
 
 /// Parse a date string (YYYY-MM-DD) into NaiveDate
 fn parse_date(s: &str) -> Option<NaiveDate> {
@@ -244,6 +239,20 @@ fn compute_summary(events: &[CalendarEvent]) -> CalendarSummary {
 }
 
 /// GET /api/v1/repos/{owner}/{repo}/calendar
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/calendar",
+    tag = "calendar",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        CalendarQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CalendarData>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_calendar(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -342,6 +351,7 @@ pub async fn get_calendar(
                             login: row.author_login,
                             avatar_url: String::new(),
                             html_url: String::new(),
+                            is_bot: row.author_is_bot,
                         },
                         comments_count: row.comments_count as u32,
                         created_at: row.created_at,
@@ -400,6 +410,7 @@ pub async fn get_calendar(
                             login: r.author_login,
                             avatar_url: String::new(),
                             html_url: String::new(),
+                            is_bot: r.author_is_bot,
                         },
                         created_at: r.created_at,
                         published_at: r.published_at,
@@ -431,6 +442,7 @@ pub async fn get_calendar(
                             login: p.author_login,
                             avatar_url: String::new(),
                             html_url: String::new(),
+                            is_bot: p.author_is_bot,
                         },
                         labels: vec![],
                         milestone: None,
@@ -459,7 +471,7 @@ pub async fn get_calendar(
                 end,
                 &web_base,
             );
-            events.sort_by(|a, b| a.date.cmp(&b.date));
+            events.sort_by_key(|e| e.date);
             let summary = compute_summary(&events);
 
             return Ok(Json(ApiResponse::ok(CalendarData {
@@ -474,7 +486,7 @@ pub async fn get_calendar(
     // Fallback: GitHub API (fetch all data and filter in-memory)
     // Individual API calls are wrapped to handle partial failures gracefully
     // (e.g., token lacking release permissions should not block issues/milestones)
-    use greport_core::client::{GitHubClient, IssueParams, PullParams, RepoId};
+    use greport_core::client::{IssueParams, PullParams, RepoId};
 
     let client = state.client_for_owner(&owner)?;
     let repo_id = RepoId::new(owner, repo);
@@ -535,7 +547,7 @@ pub async fn get_calendar(
         end,
         &web_base,
     );
-    events.sort_by(|a, b| a.date.cmp(&b.date));
+    events.sort_by_key(|e| e.date);
     let summary = compute_summary(&events);
 
     Ok(Json(ApiResponse::ok(CalendarData {
@@ -547,8 +559,21 @@ pub async fn get_calendar(
 }
 
 /// GET /api/v1/aggregate/calendar
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/calendar",
+    tag = "calendar",
+    params(
+        CalendarQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CalendarData>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_aggregate_calendar(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<CalendarQuery>,
 ) -> Result<Json<ApiResponse<CalendarData>>, ApiError> {
     let pool = state
@@ -583,6 +608,9 @@ pub async fn get_aggregate_calendar(
     let mut all_events = Vec::new();
 
     for tracked_repo in &tracked {
+        if !caller.can_see_repo(&tracked_repo.full_name) {
+            continue;
+        }
         let parts: Vec<&str> = tracked_repo.full_name.splitn(2, '/').collect();
         if parts.len() != 2 {
             continue;
@@ -637,6 +665,7 @@ pub async fn get_aggregate_calendar(
                         login: row.author_login,
                         avatar_url: String::new(),
                         html_url: String::new(),
+                        is_bot: row.author_is_bot,
                     },
                     comments_count: row.comments_count as u32,
                     created_at: row.created_at,
@@ -695,6 +724,7 @@ pub async fn get_aggregate_calendar(
                         login: r.author_login,
                         avatar_url: String::new(),
                         html_url: String::new(),
+                        is_bot: r.author_is_bot,
                     },
                     created_at: r.created_at,
                     published_at: r.published_at,
@@ -726,6 +756,7 @@ pub async fn get_aggregate_calendar(
                         login: p.author_login,
                         avatar_url: String::new(),
                         html_url: String::new(),
+                        is_bot: p.author_is_bot,
                     },
                     labels: vec![],
                     milestone: None,
@@ -757,7 +788,7 @@ pub async fn get_aggregate_calendar(
         all_events.extend(events);
     }
 
-    all_events.sort_by(|a, b| a.date.cmp(&b.date));
+    all_events.sort_by_key(|e| e.date);
     let summary = compute_summary(&all_events);
 
     Ok(Json(ApiResponse::ok(CalendarData {
@@ -4,29 +4,30 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
-use greport_core::client::{GitHubClient, IssueParams, PullParams, RepoId};
+use greport_core::client::{IssueParams, PullParams, RepoId};
 
-#[derive(Deserialize)]
-pub struct ContributorsQuery {
-    sort_by: Option<String>,
-    limit: Option<usize>,
-}
-
-#[derive(Serialize)]
-pub struct ContributorStats {
-    pub login: String,
-    pub issues_created: usize,
-    pub prs_created: usize,
-    pub prs_merged: usize,
-}
+pub use greport_types::contrib::{ContributorStats, ContributorsQuery};
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/contributors",
+    tag = "contributors",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ContributorsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ContributorStats>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_contributors(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -51,29 +52,24 @@ pub async fn list_contributors(
         fetch_from_github(&state, &owner, &repo).await?
     };
 
+    let bots = state.config.bot_filter(&owner);
     let mut contributors: HashMap<String, ContributorStats> = HashMap::new();
 
     for issue in &issues {
-        let entry = contributors
-            .entry(issue.author.login.clone())
-            .or_insert_with(|| ContributorStats {
-                login: issue.author.login.clone(),
-                issues_created: 0,
-                prs_created: 0,
-                prs_merged: 0,
-            });
+        let is_bot = bots.is_bot(&issue.author);
+        if is_bot && !bots.separate() {
+            continue;
+        }
+        let entry = contributor_entry(&mut contributors, &state, &issue.author.login, is_bot);
         entry.issues_created += 1;
     }
 
     for pr in &prs {
-        let entry = contributors
-            .entry(pr.author.login.clone())
-            .or_insert_with(|| ContributorStats {
-                login: pr.author.login.clone(),
-                issues_created: 0,
-                prs_created: 0,
-                prs_merged: 0,
-            });
+        let is_bot = bots.is_bot(&pr.author);
+        if is_bot && !bots.separate() {
+            continue;
+        }
+        let entry = contributor_entry(&mut contributors, &state, &pr.author.login, is_bot);
         entry.prs_created += 1;
         if pr.merged {
             entry.prs_merged += 1;
@@ -82,9 +78,10 @@ pub async fn list_contributors(
 
     let mut sorted: Vec<_> = contributors.into_values().collect();
 
+    // People first, bots after them
     match query.sort_by.as_deref() {
-        Some("prs") => sorted.sort_by(|a, b| b.prs_created.cmp(&a.prs_created)),
-        _ => sorted.sort_by(|a, b| b.issues_created.cmp(&a.issues_created)),
+        Some("prs") => sorted.sort_by_key(|e| (e.bot, std::cmp::Reverse(e.prs_created))),
+        _ => sorted.sort_by_key(|e| (e.bot, std::cmp::Reverse(e.issues_created))),
     }
 
     let limit = query.limit.unwrap_or(20);
@@ -93,6 +90,33 @@ pub async fn list_contributors(
     Ok(Json(ApiResponse::ok(sorted)))
 }
 
+/// Stats of the person `login` belongs to, merging the logins mapped to
+/// one person in the identities file
+fn contributor_entry<'a>(
+    contributors: &'a mut HashMap<String, ContributorStats>,
+    state: &AppState,
+    login: &str,
+    bot: bool,
+) -> &'a mut ContributorStats {
+    let identities = &state.config.identities;
+    let name = identities.canonical(login);
+    let entry = contributors
+        .entry(name.to_string())
+        .or_insert_with(|| ContributorStats {
+            login: name.to_string(),
+            issues_created: 0,
+            prs_created: 0,
+            prs_merged: 0,
+            bot,
+            team: identities.team(login).map(str::to_string),
+            logins: Vec::new(),
+        });
+    if identities.person(login).is_some() && !entry.logins.iter().any(|l| l == login) {
+        entry.logins.push(login.to_string());
+    }
+    entry
+}
+
 async fn fetch_from_github(
     state: &AppState,
     owner: &str,
@@ -1,14 +1,18 @@
 //! Health check endpoint
 
 use axum::Json;
-use serde::Serialize;
 
-#[derive(Serialize)]
-pub struct HealthStatus {
-    status: String,
-    version: String,
-}
+pub use greport_types::health::HealthStatus;
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "Success", body = HealthStatus)
+    ),
+    security(()),
+)]
 pub async fn health_check() -> Json<HealthStatus> {
     Json(HealthStatus {
         status: "healthy".to_string(),
@@ -5,30 +5,36 @@ use axum::{
     Json,
 };
 use chrono::Utc;
-use serde::Deserialize;
 
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::{ApiResponse, PaginatedResponse};
 use crate::state::AppState;
-use greport_core::client::{GitHubClient, IssueParams, IssueStateFilter, RepoId};
+use greport_core::client::{IssueParams, IssueStateFilter, RepoId};
 use greport_core::metrics::{
     IssueMetrics, IssueMetricsCalculator, Period, VelocityCalculator, VelocityMetrics,
 };
 use greport_core::models::Issue;
-use greport_core::reports::{BurndownCalculator, BurndownReport};
-
-#[derive(Deserialize)]
-pub struct ListIssuesQuery {
-    state: Option<String>,
-    labels: Option<String>,
-    assignee: Option<String>,
-    milestone: Option<String>,
-    page: Option<u32>,
-    per_page: Option<u32>,
-    days: Option<i64>,
-}
+use greport_core::reports::{BurndownCalculator, BurndownReport, EpicReport, EpicReportCalculator};
+
+pub use greport_types::issues::{
+    BurndownQuery, EpicsQuery, ListIssuesQuery, MetricsQuery, StaleQuery, VelocityQuery,
+};
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/issues",
+    tag = "issues",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ListIssuesQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<Issue>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_issues(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -102,12 +108,20 @@ pub async fn list_issues(
     )))
 }
 
-#[derive(Deserialize)]
-pub struct MetricsQuery {
-    state: Option<String>,
-    days: Option<i64>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/issues/metrics",
+    tag = "issues",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        MetricsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<IssueMetrics>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_metrics(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -119,7 +133,8 @@ pub async fn get_metrics(
             if convert::has_synced_data(pool, repo_db_id, "issues").await {
                 let issues = convert::issues_from_db(pool, repo_db_id, None, None).await?;
                 let filtered = filter_issues_by(issues, query.state.as_deref(), query.days);
-                let calculator = IssueMetricsCalculator::new(30);
+                let calculator = IssueMetricsCalculator::new(30)
+                    .with_bot_filter(state.config.bot_filter(&owner));
                 let metrics = calculator.calculate(&filtered);
                 return Ok(Json(ApiResponse::ok(metrics)));
             }
@@ -143,18 +158,27 @@ pub async fn get_metrics(
     };
 
     let filtered = filter_issues_by(issues, query.state.as_deref(), query.days);
-    let calculator = IssueMetricsCalculator::new(30);
+    let calculator =
+        IssueMetricsCalculator::new(30).with_bot_filter(state.config.bot_filter(&owner));
     let metrics = calculator.calculate(&filtered);
 
     Ok(Json(ApiResponse::ok(metrics)))
 }
 
-#[derive(Deserialize)]
-pub struct VelocityQuery {
-    period: Option<String>,
-    last: Option<usize>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/issues/velocity",
+    tag = "issues",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        VelocityQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<VelocityMetrics>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_velocity(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -199,11 +223,20 @@ pub async fn get_velocity(
     Ok(Json(ApiResponse::ok(velocity)))
 }
 
-#[derive(Deserialize)]
-pub struct BurndownQuery {
-    milestone: String,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/issues/burndown",
+    tag = "issues",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        BurndownQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<BurndownReport>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_burndown(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -246,11 +279,20 @@ pub async fn get_burndown(
     Ok(Json(ApiResponse::ok(burndown)))
 }
 
-#[derive(Deserialize)]
-pub struct StaleQuery {
-    days: Option<i64>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/issues/stale",
+    tag = "issues",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        StaleQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<Issue>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_stale(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -289,6 +331,62 @@ pub async fn get_stale(
     Ok(Json(ApiResponse::ok(stale)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/issues/epics",
+    tag = "issues",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        EpicsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<EpicReport>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
+pub async fn get_epics(
+    State(state): State<AppState>,
+    Path((owner, repo)): Path<(String, String)>,
+    Query(query): Query<EpicsQuery>,
+) -> Result<Json<ApiResponse<EpicReport>>, ApiError> {
+    let repo_id = RepoId::new(owner.clone(), repo.clone());
+    let client = state.client_for_owner(&owner)?;
+
+    // DB-first for issues; sub-issue relations are not synced
+    let mut issues = None;
+    if let Some(pool) = &state.db {
+        if let Some(repo_db_id) = convert::get_repo_db_id(pool, &owner, &repo).await {
+            if convert::has_synced_data(pool, repo_db_id, "issues").await {
+                issues = Some(convert::issues_from_db(pool, repo_db_id, None, None).await?);
+            }
+        }
+    }
+    let issues = match issues {
+        Some(issues) => issues,
+        None => client.list_issues(&repo_id, IssueParams::all()).await?,
+    };
+
+    let relations = match client.list_issue_relations(&repo_id).await {
+        Ok(relations) => relations,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to fetch sub-issues from GitHub for {}/{}: {}",
+                owner,
+                repo,
+                e
+            );
+            Vec::new()
+        }
+    };
+
+    let report = EpicReportCalculator::new(query.stale_days.unwrap_or(14))
+        .with_closed(query.all.unwrap_or(false))
+        .calculate(&repo_id.full_name(), &issues, &relations, Utc::now());
+
+    Ok(Json(ApiResponse::ok(report)))
+}
+
 fn filter_issues_by(issues: Vec<Issue>, state: Option<&str>, days: Option<i64>) -> Vec<Issue> {
     use greport_core::models::IssueState;
     let cutoff = days.map(|d| Utc::now() - chrono::Duration::days(d));
@@ -0,0 +1,188 @@
+//! Login route handlers (OAuth2/OIDC sessions)
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Redirect, Response},
+    Extension, Json,
+};
+use chrono::Duration;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::AuthInfo;
+use crate::error::{ApiError, ErrorResponse};
+use crate::oauth::OAuthSettings;
+use crate::response::ApiResponse;
+use crate::session::{cookie_header, cookie_value, SESSION_COOKIE};
+use crate::state::AppState;
+use utoipa::IntoParams;
+
+pub use greport_types::login::CurrentUser;
+
+/// Cookie holding the CSRF state between login and callback
+const STATE_COOKIE: &str = "greport_oauth_state";
+
+/// Where to go after login when no dashboard URL is configured
+const DEFAULT_LOGIN_REDIRECT: &str = "/api/v1/auth/me";
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+fn oauth(state: &AppState) -> Result<&Arc<OAuthSettings>, ApiError> {
+    state
+        .oauth
+        .as_ref()
+        .ok_or_else(|| ApiError::NotFound("Login is not configured on this server".to_string()))
+}
+
+fn set_cookie(response: &mut Response, cookie: String) {
+    if let Ok(value) = HeaderValue::from_str(&cookie) {
+        response.headers_mut().append(header::SET_COOKIE, value);
+    }
+}
+
+/// GET /api/v1/auth/login - Redirect to the identity provider
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/login",
+    tag = "auth",
+    responses(
+        (status = 303, description = "Redirect to the identity provider"),
+        (status = 404, description = "Login is not configured", body = ErrorResponse)
+    ),
+    security(()),
+)]
+pub async fn login(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let oauth = oauth(&state)?;
+    let csrf = Uuid::new_v4().simple().to_string();
+
+    let mut response = Redirect::to(&oauth.authorize_redirect(&csrf)).into_response();
+    set_cookie(
+        &mut response,
+        cookie_header(
+            STATE_COOKIE,
+            &csrf,
+            Duration::minutes(10),
+            oauth.secure_cookies,
+        ),
+    );
+    Ok(response)
+}
+
+/// GET /api/v1/auth/callback - Finish login and issue a session cookie
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/callback",
+    tag = "auth",
+    params(
+        CallbackQuery,
+    ),
+    responses(
+        (status = 303, description = "Session cookie set; redirect to the dashboard"),
+        (status = 400, description = "Login failed", body = ErrorResponse)
+    ),
+    security(()),
+)]
+pub async fn callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Result<Response, ApiError> {
+    let oauth = oauth(&state)?;
+
+    if let Some(error) = query.error {
+        return Err(ApiError::BadRequest(format!(
+            "Login failed: {}",
+            query.error_description.unwrap_or(error)
+        )));
+    }
+    let code = query
+        .code
+        .ok_or_else(|| ApiError::BadRequest("Missing authorization code".to_string()))?;
+    let expected = cookie_value(&headers, STATE_COOKIE);
+    if expected.is_none() || query.state.as_deref() != expected {
+        return Err(ApiError::BadRequest(
+            "Login state mismatch; start again from /api/v1/auth/login".to_string(),
+        ));
+    }
+
+    let access_token = oauth.exchange_code(&code).await?;
+    let identity = oauth.fetch_identity(&access_token).await?;
+    let (orgs, scopes) = oauth.grant(&identity)?;
+    let (token, claims) = oauth
+        .sessions
+        .issue(&identity.login, orgs, scopes)
+        .map_err(|e| ApiError::Internal(format!("Could not issue session: {}", e)))?;
+    tracing::info!(login = %claims.sub, scopes = ?claims.scopes, "User logged in");
+
+    let target = oauth
+        .dashboard_url
+        .as_deref()
+        .unwrap_or(DEFAULT_LOGIN_REDIRECT);
+    let mut response = Redirect::to(target).into_response();
+    set_cookie(
+        &mut response,
+        cookie_header(
+            SESSION_COOKIE,
+            &token,
+            oauth.sessions.ttl(),
+            oauth.secure_cookies,
+        ),
+    );
+    set_cookie(
+        &mut response,
+        cookie_header(STATE_COOKIE, "", Duration::zero(), oauth.secure_cookies),
+    );
+    Ok(response)
+}
+
+/// POST /api/v1/auth/logout - Clear the session cookie
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Session cookie cleared", body = ApiResponse<serde_json::Value>)
+    ),
+    security(()),
+)]
+pub async fn logout(State(state): State<AppState>) -> Response {
+    let secure = state.oauth.as_ref().is_some_and(|o| o.secure_cookies);
+    let mut response =
+        Json(ApiResponse::ok(serde_json::json!({ "logged_out": true }))).into_response();
+    set_cookie(
+        &mut response,
+        cookie_header(SESSION_COOKIE, "", Duration::zero(), secure),
+    );
+    response
+}
+
+/// GET /api/v1/auth/me - Describe the authenticated caller
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CurrentUser>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+    security(()),
+)]
+pub async fn me(
+    auth: Option<Extension<Arc<AuthInfo>>>,
+) -> Result<Json<ApiResponse<CurrentUser>>, ApiError> {
+    let Extension(auth) = auth.ok_or(ApiError::Unauthorized)?;
+    Ok(Json(ApiResponse::ok(CurrentUser {
+        login: auth.owner.clone(),
+        scopes: auth.scopes.clone(),
+        orgs: auth.github_orgs.clone(),
+    })))
+}
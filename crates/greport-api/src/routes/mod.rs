@@ -1,11 +1,13 @@
 //! API route handlers
 
+pub mod admin;
 pub mod aggregate;
 pub mod batch;
 pub mod calendar;
 pub mod contrib;
 pub mod health;
 pub mod issues;
+pub mod login;
 pub mod orgs;
 pub mod projects;
 pub mod pulls;
@@ -4,44 +4,35 @@ use axum::{
     extract::{Path, State},
     Json,
 };
-use serde::Serialize;
 
-use crate::error::ApiError;
+use crate::auth::Caller;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
 
-/// Summary of an organization (no tokens exposed).
-#[derive(Serialize)]
-pub struct OrgSummary {
-    pub name: String,
-    pub web_url: String,
-    pub repo_count: usize,
-}
-
-/// Response for the orgs list endpoint.
-#[derive(Serialize)]
-pub struct OrgsListResponse {
-    pub orgs: Vec<OrgSummary>,
-    pub default_web_url: String,
-}
-
-/// Repository entry within an organization.
-#[derive(Serialize)]
-pub struct OrgRepoEntry {
-    pub name: String,
-    pub full_name: String,
-}
+pub use greport_types::orgs::{OrgRepoEntry, OrgSummary, OrgsListResponse};
 
 /// GET /api/v1/orgs
 ///
 /// List all configured organizations.
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs",
+    tag = "organizations",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<OrgsListResponse>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_orgs(
     State(state): State<AppState>,
+    caller: Caller,
 ) -> Result<Json<ApiResponse<OrgsListResponse>>, ApiError> {
     let entries = state.registry.org_entries();
 
     let orgs: Vec<OrgSummary> = entries
         .iter()
+        .filter(|e| caller.can_see_owner(&e.name))
         .map(|e| OrgSummary {
             name: e.name.clone(),
             web_url: state.web_url_for_owner(&e.name),
@@ -60,6 +51,18 @@ pub async fn list_orgs(
 /// GET /api/v1/orgs/{org}/repos
 ///
 /// List configured repos for a specific organization.
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org}/repos",
+    tag = "organizations",
+    params(
+        ("org" = String, Path, description = "Organization"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<OrgRepoEntry>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_org_repos(
     State(state): State<AppState>,
     Path(org): Path<String>,
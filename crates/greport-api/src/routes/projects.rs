@@ -1,111 +1,43 @@
 //! GitHub Projects V2 route handlers
 
-use std::collections::HashMap;
-
 use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use chrono::Utc;
 
-use crate::error::ApiError;
+use crate::auth::Caller;
+use crate::convert;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::{ApiResponse, PaginatedResponse};
 use crate::state::AppState;
+use greport_core::metrics::ProjectMetricsCalculator;
+use greport_core::reports::{IterationReport, IterationReportCalculator};
 use greport_db::models::{ProjectFieldRow, ProjectItemRow, ProjectRow};
 
-// =============================================================================
-// Response types
-// =============================================================================
-
-#[derive(Serialize)]
-pub struct ProjectSummary {
-    pub number: i64,
-    pub owner: String,
-    pub title: String,
-    pub description: Option<String>,
-    pub url: String,
-    pub closed: bool,
-    pub total_items: i32,
-    pub synced_at: DateTime<Utc>,
-}
-
-#[derive(Serialize)]
-pub struct ProjectDetail {
-    pub number: i64,
-    pub owner: String,
-    pub title: String,
-    pub description: Option<String>,
-    pub url: String,
-    pub closed: bool,
-    pub total_items: i32,
-    pub synced_at: DateTime<Utc>,
-    pub fields: Vec<ProjectFieldSummary>,
-}
-
-#[derive(Serialize)]
-pub struct ProjectFieldSummary {
-    pub name: String,
-    pub field_type: String,
-    pub config_json: Option<Value>,
-}
-
-#[derive(Serialize)]
-pub struct ProjectItemResponse {
-    pub node_id: String,
-    pub content_type: String,
-    pub content_number: Option<i64>,
-    pub content_title: String,
-    pub content_state: Option<String>,
-    pub content_url: Option<String>,
-    pub content_repository: Option<String>,
-    pub field_values: Option<Value>,
-}
-
-#[derive(Serialize)]
-pub struct ProjectMetrics {
-    pub project_number: i64,
-    pub project_title: String,
-    pub total_items: i32,
-    pub by_status: Vec<StatusCount>,
-    pub by_content_type: Vec<ContentTypeCount>,
-}
-
-#[derive(Serialize)]
-pub struct StatusCount {
-    pub status: String,
-    pub count: i64,
-}
-
-#[derive(Serialize)]
-pub struct ContentTypeCount {
-    pub content_type: String,
-    pub count: i64,
-}
-
-// =============================================================================
-// Query parameter structs
-// =============================================================================
-
-#[derive(Deserialize)]
-pub struct ListProjectsQuery {
-    pub include_closed: Option<bool>,
-}
-
-#[derive(Deserialize)]
-pub struct ListItemsQuery {
-    pub content_type: Option<String>,
-    pub state: Option<String>,
-    pub page: Option<u32>,
-    pub per_page: Option<u32>,
-}
+pub use greport_types::projects::{
+    IterationsQuery, ListItemsQuery, ListProjectsQuery, ProjectDetail, ProjectFieldSummary,
+    ProjectItemResponse, ProjectMetricsQuery, ProjectMetricsResponse, ProjectSummary,
+};
 
 // =============================================================================
 // Endpoint handlers
 // =============================================================================
 
 /// GET /api/v1/orgs/{org}/projects
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org}/projects",
+    tag = "projects",
+    params(
+        ("org" = String, Path, description = "Organization"),
+        ListProjectsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectSummary>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_projects(
     State(state): State<AppState>,
     Path(org): Path<String>,
@@ -124,6 +56,19 @@ pub async fn list_projects(
 }
 
 /// GET /api/v1/orgs/{org}/projects/{number}
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org}/projects/{number}",
+    tag = "projects",
+    params(
+        ("org" = String, Path, description = "Organization"),
+        ("number" = i64, Path, description = "Project number"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ProjectDetail>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_project(
     State(state): State<AppState>,
     Path((org, number)): Path<(String, i64)>,
@@ -158,6 +103,20 @@ pub async fn get_project(
 }
 
 /// GET /api/v1/orgs/{org}/projects/{number}/items
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org}/projects/{number}/items",
+    tag = "projects",
+    params(
+        ("org" = String, Path, description = "Organization"),
+        ("number" = i64, Path, description = "Project number"),
+        ListItemsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<ProjectItemResponse>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_project_items(
     State(state): State<AppState>,
     Path((org, number)): Path<(String, i64)>,
@@ -213,10 +172,25 @@ pub async fn list_project_items(
 }
 
 /// GET /api/v1/orgs/{org}/projects/{number}/metrics
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org}/projects/{number}/metrics",
+    tag = "projects",
+    params(
+        ("org" = String, Path, description = "Organization"),
+        ("number" = i64, Path, description = "Project number"),
+        ProjectMetricsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ProjectMetricsResponse>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_project_metrics(
     State(state): State<AppState>,
     Path((org, number)): Path<(String, i64)>,
-) -> Result<Json<ApiResponse<ProjectMetrics>>, ApiError> {
+    Query(query): Query<ProjectMetricsQuery>,
+) -> Result<Json<ApiResponse<ProjectMetricsResponse>>, ApiError> {
     let pool = state
         .db
         .as_ref()
@@ -228,47 +202,89 @@ pub async fn get_project_metrics(
             ApiError::NotFound(format!("Project {} not found for org {}", number, org))
         })?;
 
-    // Status breakdown
-    let status_rows =
-        greport_db::queries::count_project_items_by_status(pool, &project.node_id, "Status")
-            .await?;
-    let by_status: Vec<StatusCount> = status_rows
-        .into_iter()
-        .map(|(status, count)| StatusCount { status, count })
-        .collect();
-
-    // Content type breakdown
-    let all_items =
-        greport_db::queries::list_project_items(pool, &project.node_id, None, None, None, None)
-            .await?;
-
-    let mut type_counts: HashMap<String, i64> = HashMap::new();
-    for item in &all_items {
-        *type_counts.entry(item.content_type.clone()).or_insert(0) += 1;
-    }
-    let mut by_content_type: Vec<ContentTypeCount> = type_counts
-        .into_iter()
-        .map(|(content_type, count)| ContentTypeCount {
-            content_type,
-            count,
-        })
-        .collect();
-    by_content_type.sort_by(|a, b| b.count.cmp(&a.count));
-
-    let metrics = ProjectMetrics {
+    let items = convert::project_items_from_db(pool, &project.node_id).await?;
+    let status_field = query.status_field.as_deref().unwrap_or("Status");
+    let metrics = ProjectMetricsResponse {
         project_number: project.number,
         project_title: project.title,
-        total_items: project.total_items,
-        by_status,
-        by_content_type,
+        metrics: ProjectMetricsCalculator::calculate(&items, status_field),
     };
 
     Ok(Json(ApiResponse::ok(metrics)))
 }
 
+/// GET /api/v1/orgs/{org}/projects/{number}/iterations
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org}/projects/{number}/iterations",
+    tag = "projects",
+    params(
+        ("org" = String, Path, description = "Organization"),
+        ("number" = i64, Path, description = "Project number"),
+        IterationsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<IterationReport>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
+pub async fn get_project_iterations(
+    State(state): State<AppState>,
+    Path((org, number)): Path<(String, i64)>,
+    Query(query): Query<IterationsQuery>,
+) -> Result<Json<ApiResponse<IterationReport>>, ApiError> {
+    let pool = state
+        .db
+        .as_ref()
+        .ok_or_else(|| ApiError::BadRequest("Database required for projects".into()))?;
+
+    let row = greport_db::queries::get_project(pool, &org, number)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Project {} not found for org {}", number, org))
+        })?;
+
+    let project = convert::project_from_db(pool, row).await?;
+    let items = convert::project_items_from_db(pool, &project.node_id).await?;
+
+    let done: Vec<String> = query
+        .done
+        .as_deref()
+        .map(|d| {
+            d.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let report =
+        IterationReportCalculator::new(query.field.unwrap_or_else(|| "Iteration".to_string()))
+            .with_points_field(query.points_field)
+            .with_status_field(query.status_field.unwrap_or_else(|| "Status".to_string()))
+            .with_done_values(done)
+            .calculate(&project, &items, Utc::now().date_naive())
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(ApiResponse::ok(report)))
+}
+
 /// GET /api/v1/aggregate/projects
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/projects",
+    tag = "projects",
+    params(
+        ListProjectsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectSummary>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn aggregate_projects(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<ListProjectsQuery>,
 ) -> Result<Json<ApiResponse<Vec<ProjectSummary>>>, ApiError> {
     let pool = state
@@ -280,6 +296,9 @@ pub async fn aggregate_projects(
     let mut all_summaries: Vec<ProjectSummary> = Vec::new();
 
     for entry in state.registry.org_entries() {
+        if !caller.can_see_owner(&entry.name) {
+            continue;
+        }
         let rows = greport_db::queries::list_projects(pool, &entry.name, include_closed).await?;
         all_summaries.extend(rows.iter().map(project_row_to_summary));
     }
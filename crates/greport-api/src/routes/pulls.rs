@@ -5,24 +5,31 @@ use axum::{
     Json,
 };
 use chrono::Utc;
-use serde::Deserialize;
 
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::{ApiResponse, PaginatedResponse};
 use crate::state::AppState;
-use greport_core::client::{GitHubClient, PullParams, PullStateFilter, RepoId};
+use greport_core::client::{PullParams, PullStateFilter, RepoId};
 use greport_core::metrics::{PullMetrics, PullMetricsCalculator};
 use greport_core::models::PullRequest;
 
-#[derive(Deserialize)]
-pub struct ListPullsQuery {
-    state: Option<String>,
-    page: Option<u32>,
-    per_page: Option<u32>,
-    days: Option<i64>,
-}
+pub use greport_types::pulls::{ListPullsQuery, MetricsQuery};
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/pulls",
+    tag = "pulls",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ListPullsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<PullRequest>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_pulls(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -91,12 +98,20 @@ pub async fn list_pulls(
     )))
 }
 
-#[derive(Deserialize)]
-pub struct MetricsQuery {
-    state: Option<String>,
-    days: Option<i64>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/pulls/metrics",
+    tag = "pulls",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        MetricsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PullMetrics>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_metrics(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -108,7 +123,10 @@ pub async fn get_metrics(
             if convert::has_synced_data(pool, repo_db_id, "pulls").await {
                 let prs = convert::pulls_from_db(pool, repo_db_id, None, None).await?;
                 let filtered = filter_pulls_by(prs, query.state.as_deref(), query.days);
-                let metrics = PullMetricsCalculator::calculate(&filtered);
+                let metrics = PullMetricsCalculator::calculate_with_bots(
+                    &filtered,
+                    &state.config.bot_filter(&owner),
+                );
                 return Ok(Json(ApiResponse::ok(metrics)));
             }
         }
@@ -131,7 +149,8 @@ pub async fn get_metrics(
     };
 
     let filtered = filter_pulls_by(prs, query.state.as_deref(), query.days);
-    let metrics = PullMetricsCalculator::calculate(&filtered);
+    let metrics =
+        PullMetricsCalculator::calculate_with_bots(&filtered, &state.config.bot_filter(&owner));
 
     Ok(Json(ApiResponse::ok(metrics)))
 }
@@ -5,10 +5,10 @@ use axum::{
     Json,
 };
 use chrono::{Months, Utc};
-use serde::Deserialize;
 
+use crate::auth::Caller;
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
 use greport_core::models::{
@@ -16,11 +16,7 @@ use greport_core::models::{
     TimelineEntry, UpcomingRelease, User,
 };
 
-#[derive(Deserialize)]
-pub struct ReleasePlanQuery {
-    months_back: Option<u32>,
-    months_forward: Option<u32>,
-}
+pub use greport_types::release_plan::ReleasePlanQuery;
 
 /// Classify a release as stable, prerelease, or draft
 fn classify_release(release: &Release) -> String {
@@ -156,6 +152,7 @@ async fn build_repo_release_plan(
                     login: r.author_login,
                     avatar_url: String::new(),
                     html_url: String::new(),
+                    is_bot: r.author_is_bot,
                 },
                 created_at: r.created_at,
                 published_at: r.published_at,
@@ -190,6 +187,20 @@ async fn build_repo_release_plan(
 }
 
 /// GET /api/v1/repos/{owner}/{repo}/release-plan
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/release-plan",
+    tag = "release-plan",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ReleasePlanQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ReleasePlan>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_release_plan(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -217,9 +228,9 @@ pub async fn get_release_plan(
     )
     .await?;
 
-    upcoming.sort_by(|a, b| a.milestone.due_on.cmp(&b.milestone.due_on));
-    recent_releases.sort_by(|a, b| b.release.published_at.cmp(&a.release.published_at));
-    timeline.sort_by(|a, b| a.date.cmp(&b.date));
+    upcoming.sort_by_key(|e| e.milestone.due_on);
+    recent_releases.sort_by_key(|e| std::cmp::Reverse(e.release.published_at));
+    timeline.sort_by_key(|e| e.date);
 
     Ok(Json(ApiResponse::ok(ReleasePlan {
         upcoming,
@@ -229,8 +240,21 @@ pub async fn get_release_plan(
 }
 
 /// GET /api/v1/aggregate/release-plan
+#[utoipa::path(
+    get,
+    path = "/api/v1/aggregate/release-plan",
+    tag = "release-plan",
+    params(
+        ReleasePlanQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ReleasePlan>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_aggregate_release_plan(
     State(state): State<AppState>,
+    caller: Caller,
     Query(query): Query<ReleasePlanQuery>,
 ) -> Result<Json<ApiResponse<ReleasePlan>>, ApiError> {
     let months_back = query.months_back.unwrap_or(3);
@@ -246,6 +270,9 @@ pub async fn get_aggregate_release_plan(
     let mut all_timeline = Vec::new();
 
     for tracked_repo in &tracked {
+        if !caller.can_see_repo(&tracked_repo.full_name) {
+            continue;
+        }
         let parts: Vec<&str> = tracked_repo.full_name.splitn(2, '/').collect();
         if parts.len() != 2 {
             continue;
@@ -282,9 +309,9 @@ pub async fn get_aggregate_release_plan(
         }
     }
 
-    all_upcoming.sort_by(|a, b| a.milestone.due_on.cmp(&b.milestone.due_on));
-    all_recent.sort_by(|a, b| b.release.published_at.cmp(&a.release.published_at));
-    all_timeline.sort_by(|a, b| a.date.cmp(&b.date));
+    all_upcoming.sort_by_key(|e| e.milestone.due_on);
+    all_recent.sort_by_key(|e| std::cmp::Reverse(e.release.published_at));
+    all_timeline.sort_by_key(|e| e.date);
 
     Ok(Json(ApiResponse::ok(ReleasePlan {
         upcoming: all_upcoming,
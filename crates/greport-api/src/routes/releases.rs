@@ -4,22 +4,31 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use serde::Deserialize;
 
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::{ApiResponse, PaginatedResponse};
 use crate::state::AppState;
-use greport_core::client::{GitHubClient, IssueParams, PullParams, RepoId};
+use greport_core::client::{IssueParams, PullParams, RepoId};
 use greport_core::models::{IssueState, Milestone, Release};
 use greport_core::reports::{ReleaseNotes, ReleaseNotesGenerator};
 
-#[derive(Deserialize)]
-pub struct ListReleasesQuery {
-    page: Option<u32>,
-    per_page: Option<u32>,
-}
-
+pub use greport_types::releases::{ListReleasesQuery, ReleaseNotesQuery};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/releases",
+    tag = "releases",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ListReleasesQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = PaginatedResponse<Release>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_releases(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -67,17 +76,27 @@ pub async fn list_releases(
     )))
 }
 
-#[derive(Deserialize)]
-pub struct ReleaseNotesQuery {
-    milestone: String,
-    version: Option<String>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/releases/notes",
+    tag = "releases",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ReleaseNotesQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ReleaseNotes>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_notes(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
     Query(query): Query<ReleaseNotesQuery>,
 ) -> Result<Json<ApiResponse<ReleaseNotes>>, ApiError> {
+    let bots = state.config.bot_filter(&owner);
+
     // DB-first: needs milestones, issues, and pulls all synced
     if let Some(pool) = &state.db {
         if let Some(repo_db_id) = convert::get_repo_db_id(pool, &owner, &repo).await {
@@ -103,7 +122,8 @@ pub async fn get_notes(
                 let prs = convert::pulls_from_db(pool, repo_db_id, None, None).await?;
                 let merged_prs: Vec<_> = prs.into_iter().filter(|p| p.merged).collect();
 
-                let generator = ReleaseNotesGenerator::with_defaults();
+                let generator =
+                    ReleaseNotesGenerator::with_defaults().with_bot_filter(bots.clone());
                 let version = query.version.unwrap_or_else(|| query.milestone.clone());
                 let notes = generator.generate(&version, &milestone_issues, &merged_prs);
 
@@ -136,13 +156,27 @@ pub async fn get_notes(
     let merged_prs: Vec<_> = prs.into_iter().filter(|p| p.merged).collect();
 
     // Generate notes
-    let generator = ReleaseNotesGenerator::with_defaults();
+    let generator = ReleaseNotesGenerator::with_defaults().with_bot_filter(bots.clone());
     let version = query.version.unwrap_or_else(|| query.milestone.clone());
     let notes = generator.generate(&version, &milestone_issues, &merged_prs);
 
     Ok(Json(ApiResponse::ok(notes)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/milestones/{milestone}/progress",
+    tag = "releases",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        ("milestone" = String, Path, description = "Milestone title"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Milestone>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_progress(
     State(state): State<AppState>,
     Path((owner, repo, milestone)): Path<(String, String, String)>,
@@ -4,39 +4,28 @@ use axum::{
     extract::{Json as AxumJson, Path, State},
     Json,
 };
-use serde::{Deserialize, Serialize};
 
-use crate::error::ApiError;
+use crate::auth::Caller;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
 use crate::sync;
 
-#[derive(Serialize)]
-pub struct RepoSummary {
-    pub owner: String,
-    pub name: String,
-    pub full_name: String,
-    pub description: Option<String>,
-    pub sync_status: Option<SyncStatusSummary>,
-}
-
-#[derive(Serialize)]
-pub struct SyncStatusSummary {
-    pub issues_synced: bool,
-    pub pulls_synced: bool,
-    pub releases_synced: bool,
-    pub milestones_synced: bool,
-    pub last_synced_at: Option<String>,
-}
-
-#[derive(Deserialize)]
-pub struct AddRepoRequest {
-    pub full_name: String,
-}
+pub use greport_types::repos::{AddRepoRequest, RepoSummary, SyncStatusSummary};
 
 /// GET /api/v1/repos - List all tracked repositories
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos",
+    tag = "repositories",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<RepoSummary>>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn list_repos(
     State(state): State<AppState>,
+    caller: Caller,
 ) -> Result<Json<ApiResponse<Vec<RepoSummary>>>, ApiError> {
     let pool = state
         .db
@@ -47,6 +36,9 @@ pub async fn list_repos(
 
     let mut summaries = Vec::new();
     for repo in repos {
+        if !caller.can_see_owner(&repo.owner) {
+            continue;
+        }
         let sync_status = match greport_db::queries::get_sync_status(pool, repo.id).await? {
             Some(status) => Some(SyncStatusSummary {
                 issues_synced: status.issues_synced_at.is_some(),
@@ -74,6 +66,16 @@ pub async fn list_repos(
 }
 
 /// POST /api/v1/repos - Add a repository and trigger initial sync
+#[utoipa::path(
+    post,
+    path = "/api/v1/repos",
+    tag = "repositories",
+    request_body = AddRepoRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RepoSummary>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn add_repo(
     State(state): State<AppState>,
     AxumJson(body): AxumJson<AddRepoRequest>,
@@ -120,6 +122,19 @@ pub async fn add_repo(
 }
 
 /// DELETE /api/v1/repos/{owner}/{repo} - Remove a tracked repository
+#[utoipa::path(
+    delete,
+    path = "/api/v1/repos/{owner}/{repo}",
+    tag = "repositories",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+    ),
+    responses(
+        (status = 200, description = "Repository removed"),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn remove_repo(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -135,6 +150,7 @@ pub async fn remove_repo(
         .ok_or_else(|| ApiError::NotFound(format!("Repository not found: {}", full_name)))?;
 
     greport_db::queries::delete_repository(pool, repo_row.id).await?;
+    greport_db::queries::bump_sync_generation(pool, None).await?;
 
     Ok(Json(ApiResponse::ok(())))
 }
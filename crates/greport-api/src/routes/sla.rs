@@ -5,92 +5,30 @@ use axum::{
     Json,
 };
 use chrono::{Duration, Utc};
-use serde::{Deserialize, Serialize};
 
 use crate::convert;
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
-use greport_core::client::{GitHubClient, IssueParams, RepoId};
+use greport_core::client::{IssueParams, RepoId};
 use greport_core::models::{Issue, IssueState};
 
-#[derive(Deserialize)]
-pub struct SlaQuery {
-    /// Custom response time SLA in hours
-    response_hours: Option<i64>,
-    /// Custom resolution time SLA in hours
-    resolution_hours: Option<i64>,
-    /// Filter by labels (comma-separated)
-    labels: Option<String>,
-}
-
-/// SLA compliance report
-#[derive(Serialize)]
-pub struct SlaReport {
-    /// Repository identifier
-    pub repository: String,
-    /// SLA configuration used
-    pub config: SlaConfig,
-    /// Summary statistics
-    pub summary: SlaSummary,
-    /// Issues breaching SLA
-    pub breaching_issues: Vec<SlaIssue>,
-    /// Issues at risk of breaching
-    pub at_risk_issues: Vec<SlaIssue>,
-    /// Generated at timestamp
-    pub generated_at: String,
-}
-
-/// SLA configuration
-#[derive(Serialize)]
-pub struct SlaConfig {
-    pub response_time_hours: i64,
-    pub resolution_time_hours: i64,
-}
-
-/// SLA summary statistics
-#[derive(Serialize)]
-pub struct SlaSummary {
-    /// Total open issues
-    pub total_open: usize,
-    /// Issues within SLA
-    pub within_sla: usize,
-    /// Issues breaching response SLA
-    pub response_breached: usize,
-    /// Issues breaching resolution SLA
-    pub resolution_breached: usize,
-    /// Issues at risk (>80% of SLA time elapsed)
-    pub at_risk: usize,
-    /// SLA compliance percentage
-    pub compliance_rate: f64,
-}
-
-/// Issue with SLA status
-#[derive(Serialize)]
-pub struct SlaIssue {
-    pub number: u64,
-    pub title: String,
-    pub url: String,
-    pub author: String,
-    pub created_at: String,
-    pub age_hours: i64,
-    pub sla_status: SlaStatus,
-    pub labels: Vec<String>,
-}
-
-/// SLA status for an issue
-#[derive(Serialize)]
-pub enum SlaStatus {
-    /// Within SLA limits
-    Ok,
-    /// At risk (>80% of time elapsed)
-    AtRisk { percent_elapsed: f64 },
-    /// Response time breached
-    ResponseBreached { hours_overdue: i64 },
-    /// Resolution time breached
-    ResolutionBreached { hours_overdue: i64 },
-}
-
+pub use greport_types::sla::{SlaConfig, SlaIssue, SlaQuery, SlaReport, SlaStatus, SlaSummary};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/repos/{owner}/{repo}/sla",
+    tag = "sla",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+        SlaQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SlaReport>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn get_sla_report(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
@@ -244,8 +182,8 @@ fn build_sla_report(
     }
 
     // Sort by age (oldest first)
-    breaching_issues.sort_by(|a, b| b.age_hours.cmp(&a.age_hours));
-    at_risk_issues.sort_by(|a, b| b.age_hours.cmp(&a.age_hours));
+    breaching_issues.sort_by_key(|e| std::cmp::Reverse(e.age_hours));
+    at_risk_issues.sort_by_key(|e| std::cmp::Reverse(e.age_hours));
 
     let total_open = issues.len();
     let compliance_rate = if total_open > 0 {
@@ -273,20 +211,3 @@ fn build_sla_report(
         generated_at: now.to_rfc3339(),
     }
 }
-
-impl Clone for SlaStatus {
-    fn clone(&self) -> Self {
-        match self {
-            SlaStatus::Ok => SlaStatus::Ok,
-            SlaStatus::AtRisk { percent_elapsed } => SlaStatus::AtRisk {
-                percent_elapsed: *percent_elapsed,
-            },
-            SlaStatus::ResponseBreached { hours_overdue } => SlaStatus::ResponseBreached {
-                hours_overdue: *hours_overdue,
-            },
-            SlaStatus::ResolutionBreached { hours_overdue } => SlaStatus::ResolutionBreached {
-                hours_overdue: *hours_overdue,
-            },
-        }
-    }
-}
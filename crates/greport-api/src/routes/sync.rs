@@ -5,7 +5,7 @@ use axum::{
     Json,
 };
 
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorResponse};
 use crate::response::ApiResponse;
 use crate::state::AppState;
 use crate::sync::{self, SyncResult};
@@ -13,6 +13,19 @@ use crate::sync::{self, SyncResult};
 /// POST /api/v1/repos/{owner}/{repo}/sync
 ///
 /// Triggers a full sync of the repository data from GitHub into PostgreSQL.
+#[utoipa::path(
+    post,
+    path = "/api/v1/repos/{owner}/{repo}/sync",
+    tag = "sync",
+    params(
+        ("owner" = String, Path, description = "Repository owner"),
+        ("repo" = String, Path, description = "Repository name"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SyncResult>),
+        (status = "4XX", description = "Invalid request or not authorized", body = ErrorResponse)
+    ),
+)]
 pub async fn sync_repo(
     State(state): State<AppState>,
     Path((owner, repo)): Path<(String, String)>,
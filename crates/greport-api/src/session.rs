@@ -0,0 +1,160 @@
+//! Signed session tokens issued after OAuth login
+//!
+//! Sessions are HS256 JWTs carried either in the session cookie (dashboard)
+//! or as a bearer token (API clients).
+
+use axum::http::{header, HeaderMap};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+/// Cookie holding the session token
+pub const SESSION_COOKIE: &str = "greport_session";
+
+/// Issuer claim on session tokens
+const ISSUER: &str = "greport";
+
+/// Claims carried by a session token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionClaims {
+    /// User login
+    pub sub: String,
+    /// Organizations (or OIDC groups) the user belongs to
+    pub orgs: Vec<String>,
+    /// Granted API scopes
+    pub scopes: Vec<String>,
+    pub iss: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Keys for signing and verifying session tokens
+pub struct SessionKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+}
+
+impl std::fmt::Debug for SessionKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionKeys")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionKeys {
+    /// Create keys from a shared secret
+    pub fn new(secret: &[u8], ttl: Duration) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            ttl,
+        }
+    }
+
+    /// Session lifetime
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Issue a signed session token
+    pub fn issue(
+        &self,
+        login: &str,
+        orgs: Vec<String>,
+        scopes: Vec<String>,
+    ) -> Result<(String, SessionClaims), jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let claims = SessionClaims {
+            sub: login.to_string(),
+            orgs,
+            scopes,
+            iss: ISSUER.to_string(),
+            iat: now.timestamp(),
+            exp: (now + self.ttl).timestamp(),
+        };
+        let token = jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)?;
+        Ok((token, claims))
+    }
+
+    /// Verify a session token, returning its claims if the signature is
+    /// valid and it has not expired
+    pub fn verify(&self, token: &str) -> Option<SessionClaims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[ISSUER]);
+        validation.leeway = 0;
+        jsonwebtoken::decode::<SessionClaims>(token, &self.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| tracing::debug!("Rejected session token: {}", e))
+            .ok()
+    }
+}
+
+/// Whether a bearer token is shaped like a JWT
+pub fn is_session_token(token: &str) -> bool {
+    token.starts_with("eyJ") && token.matches('.').count() == 2
+}
+
+/// Read a cookie value from request headers
+pub fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Build a `Set-Cookie` value; a zero `max_age` clears the cookie
+pub fn cookie_header(name: &str, value: &str, max_age: Duration, secure: bool) -> String {
+    let mut cookie = format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}",
+        name,
+        value,
+        max_age.num_seconds()
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+    cookie
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_session_roundtrip() {
+        let keys = SessionKeys::new(b"secret", Duration::hours(1));
+        let (token, _) = keys
+            .issue("octocat", vec!["acme".into()], vec!["read".into()])
+            .unwrap();
+        assert!(is_session_token(&token));
+
+        let claims = keys.verify(&token).unwrap();
+        assert_eq!(claims.sub, "octocat");
+        assert_eq!(claims.orgs, vec!["acme"]);
+
+        let other = SessionKeys::new(b"other", Duration::hours(1));
+        assert!(other.verify(&token).is_none());
+
+        let expired = SessionKeys::new(b"secret", Duration::seconds(-10));
+        let (token, _) = expired.issue("octocat", vec![], vec![]).unwrap();
+        assert!(keys.verify(&token).is_none());
+    }
+
+    #[test]
+    fn test_cookie_value() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; greport_session=abc.def"),
+        );
+        assert_eq!(cookie_value(&headers, SESSION_COOKIE), Some("abc.def"));
+        assert_eq!(cookie_value(&headers, "missing"), None);
+    }
+}
@@ -1,8 +1,16 @@
 //! Application state
 
+use crate::auth::CALLER_CLIENT;
+use crate::oauth::OAuthSettings;
 use crate::rate_limit::RateLimiter;
-use greport_core::{GitHubClientRegistry, OctocrabClient};
+use crate::token_cache::TokenCache;
+use greport_core::client::{Credentials, Pagination};
+use greport_core::config::BotsConfig;
+use greport_core::identities::Identities;
+use greport_core::metrics::BotFilter;
+use greport_core::{GitHubApi, GitHubClientRegistry, OctocrabClient};
 use greport_db::DbPool;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Shared application state
@@ -16,18 +24,27 @@ pub struct AppState {
     pub db: Option<DbPool>,
     /// Rate limiter
     pub rate_limiter: Arc<RateLimiter>,
+    /// Verified GitHub passthrough tokens
+    pub token_cache: Arc<TokenCache>,
+    /// OAuth/OIDC login (None when not configured)
+    pub oauth: Option<Arc<OAuthSettings>>,
 }
 
 impl AppState {
     /// Get the GitHub client for a specific repository owner/org.
     ///
     /// Resolves the correct per-org client from the registry, falling back
-    /// to the default client for unconfigured orgs.
+    /// to the default client for unconfigured orgs. When the request was
+    /// made with a verified GitHub token and `use_caller_token` is enabled,
+    /// the caller's own client is used instead.
     pub fn client_for_owner(
         &self,
         owner: &str,
-    ) -> Result<&Arc<OctocrabClient>, crate::error::ApiError> {
-        self.registry.client_for_org(owner).map_err(|e| {
+    ) -> Result<Arc<dyn GitHubApi>, crate::error::ApiError> {
+        if let Ok(client) = CALLER_CLIENT.try_with(Arc::clone) {
+            return Ok(client);
+        }
+        self.registry.client_for_org(owner).cloned().map_err(|e| {
             crate::error::ApiError::BadRequest(format!(
                 "No GitHub token configured for organization '{}': {}",
                 owner, e
@@ -56,10 +73,22 @@ pub struct ApiConfig {
     pub max_page_size: usize,
     /// Enable authentication requirement
     pub require_auth: bool,
+    /// Use a caller's verified GitHub token for GitHub fetches
+    pub use_caller_token: bool,
     /// SLA response time threshold in hours
     pub sla_response_hours: i64,
     /// SLA resolution time threshold in hours
     pub sla_resolution_hours: i64,
+    /// Maximum nesting depth of a GraphQL query
+    pub graphql_max_depth: usize,
+    /// Maximum complexity of a GraphQL query
+    pub graphql_max_complexity: usize,
+    /// Bot and automation accounts kept out of metrics
+    pub bots: BotsConfig,
+    /// Extra bot patterns per organization (lowercased name)
+    pub org_bots: HashMap<String, Vec<String>>,
+    /// Login aliases merged into one contributor
+    pub identities: Arc<Identities>,
 }
 
 impl Default for ApiConfig {
@@ -69,8 +98,14 @@ impl Default for ApiConfig {
             cache_ttl_seconds: 300,
             max_page_size: 100,
             require_auth: false,
+            use_caller_token: false,
             sla_response_hours: 24,
             sla_resolution_hours: 168, // 1 week
+            graphql_max_depth: 12,
+            graphql_max_complexity: 5000,
+            bots: BotsConfig::default(),
+            org_bots: HashMap::new(),
+            identities: Arc::default(),
         }
     }
 }
@@ -83,6 +118,7 @@ impl ApiConfig {
             cache_ttl_seconds: config.cache_ttl_seconds(),
             max_page_size: config.max_page_size(),
             require_auth: config.require_auth(),
+            use_caller_token: config.use_caller_token(),
             sla_response_hours: std::env::var("SLA_RESPONSE_HOURS")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -91,30 +127,56 @@ impl ApiConfig {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(config.sla.resolution_time_hours),
+            graphql_max_depth: config.graphql_max_depth(),
+            graphql_max_complexity: config.graphql_max_complexity(),
+            bots: config.bots.clone(),
+            org_bots: config
+                .organizations
+                .iter()
+                .map(|o| (o.name.to_lowercase(), o.exclude_bots.clone()))
+                .collect(),
+            // Read from the identities file by `AppState::with_core_config`
+            identities: Arc::default(),
         }
     }
+
+    /// Bot filter for repositories of `owner`
+    pub fn bot_filter(&self, owner: &str) -> BotFilter {
+        let extra = self
+            .org_bots
+            .get(&owner.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        self.bots.filter(extra)
+    }
 }
 
 impl AppState {
     /// Create application state from a pre-loaded Config
-    pub async fn with_core_config(core_config: greport_core::Config) -> anyhow::Result<Self> {
+    pub async fn with_core_config(mut core_config: greport_core::Config) -> anyhow::Result<Self> {
         tracing::debug!("Initializing application state");
 
+        // Stored REST responses hold private repository data, so the server
+        // only keeps them on disk when `etag_cache` is turned on explicitly
+        core_config.github.etag_cache.get_or_insert(false);
+
+        // Lists are stored and served as if whole, so one cut short by
+        // `max_items` fails instead
+        let pagination = Pagination::default()
+            .with_max_items(core_config.max_items())
+            .require_complete();
+
         let has_orgs = !core_config.organizations.is_empty();
         let registry = if has_orgs {
             tracing::info!(
                 org_count = core_config.organizations.len(),
                 "Building multi-org client registry"
             );
-            Arc::new(GitHubClientRegistry::from_config(&core_config)?)
+            Arc::new(GitHubClientRegistry::from_config_with_pagination(
+                &core_config,
+                pagination,
+            )?)
         } else {
-            let token = core_config.github_token().map_err(|_| {
-                anyhow::anyhow!(
-                    "GITHUB_TOKEN not set in environment or ~/.config/greport/config.toml"
-                )
-            })?;
-            tracing::debug!("GitHub token loaded");
-
             let base_url = std::env::var("GITHUB_BASE_URL")
                 .ok()
                 .or(core_config.github.base_url.clone());
@@ -124,19 +186,48 @@ impl AppState {
                 tracing::debug!("Using default GitHub.com API");
             }
 
-            let client = OctocrabClient::new(&token, base_url.as_deref())?;
+            let credentials = match core_config.github_app() {
+                Some(app) => {
+                    tracing::info!(
+                        app_id = app.app_id,
+                        installation_id = app.installation_id,
+                        "Authenticating as GitHub App installation"
+                    );
+                    Credentials::app(&app, base_url.as_deref())?
+                }
+                None => {
+                    let token = core_config.github_token().map_err(|_| {
+                        anyhow::anyhow!(
+                            "GITHUB_TOKEN (or a GitHub App) not set in environment or \
+                             ~/.config/greport/config.toml"
+                        )
+                    })?;
+                    tracing::debug!("GitHub token loaded");
+                    Credentials::Token(token)
+                }
+            };
+
+            let client = OctocrabClient::from_credentials(credentials, base_url.as_deref())?
+                .with_config(&core_config)?
+                .with_pagination(pagination);
             Arc::new(GitHubClientRegistry::with_default(client, base_url))
         };
         tracing::info!("GitHub client registry initialized");
 
-        let config = Arc::new(ApiConfig::from_core_config(&core_config));
+        let config = Arc::new(ApiConfig {
+            identities: Arc::new(core_config.identities()?),
+            ..ApiConfig::from_core_config(&core_config)
+        });
         tracing::debug!(
             rate_limit = config.rate_limit_per_minute,
             cache_ttl = config.cache_ttl_seconds,
             "API configuration loaded"
         );
 
-        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit_per_minute));
+        let oauth = OAuthSettings::from_config(&core_config)?.map(Arc::new);
+        if let Some(ref oauth) = oauth {
+            tracing::info!(provider = ?oauth.provider, "OAuth login enabled");
+        }
 
         // Try to connect to database (optional)
         let db = match core_config.database_url() {
@@ -167,24 +258,34 @@ impl AppState {
             }
         };
 
+        let rate_limiter = Arc::new(crate::rate_limit::from_config(
+            &core_config.rate_limit_backend(),
+            config.rate_limit_per_minute,
+            db.as_ref(),
+        )?);
+
         Ok(Self {
             registry,
             config,
             db,
             rate_limiter,
+            token_cache: Arc::new(TokenCache::default()),
+            oauth,
         })
     }
 
-    /// Create state for testing (no database)
-    #[cfg(test)]
-    #[allow(dead_code)]
-    pub fn test_state(github: OctocrabClient) -> Self {
-        let config = ApiConfig::default();
+    /// Create state from a prepared registry, with the in-memory rate
+    /// limiter and no login.
+    ///
+    /// Lets tests run the full router against `MockGitHubClient`.
+    pub fn new(registry: GitHubClientRegistry, config: ApiConfig, db: Option<DbPool>) -> Self {
         Self {
-            registry: Arc::new(GitHubClientRegistry::with_default(github, None)),
+            registry: Arc::new(registry),
             config: Arc::new(config.clone()),
-            db: None,
+            db,
             rate_limiter: Arc::new(RateLimiter::new(config.rate_limit_per_minute)),
+            token_cache: Arc::new(TokenCache::default()),
+            oauth: None,
         }
     }
 }
@@ -2,37 +2,24 @@
 //!
 //! Fetches data from GitHub and upserts it into PostgreSQL.
 
-use chrono::{DateTime, Utc};
-use greport_core::client::{GitHubClient, IssueParams, ProjectClient, PullParams, RepoId};
+use chrono::Utc;
+use greport_core::client::{GitHubApi, IssueParams, PullParams, RepoId};
 use greport_core::models::{
     FieldValue, Issue, Milestone, Project, ProjectField, ProjectFieldType, ProjectItem,
     ProjectItemContent, PullRequest, Release, Repository,
 };
-use greport_core::OctocrabClient;
 use greport_db::models::{
     IssueInput, MilestoneInput, ProjectFieldInput, ProjectInput, ProjectItemInput,
     PullRequestInput, ReleaseInput, RepositoryInput,
 };
 use greport_db::DbPool;
-use serde::Serialize;
-
-/// Result of a sync operation.
-#[derive(Debug, Serialize)]
-pub struct SyncResult {
-    pub repository: String,
-    pub issues_synced: usize,
-    pub pulls_synced: usize,
-    pub releases_synced: usize,
-    pub milestones_synced: usize,
-    pub synced_at: DateTime<Utc>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub warnings: Vec<String>,
-}
+
+pub use greport_types::{ProjectSyncResult, SyncResult};
 
 /// Sync all data for a repository from GitHub into the database.
 pub async fn sync_repository(
     pool: &DbPool,
-    github: &OctocrabClient,
+    github: &dyn GitHubApi,
     owner: &str,
     repo: &str,
 ) -> Result<SyncResult, crate::error::ApiError> {
@@ -186,6 +173,8 @@ pub async fn sync_repository(
         milestones_ok,
     )
     .await?;
+    // New generation: cached responses for this repo (and aggregates) are stale
+    greport_db::queries::bump_sync_generation(pool, Some(db_repo_id)).await?;
 
     let synced_at = Utc::now();
     tracing::info!(
@@ -258,6 +247,7 @@ fn issue_to_input(issue: &Issue, repo_id: i64) -> IssueInput {
         milestone_id: issue.milestone.as_ref().map(|m| m.id),
         author_login: issue.author.login.clone(),
         author_id: issue.author.id,
+        author_is_bot: issue.author.is_bot,
         comments_count: issue.comments_count as i32,
         created_at: issue.created_at,
         updated_at: issue.updated_at,
@@ -281,6 +271,7 @@ fn pull_to_input(pr: &PullRequest, repo_id: i64) -> PullRequestInput {
         milestone_id: pr.milestone.as_ref().map(|m| m.id),
         author_login: pr.author.login.clone(),
         author_id: pr.author.id,
+        author_is_bot: pr.author.is_bot,
         head_ref: pr.head_ref.clone(),
         base_ref: pr.base_ref.clone(),
         merged: pr.merged,
@@ -305,6 +296,7 @@ fn release_to_input(release: &Release, repo_id: i64) -> ReleaseInput {
         prerelease: release.prerelease,
         author_login: release.author.login.clone(),
         author_id: release.author.id,
+        author_is_bot: release.author.is_bot,
         created_at: release.created_at,
         published_at: release.published_at,
     }
@@ -314,21 +306,10 @@ fn release_to_input(release: &Release, repo_id: i64) -> ReleaseInput {
 // Project sync
 // ===========================================================================
 
-/// Result of a project sync operation.
-#[derive(Debug, Serialize)]
-pub struct ProjectSyncResult {
-    pub organization: String,
-    pub projects_synced: usize,
-    pub items_synced: usize,
-    pub synced_at: DateTime<Utc>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub warnings: Vec<String>,
-}
-
 /// Sync all GitHub Projects V2 data for an organization into the database.
 pub async fn sync_projects(
     pool: &DbPool,
-    client: &OctocrabClient,
+    client: &dyn GitHubApi,
     org: &str,
 ) -> Result<ProjectSyncResult, crate::error::ApiError> {
     let mut warnings: Vec<String> = vec![];
@@ -427,6 +408,10 @@ pub async fn sync_projects(
         }
     }
 
+    if let Err(e) = greport_db::queries::bump_sync_generation(pool, None).await {
+        tracing::warn!(org = org, error = ?e, "Failed to advance sync generation");
+    }
+
     let synced_at = Utc::now();
     tracing::info!(
         org = org,
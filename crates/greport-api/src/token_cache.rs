@@ -0,0 +1,142 @@
+//! Cache of verified GitHub passthrough tokens
+//!
+//! Entries are keyed by the SHA-256 hash of the token. A verified entry
+//! holds a client authenticated with the token, so the token itself stays
+//! in memory for up to `VERIFIED_TTL` after it was last verified.
+
+use greport_core::OctocrabClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// How long a verified token is trusted before GitHub is asked again
+const VERIFIED_TTL: Duration = Duration::from_secs(300);
+
+/// How long a rejected token is remembered
+const REJECTED_TTL: Duration = Duration::from_secs(60);
+
+/// GitHub identity behind a verified passthrough token
+#[derive(Debug)]
+pub struct GitHubIdentity {
+    /// GitHub login of the token owner
+    pub login: String,
+    /// Organizations the token owner belongs to (as visible to the token)
+    pub orgs: Vec<String>,
+    /// Client authenticated with the caller's token
+    pub client: Arc<OctocrabClient>,
+}
+
+/// Cached verification result
+#[derive(Debug)]
+struct CacheEntry {
+    /// None when GitHub rejected the token
+    identity: Option<Arc<GitHubIdentity>>,
+    expires_at: Instant,
+}
+
+/// Short-lived cache of token verification results
+#[derive(Debug)]
+pub struct TokenCache {
+    entries: RwLock<HashMap<String, CacheEntry>>,
+    verified_ttl: Duration,
+    rejected_ttl: Duration,
+}
+
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::new(VERIFIED_TTL, REJECTED_TTL)
+    }
+}
+
+impl TokenCache {
+    /// Create a cache with custom lifetimes for verified and rejected tokens
+    pub fn new(verified_ttl: Duration, rejected_ttl: Duration) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            verified_ttl,
+            rejected_ttl,
+        }
+    }
+
+    /// Look up a token hash.
+    ///
+    /// Returns `None` when the token is unknown or its entry expired,
+    /// `Some(None)` when it was recently rejected.
+    pub async fn get(&self, token_hash: &str) -> Option<Option<Arc<GitHubIdentity>>> {
+        let entries = self.entries.read().await;
+        entries
+            .get(token_hash)
+            .filter(|e| e.expires_at > Instant::now())
+            .map(|e| e.identity.clone())
+    }
+
+    /// Record a verification result
+    pub async fn insert(&self, token_hash: String, identity: Option<Arc<GitHubIdentity>>) {
+        let ttl = if identity.is_some() {
+            self.verified_ttl
+        } else {
+            self.rejected_ttl
+        };
+        self.entries.write().await.insert(
+            token_hash,
+            CacheEntry {
+                identity,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+    }
+
+    /// Remove expired entries
+    pub async fn cleanup(&self) {
+        let now = Instant::now();
+        self.entries.write().await.retain(|_, e| e.expires_at > now);
+    }
+}
+
+/// Start a background task to drop expired token cache entries
+pub fn start_cleanup_task(cache: Arc<TokenCache>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            cache.cleanup().await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> Arc<GitHubIdentity> {
+        Arc::new(GitHubIdentity {
+            login: "octocat".to_string(),
+            orgs: vec!["acme".to_string()],
+            client: Arc::new(OctocrabClient::new("ghp_test", None).unwrap()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_token_cache() {
+        let cache = TokenCache::default();
+        assert!(cache.get("a").await.is_none());
+
+        cache.insert("a".to_string(), Some(identity())).await;
+        cache.insert("b".to_string(), None).await;
+
+        let hit = cache.get("a").await.unwrap().unwrap();
+        assert_eq!(hit.login, "octocat");
+        assert!(cache.get("b").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_token_cache_expiry() {
+        let cache = TokenCache::new(Duration::ZERO, Duration::ZERO);
+        cache.insert("a".to_string(), Some(identity())).await;
+
+        assert!(cache.get("a").await.is_none());
+        cache.cleanup().await;
+        assert!(cache.entries.read().await.is_empty());
+    }
+}
@@ -1,11 +1,455 @@
 //! API Integration Tests
 //!
-//! These tests verify the API endpoints using mock data.
-//! They don't require a running database or GitHub API.
+//! These tests drive the full router from `build_router` against
+//! `MockGitHubClient`, so they don't need the GitHub API. Routes that read
+//! from PostgreSQL are exercised when `TEST_DATABASE_URL` is set.
 
-// Note: Full integration tests would require setting up mock state
-// which is complex due to the GitHub client dependency.
-// Below are basic structural tests.
+use axum::http::{Method, StatusCode};
+use axum_test::TestServer;
+use greport_api::{build_router, ApiConfig, AppState};
+use greport_core::client::{MockData, MockGitHubClient, Provider};
+use greport_core::config::BotsConfig;
+use greport_core::identities::Identities;
+use greport_core::{GitHubClientRegistry, OrgEntry};
+use greport_db::DbPool;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Routes served from GitHub when there is no database
+const GITHUB_ROUTES: &[&str] = &[
+    "/api/v1/repos/test-owner/test-repo/issues",
+    "/api/v1/repos/test-owner/test-repo/issues/metrics",
+    "/api/v1/repos/test-owner/test-repo/issues/velocity",
+    "/api/v1/repos/test-owner/test-repo/issues/burndown?milestone=v1.0",
+    "/api/v1/repos/test-owner/test-repo/issues/stale",
+    "/api/v1/repos/test-owner/test-repo/issues/epics",
+    "/api/v1/repos/test-owner/test-repo/pulls",
+    "/api/v1/repos/test-owner/test-repo/pulls/metrics",
+    "/api/v1/repos/test-owner/test-repo/releases",
+    "/api/v1/repos/test-owner/test-repo/releases/notes?milestone=v1.0",
+    "/api/v1/repos/test-owner/test-repo/milestones/v1.0/progress",
+    "/api/v1/repos/test-owner/test-repo/contributors",
+    "/api/v1/repos/test-owner/test-repo/calendar",
+    "/api/v1/repos/test-owner/test-repo/sla",
+    "/api/v1/orgs",
+    "/api/v1/orgs/test-owner/repos",
+];
+
+/// Routes that only read from the database
+const DATABASE_ROUTES: &[&str] = &[
+    "/api/v1/repos/test-owner/test-repo/release-plan",
+    "/api/v1/orgs/test-owner/projects",
+    "/api/v1/orgs/test-owner/projects/1",
+    "/api/v1/orgs/test-owner/projects/1/items",
+    "/api/v1/orgs/test-owner/projects/1/metrics",
+    "/api/v1/repos",
+    "/api/v1/aggregate/issues",
+    "/api/v1/aggregate/pulls",
+    "/api/v1/aggregate/issues/metrics",
+    "/api/v1/aggregate/pulls/metrics",
+    "/api/v1/aggregate/contributors",
+    "/api/v1/aggregate/velocity",
+    "/api/v1/aggregate/projects",
+    "/api/v1/aggregate/orgs/issues",
+    "/api/v1/aggregate/orgs/pulls",
+    "/api/v1/aggregate/calendar",
+    "/api/v1/aggregate/release-plan",
+];
+
+/// Registry with `test-owner` configured, backed by sample mock data
+fn mock_registry() -> GitHubClientRegistry {
+    let entry = OrgEntry {
+        name: "test-owner".to_string(),
+        base_url: None,
+        repo_count: 1,
+        repo_names: vec!["test-repo".to_string()],
+        has_token: true,
+        provider: Provider::GitHub,
+    };
+    GitHubClientRegistry::with_default(MockGitHubClient::with_sample_data(), None)
+        .with_org(entry, MockGitHubClient::with_sample_data())
+}
+
+fn mock_server(db: Option<DbPool>) -> TestServer {
+    let config = ApiConfig {
+        rate_limit_per_minute: 10_000,
+        ..Default::default()
+    };
+    TestServer::new(build_router(AppState::new(mock_registry(), config, db))).unwrap()
+}
+
+/// Serializes the database tests, since a batch sync covers every tracked
+/// repository
+static DATABASE: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Connect to `TEST_DATABASE_URL`, if set
+async fn test_pool() -> Option<DbPool> {
+    let database_url = std::env::var("TEST_DATABASE_URL").ok()?;
+    let config = greport_db::DbConfig {
+        database_url,
+        max_connections: 5,
+        acquire_timeout_secs: 5,
+        run_migrations: true,
+    };
+    Some(
+        greport_db::create_pool_with_config(&config)
+            .await
+            .expect("TEST_DATABASE_URL is not reachable"),
+    )
+}
+
+/// Store a new API key with `scope` and return its plaintext
+async fn create_key(pool: &DbPool, scope: &str) -> String {
+    let (key, input) =
+        greport_db::keys::new_api_key("integration", "test", vec![scope.to_string()], 10_000, None)
+            .unwrap();
+    greport_db::queries::create_api_key(pool, &input)
+        .await
+        .unwrap();
+    key
+}
+
+async fn get_json(server: &TestServer, path: &str) -> Value {
+    let response = server.get(path).await;
+    assert_eq!(response.status_code(), StatusCode::OK, "GET {}", path);
+    response.json::<Value>()
+}
+
+#[tokio::test]
+async fn test_github_routes_without_database() {
+    let server = mock_server(None);
+    for path in GITHUB_ROUTES {
+        let body = get_json(&server, path).await;
+        assert!(!body["data"].is_null(), "GET {} has no data", path);
+    }
+}
+
+#[tokio::test]
+async fn test_github_route_payloads() {
+    let server = mock_server(None);
+    let repo = "/api/v1/repos/test-owner/test-repo";
+
+    let issues = get_json(&server, &format!("{}/issues", repo)).await;
+    assert_eq!(issues["data"].as_array().unwrap().len(), 2);
+    assert_eq!(issues["meta"]["total"], 2);
+
+    let issues = get_json(&server, &format!("{}/issues?state=all&labels=bug", repo)).await;
+    assert_eq!(issues["data"][0]["title"], "Fix login bug");
+
+    let metrics = get_json(&server, &format!("{}/issues/metrics", repo)).await;
+    assert_eq!(metrics["data"]["total"], 3);
+    assert_eq!(metrics["data"]["open"], 2);
+
+    let pulls = get_json(&server, &format!("{}/pulls/metrics", repo)).await;
+    assert_eq!(pulls["data"]["merged"], 1);
+
+    let releases = get_json(&server, &format!("{}/releases", repo)).await;
+    assert_eq!(releases["data"][0]["tag_name"], "v0.1.0");
+
+    let progress = get_json(&server, &format!("{}/milestones/v1.0/progress", repo)).await;
+    assert_eq!(progress["data"]["title"], "v1.0");
+
+    let contributors = get_json(&server, &format!("{}/contributors", repo)).await;
+    assert_eq!(contributors["data"][0]["login"], "test-user");
+
+    let orgs = get_json(&server, "/api/v1/orgs").await;
+    assert_eq!(orgs["data"]["orgs"][0]["name"], "test-owner");
+
+    let org_repos = get_json(&server, "/api/v1/orgs/test-owner/repos").await;
+    assert_eq!(org_repos["data"][0]["full_name"], "test-owner/test-repo");
+}
+
+#[tokio::test]
+async fn test_contributors_use_identities() {
+    let identities = Identities::from_toml(
+        "[[person]]\nname = \"Tess\"\nteam = \"qa\"\nlogins = [\"test-user\", \"tess-ghe\"]\n",
+    )
+    .unwrap();
+    let config = ApiConfig {
+        rate_limit_per_minute: 10_000,
+        identities: Arc::new(identities),
+        ..Default::default()
+    };
+    let server =
+        TestServer::new(build_router(AppState::new(mock_registry(), config, None))).unwrap();
+
+    let contributors = get_json(&server, "/api/v1/repos/test-owner/test-repo/contributors").await;
+    let tess = &contributors["data"][0];
+    assert_eq!(tess["login"], "Tess");
+    assert_eq!(tess["team"], "qa");
+    assert_eq!(tess["logins"], json!(["test-user"]));
+}
+
+#[tokio::test]
+async fn test_openapi_document_is_served() {
+    let server = mock_server(None);
+
+    let doc = server.get("/api/v1/openapi.json").await.json::<Value>();
+    assert!(doc["openapi"].as_str().unwrap().starts_with("3."));
+    assert!(doc["paths"]["/api/v1/repos/{owner}/{repo}/sla"]["get"].is_object());
+    assert!(doc["components"]["schemas"]["SlaReport"].is_object());
+
+    let page = server.get("/api/v1/docs").await;
+    assert_eq!(page.status_code(), StatusCode::OK);
+    assert!(page.text().contains("openapi.json"));
+}
+
+/// POST a GraphQL query and return the response body
+async fn graphql(server: &TestServer, query: &str) -> Value {
+    let response = server
+        .post("/api/graphql")
+        .json(&json!({ "query": query }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", query);
+    response.json::<Value>()
+}
+
+#[tokio::test]
+async fn test_graphql_without_database() {
+    let server = mock_server(None);
+
+    let page = server.get("/api/graphql").await;
+    assert_eq!(page.status_code(), StatusCode::OK);
+    assert!(page.text().contains("GraphQL Playground"));
+
+    let body = graphql(&server, r#"{ repositories { fullName } }"#).await;
+    let message = body["errors"][0]["message"].as_str().unwrap();
+    assert!(message.contains("Database required"), "{}", message);
+    assert_eq!(body["errors"][0]["extensions"]["code"], "BAD_REQUEST");
+}
+
+#[tokio::test]
+async fn test_github_errors_are_mapped() {
+    let server = mock_server(None);
+
+    let response = server
+        .get("/api/v1/repos/test-owner/test-repo/milestones/v9/progress")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let response = server.get("/api/v1/orgs/unknown/repos").await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_database_routes_require_database() {
+    let server = mock_server(None);
+    for path in DATABASE_ROUTES {
+        let response = server.get(path).await;
+        assert_eq!(
+            response.status_code(),
+            StatusCode::BAD_REQUEST,
+            "GET {}",
+            path
+        );
+    }
+
+    // Writes need an API key, and keys live in the database
+    for (method, path) in [
+        (Method::POST, "/api/v1/repos/test-owner/test-repo/sync"),
+        (Method::DELETE, "/api/v1/repos/test-owner/test-repo"),
+        (Method::POST, "/api/v1/sync"),
+    ] {
+        let response = server.method(method.clone(), path).await;
+        assert_eq!(
+            response.status_code(),
+            StatusCode::UNAUTHORIZED,
+            "{} {}",
+            method,
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_routes_with_database() {
+    let Some(pool) = test_pool().await else {
+        eprintln!("TEST_DATABASE_URL not set; skipping database route tests");
+        return;
+    };
+    let _guard = DATABASE.lock().await;
+    let server = mock_server(Some(pool.clone()));
+    let sync_key = create_key(&pool, "sync").await;
+
+    // Anonymous callers may read but not sync
+    let response = server.post("/api/v1/sync").await;
+    assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+    // Track and sync the mock repository, then sync its organization's projects
+    let response = server
+        .post("/api/v1/repos")
+        .authorization_bearer(&sync_key)
+        .json(&json!({ "full_name": "test-owner/test-repo" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let sync = server
+        .post("/api/v1/sync")
+        .authorization_bearer(&sync_key)
+        .await
+        .json::<Value>();
+    assert_eq!(sync["data"]["successful"], 1);
+    assert_eq!(sync["data"]["project_results"][0]["projects_synced"], 1);
+
+    for path in GITHUB_ROUTES.iter().chain(DATABASE_ROUTES) {
+        get_json(&server, path).await;
+    }
+
+    let issues = get_json(
+        &server,
+        "/api/v1/repos/test-owner/test-repo/issues?state=all",
+    )
+    .await;
+    assert_eq!(issues["data"].as_array().unwrap().len(), 3);
+
+    let items = get_json(&server, "/api/v1/orgs/test-owner/projects/1/items").await;
+    assert_eq!(items["data"][0]["content_title"], "Fix login bug");
+
+    // The sample project has no iteration field
+    let response = server
+        .get("/api/v1/orgs/test-owner/projects/1/iterations?field=Sprint")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    let message = response.json::<Value>()["error"]["message"].clone();
+    assert!(
+        message
+            .as_str()
+            .unwrap()
+            .contains("Iteration field 'Sprint'"),
+        "{}",
+        message
+    );
+
+    // The same data through GraphQL, in one request
+    let body = graphql(
+        &server,
+        r#"{
+            repository(owner: "test-owner", name: "test-repo") {
+                fullName
+                all: issues { totalCount }
+                bugs: issues(filter: { labels: ["bug"] }) {
+                    totalCount
+                    nodes { title labels { name } }
+                    pageInfo { hasNextPage }
+                }
+                pullRequests { totalCount }
+                issueMetrics { total open }
+            }
+            projects(owner: "test-owner") { number items { totalCount } }
+        }"#,
+    )
+    .await;
+    assert!(body["errors"].is_null(), "{}", body["errors"]);
+    let repo = &body["data"]["repository"];
+    assert_eq!(repo["fullName"], "test-owner/test-repo");
+    assert_eq!(repo["all"]["totalCount"], 3);
+    assert_eq!(repo["bugs"]["nodes"][0]["title"], "Fix login bug");
+    assert_eq!(repo["bugs"]["pageInfo"]["hasNextPage"], false);
+    assert_eq!(repo["issueMetrics"]["total"], 3);
+    assert_eq!(body["data"]["projects"][0]["number"], 1);
+
+    // Admin routes need an admin key
+    let key = create_key(&pool, "admin").await;
+    let response = server
+        .get("/api/v1/admin/api-keys")
+        .authorization_bearer(&key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // A misspelled scope would grant nothing, so it is refused
+    let response = server
+        .post("/api/v1/admin/api-keys")
+        .authorization_bearer(&key)
+        .json(&json!({ "name": "typo", "scopes": ["amdin"] }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = server
+        .delete("/api/v1/repos/test-owner/test-repo")
+        .authorization_bearer(&sync_key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_github_app_authors_are_filtered_from_synced_data() {
+    let Some(pool) = test_pool().await else {
+        eprintln!("TEST_DATABASE_URL not set; skipping database route tests");
+        return;
+    };
+    let _guard = DATABASE.lock().await;
+
+    // A repository whose second pull request is opened by a GitHub App; its
+    // login does not match any `exclude` pattern
+    let sample = MockGitHubClient::with_sample_data();
+    let (repo, pulls) = {
+        let data = sample.data();
+        let mut repo = data.repositories["test-owner/test-repo"].clone();
+        repo.id = 4900;
+        repo.owner = "bot-owner".to_string();
+        repo.name = "bot-repo".to_string();
+        repo.full_name = "bot-owner/bot-repo".to_string();
+        let mut pulls = data.pulls["test-owner/test-repo"][..2].to_vec();
+        for (i, pr) in pulls.iter_mut().enumerate() {
+            pr.id = 4900 + i as i64;
+            pr.milestone = None;
+        }
+        pulls[1].author.login = "release-helper".to_string();
+        pulls[1].author.is_bot = true;
+        (repo, pulls)
+    };
+    let mock = MockGitHubClient::new(
+        MockData::new()
+            .with_repository(repo)
+            .with_pulls("bot-owner/bot-repo", pulls),
+    );
+    let config = ApiConfig {
+        rate_limit_per_minute: 10_000,
+        bots: BotsConfig {
+            github_apps: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let registry = GitHubClientRegistry::with_default(mock, None);
+    let server = TestServer::new(build_router(AppState::new(
+        registry,
+        config,
+        Some(pool.clone()),
+    )))
+    .unwrap();
+    let sync_key = create_key(&pool, "sync").await;
+
+    let response = server
+        .post("/api/v1/repos")
+        .authorization_bearer(&sync_key)
+        .json(&json!({ "full_name": "bot-owner/bot-repo" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let response = server
+        .post("/api/v1/repos/bot-owner/bot-repo/sync")
+        .authorization_bearer(&sync_key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let metrics = get_json(&server, "/api/v1/aggregate/pulls/metrics").await;
+    let repo = metrics["data"]["by_repository"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["repository"] == "bot-owner/bot-repo")
+        .unwrap();
+    assert_eq!(repo["total"], 1);
+
+    let contributors = get_json(&server, "/api/v1/aggregate/contributors").await;
+    assert!(!contributors.to_string().contains("release-helper"));
+
+    let response = server
+        .delete("/api/v1/repos/bot-owner/bot-repo")
+        .authorization_bearer(&sync_key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+}
 
 #[cfg(test)]
 mod tests {
@@ -201,3 +645,57 @@ mod tests {
         assert_eq!(period, "week");
     }
 }
+
+#[tokio::test]
+async fn test_passthrough_token_cannot_read_other_orgs_synced_data() {
+    use greport_api::token_cache::{GitHubIdentity, TokenCache};
+    use greport_core::OctocrabClient;
+
+    let Some(pool) = test_pool().await else {
+        eprintln!("TEST_DATABASE_URL not set; skipping database route tests");
+        return;
+    };
+    let _guard = DATABASE.lock().await;
+
+    // A GitHub user outside test-owner, with the server fetching through
+    // callers' tokens
+    let cache = TokenCache::default();
+    let identity = Arc::new(GitHubIdentity {
+        login: "outsider".to_string(),
+        orgs: vec!["elsewhere".to_string()],
+        client: Arc::new(OctocrabClient::new("ghp_outsider", None).unwrap()),
+    });
+    cache
+        .insert(greport_db::hash_api_key("ghp_outsider"), Some(identity))
+        .await;
+    let config = ApiConfig {
+        rate_limit_per_minute: 10_000,
+        use_caller_token: true,
+        ..Default::default()
+    };
+    let server = TestServer::new(build_router(AppState {
+        token_cache: Arc::new(cache),
+        ..AppState::new(mock_registry(), config, Some(pool.clone()))
+    }))
+    .unwrap();
+
+    let sync_key = create_key(&pool, "sync").await;
+    server
+        .post("/api/v1/repos")
+        .authorization_bearer(&sync_key)
+        .json(&json!({ "full_name": "test-owner/test-repo" }))
+        .await;
+    let response = server
+        .post("/api/v1/repos/test-owner/test-repo/sync")
+        .authorization_bearer(&sync_key)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // Issues are answered from the database, which GitHub never checks
+    let response = server
+        .get("/api/v1/repos/test-owner/test-repo/issues")
+        .authorization_bearer("ghp_outsider")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+    assert!(response.text().contains("outsider"));
+}
@@ -1,6 +1,7 @@
 //! CLI argument definitions
 
 use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 /// GitHub reporting and analytics tool
 #[derive(Parser)]
@@ -33,6 +34,30 @@ pub struct Cli {
     /// Bypass cache
     #[arg(long, global = true)]
     pub no_cache: bool,
+
+    /// Read from a greport API server (e.g. http://greport:9423) instead of GitHub
+    #[arg(long, global = true, env = "GREPORT_SERVER", value_name = "URL")]
+    pub server: Option<String>,
+
+    /// API key for --server
+    #[arg(long, global = true, env = "GREPORT_API_KEY", hide_env_values = true)]
+    pub api_key: Option<String>,
+
+    /// Stop each GitHub list call after this many items (default: fetch all)
+    #[arg(long, global = true, value_name = "N")]
+    pub max_items: Option<usize>,
+
+    /// Record every GitHub call and response to fixtures in this directory
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Answer every GitHub call from fixtures recorded with --record (offline)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub replay: Option<PathBuf>,
+
+    /// Report bot activity separately instead of hiding it (see [bots] in config)
+    #[arg(long, global = true)]
+    pub separate_bots: bool,
 }
 
 #[derive(Subcommand)]
@@ -57,6 +82,15 @@ pub enum Commands {
 
     /// Organization management
     Orgs(OrgsArgs),
+
+    /// GitHub Projects (V2) boards
+    Projects(ProjectsArgs),
+
+    /// Cross-repo issue dependency graph
+    Deps(DepsArgs),
+
+    /// API server administration
+    Admin(AdminArgs),
 }
 
 // Issues commands
@@ -143,6 +177,17 @@ pub enum IssuesCommands {
 
     /// Issue metrics summary
     Metrics,
+
+    /// Epic progress from sub-issues and task lists
+    Epics {
+        /// Days without activity before an open child counts as stalled
+        #[arg(long, default_value = "14")]
+        stale_days: i64,
+
+        /// Include closed epics
+        #[arg(long)]
+        all: bool,
+    },
 }
 
 // Pull request commands
@@ -288,6 +333,224 @@ pub enum OrgsCommands {
     },
 }
 
+// Admin commands
+#[derive(Parser)]
+pub struct AdminArgs {
+    #[command(subcommand)]
+    pub command: AdminCommands,
+}
+
+#[derive(Subcommand)]
+pub enum AdminCommands {
+    /// Manage API server keys
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum KeysCommands {
+    /// Create an API key (the key is shown only once)
+    Create {
+        /// Key name
+        name: String,
+
+        /// Owner the key belongs to
+        #[arg(long)]
+        owner: String,
+
+        /// Scopes granted to the key (repeatable or comma-separated)
+        #[arg(long = "scope", value_delimiter = ',', default_value = "read")]
+        scopes: Vec<String>,
+
+        /// Requests per minute allowed for the key
+        #[arg(long, default_value = "60")]
+        rate_limit: i32,
+
+        /// Expiry date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        expires: Option<String>,
+    },
+
+    /// List API keys
+    List {
+        /// Only keys for this owner
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Include revoked keys
+        #[arg(long)]
+        include_revoked: bool,
+    },
+
+    /// Revoke an API key
+    Revoke {
+        /// Key ID
+        id: String,
+    },
+}
+
+// Projects commands
+#[derive(Parser)]
+pub struct ProjectsArgs {
+    #[command(subcommand)]
+    pub command: ProjectsCommands,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ProjectsCommands {
+    /// List projects for the organization
+    List {
+        /// Include closed projects
+        #[arg(long)]
+        include_closed: bool,
+    },
+
+    /// Show a project and its field definitions
+    Show {
+        /// Project number
+        number: u64,
+    },
+
+    /// List project items with filters
+    Items {
+        /// Project number
+        number: u64,
+
+        #[command(flatten)]
+        filter: ProjectFilterArgs,
+
+        /// Maximum results
+        #[arg(long, default_value = "100")]
+        limit: usize,
+    },
+
+    /// Project item metrics summary
+    Metrics {
+        /// Project number
+        number: u64,
+
+        #[command(flatten)]
+        filter: ProjectFilterArgs,
+    },
+
+    /// Sprint report from an iteration field
+    Iterations {
+        /// Project number
+        number: u64,
+
+        /// Iteration field name
+        #[arg(long, default_value = "Iteration")]
+        field: String,
+
+        /// Numeric field to measure velocity in points (default: item count)
+        #[arg(long)]
+        points_field: Option<String>,
+
+        /// Single-select field holding item status
+        #[arg(long, default_value = "Status")]
+        status_field: String,
+
+        /// Status values that count as done (repeatable)
+        #[arg(long = "done", value_name = "VALUE")]
+        done: Vec<String>,
+    },
+
+    /// Set field values on a project item
+    Set {
+        /// Project number
+        number: u64,
+
+        /// Item (owner/repo#number, #number, or item node ID)
+        item: String,
+
+        /// Field values to set (Field=Value; empty value clears the field)
+        #[arg(required = true, value_name = "FIELD=VALUE")]
+        values: Vec<String>,
+    },
+
+    /// Add an issue or pull request to a project
+    Add {
+        /// Project number
+        number: u64,
+
+        /// Issue or pull request (owner/repo#number)
+        content: String,
+    },
+
+    /// Archive a project item
+    Archive {
+        /// Project number
+        number: u64,
+
+        /// Item (owner/repo#number, #number, or item node ID)
+        item: String,
+    },
+
+    /// Apply bulk update rules from a TOML file
+    Apply {
+        /// Project number
+        number: u64,
+
+        /// Rules file
+        #[arg(long)]
+        rules: String,
+
+        /// Print planned changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+// Dependency commands
+#[derive(Parser)]
+pub struct DepsArgs {
+    #[command(subcommand)]
+    pub command: DepsCommands,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum DepsCommands {
+    /// Cycles, critical path and open blockers
+    Report {
+        /// Only consider open issues in this milestone
+        #[arg(long)]
+        milestone: Option<String>,
+    },
+
+    /// Export the dependency graph
+    Export {
+        /// Graph format
+        #[arg(long, default_value = "dot")]
+        graph: GraphFormat,
+    },
+}
+
+/// Item filters shared by project subcommands
+#[derive(Parser, Clone)]
+pub struct ProjectFilterArgs {
+    /// Filter by status field value
+    #[arg(long)]
+    pub status: Option<String>,
+
+    /// Single-select field holding item status (filter and breakdown)
+    #[arg(long, default_value = "Status")]
+    pub status_field: String,
+
+    /// Filter by iteration title (e.g. "Sprint 12")
+    #[arg(long)]
+    pub iteration: Option<String>,
+
+    /// Filter by assignee
+    #[arg(long)]
+    pub assignee: Option<String>,
+
+    /// Filter by custom field value (Field=Value, repeatable)
+    #[arg(long = "field", value_name = "FIELD=VALUE")]
+    pub fields: Vec<String>,
+}
+
 // Sync command
 #[derive(Parser, Clone)]
 pub struct SyncArgs {
@@ -354,6 +617,13 @@ pub enum ContribSort {
     Comments,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Mermaid,
+}
+
 impl From<IssueStateFilter> for greport_core::client::IssueStateFilter {
     fn from(val: IssueStateFilter) -> Self {
         match val {
@@ -374,6 +644,25 @@ impl From<PrStateFilter> for greport_core::client::PullStateFilter {
     }
 }
 
+impl TryFrom<ProjectFilterArgs> for greport_core::models::ProjectItemFilter {
+    type Error = greport_core::Error;
+
+    fn try_from(val: ProjectFilterArgs) -> Result<Self, Self::Error> {
+        let fields = val
+            .fields
+            .iter()
+            .map(|f| greport_core::models::ProjectItemFilter::parse_field(f))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            status: val.status,
+            status_field: Some(val.status_field),
+            iteration: val.iteration,
+            assignee: val.assignee,
+            fields,
+        })
+    }
+}
+
 impl From<PeriodArg> for greport_core::metrics::Period {
     fn from(val: PeriodArg) -> Self {
         match val {
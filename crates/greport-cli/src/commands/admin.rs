@@ -0,0 +1,155 @@
+//! API server administration commands
+
+use crate::args::{AdminCommands, KeysCommands};
+use chrono::{NaiveDate, Utc};
+use colored::Colorize;
+use comfy_table::{Cell, Table};
+use greport_core::Config;
+use greport_db::{DbConfig, DbPool};
+use uuid::Uuid;
+
+/// Handle admin subcommands.
+pub async fn handle_admin(command: &AdminCommands, config: &Config) -> anyhow::Result<()> {
+    match command {
+        AdminCommands::Keys { command } => {
+            let pool = connect(config).await?;
+            match command {
+                KeysCommands::Create {
+                    name,
+                    owner,
+                    scopes,
+                    rate_limit,
+                    expires,
+                } => create_key(&pool, name, owner, scopes, *rate_limit, expires.as_deref()).await,
+                KeysCommands::List {
+                    owner,
+                    include_revoked,
+                } => list_keys(&pool, owner.as_deref(), *include_revoked).await,
+                KeysCommands::Revoke { id } => revoke_key(&pool, id).await,
+            }
+        }
+    }
+}
+
+async fn connect(config: &Config) -> anyhow::Result<DbPool> {
+    let database_url = config.database_url().ok_or_else(|| {
+        anyhow::anyhow!(
+            "No database configured.\n\n\
+             Set DATABASE_URL or add 'url = \"postgres://...\"' to the [database] section\n\
+             of the config file used by the API server."
+        )
+    })?;
+
+    let db_config = DbConfig {
+        database_url,
+        max_connections: 1,
+        acquire_timeout_secs: config.db_acquire_timeout_secs(),
+        run_migrations: config.db_run_migrations(),
+    };
+    Ok(greport_db::create_pool_with_config(&db_config).await?)
+}
+
+async fn create_key(
+    pool: &DbPool,
+    name: &str,
+    owner: &str,
+    scopes: &[String],
+    rate_limit: i32,
+    expires: Option<&str>,
+) -> anyhow::Result<()> {
+    let expires_at = expires
+        .map(|s| {
+            let date = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|_| anyhow::anyhow!("Invalid expiry date '{}': use YYYY-MM-DD", s))?;
+            Ok::<_, anyhow::Error>(date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        })
+        .transpose()?;
+
+    let (key, input) =
+        greport_db::keys::new_api_key(name, owner, scopes.to_vec(), rate_limit, expires_at)?;
+    let row = greport_db::queries::create_api_key(pool, &input).await?;
+
+    println!("Created API key '{}' ({})", row.name, row.id);
+    println!();
+    println!("  {}", key.bold());
+    println!();
+    println!(
+        "{}",
+        "Store this key now - it cannot be shown again.".yellow()
+    );
+    Ok(())
+}
+
+async fn list_keys(
+    pool: &DbPool,
+    owner: Option<&str>,
+    include_revoked: bool,
+) -> anyhow::Result<()> {
+    let keys: Vec<_> = greport_db::queries::list_all_api_keys(pool, include_revoked)
+        .await?
+        .into_iter()
+        .filter(|k| owner.is_none_or(|o| k.owner == o))
+        .collect();
+
+    if keys.is_empty() {
+        println!("No API keys found.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("ID"),
+        Cell::new("Name"),
+        Cell::new("Owner"),
+        Cell::new("Scopes"),
+        Cell::new("Rate Limit"),
+        Cell::new("Expires"),
+        Cell::new("Last Used"),
+        Cell::new("Status"),
+    ]);
+
+    for key in &keys {
+        let status = if key.revoked {
+            "revoked"
+        } else if key.expires_at.is_some_and(|at| at <= now) {
+            "expired"
+        } else {
+            "active"
+        };
+        table.add_row(vec![
+            Cell::new(key.id),
+            Cell::new(&key.name),
+            Cell::new(&key.owner),
+            Cell::new(key.scopes.join(",")),
+            Cell::new(format!("{}/min", key.rate_limit)),
+            Cell::new(
+                key.expires_at
+                    .map(|at| at.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "never".to_string()),
+            ),
+            Cell::new(
+                key.last_used_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::new(status),
+        ]);
+    }
+
+    println!("{table}");
+    println!();
+    println!("Total: {} key(s)", keys.len());
+    Ok(())
+}
+
+async fn revoke_key(pool: &DbPool, id: &str) -> anyhow::Result<()> {
+    let id = Uuid::parse_str(id).map_err(|_| anyhow::anyhow!("Invalid key ID: {}", id))?;
+
+    if !greport_db::queries::revoke_api_key(pool, id).await? {
+        anyhow::bail!("API key not found: {}", id);
+    }
+
+    println!("Revoked API key {}", id);
+    Ok(())
+}
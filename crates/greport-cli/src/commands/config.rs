@@ -1,13 +1,14 @@
 //! Configuration command handlers
 
 use crate::args::ConfigCommands;
-use crate::config::{create_default_config, default_config_path, load_config};
+use crate::config::{create_default_config, default_config_path};
+use greport_core::Config;
 
-pub fn handle_config(command: &ConfigCommands) -> anyhow::Result<()> {
+pub fn handle_config(command: &ConfigCommands, config: &Config) -> anyhow::Result<()> {
     match command {
         ConfigCommands::Show => {
-            let config = load_config(None)?;
-            println!("{}", toml::to_string_pretty(&config)?);
+            // Secrets show where they come from (or masked), never the value
+            println!("{}", toml::to_string_pretty(&config.redacted())?);
         }
 
         ConfigCommands::Init { force } => {
@@ -2,13 +2,17 @@
 
 use crate::args::{ContribCommands, ContribSort, OutputFormat};
 use greport_core::client::{GitHubClient, IssueParams, PullParams, RepoId};
+use greport_core::identities::Identities;
+use greport_core::metrics::BotFilter;
 use std::collections::HashMap;
 
 pub async fn handle_contrib(
-    client: &impl GitHubClient,
+    client: &(impl GitHubClient + ?Sized),
     repo: &RepoId,
     command: ContribCommands,
     _format: OutputFormat,
+    bots: &BotFilter,
+    identities: &Identities,
 ) -> anyhow::Result<()> {
     match command {
         ContribCommands::List { sort_by, limit } => {
@@ -16,47 +20,79 @@ pub async fn handle_contrib(
             let issues = client.list_issues(repo, IssueParams::all()).await?;
             let prs = client.list_pulls(repo, PullParams::all()).await?;
 
-            // Aggregate stats per contributor
+            // Aggregate stats per person (merging their logins), keeping bots apart
             let mut contributors: HashMap<String, ContribStats> = HashMap::new();
+            let mut bot_contributors: HashMap<String, ContribStats> = HashMap::new();
 
             for issue in &issues {
-                let entry = contributors.entry(issue.author.login.clone()).or_default();
+                let stats = if bots.is_bot(&issue.author) {
+                    &mut bot_contributors
+                } else {
+                    &mut contributors
+                };
+                let name = identities.canonical(&issue.author.login);
+                let entry = stats.entry(name.to_string()).or_default();
                 entry.issues_created += 1;
             }
 
             for pr in &prs {
-                let entry = contributors.entry(pr.author.login.clone()).or_default();
+                let stats = if bots.is_bot(&pr.author) {
+                    &mut bot_contributors
+                } else {
+                    &mut contributors
+                };
+                let name = identities.canonical(&pr.author.login);
+                let entry = stats.entry(name.to_string()).or_default();
                 entry.prs_created += 1;
                 if pr.merged {
                     entry.prs_merged += 1;
                 }
             }
 
-            // Sort by specified metric
-            let mut sorted: Vec<_> = contributors.into_iter().collect();
-            match sort_by {
-                ContribSort::Issues => {
-                    sorted.sort_by(|a, b| b.1.issues_created.cmp(&a.1.issues_created))
-                }
-                ContribSort::Prs => sorted.sort_by(|a, b| b.1.prs_created.cmp(&a.1.prs_created)),
-                ContribSort::Comments => {
-                    sorted.sort_by(|a, b| b.1.issues_created.cmp(&a.1.issues_created))
-                }
-            }
+            let sorted = sort_contributors(contributors, sort_by);
 
             // Print results
             println!("Contributors (top {}):\n", limit);
-            println!(
-                "{:<20} {:>8} {:>8} {:>8}",
-                "User", "Issues", "PRs", "Merged"
-            );
-            println!("{}", "-".repeat(48));
-
-            for (user, stats) in sorted.into_iter().take(limit) {
+            if identities.is_empty() {
                 println!(
                     "{:<20} {:>8} {:>8} {:>8}",
-                    user, stats.issues_created, stats.prs_created, stats.prs_merged
+                    "User", "Issues", "PRs", "Merged"
+                );
+                println!("{}", "-".repeat(48));
+            } else {
+                println!(
+                    "{:<20} {:<12} {:>8} {:>8} {:>8}",
+                    "User", "Team", "Issues", "PRs", "Merged"
                 );
+                println!("{}", "-".repeat(61));
+            }
+
+            for (user, stats) in sorted.into_iter().take(limit) {
+                if identities.is_empty() {
+                    println!(
+                        "{:<20} {:>8} {:>8} {:>8}",
+                        user, stats.issues_created, stats.prs_created, stats.prs_merged
+                    );
+                } else {
+                    println!(
+                        "{:<20} {:<12} {:>8} {:>8} {:>8}",
+                        user,
+                        identities.team(&user).unwrap_or("-"),
+                        stats.issues_created,
+                        stats.prs_created,
+                        stats.prs_merged
+                    );
+                }
+            }
+
+            if bots.separate() && !bot_contributors.is_empty() {
+                println!("\nBots:\n");
+                for (user, stats) in sort_contributors(bot_contributors, sort_by) {
+                    println!(
+                        "{:<20} {:>8} {:>8} {:>8}",
+                        user, stats.issues_created, stats.prs_created, stats.prs_merged
+                    );
+                }
             }
         }
 
@@ -66,12 +102,12 @@ pub async fn handle_contrib(
 
             let user_issues: Vec<_> = issues
                 .iter()
-                .filter(|i| i.author.login.eq_ignore_ascii_case(&username))
+                .filter(|i| identities.same_person(&i.author.login, &username))
                 .collect();
 
             let user_prs: Vec<_> = prs
                 .iter()
-                .filter(|p| p.author.login.eq_ignore_ascii_case(&username))
+                .filter(|p| identities.same_person(&p.author.login, &username))
                 .collect();
 
             let assigned_issues: Vec<_> = issues
@@ -79,11 +115,20 @@ pub async fn handle_contrib(
                 .filter(|i| {
                     i.assignees
                         .iter()
-                        .any(|a| a.login.eq_ignore_ascii_case(&username))
+                        .any(|a| identities.same_person(&a.login, &username))
                 })
                 .collect();
 
-            println!("Contributor Stats: {}\n", username);
+            match identities.person(&username) {
+                Some(person) => {
+                    println!("Contributor Stats: {}\n", person.name);
+                    if let Some(team) = &person.team {
+                        println!("Team: {}", team);
+                    }
+                    println!("Logins: {}\n", person.logins.join(", "));
+                }
+                None => println!("Contributor Stats: {}\n", username),
+            }
             println!("Issues created: {}", user_issues.len());
             println!("Issues assigned: {}", assigned_issues.len());
             println!("PRs created: {}", user_prs.len());
@@ -102,6 +147,20 @@ pub async fn handle_contrib(
     Ok(())
 }
 
+/// Order contributors by the chosen metric, highest first
+fn sort_contributors(
+    contributors: HashMap<String, ContribStats>,
+    sort_by: ContribSort,
+) -> Vec<(String, ContribStats)> {
+    let mut sorted: Vec<_> = contributors.into_iter().collect();
+    match sort_by {
+        ContribSort::Issues => sorted.sort_by_key(|c| std::cmp::Reverse(c.1.issues_created)),
+        ContribSort::Prs => sorted.sort_by_key(|c| std::cmp::Reverse(c.1.prs_created)),
+        ContribSort::Comments => sorted.sort_by_key(|c| std::cmp::Reverse(c.1.issues_created)),
+    }
+    sorted
+}
+
 #[derive(Default)]
 struct ContribStats {
     issues_created: usize,
@@ -0,0 +1,57 @@
+//! Dependency graph command handlers
+
+use crate::args::{DepsCommands, GraphFormat, OutputFormat};
+use crate::output::Formatter;
+use greport_core::client::{GitHubClient, IssueParams, RepoId};
+use greport_core::reports::DependencyGraph;
+use std::sync::Arc;
+use tracing::warn;
+
+pub async fn handle_deps<C: GitHubClient + ?Sized>(
+    targets: &[(RepoId, Arc<C>)],
+    command: DepsCommands,
+    format: OutputFormat,
+) -> anyhow::Result<()> {
+    let graph = build_graph(targets).await?;
+
+    match command {
+        DepsCommands::Report { milestone } => {
+            let report = graph.report(milestone.as_deref());
+            Formatter::new(format).format_dependency_report(&report)?;
+        }
+
+        DepsCommands::Export {
+            graph: graph_format,
+        } => match graph_format {
+            GraphFormat::Dot => print!("{}", graph.to_dot()),
+            GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+        },
+    }
+
+    Ok(())
+}
+
+/// Fetch issues and relations from every target repository into one graph.
+async fn build_graph<C: GitHubClient + ?Sized>(
+    targets: &[(RepoId, Arc<C>)],
+) -> anyhow::Result<DependencyGraph> {
+    let mut graph = DependencyGraph::new();
+
+    for (repo, client) in targets {
+        let issues = client.list_issues(repo, IssueParams::all()).await?;
+        graph.add_issues(&repo.full_name(), &issues);
+
+        // Tracked/sub-issue relations need GraphQL support on the server;
+        // fall back to body references when they are unavailable.
+        match client.list_issue_relations(repo).await {
+            Ok(relations) => graph.add_relations(relations),
+            Err(e) => warn!(
+                repo = %repo,
+                error = %e,
+                "Could not fetch tracked issues and sub-issues, using body references only"
+            ),
+        }
+    }
+
+    Ok(graph)
+}
@@ -3,17 +3,19 @@
 use crate::args::{IssuesCommands, OutputFormat};
 use crate::output::Formatter;
 use greport_core::client::{GitHubClient, IssueParams, RepoId};
-use greport_core::metrics::{IssueMetricsCalculator, SlaCalculator, VelocityCalculator};
-use greport_core::reports::BurndownCalculator;
+use greport_core::metrics::{BotFilter, IssueMetricsCalculator, SlaCalculator, VelocityCalculator};
+use greport_core::reports::{BurndownCalculator, EpicReportCalculator};
 use greport_core::Config;
 use std::collections::HashMap;
+use tracing::warn;
 
 pub async fn handle_issues(
-    client: &impl GitHubClient,
+    client: &(impl GitHubClient + ?Sized),
     repo: &RepoId,
     command: IssuesCommands,
     format: OutputFormat,
     config: &Config,
+    bots: &BotFilter,
 ) -> anyhow::Result<()> {
     let formatter = Formatter::new(format);
 
@@ -58,7 +60,7 @@ pub async fn handle_issues(
             };
 
             let issues = client.list_issues(repo, params).await?;
-            let calculator = IssueMetricsCalculator::new(30);
+            let calculator = IssueMetricsCalculator::new(30).with_bot_filter(bots.clone());
             let metrics = calculator.calculate(&issues);
 
             // Print based on group_by
@@ -99,7 +101,7 @@ pub async fn handle_issues(
             };
 
             let issues = client.list_issues(repo, params).await?;
-            let calculator = IssueMetricsCalculator::new(30);
+            let calculator = IssueMetricsCalculator::new(30).with_bot_filter(bots.clone());
             let metrics = calculator.calculate(&issues);
 
             println!("Age Distribution:");
@@ -158,10 +160,30 @@ pub async fn handle_issues(
 
         IssuesCommands::Metrics => {
             let issues = client.list_issues(repo, IssueParams::all()).await?;
-            let calculator = IssueMetricsCalculator::new(30);
+            let calculator = IssueMetricsCalculator::new(30).with_bot_filter(bots.clone());
             let metrics = calculator.calculate(&issues);
             formatter.format_issue_metrics(&metrics)?;
         }
+
+        IssuesCommands::Epics { stale_days, all } => {
+            let issues = client.list_issues(repo, IssueParams::all()).await?;
+            let relations = match client.list_issue_relations(repo).await {
+                Ok(relations) => relations,
+                Err(e) => {
+                    warn!(
+                        repo = %repo,
+                        error = %e,
+                        "Could not fetch sub-issues, using task lists only"
+                    );
+                    Vec::new()
+                }
+            };
+
+            let report = EpicReportCalculator::new(stale_days)
+                .with_closed(all)
+                .calculate(&repo.full_name(), &issues, &relations, chrono::Utc::now());
+            formatter.format_epic_report(&report)?;
+        }
     }
 
     Ok(())
@@ -1,9 +1,12 @@
 //! CLI command handlers
 
+pub mod admin;
 pub mod config;
 pub mod contrib;
+pub mod deps;
 pub mod issues;
 pub mod orgs;
+pub mod projects;
 pub mod pulls;
 pub mod releases;
 pub mod sync;
@@ -1,18 +1,59 @@
 //! Organization management commands
 
 use crate::args::OrgsCommands;
+use chrono::Utc;
 use comfy_table::{Cell, Table};
-use greport_core::config::{mask_token, Config};
+use greport_core::client::{Provider, RateBudgetSnapshot, ResourceBudget, GITLAB_API_URL};
+use greport_core::config::{Config, OrgConfig};
+use greport_core::GitHubClientRegistry;
 
 /// Handle orgs subcommands.
-pub fn handle_orgs(command: &OrgsCommands, config: &Config) -> anyhow::Result<()> {
+pub async fn handle_orgs(command: &OrgsCommands, config: &Config) -> anyhow::Result<()> {
     match command {
-        OrgsCommands::List => list_orgs(config),
-        OrgsCommands::Show { name } => show_org(config, name),
+        OrgsCommands::List => list_orgs(config).await,
+        OrgsCommands::Show { name } => show_org(config, name).await,
     }
 }
 
-fn list_orgs(config: &Config) -> anyhow::Result<()> {
+/// Build the registry and fetch each token's rate-limit budget.
+///
+/// Budgets are informational, so a registry that cannot be built (e.g. no
+/// tokens) just leaves them out.
+async fn registry_with_budgets(config: &Config) -> Option<GitHubClientRegistry> {
+    let registry = GitHubClientRegistry::from_config(config).ok()?;
+    registry.refresh_rate_budgets().await;
+    Some(registry)
+}
+
+/// API base URL of an organization, defaulting per provider
+fn api_url(org: &OrgConfig) -> &str {
+    match (&org.base_url, org.provider) {
+        (Some(url), _) => url,
+        (None, Provider::GitLab) => GITLAB_API_URL,
+        (None, _) => "https://api.github.com",
+    }
+}
+
+/// Format a budget as "remaining/limit"
+fn budget_cell(budget: Option<&ResourceBudget>) -> String {
+    budget.map_or_else(
+        || "-".to_string(),
+        |b| format!("{}/{}", b.remaining, b.limit),
+    )
+}
+
+/// Format the time until the REST budget resets
+fn reset_cell(snapshot: Option<&RateBudgetSnapshot>) -> String {
+    match snapshot.and_then(|s| s.core.as_ref()) {
+        Some(core) => {
+            let minutes = (core.reset - Utc::now()).num_minutes().max(0);
+            format!("in {}m", minutes)
+        }
+        None => "-".to_string(),
+    }
+}
+
+async fn list_orgs(config: &Config) -> anyhow::Result<()> {
     if config.organizations.is_empty() {
         println!("No organizations configured.");
         println!();
@@ -24,20 +65,33 @@ fn list_orgs(config: &Config) -> anyhow::Result<()> {
         return Ok(());
     }
 
+    let registry = registry_with_budgets(config).await;
+
     let mut table = Table::new();
     table.set_header(vec![
         Cell::new("Name"),
         Cell::new("Base URL"),
         Cell::new("Repos"),
+        Cell::new("REST Left"),
+        Cell::new("GraphQL Left"),
+        Cell::new("Resets"),
     ]);
 
     for org in &config.organizations {
-        let base_url = org.base_url.as_deref().unwrap_or("https://api.github.com");
+        let base_url = api_url(org);
         let repo_count = org.repos.as_deref().map_or(0, |r| r.len());
+        let budget = registry
+            .as_ref()
+            .and_then(|r| r.rate_budget_for_org(&org.name));
         table.add_row(vec![
             Cell::new(&org.name),
             Cell::new(base_url),
             Cell::new(repo_count),
+            Cell::new(budget_cell(budget.as_ref().and_then(|b| b.core.as_ref()))),
+            Cell::new(budget_cell(
+                budget.as_ref().and_then(|b| b.graphql.as_ref()),
+            )),
+            Cell::new(reset_cell(budget.as_ref())),
         ]);
     }
 
@@ -47,7 +101,7 @@ fn list_orgs(config: &Config) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn show_org(config: &Config, name: &str) -> anyhow::Result<()> {
+async fn show_org(config: &Config, name: &str) -> anyhow::Result<()> {
     let name_lower = name.to_lowercase();
     let org = config
         .organizations
@@ -57,11 +111,15 @@ fn show_org(config: &Config, name: &str) -> anyhow::Result<()> {
     match org {
         Some(org) => {
             println!("Organization: {}", org.name);
-            println!("Token:        {}", mask_token(&org.token));
-            println!(
-                "Base URL:     {}",
-                org.base_url.as_deref().unwrap_or("https://api.github.com")
-            );
+            println!("Provider:     {}", org.provider);
+            match &org.app {
+                Some(app) => println!(
+                    "GitHub App:   {} (installation {})",
+                    app.app_id, app.installation_id
+                ),
+                None => println!("Token:        {}", Config::display_secret(&org.token)),
+            }
+            println!("Base URL:     {}", api_url(org));
 
             match &org.repos {
                 Some(repos) if !repos.is_empty() => {
@@ -74,6 +132,19 @@ fn show_org(config: &Config, name: &str) -> anyhow::Result<()> {
                     println!("Repos:        (none configured)");
                 }
             }
+
+            let budget = registry_with_budgets(config)
+                .await
+                .and_then(|r| r.rate_budget_for_org(&org.name));
+            println!(
+                "REST left:    {}",
+                budget_cell(budget.as_ref().and_then(|b| b.core.as_ref()))
+            );
+            println!(
+                "GraphQL left: {}",
+                budget_cell(budget.as_ref().and_then(|b| b.graphql.as_ref()))
+            );
+            println!("Resets:       {}", reset_cell(budget.as_ref()));
             Ok(())
         }
         None => {
@@ -0,0 +1,240 @@
+//! GitHub Projects (V2) command handlers
+
+use crate::args::{OutputFormat, ProjectsCommands};
+use crate::output::Formatter;
+use chrono::Utc;
+use greport_core::client::{ProjectClient, RepoId};
+use greport_core::metrics::ProjectMetricsCalculator;
+use greport_core::models::{Project, ProjectItem, ProjectItemFilter};
+use greport_core::reports::IterationReportCalculator;
+use greport_core::rules::{PlannedAction, PlannedChange, ProjectRules};
+use std::path::Path;
+
+pub async fn handle_projects(
+    client: &(impl ProjectClient + ?Sized),
+    org: &str,
+    command: ProjectsCommands,
+    format: OutputFormat,
+) -> anyhow::Result<()> {
+    let formatter = Formatter::new(format);
+
+    match command {
+        ProjectsCommands::List { include_closed } => {
+            let projects = client.list_projects(org).await?;
+            let projects: Vec<_> = projects
+                .into_iter()
+                .filter(|p| include_closed || !p.closed)
+                .collect();
+            formatter.format_projects(&projects)?;
+        }
+
+        ProjectsCommands::Show { number } => {
+            let project = client.get_project(org, number).await?;
+            formatter.format_project(&project)?;
+        }
+
+        ProjectsCommands::Items {
+            number,
+            filter,
+            limit,
+        } => {
+            let filter = ProjectItemFilter::try_from(filter)?;
+            let (_, items) = fetch_items(client, org, number).await?;
+            let items: Vec<ProjectItem> = items
+                .into_iter()
+                .filter(|i| filter.matches(i))
+                .take(limit)
+                .collect();
+            formatter.format_project_items(&items)?;
+        }
+
+        ProjectsCommands::Metrics { number, filter } => {
+            let status_field = filter.status_field.clone();
+            let filter = ProjectItemFilter::try_from(filter)?;
+            let (_, items) = fetch_items(client, org, number).await?;
+            let metrics = ProjectMetricsCalculator::calculate(filter.apply(&items), &status_field);
+            formatter.format_project_metrics(&metrics)?;
+        }
+
+        ProjectsCommands::Iterations {
+            number,
+            field,
+            points_field,
+            status_field,
+            done,
+        } => {
+            let (project, items) = fetch_items(client, org, number).await?;
+            let report = IterationReportCalculator::new(field)
+                .with_points_field(points_field)
+                .with_status_field(status_field)
+                .with_done_values(done)
+                .calculate(&project, &items, Utc::now().date_naive())?;
+            formatter.format_iteration_report(&report)?;
+        }
+
+        ProjectsCommands::Set {
+            number,
+            item,
+            values,
+        } => {
+            let project = client.get_project(org, number).await?;
+            let items = client.list_project_items(&project.node_id).await?;
+            let item = find_item(&items, &item)?;
+
+            let mut changes = Vec::new();
+            for expr in &values {
+                let (field_name, value) = ProjectItemFilter::parse_field(expr)?;
+                let (field, update) = project.resolve_field_update(&field_name, &value)?;
+                changes.push(PlannedChange {
+                    rule: "manual".to_string(),
+                    item_node_id: item.node_id.clone(),
+                    item: item.reference(),
+                    title: item.title().to_string(),
+                    action: PlannedAction::SetField {
+                        field: field.name.clone(),
+                        field_node_id: field.node_id.clone(),
+                        from: item.field_display(&field.name),
+                        to: value,
+                        update,
+                    },
+                });
+            }
+
+            apply_changes(client, &project.node_id, &changes).await?;
+            formatter.format_planned_changes(&changes)?;
+        }
+
+        ProjectsCommands::Add { number, content } => {
+            let (repo, content_number) = parse_content_ref(&content)?;
+            let project = client.get_project(org, number).await?;
+            let item_id = client
+                .add_project_item(&project.node_id, &repo, content_number)
+                .await?;
+            eprintln!(
+                "Added {} to project #{} (item {})",
+                content, project.number, item_id
+            );
+        }
+
+        ProjectsCommands::Archive { number, item } => {
+            let project = client.get_project(org, number).await?;
+            let items = client.list_project_items(&project.node_id).await?;
+            let item = find_item(&items, &item)?;
+            client
+                .archive_project_item(&project.node_id, &item.node_id)
+                .await?;
+            eprintln!(
+                "Archived {} from project #{}",
+                item.reference(),
+                project.number
+            );
+        }
+
+        ProjectsCommands::Apply {
+            number,
+            rules,
+            dry_run,
+        } => {
+            let rules = ProjectRules::load(Path::new(&rules))?;
+            let project = client.get_project(org, number).await?;
+            let items = client.list_project_items(&project.node_id).await?;
+            let changes = rules.plan(&project, &items, Utc::now())?;
+
+            formatter.format_planned_changes(&changes)?;
+            if dry_run {
+                eprintln!("Dry run: {} change(s) planned, none applied", changes.len());
+            } else {
+                apply_changes(client, &project.node_id, &changes).await?;
+                eprintln!("Applied {} change(s)", changes.len());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a project by number and fetch all of its items.
+async fn fetch_items(
+    client: &(impl ProjectClient + ?Sized),
+    org: &str,
+    number: u64,
+) -> anyhow::Result<(Project, Vec<ProjectItem>)> {
+    let project = client.get_project(org, number).await?;
+    let items = client.list_project_items(&project.node_id).await?;
+    Ok((project, items))
+}
+
+/// Apply planned changes in order, stopping at the first failure.
+async fn apply_changes(
+    client: &(impl ProjectClient + ?Sized),
+    project_node_id: &str,
+    changes: &[PlannedChange],
+) -> anyhow::Result<()> {
+    for change in changes {
+        match &change.action {
+            PlannedAction::SetField {
+                field_node_id,
+                update,
+                ..
+            } => {
+                client
+                    .update_project_item_field(
+                        project_node_id,
+                        &change.item_node_id,
+                        field_node_id,
+                        update,
+                    )
+                    .await?
+            }
+            PlannedAction::Archive => {
+                client
+                    .archive_project_item(project_node_id, &change.item_node_id)
+                    .await?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Find a project item by node ID, "owner/repo#number" or "#number".
+fn find_item<'a>(items: &'a [ProjectItem], reference: &str) -> anyhow::Result<&'a ProjectItem> {
+    if let Some(item) = items.iter().find(|i| i.node_id == reference) {
+        return Ok(item);
+    }
+
+    let (repo, number) = reference
+        .rsplit_once('#')
+        .and_then(|(repo, n)| n.parse::<u64>().ok().map(|n| (repo, n)))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid item '{}': expected owner/repo#number, #number or an item node ID",
+                reference
+            )
+        })?;
+
+    let matches: Vec<&ProjectItem> = items
+        .iter()
+        .filter(|i| i.number() == Some(number))
+        .filter(|i| repo.is_empty() || i.repository().is_some_and(|r| r.eq_ignore_ascii_case(repo)))
+        .collect();
+
+    match matches.as_slice() {
+        [item] => Ok(item),
+        [] => anyhow::bail!("Item '{}' not found in project", reference),
+        _ => anyhow::bail!("Item '{}' is ambiguous; use owner/repo#number", reference),
+    }
+}
+
+/// Parse "owner/repo#number" into a repository and issue/PR number.
+fn parse_content_ref(reference: &str) -> anyhow::Result<(RepoId, u64)> {
+    let (repo, number) = reference.rsplit_once('#').ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid reference '{}': expected owner/repo#number",
+            reference
+        )
+    })?;
+    let number = number
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid issue number in '{}'", reference))?;
+    Ok((RepoId::parse(repo)?, number))
+}
@@ -3,13 +3,14 @@
 use crate::args::{OutputFormat, PrsCommands};
 use crate::output::Formatter;
 use greport_core::client::{GitHubClient, PullParams, RepoId};
-use greport_core::metrics::PullMetricsCalculator;
+use greport_core::metrics::{BotFilter, PullMetricsCalculator};
 
 pub async fn handle_pulls(
-    client: &impl GitHubClient,
+    client: &(impl GitHubClient + ?Sized),
     repo: &RepoId,
     command: PrsCommands,
     format: OutputFormat,
+    bots: &BotFilter,
 ) -> anyhow::Result<()> {
     let formatter = Formatter::new(format);
 
@@ -38,7 +39,7 @@ pub async fn handle_pulls(
 
         PrsCommands::Metrics => {
             let prs = client.list_pulls(repo, PullParams::all()).await?;
-            let metrics = PullMetricsCalculator::calculate(&prs);
+            let metrics = PullMetricsCalculator::calculate_with_bots(&prs, bots);
             formatter.format_pull_metrics(&metrics)?;
         }
 
@@ -46,10 +47,10 @@ pub async fn handle_pulls(
             let prs = client.list_pulls(repo, PullParams::open()).await?;
 
             // Filter for PRs that are ready for review (not drafts)
-            let unreviewed: Vec<_> = prs
+            let (unreviewed, bot_prs): (Vec<_>, Vec<_>) = prs
                 .into_iter()
                 .filter(|pr| pr.is_ready_for_review())
-                .collect();
+                .partition(|pr| !bots.is_bot(&pr.author));
 
             if unreviewed.is_empty() {
                 println!("No unreviewed pull requests found.");
@@ -57,6 +58,11 @@ pub async fn handle_pulls(
                 println!("Found {} PRs awaiting review:\n", unreviewed.len());
                 formatter.format_pulls(&unreviewed)?;
             }
+
+            if bots.separate() && !bot_prs.is_empty() {
+                println!("\n{} bot PRs awaiting review:\n", bot_prs.len());
+                formatter.format_pulls(&bot_prs)?;
+            }
         }
     }
 
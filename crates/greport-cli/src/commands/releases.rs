@@ -3,14 +3,16 @@
 use crate::args::{OutputFormat, ReleasesCommands};
 use crate::output::Formatter;
 use greport_core::client::{GitHubClient, IssueParams, PullParams, RepoId};
+use greport_core::metrics::BotFilter;
 use greport_core::models::IssueState;
 use greport_core::reports::ReleaseNotesGenerator;
 
 pub async fn handle_releases(
-    client: &impl GitHubClient,
+    client: &(impl GitHubClient + ?Sized),
     repo: &RepoId,
     command: ReleasesCommands,
     format: OutputFormat,
+    bots: &BotFilter,
 ) -> anyhow::Result<()> {
     let formatter = Formatter::new(format);
 
@@ -42,7 +44,7 @@ pub async fn handle_releases(
             let merged_prs: Vec<_> = prs.into_iter().filter(|p| p.merged).collect();
 
             // Generate release notes
-            let generator = ReleaseNotesGenerator::with_defaults();
+            let generator = ReleaseNotesGenerator::with_defaults().with_bot_filter(bots.clone());
             let version_str = version.unwrap_or_else(|| milestone.clone());
             let notes = generator.generate(&version_str, &milestone_issues, &merged_prs);
 
@@ -4,7 +4,7 @@ use crate::args::SyncArgs;
 use greport_core::client::{GitHubClient, IssueParams, PullParams, RepoId};
 
 pub async fn handle_sync(
-    client: &impl GitHubClient,
+    client: &(impl GitHubClient + ?Sized),
     repo: &RepoId,
     args: SyncArgs,
 ) -> anyhow::Result<()> {
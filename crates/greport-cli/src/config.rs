@@ -10,14 +10,8 @@ pub fn load_config(path: Option<&str>) -> anyhow::Result<Config> {
         None => default_config_path()?,
     };
 
-    if !config_path.exists() {
-        return Ok(Config::default());
-    }
-
-    let content = std::fs::read_to_string(&config_path)?;
-    let config: Config = toml::from_str(&content)?;
-
-    Ok(config)
+    // Resolves secret references and GREPORT_ORG_*_TOKEN variables
+    Ok(Config::load(Some(&config_path))?)
 }
 
 /// Get default configuration file path
@@ -52,6 +46,9 @@ pub fn create_default_config(force: bool) -> anyhow::Result<PathBuf> {
 # GitHub Enterprise base URL (optional, for GitHub Enterprise Server)
 # base_url = "https://github.mycompany.com/api/v3"
 
+# List issues and PRs with bulk GraphQL queries, which include PR sizes (rest, graphql)
+# data_source = "graphql"
+
 [defaults]
 # Default repository (owner/repo)
 # repo = "owner/repo"
@@ -7,7 +7,10 @@ mod output;
 
 use args::{Cli, Commands};
 use clap::Parser;
-use greport_core::{Config, GitHubClientRegistry, OctocrabClient, RepoId};
+use greport_client::GreportClient;
+use greport_core::client::{Credentials, PageProgress, Pagination};
+use greport_core::{Config, GitHubApi, GitHubClientRegistry, OctocrabClient, RepoId};
+use std::io::{IsTerminal, Write};
 use std::process::ExitCode;
 use std::sync::Arc;
 use tracing::{debug, info};
@@ -50,7 +53,13 @@ fn print_error(error: &anyhow::Error) {
     // Use Display format for user-facing messages (clean, no backtrace)
     let error_display = format!("{}", error);
 
-    if error_debug.contains("Resource not accessible by personal access token") {
+    if error_debug.contains("greport server") && !error_debug.contains("not available") {
+        eprintln!("Error: {}", error_display);
+        eprintln!();
+        eprintln!("Please check:");
+        eprintln!("  - The --server URL (or GREPORT_SERVER) points at a running greport API");
+        eprintln!("  - Your API key (--api-key or GREPORT_API_KEY) has access to this repository");
+    } else if error_debug.contains("Resource not accessible by personal access token") {
         eprintln!("Error: Access denied - your token doesn't have permission for this resource.");
         eprintln!();
         eprintln!("Possible causes:");
@@ -159,6 +168,43 @@ fn print_banner() {
     eprintln!("{}", BANNER);
 }
 
+/// Item cap (--max-items > config) and progress reporting for list calls
+fn pagination(cli: &Cli, cfg: &Config) -> Pagination {
+    let show_progress = std::io::stderr().is_terminal();
+    Pagination::default()
+        .with_max_items(cli.max_items.or(cfg.max_items()))
+        .on_page(move |progress| report_page(progress, show_progress))
+}
+
+/// Show a one-line page counter while a list is fetched, and warn when the
+/// item cap cut it short
+fn report_page(progress: &PageProgress, show_progress: bool) {
+    let mut stderr = std::io::stderr();
+    if show_progress {
+        if progress.done {
+            // Clear the progress line
+            let _ = write!(stderr, "\r\x1b[K");
+        } else {
+            let total = progress
+                .total_pages
+                .map(|t| format!("/{}", t))
+                .unwrap_or_default();
+            let _ = write!(
+                stderr,
+                "\r\x1b[K{}: page {}{} ({} items)",
+                progress.operation, progress.page, total, progress.items
+            );
+        }
+        let _ = stderr.flush();
+    }
+    if progress.truncated {
+        eprintln!(
+            "Warning: {} stopped at {} items (--max-items); results are incomplete",
+            progress.operation, progress.items
+        );
+    }
+}
+
 async fn run() -> anyhow::Result<()> {
     // Load .env file if present
     let dotenv_result = dotenvy::dotenv();
@@ -168,7 +214,10 @@ async fn run() -> anyhow::Result<()> {
 
     // Load config from the correct path (--config flag or default)
     let config_path = cli.config.as_deref();
-    let cfg = config::load_config(config_path)?;
+    let mut cfg = config::load_config(config_path)?;
+    if cli.separate_bots {
+        cfg.bots.report_separately = true;
+    }
 
     // Initialize logging using the resolved config (env var > config.toml > "warn")
     tracing_subscriber::registry()
@@ -201,30 +250,60 @@ async fn run() -> anyhow::Result<()> {
 
     // Handle config command separately (doesn't need GitHub client)
     if let Commands::Config(args) = &cli.command {
-        return commands::config::handle_config(&args.command);
+        return commands::config::handle_config(&args.command, &cfg);
     }
 
-    // Handle orgs command separately (doesn't need GitHub client)
+    // Handle orgs command separately (builds its own registry for budgets)
     if let Commands::Orgs(args) = &cli.command {
-        return commands::orgs::handle_orgs(&args.command, &cfg);
+        return commands::orgs::handle_orgs(&args.command, &cfg).await;
+    }
+
+    // Handle admin command separately (talks to the database directly)
+    if let Commands::Admin(args) = &cli.command {
+        return commands::admin::handle_admin(&args.command, &cfg).await;
     }
 
     // Build client registry (supports multi-org and single-token configs)
     let has_orgs = !cfg.organizations.is_empty();
-    let registry = if has_orgs {
+    let registry = if let Some(ref dir) = cli.replay {
+        // Every call is answered from a recorded run, without a token
+        info!(dir = %dir.display(), "Replaying recorded fixtures");
+        GitHubClientRegistry::replaying(&cfg, dir)
+    } else if let Some(ref server) = cli.server {
+        // Every repository and organization is read through the server
+        info!(server = %server, "Reading from greport server");
+        let mut client = GreportClient::new(server)?;
+        if let Some(ref api_key) = cli.api_key {
+            client = client.with_api_key(api_key);
+        }
+        GitHubClientRegistry::with_default(client, None)
+    } else if has_orgs {
         info!(
             org_count = cfg.organizations.len(),
             "Building multi-org client registry"
         );
-        GitHubClientRegistry::from_config(&cfg)?
+        GitHubClientRegistry::from_config_with_pagination(&cfg, pagination(&cli, &cfg))?
     } else {
-        // Legacy single-token path: resolve token from config or env
-        let (token, token_source) = if let Some(ref t) = cfg.github.token {
+        let base_url = cfg
+            .github
+            .base_url
+            .clone()
+            .or_else(|| std::env::var("GITHUB_BASE_URL").ok());
+
+        // Legacy single-token path: resolve a GitHub App installation or a
+        // token from config or env
+        let (credentials, token_source) = if let Some(app) = cfg.github_app() {
+            debug!(app_id = app.app_id, "Using GitHub App installation");
+            (
+                Credentials::app(&app, base_url.as_deref())?,
+                "GitHub App installation",
+            )
+        } else if cfg.github.token.is_some() {
             debug!("Using GitHub token from config file");
-            (t.clone(), "config file")
+            (Credentials::Token(cfg.github_token()?), "config file")
         } else if let Ok(t) = std::env::var("GITHUB_TOKEN") {
             debug!("Using GitHub token from GITHUB_TOKEN environment variable");
-            (t, "GITHUB_TOKEN env var")
+            (Credentials::Token(t), "GITHUB_TOKEN env var")
         } else {
             anyhow::bail!(
                 "Missing GitHub token.\n\n\
@@ -232,16 +311,12 @@ async fn run() -> anyhow::Result<()> {
                  1. Environment variable: export GITHUB_TOKEN=ghp_xxx\n  \
                  2. Config file: add 'token = \"ghp_xxx\"' to [github] section\n     \
                     in ~/.config/greport/config.toml\n  \
-                 3. Multi-org: add [[organizations]] entries with per-org tokens"
+                 3. Multi-org: add [[organizations]] entries with per-org tokens\n  \
+                 4. GitHub App: add a [github.app] section with app_id,\n     \
+                    private_key_path and installation_id"
             );
         };
 
-        let base_url = cfg
-            .github
-            .base_url
-            .clone()
-            .or_else(|| std::env::var("GITHUB_BASE_URL").ok());
-
         info!(
             token_source = token_source,
             base_url = base_url
@@ -251,51 +326,56 @@ async fn run() -> anyhow::Result<()> {
         );
 
         // Build a registry with just the default client
-        let client = OctocrabClient::new(&token, base_url.as_deref())?;
+        let client = OctocrabClient::from_credentials(credentials, base_url.as_deref())?
+            .with_config(&cfg)?
+            .with_pagination(pagination(&cli, &cfg));
         // Wrap in a minimal registry so all code paths use the same type
         GitHubClientRegistry::with_default(client, base_url)
     };
+    let registry = match cli.record {
+        Some(ref dir) => {
+            info!(dir = %dir.display(), "Recording calls to fixtures");
+            registry.recording(dir)
+        }
+        None => registry,
+    };
     info!("GitHub client initialized successfully");
 
     // Validate tokens when verbose mode is enabled
-    if cli.verbose {
+    if cli.verbose && cli.server.is_none() && cli.replay.is_none() {
         let valid = registry.validate_tokens().await;
         info!(valid_tokens = valid, "Token validation complete");
     }
 
-    // Resolve repository target using precedence rules:
-    // 1. -r org/repo  -> Single repo (highest priority)
-    // 2. --org <name> without -r -> Multi: that org's configured repos
-    // 3. No -r, no --org -> Multi: all orgs' configured repos
-    // 4. No -r, no org repos -> Single: defaults.repo fallback
-    // 5. None of the above -> error
-    let target = if let Some(ref r) = cli.repo {
-        debug!(repo = %r, "Using repository from command line argument");
-        RepoTarget::Single(RepoId::parse(r)?)
-    } else if let Some(ref org_name) = cli.org {
-        let repos = cfg.resolved_repos_for_org(org_name);
-        if repos.is_empty() {
-            anyhow::bail!(
-                "No repos configured for organization '{}'. \
-                 Add repos = [\"repo1\", \"repo2\"] to the [[organizations]] entry, \
-                 or use -r org/repo to specify a single repo.",
-                org_name
-            );
-        }
-        debug!(org = %org_name, count = repos.len(), "Using repos from org config");
-        RepoTarget::Multi(repos)
-    } else {
-        let all_repos = cfg.resolved_repos();
-        if !all_repos.is_empty() {
-            debug!(count = all_repos.len(), "Using repos from all org configs");
-            RepoTarget::Multi(all_repos)
-        } else if let Some(ref r) = cfg.defaults.repo {
-            debug!(repo = %r, "Using repository from config file defaults");
-            RepoTarget::Single(RepoId::parse(r)?)
-        } else {
-            anyhow::bail!("No repository specified. Use -r/--repo or set defaults.repo in config");
+    // Projects are org-scoped, so they skip repository resolution
+    if let Commands::Projects(args) = &cli.command {
+        let org = resolve_project_org(&cli, &cfg)?;
+        info!(org = %org, "Target organization");
+        let client = registry.client_for_org(&org)?;
+        return commands::projects::handle_projects(
+            client.as_ref(),
+            &org,
+            args.command.clone(),
+            cli.format,
+        )
+        .await;
+    }
+
+    let target = resolve_repo_target(&cli, &cfg)?;
+
+    // Dependency graphs span every target repository at once
+    if let Commands::Deps(args) = &cli.command {
+        let repos = match target {
+            RepoTarget::Single(repo) => vec![repo],
+            RepoTarget::Multi(repos) => repos,
+        };
+        let mut targets = Vec::with_capacity(repos.len());
+        for repo in repos {
+            let client = registry.client_for_repo(&repo)?.clone();
+            targets.push((repo, client));
         }
-    };
+        return commands::deps::handle_deps(&targets, args.command.clone(), cli.format).await;
+    }
 
     // Execute across target repo(s)
     match target {
@@ -337,12 +417,13 @@ async fn run() -> anyhow::Result<()> {
 
 /// Execute a single command against one repository.
 async fn execute_command(
-    client: Arc<OctocrabClient>,
+    client: Arc<dyn GitHubApi>,
     repo: &RepoId,
     command: &Commands,
     format: args::OutputFormat,
     cfg: &Config,
 ) -> anyhow::Result<()> {
+    let bots = cfg.bot_filter(&repo.owner);
     match command {
         Commands::Issues(args) => {
             commands::issues::handle_issues(
@@ -351,12 +432,19 @@ async fn execute_command(
                 args.command.clone(),
                 format,
                 cfg,
+                &bots,
             )
             .await?;
         }
         Commands::Prs(args) => {
-            commands::pulls::handle_pulls(client.as_ref(), repo, args.command.clone(), format)
-                .await?;
+            commands::pulls::handle_pulls(
+                client.as_ref(),
+                repo,
+                args.command.clone(),
+                format,
+                &bots,
+            )
+            .await?;
         }
         Commands::Releases(args) => {
             commands::releases::handle_releases(
@@ -364,23 +452,92 @@ async fn execute_command(
                 repo,
                 args.command.clone(),
                 format,
+                &bots,
             )
             .await?;
         }
         Commands::Contrib(args) => {
-            commands::contrib::handle_contrib(client.as_ref(), repo, args.command.clone(), format)
-                .await?;
+            commands::contrib::handle_contrib(
+                client.as_ref(),
+                repo,
+                args.command.clone(),
+                format,
+                &bots,
+                &cfg.identities()?,
+            )
+            .await?;
         }
         Commands::Sync(args) => {
             commands::sync::handle_sync(client.as_ref(), repo, args.clone()).await?;
         }
-        Commands::Config(_) | Commands::Orgs(_) => {
+        Commands::Config(_)
+        | Commands::Orgs(_)
+        | Commands::Projects(_)
+        | Commands::Deps(_)
+        | Commands::Admin(_) => {
             unreachable!()
         }
     }
     Ok(())
 }
 
+/// Resolve repository target using precedence rules:
+/// 1. -r org/repo  -> Single repo (highest priority)
+/// 2. --org <name> without -r -> Multi: that org's configured repos
+/// 3. No -r, no --org -> Multi: all orgs' configured repos
+/// 4. No -r, no org repos -> Single: defaults.repo fallback
+/// 5. None of the above -> error
+fn resolve_repo_target(cli: &Cli, cfg: &Config) -> anyhow::Result<RepoTarget> {
+    if let Some(ref r) = cli.repo {
+        debug!(repo = %r, "Using repository from command line argument");
+        Ok(RepoTarget::Single(RepoId::parse(r)?))
+    } else if let Some(ref org_name) = cli.org {
+        let repos = cfg.resolved_repos_for_org(org_name);
+        if repos.is_empty() {
+            anyhow::bail!(
+                "No repos configured for organization '{}'. \
+                 Add repos = [\"repo1\", \"repo2\"] to the [[organizations]] entry, \
+                 or use -r org/repo to specify a single repo.",
+                org_name
+            );
+        }
+        debug!(org = %org_name, count = repos.len(), "Using repos from org config");
+        Ok(RepoTarget::Multi(repos))
+    } else {
+        let all_repos = cfg.resolved_repos();
+        if !all_repos.is_empty() {
+            debug!(count = all_repos.len(), "Using repos from all org configs");
+            Ok(RepoTarget::Multi(all_repos))
+        } else if let Some(ref r) = cfg.defaults.repo {
+            debug!(repo = %r, "Using repository from config file defaults");
+            Ok(RepoTarget::Single(RepoId::parse(r)?))
+        } else {
+            anyhow::bail!("No repository specified. Use -r/--repo or set defaults.repo in config");
+        }
+    }
+}
+
+/// Resolve the organization for org-scoped commands.
+///
+/// Precedence: `--org`, then the owner of `-r owner/repo`, then the owner of
+/// `defaults.repo`, then the only configured organization.
+fn resolve_project_org(cli: &Cli, cfg: &Config) -> anyhow::Result<String> {
+    if let Some(ref org) = cli.org {
+        return Ok(org.clone());
+    }
+    if let Some(ref r) = cli.repo {
+        return Ok(RepoId::parse(r)?.owner);
+    }
+    if let Some(ref r) = cfg.defaults.repo {
+        return Ok(RepoId::parse(r)?.owner);
+    }
+    match cfg.organizations.as_slice() {
+        [only] => Ok(only.name.clone()),
+        [] => anyhow::bail!("No organization specified. Use -o/--org <name>"),
+        _ => anyhow::bail!("Multiple organizations configured. Use -o/--org <name> to choose one"),
+    }
+}
+
 /// Format a header line for multi-repo output.
 fn format_repo_header(repo_name: &str, index: usize, total: usize) -> String {
     let prefix = format!("--- {} ({}/{}) ", repo_name, index, total);
@@ -1,8 +1,11 @@
 //! CSV output formatting
 
-use greport_core::metrics::{IssueMetrics, PullMetrics, SlaReport, VelocityMetrics};
-use greport_core::models::{Issue, PullRequest, Release};
-use greport_core::reports::BurndownReport;
+use greport_core::metrics::{
+    IssueMetrics, ProjectMetrics, PullMetrics, SlaReport, VelocityMetrics,
+};
+use greport_core::models::{Issue, Project, ProjectFieldType, ProjectItem, PullRequest, Release};
+use greport_core::reports::{BurndownReport, DependencyReport, EpicReport, IterationReport};
+use greport_core::rules::PlannedChange;
 use std::io;
 
 pub fn format_issues(issues: &[Issue]) -> anyhow::Result<()> {
@@ -186,3 +189,228 @@ pub fn format_releases(releases: &[Release]) -> anyhow::Result<()> {
     wtr.flush()?;
     Ok(())
 }
+
+pub fn format_projects(projects: &[Project]) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record([
+        "number",
+        "title",
+        "closed",
+        "total_items",
+        "updated_at",
+        "url",
+    ])?;
+
+    for p in projects {
+        wtr.write_record([
+            &p.number.to_string(),
+            &p.title,
+            &p.closed.to_string(),
+            &p.total_items.to_string(),
+            &p.updated_at.to_rfc3339(),
+            &p.url,
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_project(project: &Project) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record(["field", "type", "options"])?;
+
+    for field in &project.fields {
+        let options = match &field.field_type {
+            ProjectFieldType::SingleSelect { options } => options
+                .iter()
+                .map(|o| o.name.as_str())
+                .collect::<Vec<_>>()
+                .join(";"),
+            ProjectFieldType::Iteration { iterations } => iterations
+                .iter()
+                .map(|i| i.title.as_str())
+                .collect::<Vec<_>>()
+                .join(";"),
+            _ => String::new(),
+        };
+        wtr.write_record([&field.name, field.field_type.label(), &options])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_project_items(items: &[ProjectItem]) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record([
+        "number",
+        "title",
+        "type",
+        "status",
+        "iteration",
+        "repository",
+        "assignees",
+    ])?;
+
+    for item in items {
+        wtr.write_record([
+            &item.number().map(|n| n.to_string()).unwrap_or_default(),
+            item.title(),
+            item.content_type(),
+            &item.status().unwrap_or_default(),
+            item.iteration().unwrap_or(""),
+            item.repository().unwrap_or(""),
+            &item.assignees().join(";"),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_project_metrics(metrics: &ProjectMetrics) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record(["group", "value", "count"])?;
+    wtr.write_record(["total", "", &metrics.total_items.to_string()])?;
+    wtr.write_record(["closed", "", &metrics.closed_items.to_string()])?;
+
+    let groups = [
+        ("status", &metrics.by_status),
+        ("content_type", &metrics.by_content_type),
+        ("assignee", &metrics.by_assignee),
+        ("iteration", &metrics.by_iteration),
+    ];
+    for (group, counts) in groups {
+        for c in counts {
+            wtr.write_record([group, &c.value, &c.count.to_string()])?;
+        }
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_iteration_report(report: &IterationReport) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record([
+        "iteration",
+        "start_date",
+        "end_date",
+        "state",
+        "committed_items",
+        "completed_items",
+        "carry_over_items",
+        "committed_points",
+        "completed_points",
+        "carry_over_points",
+        "completion_ratio",
+        "velocity",
+        "carried_to",
+    ])?;
+
+    for it in &report.iterations {
+        wtr.write_record([
+            it.title.clone(),
+            it.start_date.to_string(),
+            it.end_date.to_string(),
+            format!("{:?}", it.state).to_lowercase(),
+            it.committed.items.to_string(),
+            it.completed.items.to_string(),
+            it.carry_over.items.to_string(),
+            it.committed.points.to_string(),
+            it.completed.points.to_string(),
+            it.carry_over.points.to_string(),
+            format!("{:.3}", it.completion_ratio),
+            it.velocity.to_string(),
+            it.carried_to.clone().unwrap_or_default(),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_planned_changes(changes: &[PlannedChange]) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record(["rule", "item", "title", "change"])?;
+
+    for c in changes {
+        wtr.write_record([&c.rule, &c.item, &c.title, &c.action.describe()])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_dependency_report(report: &DependencyReport) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record(["section", "issue", "title", "detail"])?;
+
+    for b in &report.open_blockers {
+        let blocks: Vec<String> = b.blocks.iter().map(|r| r.to_string()).collect();
+        wtr.write_record([
+            "blocker".to_string(),
+            b.node.issue.to_string(),
+            b.node.title.clone(),
+            blocks.join(" "),
+        ])?;
+    }
+
+    for (i, node) in report.critical_path.iter().enumerate() {
+        wtr.write_record([
+            "critical_path".to_string(),
+            node.issue.to_string(),
+            node.title.clone(),
+            (i + 1).to_string(),
+        ])?;
+    }
+
+    for (i, cycle) in report.cycles.iter().enumerate() {
+        for r in cycle {
+            wtr.write_record([
+                "cycle".to_string(),
+                r.to_string(),
+                String::new(),
+                (i + 1).to_string(),
+            ])?;
+        }
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn format_epic_report(report: &EpicReport) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    wtr.write_record([
+        "epic",
+        "title",
+        "state",
+        "completed",
+        "total",
+        "nested_completed",
+        "nested_total",
+        "percent",
+        "depth",
+        "stalled",
+    ])?;
+
+    for epic in &report.epics {
+        wtr.write_record([
+            epic.issue.to_string(),
+            epic.title.clone(),
+            if epic.open { "open" } else { "closed" }.to_string(),
+            epic.completed.to_string(),
+            epic.total.to_string(),
+            epic.nested_completed.to_string(),
+            epic.nested_total.to_string(),
+            format!("{:.1}", epic.percent),
+            epic.depth.to_string(),
+            epic.stalled_children().count().to_string(),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
@@ -1,8 +1,17 @@
 //! Markdown output formatting
 
-use greport_core::metrics::{IssueMetrics, PullMetrics, SlaReport, VelocityMetrics};
-use greport_core::models::{Issue, IssueState, Milestone, PullRequest, PullState, Release};
-use greport_core::reports::{BurndownReport, ReleaseNotes, ReleaseNotesGenerator};
+use greport_core::metrics::{
+    BotActivity, FieldCount, IssueMetrics, ProjectMetrics, PullMetrics, SlaReport, VelocityMetrics,
+};
+use greport_core::models::{
+    Issue, IssueState, Milestone, Project, ProjectFieldType, ProjectItem, PullRequest, PullState,
+    Release,
+};
+use greport_core::reports::{
+    BurndownReport, DependencyReport, EpicReport, IterationReport, IterationState, ReleaseNotes,
+    ReleaseNotesGenerator,
+};
+use greport_core::rules::PlannedChange;
 
 pub fn format_issues(issues: &[Issue]) -> anyhow::Result<()> {
     println!("# Issues\n");
@@ -66,9 +75,25 @@ pub fn format_issue_metrics(metrics: &IssueMetrics) -> anyhow::Result<()> {
         println!("| {} | {} |", bucket.label, bucket.count);
     }
 
+    if let Some(bots) = &metrics.bot_activity {
+        format_bot_activity(bots);
+    }
+
     Ok(())
 }
 
+fn format_bot_activity(bots: &BotActivity) {
+    println!("\n## Bot Activity\n");
+    println!("| Bot | Count |");
+    println!("|-----|-------|");
+    let mut authors: Vec<_> = bots.by_author.iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (author, count) in authors {
+        println!("| {} | {} |", author, count);
+    }
+    println!("| **Total** | {} |", bots.total);
+}
+
 pub fn format_velocity(velocity: &VelocityMetrics) -> anyhow::Result<()> {
     println!("# Velocity Report\n");
     println!("**Period:** {}", velocity.period.label());
@@ -123,11 +148,9 @@ pub fn format_burndown(burndown: &BurndownReport) -> anyhow::Result<()> {
     }
 
     if let Some(last) = burndown.data_points.last() {
-        let pct = if burndown.total_issues > 0 {
-            (last.completed * 100) / burndown.total_issues
-        } else {
-            0
-        };
+        let pct = (last.completed * 100)
+            .checked_div(burndown.total_issues)
+            .unwrap_or(0);
         println!(
             "\n**Progress:** {}% ({}/{})",
             pct, last.completed, burndown.total_issues
@@ -211,6 +234,10 @@ pub fn format_pull_metrics(metrics: &PullMetrics) -> anyhow::Result<()> {
         println!("| Avg Time to Merge | {:.1}h |", avg);
     }
 
+    if let Some(bots) = &metrics.bot_activity {
+        format_bot_activity(bots);
+    }
+
     Ok(())
 }
 
@@ -280,3 +307,285 @@ pub fn format_milestone_progress(milestone: &Milestone) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+pub fn format_projects(projects: &[Project]) -> anyhow::Result<()> {
+    println!("# Projects\n");
+    println!("| # | Title | State | Items | Updated |");
+    println!("|---|-------|-------|-------|---------|");
+
+    for p in projects {
+        println!(
+            "| {} | {} | {} | {} | {} |",
+            p.number,
+            p.title.replace('|', "\\|"),
+            if p.closed { "closed" } else { "open" },
+            p.total_items,
+            p.updated_at.format("%Y-%m-%d")
+        );
+    }
+
+    println!("\n**Total:** {} projects", projects.len());
+    Ok(())
+}
+
+pub fn format_project(project: &Project) -> anyhow::Result<()> {
+    println!("# Project #{}: {}\n", project.number, project.title);
+
+    if let Some(desc) = &project.description {
+        println!("{}\n", desc);
+    }
+
+    println!(
+        "- **State:** {}",
+        if project.closed { "closed" } else { "open" }
+    );
+    println!("- **Items:** {}", project.total_items);
+    println!("- **URL:** {}", project.url);
+
+    println!("\n## Fields\n");
+    println!("| Field | Type | Options |");
+    println!("|-------|------|---------|");
+    for field in &project.fields {
+        let options = match &field.field_type {
+            ProjectFieldType::SingleSelect { options } => options
+                .iter()
+                .map(|o| o.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            ProjectFieldType::Iteration { iterations } => iterations
+                .iter()
+                .map(|i| i.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => String::new(),
+        };
+        println!(
+            "| {} | {} | {} |",
+            field.name,
+            field.field_type.label(),
+            options
+        );
+    }
+
+    Ok(())
+}
+
+pub fn format_project_items(items: &[ProjectItem]) -> anyhow::Result<()> {
+    println!("# Project Items\n");
+    println!("| # | Title | Type | Status | Iteration | Assignees |");
+    println!("|---|-------|------|--------|-----------|-----------|");
+
+    for item in items {
+        println!(
+            "| {} | {} | {} | {} | {} | {} |",
+            item.number()
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            item.title().replace('|', "\\|"),
+            item.content_type(),
+            item.status().unwrap_or_else(|| "-".to_string()),
+            item.iteration().unwrap_or("-"),
+            item.assignees().join(", ")
+        );
+    }
+
+    println!("\n**Total:** {} items", items.len());
+    Ok(())
+}
+
+pub fn format_project_metrics(metrics: &ProjectMetrics) -> anyhow::Result<()> {
+    println!("# Project Metrics\n");
+    println!("| Metric | Value |");
+    println!("|--------|-------|");
+    println!("| Total Items | {} |", metrics.total_items);
+    println!("| Closed/Merged | {} |", metrics.closed_items);
+
+    print_counts_section("By Status", &metrics.by_status);
+    print_counts_section("By Type", &metrics.by_content_type);
+    print_counts_section("By Assignee", &metrics.by_assignee);
+    print_counts_section("By Iteration", &metrics.by_iteration);
+
+    Ok(())
+}
+
+fn print_counts_section(title: &str, counts: &[FieldCount]) {
+    if counts.is_empty() {
+        return;
+    }
+    println!("\n## {}\n", title);
+    println!("| Value | Count |");
+    println!("|-------|-------|");
+    for c in counts {
+        println!("| {} | {} |", c.value, c.count);
+    }
+}
+
+pub fn format_iteration_report(report: &IterationReport) -> anyhow::Result<()> {
+    println!("# Iterations: {}\n", report.project);
+    println!("- **Field:** {}", report.iteration_field);
+    println!("- **Unit:** {}", report.unit.label());
+    println!(
+        "- **Average Velocity:** {:.1} {}",
+        report.avg_velocity,
+        report.unit.label()
+    );
+    println!("- **Unscheduled Items:** {}", report.unscheduled_items);
+
+    println!("\n| Iteration | Dates | State | Committed | Completed | Carry-over | Completion |");
+    println!("|-----------|-------|-------|-----------|-----------|------------|------------|");
+    for it in &report.iterations {
+        let state = match it.state {
+            IterationState::Completed => "completed",
+            IterationState::Current => "current",
+            IterationState::Upcoming => "upcoming",
+        };
+        println!(
+            "| {} | {} - {} | {} | {} | {} | {} | {:.0}% |",
+            it.title.replace('|', "\\|"),
+            it.start_date,
+            it.end_date,
+            state,
+            it.committed.value(report.unit),
+            it.completed.value(report.unit),
+            it.carry_over.value(report.unit),
+            it.completion_ratio * 100.0
+        );
+    }
+
+    let carried: Vec<_> = report
+        .iterations
+        .iter()
+        .filter(|it| !it.carry_over_items.is_empty())
+        .collect();
+    if !carried.is_empty() {
+        println!("\n## Carry-over\n");
+        for it in carried {
+            println!(
+                "### {} -> {}\n",
+                it.title,
+                it.carried_to.as_deref().unwrap_or("(none)")
+            );
+            for item in &it.carry_over_items {
+                let number = item.number.map(|n| format!("#{} ", n)).unwrap_or_default();
+                println!("- {}{}", number, item.title);
+            }
+            println!();
+        }
+    }
+
+    println!("\n_{}_", report.note);
+    Ok(())
+}
+
+pub fn format_planned_changes(changes: &[PlannedChange]) -> anyhow::Result<()> {
+    println!("# Project Changes\n");
+    println!("| Rule | Item | Title | Change |");
+    println!("|------|------|-------|--------|");
+
+    for c in changes {
+        println!(
+            "| {} | {} | {} | {} |",
+            c.rule,
+            c.item,
+            c.title.replace('|', "\\|"),
+            c.action.describe()
+        );
+    }
+
+    println!("\n**Total:** {} changes", changes.len());
+    Ok(())
+}
+
+pub fn format_dependency_report(report: &DependencyReport) -> anyhow::Result<()> {
+    match &report.milestone {
+        Some(m) => println!("# Dependencies: {}\n", m),
+        None => println!("# Dependencies\n"),
+    }
+    println!("- **Issues:** {}", report.total_issues);
+    println!("- **Dependencies:** {}", report.total_relations);
+    println!("- **Open Blockers:** {}", report.open_blockers.len());
+    println!("- **Cycles:** {}", report.cycles.len());
+
+    if !report.open_blockers.is_empty() {
+        println!("\n## Open Blockers\n");
+        println!("| Issue | Title | Blocks |");
+        println!("|-------|-------|--------|");
+        for b in &report.open_blockers {
+            let blocks: Vec<String> = b.blocks.iter().map(|r| r.to_string()).collect();
+            println!(
+                "| {} | {} | {} |",
+                b.node.issue,
+                b.node.title.replace('|', "\\|"),
+                blocks.join(", ")
+            );
+        }
+    }
+
+    if !report.critical_path.is_empty() {
+        println!("\n## Critical Path\n");
+        for (i, node) in report.critical_path.iter().enumerate() {
+            println!("{}. {} {}", i + 1, node.issue, node.title);
+        }
+    }
+
+    if !report.cycles.is_empty() {
+        println!("\n## Cycles\n");
+        for cycle in &report.cycles {
+            let refs: Vec<String> = cycle.iter().map(|r| r.to_string()).collect();
+            println!("- {}", refs.join(" -> "));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn format_epic_report(report: &EpicReport) -> anyhow::Result<()> {
+    println!("# Epics: {}\n", report.repository);
+
+    if report.epics.is_empty() {
+        println!("No epics found.");
+        return Ok(());
+    }
+
+    println!("| Epic | Title | Progress | Nested | Depth | Stalled |");
+    println!("|------|-------|----------|--------|-------|---------|");
+    for epic in &report.epics {
+        println!(
+            "| {} | {} | {}/{} | {}/{} ({:.0}%) | {} | {} |",
+            epic.issue,
+            epic.title.replace('|', "\\|"),
+            epic.completed,
+            epic.total,
+            epic.nested_completed,
+            epic.nested_total,
+            epic.percent,
+            epic.depth,
+            epic.stalled_children().count()
+        );
+    }
+
+    if report.total_stalled > 0 {
+        println!(
+            "\n## Stalled Children (no activity in {} days)\n",
+            report.stale_days
+        );
+        for epic in &report.epics {
+            for child in epic.stalled_children() {
+                let issue = child
+                    .issue
+                    .as_ref()
+                    .map(|r| r.to_string())
+                    .unwrap_or_default();
+                println!(
+                    "- {} {} ({} days, in {})",
+                    issue,
+                    child.title,
+                    child.days_inactive.unwrap_or_default(),
+                    epic.issue
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
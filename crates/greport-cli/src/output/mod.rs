@@ -6,9 +6,14 @@ mod markdown_output;
 mod table_output;
 
 use crate::args::OutputFormat;
-use greport_core::metrics::{IssueMetrics, PullMetrics, SlaReport, VelocityMetrics};
-use greport_core::models::{Issue, Milestone, PullRequest, Release};
-use greport_core::reports::{BurndownReport, ReleaseNotes};
+use greport_core::metrics::{
+    IssueMetrics, ProjectMetrics, PullMetrics, SlaReport, VelocityMetrics,
+};
+use greport_core::models::{Issue, Milestone, Project, ProjectItem, PullRequest, Release};
+use greport_core::reports::{
+    BurndownReport, DependencyReport, EpicReport, IterationReport, ReleaseNotes,
+};
+use greport_core::rules::PlannedChange;
 
 /// Unified formatter for CLI output
 pub struct Formatter {
@@ -119,4 +124,84 @@ impl Formatter {
             _ => table_output::format_milestone_progress(milestone),
         }
     }
+
+    /// Format and print projects
+    pub fn format_projects(&self, projects: &[Project]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(projects),
+            OutputFormat::Csv => csv_output::format_projects(projects),
+            OutputFormat::Markdown => markdown_output::format_projects(projects),
+            OutputFormat::Table => table_output::format_projects(projects),
+        }
+    }
+
+    /// Format and print a single project with its fields
+    pub fn format_project(&self, project: &Project) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(project),
+            OutputFormat::Csv => csv_output::format_project(project),
+            OutputFormat::Markdown => markdown_output::format_project(project),
+            OutputFormat::Table => table_output::format_project(project),
+        }
+    }
+
+    /// Format and print project items
+    pub fn format_project_items(&self, items: &[ProjectItem]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(items),
+            OutputFormat::Csv => csv_output::format_project_items(items),
+            OutputFormat::Markdown => markdown_output::format_project_items(items),
+            OutputFormat::Table => table_output::format_project_items(items),
+        }
+    }
+
+    /// Format and print project metrics
+    pub fn format_project_metrics(&self, metrics: &ProjectMetrics) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(metrics),
+            OutputFormat::Csv => csv_output::format_project_metrics(metrics),
+            OutputFormat::Markdown => markdown_output::format_project_metrics(metrics),
+            OutputFormat::Table => table_output::format_project_metrics(metrics),
+        }
+    }
+
+    /// Format and print iteration (sprint) report
+    pub fn format_iteration_report(&self, report: &IterationReport) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(report),
+            OutputFormat::Csv => csv_output::format_iteration_report(report),
+            OutputFormat::Markdown => markdown_output::format_iteration_report(report),
+            OutputFormat::Table => table_output::format_iteration_report(report),
+        }
+    }
+
+    /// Format and print planned project changes
+    pub fn format_planned_changes(&self, changes: &[PlannedChange]) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(changes),
+            OutputFormat::Csv => csv_output::format_planned_changes(changes),
+            OutputFormat::Markdown => markdown_output::format_planned_changes(changes),
+            OutputFormat::Table => table_output::format_planned_changes(changes),
+        }
+    }
+
+    /// Format and print dependency report
+    pub fn format_dependency_report(&self, report: &DependencyReport) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(report),
+            OutputFormat::Csv => csv_output::format_dependency_report(report),
+            OutputFormat::Markdown => markdown_output::format_dependency_report(report),
+            OutputFormat::Table => table_output::format_dependency_report(report),
+        }
+    }
+
+    /// Format and print epic progress report
+    pub fn format_epic_report(&self, report: &EpicReport) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => json_output::format_json(report),
+            OutputFormat::Csv => csv_output::format_epic_report(report),
+            OutputFormat::Markdown => markdown_output::format_epic_report(report),
+            OutputFormat::Table => table_output::format_epic_report(report),
+        }
+    }
 }
@@ -2,9 +2,17 @@
 
 use colored::Colorize;
 use comfy_table::{Cell, Color, Table};
-use greport_core::metrics::{IssueMetrics, PullMetrics, SlaReport, VelocityMetrics};
-use greport_core::models::{Issue, IssueState, Milestone, PullRequest, PullState, Release};
-use greport_core::reports::BurndownReport;
+use greport_core::metrics::{
+    BotActivity, FieldCount, IssueMetrics, ProjectMetrics, PullMetrics, SlaReport, VelocityMetrics,
+};
+use greport_core::models::{
+    Issue, IssueState, Milestone, Project, ProjectFieldType, ProjectItem, PullRequest, PullState,
+    Release,
+};
+use greport_core::reports::{
+    BurndownReport, DependencyReport, EpicReport, IterationReport, IterationState,
+};
+use greport_core::rules::PlannedChange;
 
 pub fn format_issues(issues: &[Issue]) -> anyhow::Result<()> {
     let mut table = Table::new();
@@ -60,20 +68,14 @@ pub fn format_issue_metrics(metrics: &IssueMetrics) -> anyhow::Result<()> {
     println!(
         "Open:         {} ({}%)",
         metrics.open.to_string().green(),
-        if metrics.total > 0 {
-            metrics.open * 100 / metrics.total
-        } else {
-            0
-        }
+        (metrics.open * 100).checked_div(metrics.total).unwrap_or(0)
     );
     println!(
         "Closed:       {} ({}%)",
         metrics.closed.to_string().red(),
-        if metrics.total > 0 {
-            metrics.closed * 100 / metrics.total
-        } else {
-            0
-        }
+        (metrics.closed * 100)
+            .checked_div(metrics.total)
+            .unwrap_or(0)
     );
     println!("Stale:        {}", metrics.stale_count.to_string().yellow());
 
@@ -106,9 +108,22 @@ pub fn format_issue_metrics(metrics: &IssueMetrics) -> anyhow::Result<()> {
         println!("  {:>12}: {:>4} {}", bucket.label, bucket.count, bar);
     }
 
+    if let Some(bots) = &metrics.bot_activity {
+        format_bot_activity(bots);
+    }
+
     Ok(())
 }
 
+fn format_bot_activity(bots: &BotActivity) {
+    println!("\n{}", format!("Bot Activity ({}):", bots.total).bold());
+    let mut authors: Vec<_> = bots.by_author.iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (author, count) in authors {
+        println!("  {}: {}", author, count);
+    }
+}
+
 pub fn format_velocity(velocity: &VelocityMetrics) -> anyhow::Result<()> {
     println!("{}", "Velocity Report".bold());
     println!("{}", "=".repeat(60));
@@ -167,11 +182,9 @@ pub fn format_burndown(burndown: &BurndownReport) -> anyhow::Result<()> {
 
     println!("\n{}", "Progress:".bold());
     if let Some(last) = burndown.data_points.last() {
-        let completed_pct = if burndown.total_issues > 0 {
-            (last.completed * 100) / burndown.total_issues
-        } else {
-            0
-        };
+        let completed_pct = (last.completed * 100)
+            .checked_div(burndown.total_issues)
+            .unwrap_or(0);
 
         let bar_width = 40;
         let filled = (completed_pct * bar_width) / 100;
@@ -296,6 +309,10 @@ pub fn format_pull_metrics(metrics: &PullMetrics) -> anyhow::Result<()> {
         println!("  {}: {}", size, count);
     }
 
+    if let Some(bots) = &metrics.bot_activity {
+        format_bot_activity(bots);
+    }
+
     Ok(())
 }
 
@@ -366,3 +383,394 @@ pub fn format_milestone_progress(milestone: &Milestone) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+pub fn format_projects(projects: &[Project]) -> anyhow::Result<()> {
+    let mut table = Table::new();
+    table.set_header(vec!["#", "Title", "State", "Items", "Updated"]);
+
+    for p in projects {
+        let state_cell = if p.closed {
+            Cell::new("closed").fg(Color::Red)
+        } else {
+            Cell::new("open").fg(Color::Green)
+        };
+
+        table.add_row(vec![
+            Cell::new(p.number),
+            Cell::new(&p.title),
+            state_cell,
+            Cell::new(p.total_items),
+            Cell::new(p.updated_at.format("%Y-%m-%d").to_string()),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\nTotal: {} projects", projects.len());
+    Ok(())
+}
+
+pub fn format_project(project: &Project) -> anyhow::Result<()> {
+    println!(
+        "{}",
+        format!("Project #{}: {}", project.number, project.title).bold()
+    );
+    println!("{}", "=".repeat(50));
+
+    if let Some(desc) = &project.description {
+        println!("{}", desc);
+    }
+    println!("State: {}", if project.closed { "closed" } else { "open" });
+    println!("Items: {}", project.total_items);
+    println!("URL:   {}", project.url);
+
+    println!("\n{}", "Fields:".bold());
+    let mut table = Table::new();
+    table.set_header(vec!["Field", "Type", "Options"]);
+
+    for field in &project.fields {
+        let options = match &field.field_type {
+            ProjectFieldType::SingleSelect { options } => options
+                .iter()
+                .map(|o| o.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            ProjectFieldType::Iteration { iterations } => iterations
+                .iter()
+                .map(|i| i.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => String::new(),
+        };
+        table.add_row(vec![
+            Cell::new(&field.name),
+            Cell::new(field.field_type.label()),
+            Cell::new(options),
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+pub fn format_project_items(items: &[ProjectItem]) -> anyhow::Result<()> {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "#",
+        "Title",
+        "Type",
+        "Status",
+        "Iteration",
+        "Assignees",
+    ]);
+
+    for item in items {
+        let title = item.title();
+        let title = if title.len() > 45 {
+            format!("{}...", &title[..42])
+        } else {
+            title.to_string()
+        };
+
+        table.add_row(vec![
+            Cell::new(
+                item.number()
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::new(title),
+            Cell::new(item.content_type()),
+            Cell::new(item.status().unwrap_or_else(|| "-".to_string())),
+            Cell::new(item.iteration().unwrap_or("-")),
+            Cell::new(item.assignees().join(", ")),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\nTotal: {} items", items.len());
+    Ok(())
+}
+
+pub fn format_project_metrics(metrics: &ProjectMetrics) -> anyhow::Result<()> {
+    println!("{}", "Project Metrics".bold());
+    println!("{}", "=".repeat(40));
+
+    println!("Total items:    {}", metrics.total_items);
+    println!(
+        "Closed/Merged:  {}",
+        metrics.closed_items.to_string().magenta()
+    );
+
+    print_counts("By Status:", &metrics.by_status);
+    print_counts("By Type:", &metrics.by_content_type);
+    print_counts("By Assignee:", &metrics.by_assignee);
+    print_counts("By Iteration:", &metrics.by_iteration);
+
+    Ok(())
+}
+
+fn print_counts(title: &str, counts: &[FieldCount]) {
+    if counts.is_empty() {
+        return;
+    }
+    println!("\n{}", title.bold());
+    for c in counts.iter().take(15) {
+        println!("  {}: {}", c.value, c.count);
+    }
+}
+
+pub fn format_iteration_report(report: &IterationReport) -> anyhow::Result<()> {
+    println!(
+        "{}",
+        format!(
+            "Iterations: {} ({})",
+            report.project, report.iteration_field
+        )
+        .bold()
+    );
+    println!("{}", "=".repeat(60));
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Iteration",
+        "Dates",
+        "State",
+        "Committed",
+        "Completed",
+        "Carry-over",
+        "Completion",
+    ]);
+
+    for it in &report.iterations {
+        let state_cell = match it.state {
+            IterationState::Completed => Cell::new("completed"),
+            IterationState::Current => Cell::new("current").fg(Color::Yellow),
+            IterationState::Upcoming => Cell::new("upcoming").fg(Color::DarkGrey),
+        };
+        let carry_cell = if it.carry_over.items > 0 {
+            Cell::new(it.carry_over.value(report.unit)).fg(Color::Red)
+        } else {
+            Cell::new(it.carry_over.value(report.unit))
+        };
+
+        table.add_row(vec![
+            Cell::new(&it.title),
+            Cell::new(format!("{} - {}", it.start_date, it.end_date)),
+            state_cell,
+            Cell::new(it.committed.value(report.unit)),
+            Cell::new(it.completed.value(report.unit)),
+            carry_cell,
+            Cell::new(format!("{:.0}%", it.completion_ratio * 100.0)),
+        ]);
+    }
+
+    println!("{table}");
+
+    println!(
+        "\n{}",
+        format!("Velocity ({}):", report.unit.label()).bold()
+    );
+    let max = report
+        .iterations
+        .iter()
+        .map(|it| it.velocity)
+        .fold(0.0_f64, f64::max);
+    let bar_width = 40.0;
+    for it in &report.iterations {
+        let filled = if max > 0.0 {
+            ((it.velocity / max) * bar_width).round() as usize
+        } else {
+            0
+        };
+        println!(
+            "  {:<20} {} {}",
+            it.title,
+            "#".repeat(filled).green(),
+            it.velocity
+        );
+    }
+
+    println!(
+        "\nAverage velocity: {:.1} {}",
+        report.avg_velocity,
+        report.unit.label()
+    );
+    if report.unscheduled_items > 0 {
+        println!("Unscheduled items: {}", report.unscheduled_items);
+    }
+    println!("\nNote: {}", report.note);
+
+    for it in report
+        .iterations
+        .iter()
+        .filter(|it| !it.carry_over_items.is_empty())
+    {
+        println!(
+            "\n{} {} -> {}",
+            "Carry-over:".bold(),
+            it.title,
+            it.carried_to.as_deref().unwrap_or("(none)")
+        );
+        for item in &it.carry_over_items {
+            let number = item.number.map(|n| format!("#{} ", n)).unwrap_or_default();
+            println!("  {}{}", number, item.title);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn format_planned_changes(changes: &[PlannedChange]) -> anyhow::Result<()> {
+    if changes.is_empty() {
+        println!("No changes.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Rule", "Item", "Title", "Change"]);
+
+    for c in changes {
+        let title = if c.title.len() > 45 {
+            format!("{}...", &c.title[..42])
+        } else {
+            c.title.clone()
+        };
+
+        table.add_row(vec![
+            Cell::new(&c.rule),
+            Cell::new(&c.item),
+            Cell::new(title),
+            Cell::new(c.action.describe()).fg(Color::Cyan),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\nTotal: {} changes", changes.len());
+    Ok(())
+}
+
+pub fn format_dependency_report(report: &DependencyReport) -> anyhow::Result<()> {
+    let title = match &report.milestone {
+        Some(m) => format!("Dependencies: {}", m),
+        None => "Dependencies".to_string(),
+    };
+    println!("{}", title.bold());
+    println!("{}", "=".repeat(60));
+    println!("Issues:        {}", report.total_issues);
+    println!("Dependencies:  {}", report.total_relations);
+
+    if report.open_blockers.is_empty() {
+        println!("\n{}", "No open blockers.".green());
+    } else {
+        println!("\n{}", "Open blockers:".bold());
+        let mut table = Table::new();
+        table.set_header(vec!["Issue", "Title", "Blocks"]);
+        for b in &report.open_blockers {
+            let title = if b.node.title.len() > 45 {
+                format!("{}...", &b.node.title[..42])
+            } else {
+                b.node.title.clone()
+            };
+            let blocks: Vec<String> = b.blocks.iter().map(|r| r.to_string()).collect();
+            table.add_row(vec![
+                Cell::new(b.node.issue.to_string()).fg(Color::Red),
+                Cell::new(title),
+                Cell::new(blocks.join(", ")),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    if !report.critical_path.is_empty() {
+        println!(
+            "\n{} ({} issues)",
+            "Critical path:".bold(),
+            report.critical_path.len()
+        );
+        for (i, node) in report.critical_path.iter().enumerate() {
+            println!("  {}. {} {}", i + 1, node.issue, node.title);
+        }
+    }
+
+    if !report.cycles.is_empty() {
+        println!("\n{}", "Cycles:".red().bold());
+        for cycle in &report.cycles {
+            let refs: Vec<String> = cycle.iter().map(|r| r.to_string()).collect();
+            println!("  {}", refs.join(" -> "));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn format_epic_report(report: &EpicReport) -> anyhow::Result<()> {
+    println!("{}", format!("Epics: {}", report.repository).bold());
+    println!("{}", "=".repeat(60));
+
+    if report.epics.is_empty() {
+        println!("No epics found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Epic", "Title", "Progress", "Nested", "%", "Depth", "Stalled",
+    ]);
+
+    for epic in &report.epics {
+        let title = if epic.title.len() > 45 {
+            format!("{}...", &epic.title[..42])
+        } else {
+            epic.title.clone()
+        };
+        let percent_color = if epic.percent >= 80.0 {
+            Color::Green
+        } else if epic.percent >= 40.0 {
+            Color::Yellow
+        } else {
+            Color::Red
+        };
+        let stalled = epic.stalled_children().count();
+
+        table.add_row(vec![
+            Cell::new(format!("#{}", epic.issue.number)),
+            Cell::new(title),
+            Cell::new(format!("{}/{}", epic.completed, epic.total)),
+            Cell::new(format!("{}/{}", epic.nested_completed, epic.nested_total)),
+            Cell::new(format!("{:.0}%", epic.percent)).fg(percent_color),
+            Cell::new(epic.depth),
+            Cell::new(stalled).fg(if stalled > 0 {
+                Color::Red
+            } else {
+                Color::Reset
+            }),
+        ]);
+    }
+
+    println!("{table}");
+
+    if report.total_stalled > 0 {
+        println!(
+            "\n{} (no activity in {} days)",
+            "Stalled children:".red().bold(),
+            report.stale_days
+        );
+        for epic in &report.epics {
+            for child in epic.stalled_children() {
+                let issue = child
+                    .issue
+                    .as_ref()
+                    .map(|r| r.to_string())
+                    .unwrap_or_default();
+                println!(
+                    "  {} {} ({} days, in #{})",
+                    issue,
+                    child.title,
+                    child.days_inactive.unwrap_or_default(),
+                    epic.issue.number
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,620 @@
+//! Typed methods for the `/api/v1` routes
+
+use crate::client::GreportClient;
+use crate::error::Result;
+use crate::pages::Pages;
+use crate::types::*;
+use greport_core::metrics::{IssueMetrics, PullMetrics, VelocityMetrics};
+use greport_core::models::{CalendarData, Issue, Milestone, PullRequest, Release, ReleasePlan};
+use greport_core::reports::{BurndownReport, EpicReport, IterationReport, ReleaseNotes};
+use reqwest::{Method, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Query for routes without parameters
+const NO_QUERY: &[(&str, &str)] = &[];
+
+impl GreportClient {
+    /// GET a route wrapped in `ApiResponse` and unwrap `data`
+    async fn get_data<T: DeserializeOwned>(
+        &self,
+        url: Url,
+        query: &(impl Serialize + ?Sized),
+    ) -> Result<T> {
+        let response: ApiResponse<T> = self
+            .send(self.request(Method::GET, url).query(query))
+            .await?;
+        Ok(response.data)
+    }
+
+    /// Send a body-less request wrapped in `ApiResponse` and unwrap `data`
+    async fn call<T: DeserializeOwned>(&self, method: Method, url: Url) -> Result<T> {
+        let response: ApiResponse<T> = self.send(self.request(method, url)).await?;
+        Ok(response.data)
+    }
+
+    fn repo_endpoint(&self, owner: &str, repo: &str, rest: &[&str]) -> Url {
+        let mut segments = vec!["repos", owner, repo];
+        segments.extend_from_slice(rest);
+        self.endpoint(&segments)
+    }
+
+    fn project_endpoint(&self, org: &str, number: u64, rest: &[&str]) -> Url {
+        let number = number.to_string();
+        let mut segments = vec!["orgs", org, "projects", &number];
+        segments.extend_from_slice(rest);
+        self.endpoint(&segments)
+    }
+
+    // =========================================================================
+    // Service
+    // =========================================================================
+
+    /// GET /health
+    pub async fn health(&self) -> Result<health::HealthStatus> {
+        self.send(self.request(Method::GET, self.url(&["health"])))
+            .await
+    }
+
+    /// GET /api/v1/openapi.json
+    pub async fn openapi(&self) -> Result<serde_json::Value> {
+        self.send(self.request(Method::GET, self.endpoint(&["openapi.json"])))
+            .await
+    }
+
+    /// POST /api/graphql - run a GraphQL query and return the whole response
+    ///
+    /// The response is returned as-is, so GraphQL `errors` are left for the
+    /// caller to inspect alongside `data`.
+    pub async fn graphql(
+        &self,
+        query: &str,
+        variables: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let mut body = serde_json::json!({ "query": query });
+        if let Some(variables) = variables {
+            body["variables"] = variables;
+        }
+        self.send(
+            self.request(Method::POST, self.url(&["api", "graphql"]))
+                .json(&body),
+        )
+        .await
+    }
+
+    /// GET /api/v1/auth/me - the authenticated caller
+    pub async fn me(&self) -> Result<login::CurrentUser> {
+        self.get_data(self.endpoint(&["auth", "me"]), NO_QUERY)
+            .await
+    }
+
+    // =========================================================================
+    // Issues
+    // =========================================================================
+
+    /// GET /api/v1/repos/{owner}/{repo}/issues
+    pub fn issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &issues::ListIssuesQuery,
+    ) -> Pages<'_, Issue> {
+        Pages::new(self, self.repo_endpoint(owner, repo, &["issues"]), query)
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/issues/metrics
+    pub async fn issue_metrics(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &issues::MetricsQuery,
+    ) -> Result<IssueMetrics> {
+        self.get_data(
+            self.repo_endpoint(owner, repo, &["issues", "metrics"]),
+            query,
+        )
+        .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/issues/velocity
+    pub async fn issue_velocity(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &issues::VelocityQuery,
+    ) -> Result<VelocityMetrics> {
+        self.get_data(
+            self.repo_endpoint(owner, repo, &["issues", "velocity"]),
+            query,
+        )
+        .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/issues/burndown
+    pub async fn burndown(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &issues::BurndownQuery,
+    ) -> Result<BurndownReport> {
+        self.get_data(
+            self.repo_endpoint(owner, repo, &["issues", "burndown"]),
+            query,
+        )
+        .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/issues/stale
+    pub async fn stale_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &issues::StaleQuery,
+    ) -> Result<Vec<Issue>> {
+        self.get_data(self.repo_endpoint(owner, repo, &["issues", "stale"]), query)
+            .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/issues/epics
+    pub async fn epics(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &issues::EpicsQuery,
+    ) -> Result<EpicReport> {
+        self.get_data(self.repo_endpoint(owner, repo, &["issues", "epics"]), query)
+            .await
+    }
+
+    // =========================================================================
+    // Pull requests
+    // =========================================================================
+
+    /// GET /api/v1/repos/{owner}/{repo}/pulls
+    pub fn pulls(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &pulls::ListPullsQuery,
+    ) -> Pages<'_, PullRequest> {
+        Pages::new(self, self.repo_endpoint(owner, repo, &["pulls"]), query)
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/pulls/metrics
+    pub async fn pull_metrics(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &pulls::MetricsQuery,
+    ) -> Result<PullMetrics> {
+        self.get_data(
+            self.repo_endpoint(owner, repo, &["pulls", "metrics"]),
+            query,
+        )
+        .await
+    }
+
+    // =========================================================================
+    // Releases
+    // =========================================================================
+
+    /// GET /api/v1/repos/{owner}/{repo}/releases
+    pub fn releases(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &releases::ListReleasesQuery,
+    ) -> Pages<'_, Release> {
+        Pages::new(self, self.repo_endpoint(owner, repo, &["releases"]), query)
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/releases/notes
+    pub async fn release_notes(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &releases::ReleaseNotesQuery,
+    ) -> Result<ReleaseNotes> {
+        self.get_data(
+            self.repo_endpoint(owner, repo, &["releases", "notes"]),
+            query,
+        )
+        .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/milestones/{milestone}/progress
+    pub async fn milestone_progress(
+        &self,
+        owner: &str,
+        repo: &str,
+        milestone: &str,
+    ) -> Result<Milestone> {
+        self.get_data(
+            self.repo_endpoint(owner, repo, &["milestones", milestone, "progress"]),
+            NO_QUERY,
+        )
+        .await
+    }
+
+    // =========================================================================
+    // Repository reports
+    // =========================================================================
+
+    /// GET /api/v1/repos/{owner}/{repo}/contributors
+    pub async fn contributors(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &contrib::ContributorsQuery,
+    ) -> Result<Vec<contrib::ContributorStats>> {
+        self.get_data(self.repo_endpoint(owner, repo, &["contributors"]), query)
+            .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/calendar
+    pub async fn calendar(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &calendar::CalendarQuery,
+    ) -> Result<CalendarData> {
+        self.get_data(self.repo_endpoint(owner, repo, &["calendar"]), query)
+            .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/release-plan
+    pub async fn release_plan(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &release_plan::ReleasePlanQuery,
+    ) -> Result<ReleasePlan> {
+        self.get_data(self.repo_endpoint(owner, repo, &["release-plan"]), query)
+            .await
+    }
+
+    /// GET /api/v1/repos/{owner}/{repo}/sla
+    pub async fn sla_report(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &sla::SlaQuery,
+    ) -> Result<sla::SlaReport> {
+        self.get_data(self.repo_endpoint(owner, repo, &["sla"]), query)
+            .await
+    }
+
+    // =========================================================================
+    // Projects
+    // =========================================================================
+
+    /// GET /api/v1/orgs/{org}/projects
+    pub async fn projects(
+        &self,
+        org: &str,
+        query: &projects::ListProjectsQuery,
+    ) -> Result<Vec<projects::ProjectSummary>> {
+        self.get_data(self.endpoint(&["orgs", org, "projects"]), query)
+            .await
+    }
+
+    /// GET /api/v1/orgs/{org}/projects/{number}
+    pub async fn project(&self, org: &str, number: u64) -> Result<projects::ProjectDetail> {
+        self.get_data(self.project_endpoint(org, number, &[]), NO_QUERY)
+            .await
+    }
+
+    /// GET /api/v1/orgs/{org}/projects/{number}/items
+    pub fn project_items(
+        &self,
+        org: &str,
+        number: u64,
+        query: &projects::ListItemsQuery,
+    ) -> Pages<'_, projects::ProjectItemResponse> {
+        Pages::new(self, self.project_endpoint(org, number, &["items"]), query)
+    }
+
+    /// GET /api/v1/orgs/{org}/projects/{number}/metrics
+    pub async fn project_metrics(
+        &self,
+        org: &str,
+        number: u64,
+        query: &projects::ProjectMetricsQuery,
+    ) -> Result<projects::ProjectMetricsResponse> {
+        self.get_data(self.project_endpoint(org, number, &["metrics"]), query)
+            .await
+    }
+
+    /// GET /api/v1/orgs/{org}/projects/{number}/iterations
+    pub async fn project_iterations(
+        &self,
+        org: &str,
+        number: u64,
+        query: &projects::IterationsQuery,
+    ) -> Result<IterationReport> {
+        self.get_data(self.project_endpoint(org, number, &["iterations"]), query)
+            .await
+    }
+
+    // =========================================================================
+    // Repositories and organizations
+    // =========================================================================
+
+    /// GET /api/v1/repos - tracked repositories
+    pub async fn repos(&self) -> Result<Vec<repos::RepoSummary>> {
+        self.get_data(self.endpoint(&["repos"]), NO_QUERY).await
+    }
+
+    /// GET /api/v1/orgs
+    pub async fn orgs(&self) -> Result<orgs::OrgsListResponse> {
+        self.get_data(self.endpoint(&["orgs"]), NO_QUERY).await
+    }
+
+    /// GET /api/v1/orgs/{org}/repos
+    pub async fn org_repos(&self, org: &str) -> Result<Vec<orgs::OrgRepoEntry>> {
+        self.get_data(self.endpoint(&["orgs", org, "repos"]), NO_QUERY)
+            .await
+    }
+
+    // =========================================================================
+    // Aggregates across tracked repositories
+    // =========================================================================
+
+    /// GET /api/v1/aggregate/issues
+    pub fn aggregate_issues(
+        &self,
+        query: &aggregate::AggregateListQuery,
+    ) -> Pages<'_, aggregate::AggregateIssueItem> {
+        Pages::new(self, self.endpoint(&["aggregate", "issues"]), query)
+    }
+
+    /// GET /api/v1/aggregate/pulls
+    pub fn aggregate_pulls(
+        &self,
+        query: &aggregate::AggregateListQuery,
+    ) -> Pages<'_, aggregate::AggregatePullItem> {
+        Pages::new(self, self.endpoint(&["aggregate", "pulls"]), query)
+    }
+
+    /// GET /api/v1/aggregate/issues/metrics
+    pub async fn aggregate_issue_metrics(
+        &self,
+        query: &aggregate::IssueMetricsQuery,
+    ) -> Result<aggregate::AggregateIssueMetrics> {
+        self.get_data(self.endpoint(&["aggregate", "issues", "metrics"]), query)
+            .await
+    }
+
+    /// GET /api/v1/aggregate/pulls/metrics
+    pub async fn aggregate_pull_metrics(
+        &self,
+        query: &aggregate::PullMetricsQuery,
+    ) -> Result<aggregate::AggregatePullMetrics> {
+        self.get_data(self.endpoint(&["aggregate", "pulls", "metrics"]), query)
+            .await
+    }
+
+    /// GET /api/v1/aggregate/contributors
+    pub async fn aggregate_contributors(
+        &self,
+    ) -> Result<Vec<aggregate::AggregateContributorStats>> {
+        self.get_data(self.endpoint(&["aggregate", "contributors"]), NO_QUERY)
+            .await
+    }
+
+    /// GET /api/v1/aggregate/velocity
+    pub async fn aggregate_velocity(
+        &self,
+        query: &aggregate::VelocityQuery,
+    ) -> Result<aggregate::AggregateVelocityMetrics> {
+        self.get_data(self.endpoint(&["aggregate", "velocity"]), query)
+            .await
+    }
+
+    /// GET /api/v1/aggregate/projects
+    pub async fn aggregate_projects(
+        &self,
+        query: &projects::ListProjectsQuery,
+    ) -> Result<Vec<projects::ProjectSummary>> {
+        self.get_data(self.endpoint(&["aggregate", "projects"]), query)
+            .await
+    }
+
+    /// GET /api/v1/aggregate/orgs/issues
+    pub fn aggregate_org_issues(
+        &self,
+        query: &aggregate::AggregateListQuery,
+    ) -> Pages<'_, aggregate::OrgAggregateIssueItem> {
+        Pages::new(self, self.endpoint(&["aggregate", "orgs", "issues"]), query)
+    }
+
+    /// GET /api/v1/aggregate/orgs/pulls
+    pub fn aggregate_org_pulls(
+        &self,
+        query: &aggregate::AggregateListQuery,
+    ) -> Pages<'_, aggregate::OrgAggregatePullItem> {
+        Pages::new(self, self.endpoint(&["aggregate", "orgs", "pulls"]), query)
+    }
+
+    /// GET /api/v1/aggregate/calendar
+    pub async fn aggregate_calendar(
+        &self,
+        query: &calendar::CalendarQuery,
+    ) -> Result<CalendarData> {
+        self.get_data(self.endpoint(&["aggregate", "calendar"]), query)
+            .await
+    }
+
+    /// GET /api/v1/aggregate/release-plan
+    pub async fn aggregate_release_plan(
+        &self,
+        query: &release_plan::ReleasePlanQuery,
+    ) -> Result<ReleasePlan> {
+        self.get_data(self.endpoint(&["aggregate", "release-plan"]), query)
+            .await
+    }
+
+    // =========================================================================
+    // Sync (sync scope)
+    // =========================================================================
+
+    /// POST /api/v1/repos/{owner}/{repo}/sync
+    pub async fn sync_repo(&self, owner: &str, repo: &str) -> Result<SyncResult> {
+        self.call(Method::POST, self.repo_endpoint(owner, repo, &["sync"]))
+            .await
+    }
+
+    /// POST /api/v1/repos - start tracking a repository
+    pub async fn add_repo(&self, request: &repos::AddRepoRequest) -> Result<repos::RepoSummary> {
+        let response: ApiResponse<repos::RepoSummary> = self
+            .send(
+                self.request(Method::POST, self.endpoint(&["repos"]))
+                    .json(request),
+            )
+            .await?;
+        Ok(response.data)
+    }
+
+    /// DELETE /api/v1/repos/{owner}/{repo} - stop tracking a repository
+    pub async fn remove_repo(&self, owner: &str, repo: &str) -> Result<()> {
+        self.call(Method::DELETE, self.repo_endpoint(owner, repo, &[]))
+            .await
+    }
+
+    /// POST /api/v1/sync - sync every tracked repository
+    pub async fn batch_sync(&self) -> Result<batch::BatchSyncResult> {
+        self.call(Method::POST, self.endpoint(&["sync"])).await
+    }
+
+    // =========================================================================
+    // API keys (admin scope)
+    // =========================================================================
+
+    /// GET /api/v1/admin/api-keys
+    pub async fn api_keys(
+        &self,
+        query: &admin::ListApiKeysQuery,
+    ) -> Result<Vec<admin::ApiKeySummary>> {
+        self.get_data(self.endpoint(&["admin", "api-keys"]), query)
+            .await
+    }
+
+    /// POST /api/v1/admin/api-keys
+    pub async fn create_api_key(
+        &self,
+        request: &admin::CreateApiKeyRequest,
+    ) -> Result<admin::CreatedApiKey> {
+        let response: ApiResponse<admin::CreatedApiKey> = self
+            .send(
+                self.request(Method::POST, self.endpoint(&["admin", "api-keys"]))
+                    .json(request),
+            )
+            .await?;
+        Ok(response.data)
+    }
+
+    /// DELETE /api/v1/admin/api-keys/{id}
+    pub async fn revoke_api_key(&self, id: Uuid) -> Result<admin::ApiKeySummary> {
+        let id = id.to_string();
+        self.call(Method::DELETE, self.endpoint(&["admin", "api-keys", &id]))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use greport_api::openapi::ApiDoc;
+    use std::collections::BTreeSet;
+    use utoipa::OpenApi;
+
+    /// Operations with a client method, as `(method, path)`
+    const COVERED: &[(&str, &str)] = &[
+        ("get", "/health"),
+        ("get", "/api/v1/openapi.json"),
+        ("get", "/api/v1/auth/me"),
+        ("post", "/api/graphql"),
+        ("get", "/api/v1/repos/{owner}/{repo}/issues"),
+        ("get", "/api/v1/repos/{owner}/{repo}/issues/metrics"),
+        ("get", "/api/v1/repos/{owner}/{repo}/issues/velocity"),
+        ("get", "/api/v1/repos/{owner}/{repo}/issues/burndown"),
+        ("get", "/api/v1/repos/{owner}/{repo}/issues/stale"),
+        ("get", "/api/v1/repos/{owner}/{repo}/issues/epics"),
+        ("get", "/api/v1/repos/{owner}/{repo}/pulls"),
+        ("get", "/api/v1/repos/{owner}/{repo}/pulls/metrics"),
+        ("get", "/api/v1/repos/{owner}/{repo}/releases"),
+        ("get", "/api/v1/repos/{owner}/{repo}/releases/notes"),
+        (
+            "get",
+            "/api/v1/repos/{owner}/{repo}/milestones/{milestone}/progress",
+        ),
+        ("get", "/api/v1/repos/{owner}/{repo}/contributors"),
+        ("get", "/api/v1/repos/{owner}/{repo}/calendar"),
+        ("get", "/api/v1/repos/{owner}/{repo}/release-plan"),
+        ("get", "/api/v1/repos/{owner}/{repo}/sla"),
+        ("get", "/api/v1/orgs/{org}/projects"),
+        ("get", "/api/v1/orgs/{org}/projects/{number}"),
+        ("get", "/api/v1/orgs/{org}/projects/{number}/items"),
+        ("get", "/api/v1/orgs/{org}/projects/{number}/metrics"),
+        ("get", "/api/v1/orgs/{org}/projects/{number}/iterations"),
+        ("get", "/api/v1/repos"),
+        ("get", "/api/v1/orgs"),
+        ("get", "/api/v1/orgs/{org}/repos"),
+        ("get", "/api/v1/aggregate/issues"),
+        ("get", "/api/v1/aggregate/pulls"),
+        ("get", "/api/v1/aggregate/issues/metrics"),
+        ("get", "/api/v1/aggregate/pulls/metrics"),
+        ("get", "/api/v1/aggregate/contributors"),
+        ("get", "/api/v1/aggregate/velocity"),
+        ("get", "/api/v1/aggregate/projects"),
+        ("get", "/api/v1/aggregate/orgs/issues"),
+        ("get", "/api/v1/aggregate/orgs/pulls"),
+        ("get", "/api/v1/aggregate/calendar"),
+        ("get", "/api/v1/aggregate/release-plan"),
+        ("post", "/api/v1/repos/{owner}/{repo}/sync"),
+        ("post", "/api/v1/repos"),
+        ("delete", "/api/v1/repos/{owner}/{repo}"),
+        ("post", "/api/v1/sync"),
+        ("get", "/api/v1/admin/api-keys"),
+        ("post", "/api/v1/admin/api-keys"),
+        ("delete", "/api/v1/admin/api-keys/{id}"),
+    ];
+
+    /// Browser-facing operations the client leaves out
+    const BROWSER_ONLY: &[(&str, &str)] = &[
+        ("get", "/api/v1/docs"),
+        ("get", "/api/graphql"),
+        ("get", "/api/v1/auth/login"),
+        ("get", "/api/v1/auth/callback"),
+        ("post", "/api/v1/auth/logout"),
+    ];
+
+    #[test]
+    fn test_every_route_has_a_method() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut documented = BTreeSet::new();
+        for (path, item) in doc["paths"].as_object().unwrap() {
+            for method in item.as_object().unwrap().keys() {
+                documented.insert((method.clone(), path.clone()));
+            }
+        }
+        let known: BTreeSet<(String, String)> = COVERED
+            .iter()
+            .chain(BROWSER_ONLY)
+            .map(|(m, p)| (m.to_string(), p.to_string()))
+            .collect();
+
+        let missing: Vec<_> = documented.difference(&known).collect();
+        assert!(
+            missing.is_empty(),
+            "routes without a client method: {:?}",
+            missing
+        );
+        let stale: Vec<_> = known.difference(&documented).collect();
+        assert!(
+            stale.is_empty(),
+            "client entries with no route: {:?}",
+            stale
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! HTTP transport: URLs, authentication and retries
+
+use crate::error::{Error, Result};
+use crate::github::Listings;
+use crate::types::ErrorResponse;
+use greport_core::client::RetryConfig;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+/// Client for a greport API server
+#[derive(Clone)]
+pub struct GreportClient {
+    http: reqwest::Client,
+    base_url: Url,
+    api_key: Option<String>,
+    retry: RetryConfig,
+    pub(crate) listings: Listings,
+}
+
+impl std::fmt::Debug for GreportClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GreportClient")
+            .field("base_url", &self.base_url.as_str())
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl GreportClient {
+    /// Create a client for the server at `server_url` (e.g.
+    /// `http://localhost:9423`); routes are resolved under `/api/v1`
+    pub fn new(server_url: &str) -> Result<Self> {
+        let mut base_url =
+            Url::parse(server_url).map_err(|e| Error::InvalidUrl(format!("{server_url}: {e}")))?;
+        if base_url.cannot_be_a_base() {
+            return Err(Error::InvalidUrl(server_url.to_string()));
+        }
+        // Tolerate a URL that already points at the API root
+        let path = base_url.path().trim_end_matches('/');
+        let path = path.strip_suffix("/api/v1").unwrap_or(path).to_string();
+        base_url.set_path(&path);
+
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("greport-client/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        Ok(Self {
+            http,
+            base_url,
+            api_key: None,
+            retry: RetryConfig::default(),
+            listings: Listings::default(),
+        })
+    }
+
+    /// Authenticate requests with an API key (or any bearer token the server
+    /// accepts)
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Set custom retry configuration
+    pub fn with_retry_config(mut self, config: RetryConfig) -> Self {
+        self.retry = config;
+        self
+    }
+
+    /// Server URL the client was created with
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// URL of `/api/v1/<segments...>`, percent-encoding each segment
+    pub(crate) fn endpoint(&self, segments: &[&str]) -> Url {
+        self.url(["api", "v1"].iter().chain(segments))
+    }
+
+    /// URL of `/<segments...>` relative to the server root
+    pub(crate) fn url<'a>(&self, segments: impl IntoIterator<Item = &'a &'a str>) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("base URL checked in new()")
+            .pop_if_empty()
+            .extend(segments);
+        url
+    }
+
+    /// Start a request with authentication applied
+    pub(crate) fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let request = self.http.request(method, url);
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// Send a request, retrying transient failures, and decode the body
+    pub(crate) async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let request = request.build()?;
+        // A POST that reached the server may have run; only retry it when
+        // the server refused it outright
+        let idempotent = request.method() != Method::POST;
+        let mut backoff = self.retry.initial_backoff;
+        let mut attempt = 0;
+
+        loop {
+            let attempt_request = request
+                .try_clone()
+                .expect("request bodies are buffered JSON");
+            let outcome = self.http.execute(attempt_request).await;
+
+            let wait = match &outcome {
+                Ok(response) if is_retryable_status(response.status(), idempotent) => {
+                    Some(retry_after(response.headers()).unwrap_or(backoff))
+                }
+                Err(e) if e.is_connect() || (idempotent && e.is_timeout()) => Some(backoff),
+                _ => None,
+            };
+
+            match wait {
+                Some(wait) if attempt < self.retry.max_retries => {
+                    attempt += 1;
+                    let wait = wait.min(self.retry.max_backoff);
+                    warn!(
+                        method = %request.method(),
+                        url = %request.url(),
+                        attempt,
+                        max_retries = self.retry.max_retries,
+                        wait_ms = wait.as_millis() as u64,
+                        "Retrying greport server request"
+                    );
+                    sleep(wait).await;
+                    backoff = Duration::from_secs_f64(
+                        (backoff.as_secs_f64() * self.retry.multiplier)
+                            .min(self.retry.max_backoff.as_secs_f64()),
+                    );
+                }
+                _ => {
+                    debug!(method = %request.method(), url = %request.url(), "greport server request");
+                    return decode(outcome?).await;
+                }
+            }
+        }
+    }
+}
+
+/// Whether a response status is worth retrying
+fn is_retryable_status(status: StatusCode, idempotent: bool) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => true,
+        StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT | StatusCode::REQUEST_TIMEOUT => {
+            idempotent
+        }
+        s => idempotent && s.is_server_error(),
+    }
+}
+
+/// Delay requested by a `Retry-After: <seconds>` header
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Decode a success body, or turn an error response into [`Error::Api`]
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response.json::<T>().await?);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let (code, message) = match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(err) => (err.error.code, err.error.message),
+        Err(_) => (
+            status
+                .canonical_reason()
+                .unwrap_or("UNKNOWN")
+                .to_uppercase()
+                .replace(' ', "_"),
+            if body.is_empty() {
+                status.to_string()
+            } else {
+                body
+            },
+        ),
+    };
+    Err(Error::Api {
+        status: status.as_u16(),
+        code,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_encodes_segments() {
+        let client = GreportClient::new("http://localhost:9423").unwrap();
+        assert_eq!(
+            client
+                .endpoint(&["repos", "o", "r", "milestones", "v1.0 beta", "progress"])
+                .as_str(),
+            "http://localhost:9423/api/v1/repos/o/r/milestones/v1.0%20beta/progress"
+        );
+        assert_eq!(
+            client.url(&["health"]).as_str(),
+            "http://localhost:9423/health"
+        );
+    }
+
+    #[test]
+    fn test_base_url_variants() {
+        for url in [
+            "http://host:9423/",
+            "http://host:9423/api/v1",
+            "http://host:9423/api/v1/",
+        ] {
+            let client = GreportClient::new(url).unwrap();
+            assert_eq!(
+                client.endpoint(&["repos"]).as_str(),
+                "http://host:9423/api/v1/repos",
+                "{}",
+                url
+            );
+        }
+
+        let client = GreportClient::new("https://example.com/greport").unwrap();
+        assert_eq!(
+            client.endpoint(&["orgs"]).as_str(),
+            "https://example.com/greport/api/v1/orgs"
+        );
+
+        assert!(GreportClient::new("not a url").is_err());
+        assert!(GreportClient::new("mailto:someone@example.com").is_err());
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS, false));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE, false));
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR, true));
+        assert!(!is_retryable_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            false
+        ));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND, true));
+    }
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let client = GreportClient::new("http://localhost:9423")
+            .unwrap()
+            .with_api_key("grk_secret");
+        assert!(!format!("{:?}", client).contains("grk_secret"));
+    }
+}
@@ -0,0 +1,63 @@
+//! Error types for greport-client
+
+use thiserror::Error;
+
+/// Result type alias using the client Error
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors returned by [`GreportClient`](crate::GreportClient)
+#[derive(Error, Debug)]
+pub enum Error {
+    /// The server answered with an error response
+    #[error("greport server returned {status} ({code}): {message}")]
+    Api {
+        /// HTTP status code
+        status: u16,
+        /// Machine-readable error code (e.g. `NOT_FOUND`)
+        code: String,
+        /// Error message
+        message: String,
+    },
+
+    /// The request failed before a response arrived, or the response body
+    /// could not be decoded
+    #[error("Request to greport server failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// Invalid server URL
+    #[error("Invalid greport server URL: {0}")]
+    InvalidUrl(String),
+}
+
+impl Error {
+    /// HTTP status of an error response
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Api { status, .. } => Some(*status),
+            Error::Http(e) => e.status().map(|s| s.as_u16()),
+            Error::InvalidUrl(_) => None,
+        }
+    }
+}
+
+impl From<Error> for greport_core::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Api {
+                status: 404,
+                message,
+                ..
+            } => greport_core::Error::NotFound(message),
+            Error::Api { status: 429, .. } => greport_core::Error::RateLimitExceeded {
+                reset_at: "a later time (greport server rate limit)".to_string(),
+            },
+            Error::Http(e) if e.is_connect() || e.is_timeout() => {
+                greport_core::Error::Network(format!("greport server unreachable: {}", e))
+            }
+            Error::InvalidUrl(url) => {
+                greport_core::Error::Config(format!("Invalid greport server URL: {}", url))
+            }
+            other => greport_core::Error::Custom(other.to_string()),
+        }
+    }
+}
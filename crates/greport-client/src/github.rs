@@ -0,0 +1,324 @@
+//! `GitHubClient` backed by a greport server
+//!
+//! Lets code written against the core client traits (the CLI commands in
+//! particular) read issues, pull requests and releases from a central greport
+//! server. Data the server does not expose (issue events, sub-issues, reviews,
+//! users, rate limits) and every Projects call return an error; callers that
+//! treat those as optional keep working.
+//!
+//! The server has no single-item routes, so `get_issue`, `get_pull` and
+//! `get_release` page through the repository's whole listing. The first
+//! lookup in a repository fetches it and later lookups reuse it for the life
+//! of the client (clones share it); create a new client to see newer data.
+
+use crate::client::GreportClient;
+use crate::types::{issues::ListIssuesQuery, pulls::ListPullsQuery, releases::ListReleasesQuery};
+use async_trait::async_trait;
+use greport_core::client::{
+    GitHubClient, IssueParams, IssueStateFilter, ProjectClient, PullParams, PullStateFilter,
+    RateLimitInfo, RepoId,
+};
+use greport_core::models::{
+    FieldUpdate, Issue, IssueEvent, IssueRelation, Milestone, Project, ProjectItem, PullRequest,
+    Release, Repository, Review, User,
+};
+use greport_core::{Error, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Page size for list calls; the server caps what it returns per page
+const PER_PAGE: u32 = 100;
+
+/// Error for data a greport server does not serve
+fn unsupported(what: &str) -> Error {
+    Error::custom(format!(
+        "{} is not available when reading from a greport server",
+        what
+    ))
+}
+
+/// Listings of one kind, keyed by `owner/repo`
+type Listing<T> = Arc<Mutex<HashMap<String, Arc<Vec<T>>>>>;
+
+/// Whole-repository listings kept for single-item lookups
+#[derive(Clone, Default)]
+pub(crate) struct Listings {
+    issues: Listing<Issue>,
+    pulls: Listing<PullRequest>,
+    releases: Listing<Release>,
+}
+
+/// The listing of `repo`, fetched on first use
+async fn cached<T>(
+    listing: &Listing<T>,
+    repo: &RepoId,
+    fetch: impl Future<Output = Result<Vec<T>>>,
+) -> Result<Arc<Vec<T>>> {
+    let key = repo.full_name();
+    let lock = || listing.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(items) = lock().get(&key) {
+        return Ok(items.clone());
+    }
+    let items = Arc::new(fetch.await?);
+    lock().insert(key, items.clone());
+    Ok(items)
+}
+
+fn issue_state(state: IssueStateFilter) -> &'static str {
+    match state {
+        IssueStateFilter::Open => "open",
+        IssueStateFilter::Closed => "closed",
+        IssueStateFilter::All => "all",
+    }
+}
+
+fn pull_state(state: PullStateFilter) -> &'static str {
+    match state {
+        PullStateFilter::Open => "open",
+        PullStateFilter::Closed => "closed",
+        PullStateFilter::All => "all",
+    }
+}
+
+/// Whether a milestone filter (title or number, as GitHub accepts) matches
+fn milestone_matches(milestone: Option<&Milestone>, filter: &str) -> bool {
+    match (milestone, filter) {
+        (None, "none") => true,
+        (Some(_), "*") => true,
+        (Some(m), filter) => m.title.eq_ignore_ascii_case(filter) || m.number.to_string() == filter,
+        (None, _) => false,
+    }
+}
+
+/// Apply the `IssueParams` filters the server may not have applied (its
+/// database path filters by state only)
+fn filter_issues(issues: Vec<Issue>, params: &IssueParams) -> Vec<Issue> {
+    issues
+        .into_iter()
+        .filter(|i| {
+            params.labels.as_ref().is_none_or(|labels| {
+                labels
+                    .iter()
+                    .all(|l| i.labels.iter().any(|il| il.name.eq_ignore_ascii_case(l)))
+            })
+        })
+        .filter(|i| {
+            params
+                .assignee
+                .as_ref()
+                .is_none_or(|a| i.assignees.iter().any(|u| &u.login == a))
+        })
+        .filter(|i| {
+            params
+                .milestone
+                .as_deref()
+                .is_none_or(|m| milestone_matches(i.milestone.as_ref(), m))
+        })
+        .filter(|i| params.creator.as_ref().is_none_or(|c| &i.author.login == c))
+        .filter(|i| params.since.is_none_or(|since| i.updated_at >= since))
+        .collect()
+}
+
+#[async_trait]
+impl GitHubClient for GreportClient {
+    async fn get_repository(&self, _repo: &RepoId) -> Result<Repository> {
+        Err(unsupported("Repository metadata"))
+    }
+
+    async fn list_org_repos(&self, _org: &str) -> Result<Vec<Repository>> {
+        Err(unsupported("Organization repository metadata"))
+    }
+
+    async fn list_issues(&self, repo: &RepoId, params: IssueParams) -> Result<Vec<Issue>> {
+        let query = ListIssuesQuery {
+            state: Some(issue_state(params.state).to_string()),
+            labels: params.labels.as_ref().map(|l| l.join(",")),
+            assignee: params.assignee.clone(),
+            milestone: params.milestone.clone(),
+            per_page: Some(PER_PAGE),
+            ..Default::default()
+        };
+        let issues = self.issues(&repo.owner, &repo.name, &query).all().await?;
+        Ok(filter_issues(issues, &params))
+    }
+
+    async fn get_issue(&self, repo: &RepoId, number: u64) -> Result<Issue> {
+        let fetch = self.list_issues(repo, IssueParams::all());
+        cached(&self.listings.issues, repo, fetch)
+            .await?
+            .iter()
+            .find(|i| i.number == number)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Issue #{} not found", number)))
+    }
+
+    async fn list_issue_events(&self, _repo: &RepoId, _number: u64) -> Result<Vec<IssueEvent>> {
+        Err(unsupported("Issue events"))
+    }
+
+    async fn list_issue_relations(&self, _repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        Err(unsupported("Sub-issue and tracked-issue data"))
+    }
+
+    /// Milestones referenced by the repository's issues; milestones with no
+    /// issues are not listed
+    async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
+        let issues = self.list_issues(repo, IssueParams::all()).await?;
+        let milestones: BTreeMap<u64, Milestone> = issues
+            .into_iter()
+            .filter_map(|i| i.milestone)
+            .map(|m| (m.number, m))
+            .collect();
+        Ok(milestones.into_values().collect())
+    }
+
+    async fn list_pulls(&self, repo: &RepoId, params: PullParams) -> Result<Vec<PullRequest>> {
+        let query = ListPullsQuery {
+            state: Some(pull_state(params.state).to_string()),
+            per_page: Some(PER_PAGE),
+            ..Default::default()
+        };
+        let pulls = self.pulls(&repo.owner, &repo.name, &query).all().await?;
+        Ok(pulls
+            .into_iter()
+            .filter(|p| params.head.as_ref().is_none_or(|h| &p.head_ref == h))
+            .filter(|p| params.base.as_ref().is_none_or(|b| &p.base_ref == b))
+            .collect())
+    }
+
+    async fn get_pull(&self, repo: &RepoId, number: u64) -> Result<PullRequest> {
+        let fetch = self.list_pulls(repo, PullParams::all());
+        cached(&self.listings.pulls, repo, fetch)
+            .await?
+            .iter()
+            .find(|p| p.number == number)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Pull request #{} not found", number)))
+    }
+
+    async fn list_pull_reviews(&self, _repo: &RepoId, _number: u64) -> Result<Vec<Review>> {
+        Err(unsupported("Pull request reviews"))
+    }
+
+    async fn list_releases(&self, repo: &RepoId) -> Result<Vec<Release>> {
+        let query = ListReleasesQuery {
+            per_page: Some(PER_PAGE),
+            ..Default::default()
+        };
+        Ok(self.releases(&repo.owner, &repo.name, &query).all().await?)
+    }
+
+    async fn get_release(&self, repo: &RepoId, tag: &str) -> Result<Release> {
+        cached(&self.listings.releases, repo, self.list_releases(repo))
+            .await?
+            .iter()
+            .find(|r| r.tag_name == tag)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Release {} not found", tag)))
+    }
+
+    async fn get_user(&self, _username: &str) -> Result<User> {
+        Err(unsupported("User data"))
+    }
+
+    async fn rate_limit(&self) -> Result<RateLimitInfo> {
+        Err(unsupported("GitHub rate limit information"))
+    }
+}
+
+/// The server's project routes return summaries rather than the GraphQL
+/// models, so Projects commands need a direct GitHub connection
+#[async_trait]
+impl ProjectClient for GreportClient {
+    async fn list_projects(&self, _org: &str) -> Result<Vec<Project>> {
+        Err(unsupported("GitHub Projects data"))
+    }
+
+    async fn get_project(&self, _org: &str, _project_number: u64) -> Result<Project> {
+        Err(unsupported("GitHub Projects data"))
+    }
+
+    async fn list_project_items(&self, _project_node_id: &str) -> Result<Vec<ProjectItem>> {
+        Err(unsupported("GitHub Projects data"))
+    }
+
+    async fn update_project_item_field(
+        &self,
+        _project_node_id: &str,
+        _item_node_id: &str,
+        _field_node_id: &str,
+        _update: &FieldUpdate,
+    ) -> Result<()> {
+        Err(unsupported("Updating GitHub Projects"))
+    }
+
+    async fn add_project_item(
+        &self,
+        _project_node_id: &str,
+        _repo: &RepoId,
+        _number: u64,
+    ) -> Result<String> {
+        Err(unsupported("Updating GitHub Projects"))
+    }
+
+    async fn archive_project_item(
+        &self,
+        _project_node_id: &str,
+        _item_node_id: &str,
+    ) -> Result<()> {
+        Err(unsupported("Updating GitHub Projects"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use greport_core::client::MockGitHubClient;
+
+    #[tokio::test]
+    async fn test_filter_issues_matches_mock_client() {
+        let mock = MockGitHubClient::with_sample_data();
+        let repo = RepoId::new("test-owner", "test-repo");
+        let all = mock.list_issues(&repo, IssueParams::all()).await.unwrap();
+
+        let params = IssueParams::all().with_labels(vec!["bug".to_string()]);
+        let expected = mock.list_issues(&repo, params.clone()).await.unwrap();
+        let filtered = filter_issues(all.clone(), &params);
+        assert_eq!(
+            filtered.iter().map(|i| i.number).collect::<Vec<_>>(),
+            expected.iter().map(|i| i.number).collect::<Vec<_>>()
+        );
+
+        let params = IssueParams::all().with_milestone("v1.0");
+        let filtered = filter_issues(all.clone(), &params);
+        assert!(!filtered.is_empty());
+        assert!(filtered
+            .iter()
+            .all(|i| i.milestone.as_ref().unwrap().title == "v1.0"));
+    }
+
+    #[test]
+    fn test_milestone_filter() {
+        let milestone: Milestone = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "number": 3,
+            "title": "v1.0",
+            "description": null,
+            "state": "open",
+            "open_issues": 1,
+            "closed_issues": 0,
+            "due_on": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "closed_at": null
+        }))
+        .unwrap();
+        assert!(milestone_matches(Some(&milestone), "v1.0"));
+        assert!(milestone_matches(Some(&milestone), "V1.0"));
+        assert!(milestone_matches(Some(&milestone), "3"));
+        assert!(milestone_matches(Some(&milestone), "*"));
+        assert!(!milestone_matches(Some(&milestone), "none"));
+        assert!(milestone_matches(None, "none"));
+        assert!(!milestone_matches(None, "v1.0"));
+    }
+}
@@ -0,0 +1,28 @@
+//! greport-client - Typed client for the greport REST API
+//!
+//! [`GreportClient`] has a method for each `/api/v1` route and returns the
+//! same response types the server serializes (re-exported from
+//! `greport_types` as [`types`]). List routes return [`Pages`], which walks
+//! `PaginatedResponse` pages on demand. Requests carry an optional API key
+//! and are retried on connection errors, `429` and `5xx` responses.
+//!
+//! The client also implements `GitHubClient` and `ProjectClient`, so the CLI
+//! can read issues, pull requests and releases from a greport server instead
+//! of GitHub.
+//!
+//! The browser login routes (`/auth/login`, `/auth/callback`,
+//! `/auth/logout`) and the `/docs` page are not covered.
+
+mod api;
+mod client;
+mod error;
+mod github;
+mod pages;
+
+pub use client::GreportClient;
+pub use error::{Error, Result};
+pub use pages::Pages;
+
+/// Request and response types shared with the server, grouped like the
+/// server's route modules
+pub use greport_types as types;
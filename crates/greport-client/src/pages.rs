@@ -0,0 +1,83 @@
+//! Iteration over `PaginatedResponse` pages
+
+use crate::client::GreportClient;
+use crate::error::Result;
+use crate::types::PaginatedResponse;
+use reqwest::{Method, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// Page size used when the query leaves `per_page` unset
+const DEFAULT_PER_PAGE: u32 = 100;
+
+/// Pages of a list route, fetched one request at a time
+///
+/// Starts at the query's `page` (default 1) and stops after the last page
+/// reported in `meta`. Routes that answer from memory return the whole list
+/// on every page; iteration stops after the first page in that case.
+pub struct Pages<'a, T> {
+    client: &'a GreportClient,
+    url: Url,
+    query: Vec<(String, String)>,
+    next_page: Option<u32>,
+    per_page: u32,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: DeserializeOwned> Pages<'a, T> {
+    pub(crate) fn new(client: &'a GreportClient, url: Url, query: &impl Serialize) -> Self {
+        let encoded = serde_urlencoded::to_string(query).expect("query structs are flat");
+        let mut pairs: Vec<(String, String)> =
+            serde_urlencoded::from_str(&encoded).expect("round-trips its own encoding");
+
+        let mut take = |key: &str| -> Option<u32> {
+            let index = pairs.iter().position(|(k, _)| k == key)?;
+            pairs.remove(index).1.parse().ok()
+        };
+        let page = take("page").unwrap_or(1).max(1);
+        let per_page = take("per_page").unwrap_or(DEFAULT_PER_PAGE).max(1);
+
+        Self {
+            client,
+            url,
+            query: pairs,
+            next_page: Some(page),
+            per_page,
+            _item: PhantomData,
+        }
+    }
+
+    /// Fetch the next page, or `None` once every page has been returned
+    pub async fn next_page(&mut self) -> Option<Result<PaginatedResponse<T>>> {
+        let page = self.next_page.take()?;
+        let request = self
+            .client
+            .request(Method::GET, self.url.clone())
+            .query(&self.query)
+            .query(&[("page", page), ("per_page", self.per_page)]);
+
+        let response: PaginatedResponse<T> = match self.client.send(request).await {
+            Ok(response) => response,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let meta = &response.meta;
+        let complete = response.data.is_empty()
+            || page >= meta.total_pages
+            || response.data.len() as u32 >= meta.total;
+        if !complete {
+            self.next_page = Some(page + 1);
+        }
+        Some(Ok(response))
+    }
+
+    /// Fetch every remaining page and concatenate the items
+    pub async fn all(mut self) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        while let Some(page) = self.next_page().await {
+            items.extend(page?.data);
+        }
+        Ok(items)
+    }
+}
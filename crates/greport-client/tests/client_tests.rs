@@ -0,0 +1,337 @@
+//! Client integration tests
+//!
+//! The route tests run the real API router (backed by `MockGitHubClient`) on
+//! a local port; pagination and retry behavior is checked against wiremock.
+
+use greport_api::{build_router, ApiConfig, AppState};
+use greport_client::types::{contrib, issues, pulls, releases, sla};
+use greport_client::{Error, GreportClient};
+use greport_core::client::{
+    GitHubClient, IssueParams, MockGitHubClient, ProjectClient, Provider, PullParams, RetryConfig,
+};
+use greport_core::{GitHubClientRegistry, OrgEntry, RepoId};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Serve the API router with mock GitHub data and return its URL
+async fn spawn_server() -> String {
+    let entry = OrgEntry {
+        name: "test-owner".to_string(),
+        base_url: None,
+        repo_count: 1,
+        repo_names: vec!["test-repo".to_string()],
+        has_token: true,
+        provider: Provider::GitHub,
+    };
+    let registry = GitHubClientRegistry::with_default(MockGitHubClient::with_sample_data(), None)
+        .with_org(entry, MockGitHubClient::with_sample_data());
+    let config = ApiConfig {
+        rate_limit_per_minute: 10_000,
+        ..Default::default()
+    };
+    let router = build_router(AppState::new(registry, config, None));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+/// Retries without real waiting
+fn fast_retry() -> RetryConfig {
+    RetryConfig {
+        max_retries: 2,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        multiplier: 2.0,
+    }
+}
+
+fn page(data: serde_json::Value, page: u32, per_page: u32, total: u32) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "data": data,
+        "meta": {
+            "page": page,
+            "per_page": per_page,
+            "total": total,
+            "total_pages": total.div_ceil(per_page),
+        }
+    }))
+}
+
+#[tokio::test]
+async fn test_typed_routes() {
+    let client = GreportClient::new(&spawn_server().await).unwrap();
+
+    assert_eq!(client.health().await.unwrap().status, "healthy");
+    let doc = client.openapi().await.unwrap();
+    assert!(doc["paths"]["/api/v1/repos/{owner}/{repo}/issues"].is_object());
+
+    let open = client
+        .issues("test-owner", "test-repo", &Default::default())
+        .all()
+        .await
+        .unwrap();
+    assert_eq!(open.len(), 2);
+
+    let bugs = client
+        .issues(
+            "test-owner",
+            "test-repo",
+            &issues::ListIssuesQuery {
+                state: Some("all".to_string()),
+                labels: Some("bug".to_string()),
+                ..Default::default()
+            },
+        )
+        .all()
+        .await
+        .unwrap();
+    assert_eq!(bugs[0].title, "Fix login bug");
+
+    let metrics = client
+        .issue_metrics("test-owner", "test-repo", &issues::MetricsQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(metrics.total, 3);
+    assert_eq!(metrics.open, 2);
+
+    let pull_metrics = client
+        .pull_metrics("test-owner", "test-repo", &pulls::MetricsQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(pull_metrics.merged, 1);
+
+    let releases = client
+        .releases(
+            "test-owner",
+            "test-repo",
+            &releases::ListReleasesQuery::default(),
+        )
+        .all()
+        .await
+        .unwrap();
+    assert_eq!(releases[0].tag_name, "v0.1.0");
+
+    let milestone = client
+        .milestone_progress("test-owner", "test-repo", "v1.0")
+        .await
+        .unwrap();
+    assert_eq!(milestone.title, "v1.0");
+
+    let contributors = client
+        .contributors(
+            "test-owner",
+            "test-repo",
+            &contrib::ContributorsQuery::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(contributors[0].login, "test-user");
+
+    let report = client
+        .sla_report("test-owner", "test-repo", &sla::SlaQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(report.repository, "test-owner/test-repo");
+
+    let orgs = client.orgs().await.unwrap();
+    assert_eq!(orgs.orgs[0].name, "test-owner");
+    let org_repos = client.org_repos("test-owner").await.unwrap();
+    assert_eq!(org_repos[0].full_name, "test-owner/test-repo");
+}
+
+#[tokio::test]
+async fn test_error_responses() {
+    let client = GreportClient::new(&spawn_server().await).unwrap();
+
+    let err = client
+        .milestone_progress("test-owner", "test-repo", "v9")
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(404));
+    assert!(matches!(err, Error::Api { ref code, .. } if code == "NOT_FOUND"));
+
+    // Database-only routes report the missing database
+    let err = client.repos().await.unwrap_err();
+    assert_eq!(err.status(), Some(400));
+
+    // Anonymous callers have no identity
+    let err = client.me().await.unwrap_err();
+    assert_eq!(err.status(), Some(401));
+}
+
+#[tokio::test]
+async fn test_github_client_over_server() {
+    let client = GreportClient::new(&spawn_server().await).unwrap();
+    let mock = MockGitHubClient::with_sample_data();
+    let repo = RepoId::new("test-owner", "test-repo");
+
+    for params in [
+        IssueParams::all(),
+        IssueParams::open(),
+        IssueParams::all().with_labels(vec!["bug".to_string()]),
+    ] {
+        let remote = client.list_issues(&repo, params.clone()).await.unwrap();
+        let direct = mock.list_issues(&repo, params).await.unwrap();
+        assert_eq!(
+            remote.iter().map(|i| i.number).collect::<Vec<_>>(),
+            direct.iter().map(|i| i.number).collect::<Vec<_>>()
+        );
+    }
+
+    let pulls = client.list_pulls(&repo, PullParams::all()).await.unwrap();
+    assert_eq!(
+        pulls.len(),
+        mock.list_pulls(&repo, PullParams::all())
+            .await
+            .unwrap()
+            .len()
+    );
+    assert_eq!(client.get_issue(&repo, 1).await.unwrap().number, 1);
+
+    let milestones = client.list_milestones(&repo).await.unwrap();
+    assert!(milestones.iter().any(|m| m.title == "v1.0"));
+
+    let release = client.get_release(&repo, "v0.1.0").await.unwrap();
+    assert_eq!(release.tag_name, "v0.1.0");
+
+    let err = client.list_issue_relations(&repo).await.unwrap_err();
+    assert!(err.to_string().contains("greport server"));
+    assert!(client.list_projects("test-owner").await.is_err());
+}
+
+fn release(id: u64) -> serde_json::Value {
+    json!({
+        "id": id,
+        "tag_name": format!("v{}", id),
+        "name": null,
+        "body": null,
+        "draft": false,
+        "prerelease": false,
+        "author": { "id": 1, "login": "u", "avatar_url": "", "html_url": "" },
+        "created_at": "2024-01-01T00:00:00Z",
+        "published_at": null
+    })
+}
+
+#[tokio::test]
+async fn test_pages_follow_meta() {
+    let server = MockServer::start().await;
+    for (n, data) in [
+        (1, json!([release(1), release(2)])),
+        (2, json!([release(3), release(4)])),
+        (3, json!([release(5)])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/o/r/releases"))
+            .and(query_param("page", n.to_string()))
+            .and(query_param("per_page", "2"))
+            .respond_with(page(data, n, 2, 5))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = GreportClient::new(&server.uri()).unwrap();
+    let query = releases::ListReleasesQuery {
+        per_page: Some(2),
+        ..Default::default()
+    };
+    let all = client.releases("o", "r", &query).all().await.unwrap();
+    assert_eq!(
+        all.iter().map(|r| r.id).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5]
+    );
+}
+
+#[tokio::test]
+async fn test_pages_stop_when_server_returns_everything() {
+    // Routes that answer from memory ignore `page`: three items with
+    // per_page 1 still come back in a single response
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/o/r/releases"))
+        .respond_with(page(json!([release(1), release(2), release(3)]), 1, 1, 3))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GreportClient::new(&server.uri()).unwrap();
+    let query = releases::ListReleasesQuery {
+        per_page: Some(1),
+        ..Default::default()
+    };
+    let all = client.releases("o", "r", &query).all().await.unwrap();
+    assert_eq!(all.len(), 3);
+}
+
+#[tokio::test]
+async fn test_single_item_lookups_reuse_the_listing() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/o/r/releases"))
+        .respond_with(page(json!([release(1), release(2)]), 1, 100, 2))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GreportClient::new(&server.uri()).unwrap();
+    let repo = RepoId::new("o", "r");
+    assert_eq!(client.get_release(&repo, "v1").await.unwrap().id, 1);
+    assert_eq!(client.clone().get_release(&repo, "v2").await.unwrap().id, 2);
+    let err = client.get_release(&repo, "v3").await.unwrap_err();
+    assert!(err.to_string().contains("v3"));
+}
+
+#[tokio::test]
+async fn test_retries_and_api_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/orgs"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "0")
+                .set_body_json(
+                    json!({ "error": { "code": "RATE_LIMITED", "message": "slow down" } }),
+                ),
+        )
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/orgs"))
+        .and(header("authorization", "Bearer grk_test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "orgs": [], "default_web_url": "https://github.com" }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = GreportClient::new(&server.uri())
+        .unwrap()
+        .with_api_key("grk_test")
+        .with_retry_config(fast_retry());
+    assert!(client.orgs().await.unwrap().orgs.is_empty());
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/sync"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = GreportClient::new(&server.uri())
+        .unwrap()
+        .with_retry_config(RetryConfig {
+            max_retries: 3,
+            ..fast_retry()
+        });
+    // POSTs are not retried on 500
+    let err = client.batch_sync().await.unwrap_err();
+    assert_eq!(err.status(), Some(500));
+}
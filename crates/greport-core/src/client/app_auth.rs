@@ -0,0 +1,398 @@
+//! GitHub App installation authentication
+//!
+//! A GitHub App acts on an account as one of its installations, using an
+//! installation access token. Tokens are minted by posting a short-lived JWT,
+//! signed locally with the App's private key, to
+//! `POST /app/installations/{id}/access_tokens`. They last an hour;
+//! [`InstallationTokens`] keeps the current one and mints the next shortly
+//! before it expires.
+
+use crate::config::GitHubAppConfig;
+use crate::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::EncodingKey;
+use octocrab::models::AppId;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// Tokens with less than this left are replaced before use
+const REFRESH_MARGIN: Duration = Duration::minutes(5);
+
+/// Identity and private key of a GitHub App installation
+pub struct AppCredentials {
+    /// App ID
+    pub app_id: u64,
+    /// Installation ID for the account the App is installed on
+    pub installation_id: u64,
+    key: EncodingKey,
+}
+
+impl std::fmt::Debug for AppCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppCredentials")
+            .field("app_id", &self.app_id)
+            .field("installation_id", &self.installation_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AppCredentials {
+    /// Create credentials from a PEM-encoded RSA private key
+    pub fn new(app_id: u64, installation_id: u64, private_key_pem: &[u8]) -> Result<Self> {
+        let key = EncodingKey::from_rsa_pem(private_key_pem).map_err(|e| {
+            Error::Config(format!(
+                "Invalid private key for GitHub App {}: {}",
+                app_id, e
+            ))
+        })?;
+        Ok(Self {
+            app_id,
+            installation_id,
+            key,
+        })
+    }
+
+    /// Create credentials from the private key file downloaded from the
+    /// App's settings page
+    pub fn from_key_file(app_id: u64, installation_id: u64, path: &Path) -> Result<Self> {
+        let pem = std::fs::read(path).map_err(|e| {
+            Error::Config(format!(
+                "Failed to read GitHub App private key {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::new(app_id, installation_id, &pem)
+    }
+
+    /// Create credentials from a `[github.app]` / `[organizations.app]` table
+    pub fn from_config(config: &GitHubAppConfig) -> Result<Self> {
+        Self::from_key_file(
+            config.app_id,
+            config.installation_id,
+            &config.private_key_path,
+        )
+    }
+
+    /// Sign a JWT authenticating as the App (valid for nine minutes)
+    fn jwt(&self) -> Result<String> {
+        octocrab::auth::create_jwt(AppId(self.app_id), &self.key)
+            .map_err(|e| Error::Custom(format!("Failed to sign GitHub App JWT: {}", e)))
+    }
+}
+
+/// An installation access token and its expiry
+#[derive(Debug, Clone, Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Installation access tokens of a GitHub App, minted on demand
+///
+/// [`InstallationTokens::token`] returns the current token, minting a new
+/// one when none has been issued yet or the last one is about to expire.
+/// Concurrent callers wait for a single refresh.
+pub struct InstallationTokens {
+    credentials: AppCredentials,
+    http: reqwest::Client,
+    token_url: String,
+    current: Mutex<Option<InstallationToken>>,
+}
+
+impl std::fmt::Debug for InstallationTokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstallationTokens")
+            .field("credentials", &self.credentials)
+            .field("token_url", &self.token_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InstallationTokens {
+    /// Mint tokens for `credentials` from the API at `base_url`
+    /// (GitHub.com when None)
+    pub fn new(credentials: AppCredentials, base_url: Option<&str>) -> Result<Self> {
+        let api = base_url
+            .unwrap_or("https://api.github.com")
+            .trim_end_matches('/');
+        let token_url = format!(
+            "{}/app/installations/{}/access_tokens",
+            api, credentials.installation_id
+        );
+        let http = reqwest::Client::builder()
+            .user_agent("greport")
+            .build()
+            .map_err(|e| Error::Custom(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            credentials,
+            http,
+            token_url,
+            current: Mutex::new(None),
+        })
+    }
+
+    /// The App and installation the tokens are minted for
+    pub fn credentials(&self) -> &AppCredentials {
+        &self.credentials
+    }
+
+    /// Current installation access token, refreshed when it expires within
+    /// five minutes
+    pub async fn token(&self) -> Result<String> {
+        let mut current = self.current.lock().await;
+        if let Some(token) = current.as_ref() {
+            if token.expires_at - Utc::now() > REFRESH_MARGIN {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let token = self.mint().await?;
+        let value = token.token.clone();
+        *current = Some(token);
+        Ok(value)
+    }
+
+    async fn mint(&self) -> Result<InstallationToken> {
+        debug!(
+            app_id = self.credentials.app_id,
+            installation_id = self.credentials.installation_id,
+            "Minting GitHub App installation token"
+        );
+        let response = self
+            .http
+            .post(&self.token_url)
+            .bearer_auth(self.credentials.jwt()?)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| Error::Network(format!("Installation token request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(match status.as_u16() {
+                401 => Error::InvalidToken,
+                404 => Error::Config(format!(
+                    "GitHub App {} has no installation {}",
+                    self.credentials.app_id, self.credentials.installation_id
+                )),
+                s if s >= 500 => Error::Network(format!("Installation token request: HTTP {}", s)),
+                s => Error::GitHubApi(format!("{}: {}", s, body)),
+            });
+        }
+
+        let token: InstallationToken = response
+            .json()
+            .await
+            .map_err(|e| Error::GitHubApi(format!("Invalid installation token response: {}", e)))?;
+        info!(
+            installation_id = self.credentials.installation_id,
+            expires_at = %token.expires_at,
+            "GitHub App installation token issued"
+        );
+        Ok(token)
+    }
+}
+
+/// How a client authenticates to GitHub
+#[derive(Clone)]
+pub enum Credentials {
+    /// Personal access or OAuth token
+    Token(String),
+    /// GitHub App installation
+    App(Arc<InstallationTokens>),
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Credentials::Token(_) => f.write_str("Token(..)"),
+            Credentials::App(tokens) => f.debug_tuple("App").field(tokens).finish(),
+        }
+    }
+}
+
+impl Credentials {
+    /// Authenticate as the GitHub App installation described by `config`
+    pub fn app(config: &GitHubAppConfig, base_url: Option<&str>) -> Result<Self> {
+        let credentials = AppCredentials::from_config(config)?;
+        Ok(Credentials::App(Arc::new(InstallationTokens::new(
+            credentials,
+            base_url,
+        )?)))
+    }
+
+    /// Token to send in the `Authorization` header
+    pub async fn token(&self) -> Result<String> {
+        match self {
+            Credentials::Token(token) => Ok(token.clone()),
+            Credentials::App(tokens) => tokens.token().await,
+        }
+    }
+
+    /// Stable identity of the credentials, which stays the same when an
+    /// installation token is renewed (a token is only kept as its hash)
+    pub(crate) fn scope(&self) -> String {
+        match self {
+            Credentials::Token(token) => {
+                format!("token:{}", super::etag_cache::sha256_hex(token.as_bytes()))
+            }
+            Credentials::App(tokens) => format!(
+                "app:{}/{}",
+                tokens.credentials.app_id, tokens.credentials.installation_id
+            ),
+        }
+    }
+
+    /// Short description for logs (never includes a token)
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Credentials::Token(token) if token.starts_with("ghp_") => "classic PAT",
+            Credentials::Token(token) if token.starts_with("github_pat_") => "fine-grained PAT",
+            Credentials::Token(token) if token.starts_with("gho_") => "OAuth token",
+            Credentials::Token(_) => "unknown token type",
+            Credentials::App(_) => "GitHub App installation",
+        }
+    }
+}
+
+impl From<&str> for Credentials {
+    fn from(token: &str) -> Self {
+        Credentials::Token(token.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/fixtures/github_app_key.pem");
+    const PUBLIC_KEY: &str = include_str!("../../tests/fixtures/github_app_key.pub.pem");
+
+    #[derive(Debug, Deserialize)]
+    struct Claims {
+        iss: u64,
+        iat: i64,
+        exp: i64,
+    }
+
+    fn tokens(server: &MockServer) -> InstallationTokens {
+        let credentials = AppCredentials::new(1234, 42, PRIVATE_KEY.as_bytes()).unwrap();
+        InstallationTokens::new(credentials, Some(&server.uri())).unwrap()
+    }
+
+    fn token_response(token: &str, expires_in: Duration) -> ResponseTemplate {
+        ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "token": token,
+            "expires_at": (Utc::now() + expires_in).to_rfc3339(),
+            "permissions": { "issues": "read" },
+        }))
+    }
+
+    #[test]
+    fn test_jwt_is_signed_for_the_app() {
+        let credentials = AppCredentials::new(1234, 42, PRIVATE_KEY.as_bytes()).unwrap();
+        let jwt = credentials.jwt().unwrap();
+
+        let key = DecodingKey::from_rsa_pem(PUBLIC_KEY.as_bytes()).unwrap();
+        let claims = jsonwebtoken::decode::<Claims>(&jwt, &key, &Validation::new(Algorithm::RS256))
+            .unwrap()
+            .claims;
+        assert_eq!(claims.iss, 1234);
+        // GitHub rejects JWTs that live longer than ten minutes
+        assert!(claims.exp - claims.iat <= 600);
+    }
+
+    #[test]
+    fn test_invalid_key_is_a_config_error() {
+        let err = AppCredentials::new(1, 2, b"not a key").unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+
+        let err =
+            AppCredentials::from_key_file(1, 2, Path::new("/nonexistent/key.pem")).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/key.pem"));
+    }
+
+    #[tokio::test]
+    async fn test_token_is_minted_once_and_reused() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/42/access_tokens"))
+            .and(header_exists("authorization"))
+            .respond_with(token_response("ghs_first", Duration::hours(1)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tokens = tokens(&server);
+        assert_eq!(tokens.token().await.unwrap(), "ghs_first");
+        assert_eq!(tokens.token().await.unwrap(), "ghs_first");
+
+        // The request was authenticated with a JWT for the App
+        let requests: Vec<Request> = server.received_requests().await.unwrap();
+        let auth = requests[0].headers["authorization"].to_str().unwrap();
+        let jwt = auth.strip_prefix("Bearer ").unwrap();
+        let key = DecodingKey::from_rsa_pem(PUBLIC_KEY.as_bytes()).unwrap();
+        let claims = jsonwebtoken::decode::<Claims>(jwt, &key, &Validation::new(Algorithm::RS256))
+            .unwrap()
+            .claims;
+        assert_eq!(claims.iss, 1234);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_refreshed_before_expiry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/42/access_tokens"))
+            .respond_with(token_response("ghs_expiring", Duration::minutes(2)))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/42/access_tokens"))
+            .respond_with(token_response("ghs_fresh", Duration::hours(1)))
+            .mount(&server)
+            .await;
+
+        let tokens = tokens(&server);
+        assert_eq!(tokens.token().await.unwrap(), "ghs_expiring");
+        // Less than five minutes left, so the next call mints a new token
+        assert_eq!(tokens.token().await.unwrap(), "ghs_fresh");
+        assert_eq!(tokens.token().await.unwrap(), "ghs_fresh");
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_installation_is_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let err = tokens(&server).token().await.unwrap_err();
+        assert!(err.to_string().contains("no installation 42"));
+    }
+
+    #[test]
+    fn test_credentials_scope_survives_token_renewal() {
+        let credentials = AppCredentials::new(7, 9, PRIVATE_KEY.as_bytes()).unwrap();
+        let app = Credentials::App(Arc::new(
+            InstallationTokens::new(credentials, None).unwrap(),
+        ));
+        assert_eq!(app.scope(), "app:7/9");
+        let token = Credentials::from("ghp_abc").scope();
+        assert!(token.starts_with("token:") && !token.contains("ghp_abc"));
+        assert_eq!(app.kind(), "GitHub App installation");
+        assert_eq!(Credentials::from("ghp_abc").kind(), "classic PAT");
+        assert_eq!(format!("{:?}", Credentials::from("ghp_abc")), "Token(..)");
+    }
+}
@@ -0,0 +1,340 @@
+//! Rate-limit budget shared by the REST and GraphQL clients
+//!
+//! GitHub reports the remaining primary budget in `X-RateLimit-*` headers on
+//! every response and asks for a pause with `Retry-After` when a secondary
+//! limit trips. [`RateBudget`] keeps the latest values for one token and
+//! makes callers wait for the reset instead of running the budget dry.
+
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Requests left untouched before calls pause for the reset
+pub const DEFAULT_RATE_LIMIT_RESERVE: u32 = 50;
+
+/// Wait after a secondary limit that came without `Retry-After`
+const SECONDARY_LIMIT_PAUSE: Duration = Duration::from_secs(60);
+
+/// Rate-limit bucket a request is charged to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateResource {
+    /// REST API requests
+    Core,
+    /// GraphQL API points
+    GraphQL,
+}
+
+impl RateResource {
+    fn from_header(value: &str) -> Option<Self> {
+        match value {
+            "core" => Some(RateResource::Core),
+            "graphql" => Some(RateResource::GraphQL),
+            _ => None,
+        }
+    }
+}
+
+/// Primary budget of one rate-limit bucket, as last reported by GitHub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResourceBudget {
+    /// Requests (or GraphQL points) allowed per window
+    pub limit: u32,
+    /// Requests left in the current window
+    pub remaining: u32,
+    /// Requests used in the current window
+    pub used: u32,
+    /// When the window resets
+    pub reset: DateTime<Utc>,
+}
+
+/// Point-in-time view of a token's rate-limit budget
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RateBudgetSnapshot {
+    /// REST budget (None until the first response)
+    pub core: Option<ResourceBudget>,
+    /// GraphQL point budget (None until the first response)
+    pub graphql: Option<ResourceBudget>,
+    /// End of a secondary-limit pause, if one is in effect
+    pub paused_until: Option<DateTime<Utc>>,
+    /// Requests held back in each window before calls pause
+    pub reserve: u32,
+}
+
+impl RateBudgetSnapshot {
+    /// Budget of one bucket
+    pub fn resource(&self, resource: RateResource) -> Option<&ResourceBudget> {
+        match resource {
+            RateResource::Core => self.core.as_ref(),
+            RateResource::GraphQL => self.graphql.as_ref(),
+        }
+    }
+
+    /// When requests against `resource` may continue, if they must wait
+    pub fn resume_at(&self, resource: RateResource) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        let primary = self
+            .resource(resource)
+            .filter(|b| b.remaining <= self.reserve && b.reset > now)
+            .map(|b| b.reset);
+        let secondary = self.paused_until.filter(|t| *t > now);
+        primary.max(secondary)
+    }
+
+    fn resource_mut(&mut self, resource: RateResource) -> Option<&mut ResourceBudget> {
+        match resource {
+            RateResource::Core => self.core.as_mut(),
+            RateResource::GraphQL => self.graphql.as_mut(),
+        }
+    }
+
+    fn set(&mut self, resource: RateResource, budget: ResourceBudget) {
+        match resource {
+            RateResource::Core => self.core = Some(budget),
+            RateResource::GraphQL => self.graphql = Some(budget),
+        }
+    }
+}
+
+/// Live rate-limit budget of one token
+///
+/// Shared by an `OctocrabClient` and its `GraphQLClient`. Every request first
+/// calls [`RateBudget::acquire`], which sleeps through secondary-limit pauses
+/// and, once fewer than `reserve` requests are left, until the window resets.
+#[derive(Debug)]
+pub struct RateBudget {
+    state: Mutex<RateBudgetSnapshot>,
+}
+
+impl Default for RateBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_RATE_LIMIT_RESERVE)
+    }
+}
+
+impl RateBudget {
+    /// Create an empty budget that pauses with `reserve` requests left
+    pub fn new(reserve: u32) -> Self {
+        Self {
+            state: Mutex::new(RateBudgetSnapshot {
+                reserve,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Change the number of requests held back before pausing
+    pub fn set_reserve(&self, reserve: u32) {
+        self.lock().reserve = reserve;
+    }
+
+    /// Current budget
+    pub fn snapshot(&self) -> RateBudgetSnapshot {
+        self.lock().clone()
+    }
+
+    /// When requests against `resource` may continue, if they must wait
+    pub fn resume_at(&self, resource: RateResource) -> Option<DateTime<Utc>> {
+        self.lock().resume_at(resource)
+    }
+
+    /// Wait until a request against `resource` may be sent, then count it
+    pub(crate) async fn acquire(&self, resource: RateResource) {
+        if let Some(until) = self.resume_at(resource) {
+            let wait = (until - Utc::now()).to_std().unwrap_or_default();
+            warn!(
+                resource = ?resource,
+                resume_at = %until,
+                wait_secs = wait.as_secs(),
+                "GitHub rate limit nearly exhausted, pausing"
+            );
+            tokio::time::sleep(wait).await;
+        }
+
+        // Count the request so concurrent callers see it before the
+        // response headers arrive
+        let mut state = self.lock();
+        if let Some(budget) = state.resource_mut(resource) {
+            if budget.reset > Utc::now() {
+                budget.remaining = budget.remaining.saturating_sub(1);
+                budget.used += 1;
+            }
+        }
+    }
+
+    /// Update the budget from the status and headers of a response to a
+    /// `resource` request
+    pub(crate) fn record(&self, resource: RateResource, status: u16, headers: &HeaderMap) {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let number = |name: &str| header(name).and_then(|v| v.trim().parse::<u64>().ok());
+
+        let mut state = self.lock();
+
+        // Older GitHub Enterprise servers leave out the resource header
+        let resource = match header("x-ratelimit-resource") {
+            Some(name) => RateResource::from_header(name),
+            None => Some(resource),
+        };
+        if let (Some(resource), Some(limit), Some(remaining), Some(reset)) = (
+            resource,
+            number("x-ratelimit-limit"),
+            number("x-ratelimit-remaining"),
+            number("x-ratelimit-reset"),
+        ) {
+            let budget = ResourceBudget {
+                limit: limit as u32,
+                remaining: remaining as u32,
+                used: number("x-ratelimit-used").unwrap_or(limit.saturating_sub(remaining)) as u32,
+                reset: unix_time(reset),
+            };
+            debug!(resource = ?resource, remaining = budget.remaining, "Rate budget updated");
+            state.set(resource, budget);
+        }
+
+        if status == 403 || status == 429 {
+            let primary_exhausted = number("x-ratelimit-remaining") == Some(0);
+            let pause = match number("retry-after") {
+                Some(secs) => Some(Duration::from_secs(secs)),
+                None if !primary_exhausted && status == 429 => Some(SECONDARY_LIMIT_PAUSE),
+                None => None,
+            };
+            if let Some(pause) = pause {
+                let until = Utc::now() + chrono::Duration::from_std(pause).unwrap_or_default();
+                warn!(
+                    retry_after_secs = pause.as_secs(),
+                    "GitHub secondary rate limit hit"
+                );
+                state.paused_until = Some(until);
+            }
+        }
+    }
+
+    /// Replace the budget of `resource` (e.g. from `GET /rate_limit`)
+    pub(crate) fn update(&self, resource: RateResource, budget: ResourceBudget) {
+        self.lock().set(resource, budget);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RateBudgetSnapshot> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Convert a Unix timestamp from GitHub to a UTC time
+pub(crate) fn unix_time(secs: u64) -> DateTime<Utc> {
+    Utc.timestamp_opt(secs as i64, 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    fn rate_headers(resource: &str, remaining: u32, reset: DateTime<Utc>) -> HeaderMap {
+        headers(&[
+            ("x-ratelimit-resource", resource.to_string()),
+            ("x-ratelimit-limit", "5000".to_string()),
+            ("x-ratelimit-remaining", remaining.to_string()),
+            ("x-ratelimit-used", (5000 - remaining).to_string()),
+            ("x-ratelimit-reset", reset.timestamp().to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_record_tracks_each_resource() {
+        let budget = RateBudget::default();
+        let reset = Utc::now() + chrono::Duration::minutes(30);
+        budget.record(RateResource::Core, 200, &rate_headers("core", 4000, reset));
+        budget.record(
+            RateResource::GraphQL,
+            200,
+            &rate_headers("graphql", 4900, reset),
+        );
+        budget.record(RateResource::Core, 200, &rate_headers("search", 10, reset));
+
+        let snapshot = budget.snapshot();
+        let core = snapshot.core.unwrap();
+        assert_eq!(core.remaining, 4000);
+        assert_eq!(core.used, 1000);
+        assert_eq!(core.reset.timestamp(), reset.timestamp());
+        assert_eq!(snapshot.graphql.unwrap().remaining, 4900);
+        assert!(snapshot.paused_until.is_none());
+    }
+
+    #[test]
+    fn test_pauses_at_reserve_until_reset() {
+        let budget = RateBudget::new(50);
+        let reset = Utc::now() + chrono::Duration::minutes(10);
+        budget.record(RateResource::Core, 200, &rate_headers("core", 51, reset));
+        assert!(budget.resume_at(RateResource::Core).is_none());
+
+        budget.record(RateResource::Core, 200, &rate_headers("core", 50, reset));
+        let resume = budget.resume_at(RateResource::Core).unwrap();
+        assert_eq!(resume.timestamp(), reset.timestamp());
+        // The GraphQL bucket is separate
+        assert!(budget.resume_at(RateResource::GraphQL).is_none());
+
+        // A window that has already reset does not block
+        budget.record(
+            RateResource::Core,
+            200,
+            &rate_headers("core", 0, Utc::now()),
+        );
+        assert!(budget.resume_at(RateResource::Core).is_none());
+    }
+
+    #[test]
+    fn test_retry_after_pauses_every_resource() {
+        let budget = RateBudget::default();
+        budget.record(
+            RateResource::GraphQL,
+            403,
+            &headers(&[("retry-after", "30".to_string())]),
+        );
+
+        let resume = budget.resume_at(RateResource::GraphQL).unwrap();
+        let secs = (resume - Utc::now()).num_seconds();
+        assert!((28..=30).contains(&secs));
+        assert!(budget.resume_at(RateResource::Core).is_some());
+
+        // A plain 403 (e.g. missing permission) does not pause
+        let budget = RateBudget::default();
+        budget.record(RateResource::GraphQL, 403, &HeaderMap::new());
+        assert!(budget.snapshot().paused_until.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_counts_requests() {
+        let budget = RateBudget::new(0);
+        budget.update(
+            RateResource::Core,
+            ResourceBudget {
+                limit: 5000,
+                remaining: 2,
+                used: 4998,
+                reset: Utc::now() + chrono::Duration::minutes(5),
+            },
+        );
+        budget.acquire(RateResource::Core).await;
+        let core = budget.snapshot().core.unwrap();
+        assert_eq!(core.remaining, 1);
+        assert_eq!(core.used, 4999);
+
+        // Unknown buckets are not blocked
+        budget.acquire(RateResource::GraphQL).await;
+        assert!(budget.snapshot().graphql.is_none());
+    }
+}
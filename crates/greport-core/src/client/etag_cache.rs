@@ -0,0 +1,233 @@
+//! Stored responses for conditional REST requests
+//!
+//! GitHub answers a request carrying `If-None-Match` (or `If-Modified-Since`)
+//! with `304 Not Modified` when nothing changed, and 304s do not count
+//! against the rate limit. `OctocrabClient` keeps each response's validators
+//! and body in an [`EtagStore`] and replays the body on a 304, so re-running
+//! a report on an unchanged repository costs next to nothing.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// A stored response to a `GET` request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// Request URL (guards against key collisions)
+    pub url: String,
+    /// `ETag` response header
+    pub etag: Option<String>,
+    /// `Last-Modified` response header
+    pub last_modified: Option<String>,
+    /// `Link` response header, which carries the pagination links
+    pub link: Option<String>,
+    /// Response body
+    pub body: String,
+}
+
+impl CachedResponse {
+    /// Whether the response has a validator to send back
+    pub fn is_conditional(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// Storage for conditional-request responses
+///
+/// `key` identifies the token and URL; implementations only store and
+/// return entries. Failures should be logged and otherwise ignored, since a
+/// missing entry only costs a full request.
+pub trait EtagStore: Send + Sync + std::fmt::Debug {
+    /// Load the response stored for `key`
+    fn load(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store `response` under `key`, replacing any earlier one
+    fn store(&self, key: &str, response: &CachedResponse);
+}
+
+/// [`EtagStore`] keeping one JSON file per request under a directory
+///
+/// Entries hold response bodies, private repository data included, so the
+/// directory is created `0700` and each file `0600`.
+#[derive(Debug, Clone)]
+pub struct FileEtagStore {
+    dir: PathBuf,
+}
+
+impl FileEtagStore {
+    /// Store responses under `dir`, which is created on first write
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Default location: `<cache dir>/greport/github`
+    /// (e.g. `~/.cache/greport/github` on Linux)
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|d| d.join("greport").join("github"))
+    }
+
+    /// Directory the responses are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", sha256_hex(key.as_bytes())))
+    }
+}
+
+impl EtagStore for FileEtagStore {
+    fn load(&self, key: &str) -> Option<CachedResponse> {
+        let path = self.path(key);
+        let data = std::fs::read(&path).ok()?;
+        match serde_json::from_slice::<StoredEntry>(&data) {
+            Ok(entry) if entry.key == key => Some(entry.response),
+            Ok(_) => None,
+            Err(e) => {
+                debug!(path = %path.display(), error = %e, "Ignoring unreadable cached response");
+                None
+            }
+        }
+    }
+
+    fn store(&self, key: &str, response: &CachedResponse) {
+        let path = self.path(key);
+        let entry = StoredEntry {
+            key: key.to_string(),
+            response: response.clone(),
+        };
+        // Write to a temporary file first so readers never see half an entry
+        let tmp = path.with_extension("tmp");
+        let result = create_private_dir(&self.dir)
+            .and_then(|_| write_private(&tmp, &serde_json::to_vec(&entry).unwrap_or_default()))
+            .and_then(|_| std::fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            warn!(path = %path.display(), error = %e, "Failed to store cached response");
+        }
+    }
+}
+
+/// Create `dir` (and its parents), readable by the owner only
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+
+/// Write `data` to a new file at `path`, readable by the owner only
+fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(data)
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    key: String,
+    response: CachedResponse,
+}
+
+/// Store key for a request: the credentials and base URL (different
+/// tokens see different private data) plus the request URL
+///
+/// The scope is hashed so no token ends up in a file on disk.
+pub(crate) fn store_key(scope: &str, url: &str) -> String {
+    format!("{} {}", sha256_hex(scope.as_bytes()), url)
+}
+
+/// Hex-encoded SHA-256 digest of `bytes`
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// 64-bit FNV-1a hash, stable across builds (unlike `DefaultHasher`)
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> FileEtagStore {
+        let dir =
+            std::env::temp_dir().join(format!("greport-etag-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        FileEtagStore::new(dir)
+    }
+
+    fn response(url: &str) -> CachedResponse {
+        CachedResponse {
+            url: url.to_string(),
+            etag: Some("W/\"abc\"".to_string()),
+            last_modified: None,
+            link: Some("<https://api.github.com/x?page=2>; rel=\"next\"".to_string()),
+            body: "[1,2,3]".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let store = temp_store("round-trip");
+        let key = store_key("ghp_a https://api.github.com", "/repos/o/r/issues");
+        assert!(store.load(&key).is_none());
+
+        store.store(&key, &response("/repos/o/r/issues"));
+        assert_eq!(store.load(&key), Some(response("/repos/o/r/issues")));
+
+        // Same URL under another token is a different entry
+        let other = store_key("ghp_b https://api.github.com", "/repos/o/r/issues");
+        assert_ne!(key, other);
+        assert!(store.load(&other).is_none());
+
+        let _ = std::fs::remove_dir_all(store.dir());
+    }
+
+    #[test]
+    fn test_file_store_keeps_no_tokens() {
+        let store = temp_store("private");
+        let key = store_key("ghp_secret https://api.github.com", "/user");
+        assert!(!key.contains("ghp_secret"));
+        store.store(&key, &response("/user"));
+
+        let path = store.path(&key);
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("ghp_secret"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&path), 0o600);
+            assert_eq!(mode(store.dir()), 0o700);
+        }
+
+        let _ = std::fs::remove_dir_all(store.dir());
+    }
+
+    #[test]
+    fn test_file_store_ignores_corrupt_entries() {
+        let store = temp_store("corrupt");
+        let key = store_key("scope", "/user");
+        store.store(&key, &response("/user"));
+        std::fs::write(store.path(&key), b"not json").unwrap();
+        assert!(store.load(&key).is_none());
+
+        let _ = std::fs::remove_dir_all(store.dir());
+    }
+
+    #[test]
+    fn test_fnv1a_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -0,0 +1,655 @@
+//! Recorded fixtures for reproducible reports
+//!
+//! [`RecordingClient`] wraps a live client and writes every call and its
+//! response (or error) to a fixture directory, one JSON file per distinct
+//! call. [`ReplayClient`] later serves those files offline, so a report
+//! that came out oddly on someone's data can be reproduced exactly, in the
+//! CLI (`--record dir`, then `--replay dir`) or in a test.
+//!
+//! Calls are matched on their method and arguments. Issue and pull request
+//! listings are keyed on their filters without `since` and paging, so
+//! reports relative to "now" still find their fixtures when replayed later;
+//! the recorded `since` is kept and a replay asking for another one logs a
+//! warning.
+
+use super::{
+    GitHubApi, GitHubClient, IssueParams, IssueSort, IssueStateFilter, ProjectClient, PullParams,
+    PullStateFilter, RateBudgetSnapshot, RateLimitInfo, RepoId, SortDirection,
+};
+use crate::models::{
+    FieldUpdate, Issue, IssueEvent, IssueRelation, Milestone, Project, ProjectItem, PullRequest,
+    Release, Repository, Review, User,
+};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Directory of fixtures, stored as `<dir>/<method>/<sha256 of args>.json`
+#[derive(Debug, Clone)]
+struct FixtureDir {
+    dir: PathBuf,
+}
+
+impl FixtureDir {
+    fn path(&self, method: &str, args: &str) -> PathBuf {
+        self.dir.join(method).join(format!(
+            "{}.json",
+            super::etag_cache::sha256_hex(args.as_bytes())
+        ))
+    }
+
+    /// Write the outcome of a call, logging (not failing) on I/O errors
+    fn save<T: Serialize>(
+        &self,
+        method: &str,
+        args: &str,
+        since: Option<DateTime<Utc>>,
+        result: &Result<T>,
+    ) {
+        let outcome = match result {
+            Ok(value) => match serde_json::to_value(value) {
+                Ok(value) => Outcome::Response(value),
+                Err(e) => {
+                    warn!(method = method, error = %e, "Failed to serialize response");
+                    return;
+                }
+            },
+            Err(e) => Outcome::Error(RecordedError::from(e)),
+        };
+        let fixture = Fixture {
+            method: method.to_string(),
+            args: args.to_string(),
+            since,
+            outcome,
+        };
+
+        let path = self.path(method, args);
+        let tmp = path.with_extension("tmp");
+        let result = std::fs::create_dir_all(path.parent().unwrap_or(&self.dir))
+            .and_then(|_| {
+                std::fs::write(
+                    &tmp,
+                    serde_json::to_vec_pretty(&fixture).unwrap_or_default(),
+                )
+            })
+            .and_then(|_| std::fs::rename(&tmp, &path));
+        match result {
+            Ok(()) => debug!(method = method, args = args, "Recorded fixture"),
+            Err(e) => warn!(path = %path.display(), error = %e, "Failed to record fixture"),
+        }
+    }
+
+    /// Read back the outcome of a call
+    fn load<T: DeserializeOwned>(&self, method: &str, args: &str) -> Result<T> {
+        self.load_since(method, args, None)
+    }
+
+    /// Read back the outcome of a listing fetched from `since`, warning when
+    /// it was recorded from another date
+    fn load_since<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        args: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<T> {
+        let path = self.path(method, args);
+        let missing = || {
+            Error::Custom(format!(
+                "No recorded {} call for '{}' in {}",
+                method,
+                args,
+                self.dir.display()
+            ))
+        };
+        let data = std::fs::read(&path).map_err(|_| missing())?;
+        let fixture: Fixture = serde_json::from_slice(&data)
+            .map_err(|e| Error::Custom(format!("Unreadable fixture {}: {}", path.display(), e)))?;
+        if fixture.method != method || fixture.args != args {
+            return Err(missing());
+        }
+        if fixture.since != since {
+            let show = |since: Option<DateTime<Utc>>| {
+                since.map_or_else(|| "the beginning".to_string(), |s| s.to_rfc3339())
+            };
+            warn!(
+                method = method,
+                args = args,
+                recorded = %show(fixture.since),
+                requested = %show(since),
+                "Replaying a listing recorded from a different since"
+            );
+        }
+        match fixture.outcome {
+            Outcome::Response(value) => serde_json::from_value(value).map_err(|e| {
+                Error::Custom(format!("Unreadable fixture {}: {}", path.display(), e))
+            }),
+            Outcome::Error(e) => Err(e.into()),
+        }
+    }
+}
+
+/// One recorded call
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    args: String,
+    /// `since` of a listing, which is not part of `args`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    Response(serde_json::Value),
+    Error(RecordedError),
+}
+
+/// The parts of an [`Error`] that callers branch on
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+enum RecordedError {
+    NotFound(String),
+    InvalidToken,
+    RateLimitExceeded(String),
+    Other(String),
+}
+
+impl From<&Error> for RecordedError {
+    fn from(e: &Error) -> Self {
+        match e {
+            Error::NotFound(what) => RecordedError::NotFound(what.clone()),
+            Error::InvalidToken => RecordedError::InvalidToken,
+            Error::RateLimitExceeded { reset_at } => {
+                RecordedError::RateLimitExceeded(reset_at.clone())
+            }
+            other => RecordedError::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<RecordedError> for Error {
+    fn from(e: RecordedError) -> Self {
+        match e {
+            RecordedError::NotFound(what) => Error::NotFound(what),
+            RecordedError::InvalidToken => Error::InvalidToken,
+            RecordedError::RateLimitExceeded(reset_at) => Error::RateLimitExceeded { reset_at },
+            RecordedError::Other(message) => Error::Custom(message),
+        }
+    }
+}
+
+/// Fixture key of an issue listing: its filters, without `since` and paging
+#[derive(Serialize)]
+struct IssueListingKey<'a> {
+    repo: String,
+    state: IssueStateFilter,
+    labels: Option<&'a [String]>,
+    assignee: Option<&'a str>,
+    milestone: Option<&'a str>,
+    creator: Option<&'a str>,
+    mentioned: Option<&'a str>,
+    sort: IssueSort,
+    direction: SortDirection,
+}
+
+/// Fixture key of a pull request listing: its filters, without paging
+#[derive(Serialize)]
+struct PullListingKey<'a> {
+    repo: String,
+    state: PullStateFilter,
+    head: Option<&'a str>,
+    base: Option<&'a str>,
+    sort: IssueSort,
+    direction: SortDirection,
+}
+
+/// Fixture arguments of an issue listing
+fn issue_args(repo: &RepoId, params: &IssueParams) -> String {
+    let key = IssueListingKey {
+        repo: repo.to_string(),
+        state: params.state,
+        labels: params.labels.as_deref(),
+        assignee: params.assignee.as_deref(),
+        milestone: params.milestone.as_deref(),
+        creator: params.creator.as_deref(),
+        mentioned: params.mentioned.as_deref(),
+        sort: params.sort,
+        direction: params.direction,
+    };
+    serde_json::to_string(&key).unwrap_or_default()
+}
+
+/// Fixture arguments of a pull request listing
+fn pull_args(repo: &RepoId, params: &PullParams) -> String {
+    let key = PullListingKey {
+        repo: repo.to_string(),
+        state: params.state,
+        head: params.head.as_deref(),
+        base: params.base.as_deref(),
+        sort: params.sort,
+        direction: params.direction,
+    };
+    serde_json::to_string(&key).unwrap_or_default()
+}
+
+/// Fixture arguments of a project field update
+fn update_args(project: &str, item: &str, field: &str, update: &FieldUpdate) -> String {
+    format!(
+        "{} {} {} {}",
+        project,
+        item,
+        field,
+        serde_json::to_string(update).unwrap_or_default()
+    )
+}
+
+/// Client that passes every call through and records it to a fixture directory
+pub struct RecordingClient {
+    inner: Arc<dyn GitHubApi>,
+    fixtures: FixtureDir,
+}
+
+impl RecordingClient {
+    /// Record the calls made to `inner` under `dir`, which is created on
+    /// first write. Fixtures of earlier runs are overwritten call by call.
+    pub fn new(inner: Arc<dyn GitHubApi>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            fixtures: FixtureDir { dir: dir.into() },
+        }
+    }
+
+    /// Directory the fixtures are written to
+    pub fn dir(&self) -> &Path {
+        &self.fixtures.dir
+    }
+
+    fn record<T: Serialize>(&self, method: &str, args: &str, result: Result<T>) -> Result<T> {
+        self.fixtures.save(method, args, None, &result);
+        result
+    }
+}
+
+#[async_trait]
+impl GitHubClient for RecordingClient {
+    async fn get_repository(&self, repo: &RepoId) -> Result<Repository> {
+        let result = self.inner.get_repository(repo).await;
+        self.record("get_repository", &repo.to_string(), result)
+    }
+
+    async fn list_org_repos(&self, org: &str) -> Result<Vec<Repository>> {
+        let result = self.inner.list_org_repos(org).await;
+        self.record("list_org_repos", org, result)
+    }
+
+    async fn list_issues(&self, repo: &RepoId, params: IssueParams) -> Result<Vec<Issue>> {
+        let args = issue_args(repo, &params);
+        let since = params.since;
+        let result = self.inner.list_issues(repo, params).await;
+        self.fixtures.save("list_issues", &args, since, &result);
+        result
+    }
+
+    async fn get_issue(&self, repo: &RepoId, number: u64) -> Result<Issue> {
+        let result = self.inner.get_issue(repo, number).await;
+        self.record("get_issue", &format!("{}#{}", repo, number), result)
+    }
+
+    async fn list_issue_events(&self, repo: &RepoId, number: u64) -> Result<Vec<IssueEvent>> {
+        let result = self.inner.list_issue_events(repo, number).await;
+        self.record("list_issue_events", &format!("{}#{}", repo, number), result)
+    }
+
+    async fn list_issue_relations(&self, repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        let result = self.inner.list_issue_relations(repo).await;
+        self.record("list_issue_relations", &repo.to_string(), result)
+    }
+
+    async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
+        let result = self.inner.list_milestones(repo).await;
+        self.record("list_milestones", &repo.to_string(), result)
+    }
+
+    async fn list_pulls(&self, repo: &RepoId, params: PullParams) -> Result<Vec<PullRequest>> {
+        let args = pull_args(repo, &params);
+        let result = self.inner.list_pulls(repo, params).await;
+        self.record("list_pulls", &args, result)
+    }
+
+    async fn get_pull(&self, repo: &RepoId, number: u64) -> Result<PullRequest> {
+        let result = self.inner.get_pull(repo, number).await;
+        self.record("get_pull", &format!("{}#{}", repo, number), result)
+    }
+
+    async fn list_pull_reviews(&self, repo: &RepoId, number: u64) -> Result<Vec<Review>> {
+        let result = self.inner.list_pull_reviews(repo, number).await;
+        self.record("list_pull_reviews", &format!("{}#{}", repo, number), result)
+    }
+
+    async fn list_releases(&self, repo: &RepoId) -> Result<Vec<Release>> {
+        let result = self.inner.list_releases(repo).await;
+        self.record("list_releases", &repo.to_string(), result)
+    }
+
+    async fn get_release(&self, repo: &RepoId, tag: &str) -> Result<Release> {
+        let result = self.inner.get_release(repo, tag).await;
+        self.record("get_release", &format!("{}@{}", repo, tag), result)
+    }
+
+    async fn get_user(&self, username: &str) -> Result<User> {
+        let result = self.inner.get_user(username).await;
+        self.record("get_user", username, result)
+    }
+
+    async fn rate_limit(&self) -> Result<RateLimitInfo> {
+        let result = self.inner.rate_limit().await;
+        self.record("rate_limit", "", result)
+    }
+
+    fn rate_budget(&self) -> Option<RateBudgetSnapshot> {
+        self.inner.rate_budget()
+    }
+}
+
+#[async_trait]
+impl ProjectClient for RecordingClient {
+    async fn list_projects(&self, org: &str) -> Result<Vec<Project>> {
+        let result = self.inner.list_projects(org).await;
+        self.record("list_projects", org, result)
+    }
+
+    async fn get_project(&self, org: &str, project_number: u64) -> Result<Project> {
+        let result = self.inner.get_project(org, project_number).await;
+        self.record(
+            "get_project",
+            &format!("{}/{}", org, project_number),
+            result,
+        )
+    }
+
+    async fn list_project_items(&self, project_node_id: &str) -> Result<Vec<ProjectItem>> {
+        let result = self.inner.list_project_items(project_node_id).await;
+        self.record("list_project_items", project_node_id, result)
+    }
+
+    async fn update_project_item_field(
+        &self,
+        project_node_id: &str,
+        item_node_id: &str,
+        field_node_id: &str,
+        update: &FieldUpdate,
+    ) -> Result<()> {
+        let result = self
+            .inner
+            .update_project_item_field(project_node_id, item_node_id, field_node_id, update)
+            .await;
+        let args = update_args(project_node_id, item_node_id, field_node_id, update);
+        self.record("update_project_item_field", &args, result)
+    }
+
+    async fn add_project_item(
+        &self,
+        project_node_id: &str,
+        repo: &RepoId,
+        number: u64,
+    ) -> Result<String> {
+        let result = self
+            .inner
+            .add_project_item(project_node_id, repo, number)
+            .await;
+        let args = format!("{} {}#{}", project_node_id, repo, number);
+        self.record("add_project_item", &args, result)
+    }
+
+    async fn archive_project_item(&self, project_node_id: &str, item_node_id: &str) -> Result<()> {
+        let result = self
+            .inner
+            .archive_project_item(project_node_id, item_node_id)
+            .await;
+        let args = format!("{} {}", project_node_id, item_node_id);
+        self.record("archive_project_item", &args, result)
+    }
+}
+
+/// Client that answers every call from fixtures written by [`RecordingClient`]
+///
+/// A call with no fixture fails with an error naming the call and directory.
+#[derive(Debug, Clone)]
+pub struct ReplayClient {
+    fixtures: FixtureDir,
+}
+
+impl ReplayClient {
+    /// Serve the fixtures recorded under `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            fixtures: FixtureDir { dir: dir.into() },
+        }
+    }
+
+    /// Directory the fixtures are read from
+    pub fn dir(&self) -> &Path {
+        &self.fixtures.dir
+    }
+}
+
+#[async_trait]
+impl GitHubClient for ReplayClient {
+    async fn get_repository(&self, repo: &RepoId) -> Result<Repository> {
+        self.fixtures.load("get_repository", &repo.to_string())
+    }
+
+    async fn list_org_repos(&self, org: &str) -> Result<Vec<Repository>> {
+        self.fixtures.load("list_org_repos", org)
+    }
+
+    async fn list_issues(&self, repo: &RepoId, params: IssueParams) -> Result<Vec<Issue>> {
+        self.fixtures
+            .load_since("list_issues", &issue_args(repo, &params), params.since)
+    }
+
+    async fn get_issue(&self, repo: &RepoId, number: u64) -> Result<Issue> {
+        self.fixtures
+            .load("get_issue", &format!("{}#{}", repo, number))
+    }
+
+    async fn list_issue_events(&self, repo: &RepoId, number: u64) -> Result<Vec<IssueEvent>> {
+        self.fixtures
+            .load("list_issue_events", &format!("{}#{}", repo, number))
+    }
+
+    async fn list_issue_relations(&self, repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        self.fixtures
+            .load("list_issue_relations", &repo.to_string())
+    }
+
+    async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
+        self.fixtures.load("list_milestones", &repo.to_string())
+    }
+
+    async fn list_pulls(&self, repo: &RepoId, params: PullParams) -> Result<Vec<PullRequest>> {
+        self.fixtures.load("list_pulls", &pull_args(repo, &params))
+    }
+
+    async fn get_pull(&self, repo: &RepoId, number: u64) -> Result<PullRequest> {
+        self.fixtures
+            .load("get_pull", &format!("{}#{}", repo, number))
+    }
+
+    async fn list_pull_reviews(&self, repo: &RepoId, number: u64) -> Result<Vec<Review>> {
+        self.fixtures
+            .load("list_pull_reviews", &format!("{}#{}", repo, number))
+    }
+
+    async fn list_releases(&self, repo: &RepoId) -> Result<Vec<Release>> {
+        self.fixtures.load("list_releases", &repo.to_string())
+    }
+
+    async fn get_release(&self, repo: &RepoId, tag: &str) -> Result<Release> {
+        self.fixtures
+            .load("get_release", &format!("{}@{}", repo, tag))
+    }
+
+    async fn get_user(&self, username: &str) -> Result<User> {
+        self.fixtures.load("get_user", username)
+    }
+
+    async fn rate_limit(&self) -> Result<RateLimitInfo> {
+        self.fixtures.load("rate_limit", "")
+    }
+}
+
+#[async_trait]
+impl ProjectClient for ReplayClient {
+    async fn list_projects(&self, org: &str) -> Result<Vec<Project>> {
+        self.fixtures.load("list_projects", org)
+    }
+
+    async fn get_project(&self, org: &str, project_number: u64) -> Result<Project> {
+        self.fixtures
+            .load("get_project", &format!("{}/{}", org, project_number))
+    }
+
+    async fn list_project_items(&self, project_node_id: &str) -> Result<Vec<ProjectItem>> {
+        self.fixtures.load("list_project_items", project_node_id)
+    }
+
+    async fn update_project_item_field(
+        &self,
+        project_node_id: &str,
+        item_node_id: &str,
+        field_node_id: &str,
+        update: &FieldUpdate,
+    ) -> Result<()> {
+        let args = update_args(project_node_id, item_node_id, field_node_id, update);
+        self.fixtures.load("update_project_item_field", &args)
+    }
+
+    async fn add_project_item(
+        &self,
+        project_node_id: &str,
+        repo: &RepoId,
+        number: u64,
+    ) -> Result<String> {
+        let args = format!("{} {}#{}", project_node_id, repo, number);
+        self.fixtures.load("add_project_item", &args)
+    }
+
+    async fn archive_project_item(&self, project_node_id: &str, item_node_id: &str) -> Result<()> {
+        let args = format!("{} {}", project_node_id, item_node_id);
+        self.fixtures.load("archive_project_item", &args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::MockGitHubClient;
+    use chrono::{Duration, Utc};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("greport-fixtures-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = temp_dir("round-trip");
+        let repo = RepoId::new("test-owner", "test-repo");
+        let recorder = RecordingClient::new(Arc::new(MockGitHubClient::with_sample_data()), &dir);
+
+        let since = Utc::now() - Duration::days(30);
+        let issues = recorder
+            .list_issues(&repo, IssueParams::open().since(since))
+            .await
+            .unwrap();
+        let pulls = recorder.list_pulls(&repo, PullParams::all()).await.unwrap();
+        let projects = recorder.list_projects("test-owner").await.unwrap();
+        assert!(matches!(
+            recorder.get_issue(&repo, 9999).await,
+            Err(Error::NotFound(_))
+        ));
+
+        let replay = ReplayClient::new(&dir);
+        // A later `since` still finds the recorded listing
+        let replayed = replay
+            .list_issues(&repo, IssueParams::open().since(since + Duration::hours(1)))
+            .await
+            .unwrap();
+        assert_eq!(
+            replayed.iter().map(|i| i.number).collect::<Vec<_>>(),
+            issues.iter().map(|i| i.number).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            replay
+                .list_pulls(&repo, PullParams::all())
+                .await
+                .unwrap()
+                .len(),
+            pulls.len()
+        );
+        assert_eq!(
+            replay.list_projects("test-owner").await.unwrap()[0].title,
+            projects[0].title
+        );
+        assert!(matches!(
+            replay.get_issue(&repo, 9999).await,
+            Err(Error::NotFound(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_listing_keys_are_stable() {
+        let repo = RepoId::new("acme", "api");
+        let params = IssueParams::open().since(Utc::now());
+        assert_eq!(
+            issue_args(&repo, &params),
+            r#"{"repo":"acme/api","state":"open","labels":null,"assignee":null,"milestone":null,"creator":null,"mentioned":null,"sort":"created","direction":"desc"}"#
+        );
+        assert_eq!(
+            pull_args(&repo, &PullParams::all()),
+            r#"{"repo":"acme/api","state":"all","head":null,"base":null,"sort":"created","direction":"desc"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_listing_keeps_since() {
+        let dir = temp_dir("since");
+        let repo = RepoId::new("test-owner", "test-repo");
+        let recorder = RecordingClient::new(Arc::new(MockGitHubClient::with_sample_data()), &dir);
+        let since = Utc::now() - Duration::days(7);
+        let params = IssueParams::open().since(since);
+        recorder.list_issues(&repo, params.clone()).await.unwrap();
+
+        let path = recorder
+            .fixtures
+            .path("list_issues", &issue_args(&repo, &params));
+        let fixture: Fixture = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!(fixture.since, Some(since));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_replay_without_fixture() {
+        let dir = temp_dir("missing");
+        let replay = ReplayClient::new(&dir);
+        let err = replay
+            .list_releases(&RepoId::new("acme", "api"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No recorded list_releases call"));
+    }
+}
@@ -0,0 +1,811 @@
+//! Gitea backend
+//!
+//! Maps Gitea (and Forgejo) repositories, issues, pull requests, milestones
+//! and releases onto the GitHub-shaped models, so metrics, reports and API
+//! routes work on an organization configured with `provider = "gitea"`.
+//! Gitea's API mirrors GitHub's closely; the differences handled here are
+//! the `limit` page size, issue events read from the issue timeline, and
+//! filters Gitea lacks (branches, "no milestone"), applied client-side.
+//!
+//! Gitea has no Projects V2 and no sub-issue hierarchy, so Projects calls
+//! fail and no issue relations are listed.
+
+use super::rest::{encode_segment, Query, RestApi};
+use super::{
+    GitHubClient, IssueParams, IssueSort, IssueStateFilter, Pagination, ProjectClient, PullParams,
+    PullStateFilter, RateLimitInfo, RepoId, RetryConfig, SortDirection,
+};
+use crate::models::{
+    FieldUpdate, Issue, IssueEvent, IssueRelation, IssueState, Label, Milestone, MilestoneState,
+    Project, ProjectItem, PullRequest, PullState, Release, Repository, Review, User,
+};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tracing::{info, instrument};
+
+/// Client for the Gitea REST API (v1)
+#[derive(Debug)]
+pub struct GiteaClient {
+    api: RestApi,
+    pagination: Pagination,
+}
+
+impl GiteaClient {
+    /// Create a client with an access token
+    ///
+    /// `base_url` is the API root, e.g. `https://gitea.example.com/api/v1`.
+    pub fn new(token: &str, base_url: &str) -> Result<Self> {
+        info!(base_url = %base_url, "Creating Gitea client");
+        Ok(Self {
+            api: RestApi::new(
+                "Gitea",
+                base_url,
+                "authorization",
+                &format!("token {}", token),
+                "limit",
+            )?,
+            pagination: Pagination::default(),
+        })
+    }
+
+    /// Set custom retry configuration
+    pub fn with_retry_config(mut self, config: RetryConfig) -> Self {
+        self.api.set_retry_config(config);
+        self
+    }
+
+    /// Set the item cap and progress callback for list calls
+    pub fn with_pagination(mut self, pagination: Pagination) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// API path of a repository
+    fn repo(repo: &RepoId) -> String {
+        format!(
+            "/repos/{}/{}",
+            encode_segment(&repo.owner),
+            encode_segment(&repo.name)
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Gitea API types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct GtUser {
+    id: i64,
+    login: String,
+    #[serde(default)]
+    avatar_url: String,
+    #[serde(default)]
+    html_url: String,
+}
+
+impl From<GtUser> for User {
+    fn from(user: GtUser) -> Self {
+        User {
+            id: user.id,
+            login: user.login,
+            avatar_url: user.avatar_url,
+            html_url: user.html_url,
+            is_bot: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtLabel {
+    id: i64,
+    name: String,
+    color: String,
+    description: Option<String>,
+}
+
+impl From<GtLabel> for Label {
+    fn from(label: GtLabel) -> Self {
+        Label {
+            id: label.id,
+            name: label.name,
+            color: label.color.trim_start_matches('#').to_string(),
+            description: label.description.filter(|d| !d.is_empty()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtMilestone {
+    id: i64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    #[serde(default)]
+    open_issues: u32,
+    #[serde(default)]
+    closed_issues: u32,
+    created_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    due_on: Option<DateTime<Utc>>,
+}
+
+impl From<GtMilestone> for Milestone {
+    fn from(milestone: GtMilestone) -> Self {
+        Milestone {
+            id: milestone.id,
+            // Gitea milestones have no per-repository number
+            number: milestone.id as u64,
+            title: milestone.title,
+            description: milestone.description.filter(|d| !d.is_empty()),
+            state: if milestone.state == "closed" {
+                MilestoneState::Closed
+            } else {
+                MilestoneState::Open
+            },
+            open_issues: milestone.open_issues,
+            closed_issues: milestone.closed_issues,
+            due_on: milestone.due_on,
+            created_at: milestone.created_at,
+            closed_at: milestone.closed_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtIssue {
+    id: i64,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<GtLabel>,
+    /// `null` when unassigned
+    assignees: Option<Vec<GtUser>>,
+    milestone: Option<GtMilestone>,
+    user: GtUser,
+    #[serde(default)]
+    comments: u32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+impl From<GtIssue> for Issue {
+    fn from(issue: GtIssue) -> Self {
+        Issue {
+            id: issue.id,
+            number: issue.number,
+            title: issue.title,
+            body: issue.body.filter(|b| !b.is_empty()),
+            state: if issue.state == "closed" {
+                IssueState::Closed
+            } else {
+                IssueState::Open
+            },
+            labels: issue.labels.into_iter().map(Label::from).collect(),
+            assignees: issue
+                .assignees
+                .unwrap_or_default()
+                .into_iter()
+                .map(User::from)
+                .collect(),
+            milestone: issue.milestone.map(Milestone::from),
+            author: issue.user.into(),
+            comments_count: issue.comments,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            closed_at: issue.closed_at,
+            closed_by: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtBranch {
+    #[serde(rename = "ref")]
+    ref_name: String,
+}
+
+#[derive(Deserialize)]
+struct GtPull {
+    id: i64,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    user: GtUser,
+    #[serde(default)]
+    labels: Vec<GtLabel>,
+    milestone: Option<GtMilestone>,
+    head: GtBranch,
+    base: GtBranch,
+    #[serde(default)]
+    merged: bool,
+    merged_at: Option<DateTime<Utc>>,
+    additions: Option<u32>,
+    deletions: Option<u32>,
+    changed_files: Option<u32>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+impl From<GtPull> for PullRequest {
+    fn from(pull: GtPull) -> Self {
+        PullRequest {
+            id: pull.id,
+            number: pull.number,
+            title: pull.title,
+            body: pull.body.filter(|b| !b.is_empty()),
+            state: if pull.state == "closed" {
+                PullState::Closed
+            } else {
+                PullState::Open
+            },
+            draft: pull.draft,
+            author: pull.user.into(),
+            labels: pull.labels.into_iter().map(Label::from).collect(),
+            milestone: pull.milestone.map(Milestone::from),
+            head_ref: pull.head.ref_name,
+            base_ref: pull.base.ref_name,
+            merged: pull.merged,
+            merged_at: pull.merged_at,
+            additions: pull.additions.unwrap_or(0),
+            deletions: pull.deletions.unwrap_or(0),
+            changed_files: pull.changed_files.unwrap_or(0),
+            created_at: pull.created_at,
+            updated_at: pull.updated_at,
+            closed_at: pull.closed_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtRepository {
+    id: i64,
+    owner: GtUser,
+    name: String,
+    full_name: String,
+    description: Option<String>,
+    #[serde(default)]
+    private: bool,
+    default_branch: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<GtRepository> for Repository {
+    fn from(repo: GtRepository) -> Self {
+        Repository {
+            id: repo.id,
+            owner: repo.owner.login,
+            name: repo.name,
+            full_name: repo.full_name,
+            description: repo.description.filter(|d| !d.is_empty()),
+            private: repo.private,
+            default_branch: repo.default_branch.unwrap_or_else(|| "main".to_string()),
+            created_at: repo.created_at,
+            updated_at: repo.updated_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtRelease {
+    id: i64,
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    author: Option<GtUser>,
+    created_at: DateTime<Utc>,
+    published_at: Option<DateTime<Utc>>,
+}
+
+impl From<GtRelease> for Release {
+    fn from(release: GtRelease) -> Self {
+        Release {
+            id: release.id,
+            tag_name: release.tag_name,
+            name: release.name.filter(|n| !n.is_empty()),
+            body: release.body.filter(|b| !b.is_empty()),
+            draft: release.draft,
+            prerelease: release.prerelease,
+            author: release.author.map(User::from).unwrap_or_else(User::unknown),
+            created_at: release.created_at,
+            published_at: release.published_at.filter(|_| !release.draft),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtReview {
+    id: i64,
+    user: Option<GtUser>,
+    body: Option<String>,
+    state: String,
+    submitted_at: Option<DateTime<Utc>>,
+}
+
+impl From<GtReview> for Review {
+    fn from(review: GtReview) -> Self {
+        Review {
+            id: review.id,
+            user: review.user.map(User::from),
+            body: review.body.filter(|b| !b.is_empty()),
+            state: match review.state.as_str() {
+                "REQUEST_CHANGES" => "CHANGES_REQUESTED".to_string(),
+                "COMMENT" => "COMMENTED".to_string(),
+                state => state.to_string(),
+            },
+            submitted_at: review.submitted_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GtLabelName {
+    name: String,
+}
+
+/// An entry of an issue's timeline
+#[derive(Deserialize)]
+struct GtTimelineEvent {
+    id: i64,
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    body: String,
+    user: Option<GtUser>,
+    created_at: DateTime<Utc>,
+    label: Option<GtLabelName>,
+    assignee: Option<GtUser>,
+    #[serde(default)]
+    removed_assignee: bool,
+}
+
+impl GtTimelineEvent {
+    /// The GitHub event this entry corresponds to, if any
+    fn into_event(self) -> Option<IssueEvent> {
+        let event_type = match self.event_type.as_str() {
+            "comment" => "commented",
+            "close" => "closed",
+            "reopen" => "reopened",
+            // Gitea stores "1" in the body of a label being added
+            "label" if self.body == "1" => "labeled",
+            "label" => "unlabeled",
+            "assignees" if self.removed_assignee => "unassigned",
+            "assignees" => "assigned",
+            "milestone" => "milestoned",
+            _ => return None,
+        };
+        Some(IssueEvent {
+            id: self.id,
+            event_type: event_type.to_string(),
+            actor: self.user.map(User::from),
+            created_at: self.created_at,
+            label_name: self.label.map(|l| l.name),
+            assignee: self.assignee.map(User::from),
+        })
+    }
+}
+
+#[async_trait]
+impl GitHubClient for GiteaClient {
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_repository(&self, repo: &RepoId) -> Result<Repository> {
+        let repository: GtRepository = self
+            .api
+            .get("get_repository", &Self::repo(repo), &Query::new())
+            .await?;
+        Ok(repository.into())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_org_repos(&self, org: &str) -> Result<Vec<Repository>> {
+        let path = format!("/orgs/{}/repos", encode_segment(org));
+        let repos: Vec<GtRepository> = self
+            .api
+            .list("list_org_repos", &path, &Query::new(), &self.pagination)
+            .await?;
+        Ok(repos.into_iter().map(Repository::from).collect())
+    }
+
+    #[instrument(skip(self, params), fields(repo = %repo))]
+    async fn list_issues(&self, repo: &RepoId, params: IssueParams) -> Result<Vec<Issue>> {
+        let state = match params.state {
+            IssueStateFilter::Open => "open",
+            IssueStateFilter::Closed => "closed",
+            IssueStateFilter::All => "all",
+        };
+        let mut query: Query = vec![("type", "issues".to_string()), ("state", state.to_string())];
+        if let Some(labels) = &params.labels {
+            query.push(("labels", labels.join(",")));
+        }
+        if let Some(assignee) = &params.assignee {
+            query.push(("assigned_by", assignee.clone()));
+        }
+        // "none" and "*" have no Gitea equivalent and are filtered below
+        let milestone = params.milestone.as_deref();
+        if let Some(milestone) = milestone.filter(|m| *m != "none" && *m != "*") {
+            query.push(("milestones", milestone.to_string()));
+        }
+        if let Some(creator) = &params.creator {
+            query.push(("created_by", creator.clone()));
+        }
+        if let Some(mentioned) = &params.mentioned {
+            query.push(("mentioned_by", mentioned.clone()));
+        }
+        if let Some(since) = params.since {
+            query.push(("since", since.to_rfc3339()));
+        }
+
+        let path = format!("{}/issues", Self::repo(repo));
+        let issues: Vec<GtIssue> = self
+            .api
+            .list("list_issues", &path, &query, &self.pagination)
+            .await?;
+        Ok(issues
+            .into_iter()
+            .map(Issue::from)
+            .filter(|i| match milestone {
+                Some("none") => i.milestone.is_none(),
+                Some("*") => i.milestone.is_some(),
+                _ => true,
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_issue(&self, repo: &RepoId, number: u64) -> Result<Issue> {
+        let path = format!("{}/issues/{}", Self::repo(repo), number);
+        let issue: GtIssue = self.api.get("get_issue", &path, &Query::new()).await?;
+        Ok(issue.into())
+    }
+
+    /// Comments, state, label, assignee and milestone changes, from the
+    /// issue timeline
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_issue_events(&self, repo: &RepoId, number: u64) -> Result<Vec<IssueEvent>> {
+        let path = format!("{}/issues/{}/timeline", Self::repo(repo), number);
+        let timeline: Vec<GtTimelineEvent> = self
+            .api
+            .list("list_issue_events", &path, &Query::new(), &self.pagination)
+            .await?;
+        Ok(timeline
+            .into_iter()
+            .filter_map(GtTimelineEvent::into_event)
+            .collect())
+    }
+
+    /// Gitea issue dependencies are not mapped to relations
+    async fn list_issue_relations(&self, _repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        Ok(Vec::new())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
+        let path = format!("{}/milestones", Self::repo(repo));
+        let query = vec![("state", "all".to_string())];
+        let milestones: Vec<GtMilestone> = self
+            .api
+            .list("list_milestones", &path, &query, &self.pagination)
+            .await?;
+        Ok(milestones.into_iter().map(Milestone::from).collect())
+    }
+
+    #[instrument(skip(self, params), fields(repo = %repo))]
+    async fn list_pulls(&self, repo: &RepoId, params: PullParams) -> Result<Vec<PullRequest>> {
+        let state = match params.state {
+            PullStateFilter::Open => "open",
+            PullStateFilter::Closed => "closed",
+            PullStateFilter::All => "all",
+        };
+        let mut query: Query = vec![("state", state.to_string())];
+        let sort = match (params.sort, params.direction) {
+            (IssueSort::Created, SortDirection::Asc) => Some("oldest"),
+            (IssueSort::Created, SortDirection::Desc) => None,
+            (IssueSort::Updated, SortDirection::Asc) => Some("leastupdate"),
+            (IssueSort::Updated, SortDirection::Desc) => Some("recentupdate"),
+            (IssueSort::Comments, SortDirection::Asc) => Some("leastcomment"),
+            (IssueSort::Comments, SortDirection::Desc) => Some("mostcomment"),
+        };
+        if let Some(sort) = sort {
+            query.push(("sort", sort.to_string()));
+        }
+
+        let path = format!("{}/pulls", Self::repo(repo));
+        let pulls: Vec<GtPull> = self
+            .api
+            .list("list_pulls", &path, &query, &self.pagination)
+            .await?;
+        // Gitea cannot filter the list by branch
+        Ok(pulls
+            .into_iter()
+            .map(PullRequest::from)
+            .filter(|p| params.head.as_ref().is_none_or(|h| *h == p.head_ref))
+            .filter(|p| params.base.as_ref().is_none_or(|b| *b == p.base_ref))
+            .collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_pull(&self, repo: &RepoId, number: u64) -> Result<PullRequest> {
+        let path = format!("{}/pulls/{}", Self::repo(repo), number);
+        let pull: GtPull = self.api.get("get_pull", &path, &Query::new()).await?;
+        Ok(pull.into())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_pull_reviews(&self, repo: &RepoId, number: u64) -> Result<Vec<Review>> {
+        let path = format!("{}/pulls/{}/reviews", Self::repo(repo), number);
+        let reviews: Vec<GtReview> = self
+            .api
+            .list("list_pull_reviews", &path, &Query::new(), &self.pagination)
+            .await?;
+        Ok(reviews.into_iter().map(Review::from).collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_releases(&self, repo: &RepoId) -> Result<Vec<Release>> {
+        let path = format!("{}/releases", Self::repo(repo));
+        let releases: Vec<GtRelease> = self
+            .api
+            .list("list_releases", &path, &Query::new(), &self.pagination)
+            .await?;
+        Ok(releases.into_iter().map(Release::from).collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_release(&self, repo: &RepoId, tag: &str) -> Result<Release> {
+        let path = format!("{}/releases/tags/{}", Self::repo(repo), encode_segment(tag));
+        let release: GtRelease = self.api.get("get_release", &path, &Query::new()).await?;
+        Ok(release.into())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user(&self, username: &str) -> Result<User> {
+        let path = format!("/users/{}", encode_segment(username));
+        let user: GtUser = self.api.get("get_user", &path, &Query::new()).await?;
+        Ok(user.into())
+    }
+
+    /// Gitea does not rate limit its API; this checks the token with a
+    /// `GET /user` and reports `u32::MAX`
+    async fn rate_limit(&self) -> Result<RateLimitInfo> {
+        self.api
+            .get_response("rate_limit", "/user", &Query::new())
+            .await?;
+        Ok(RateLimitInfo {
+            remaining: u32::MAX,
+            limit: u32::MAX,
+            reset: 0,
+        })
+    }
+}
+
+/// Gitea has no GitHub Projects
+#[async_trait]
+impl ProjectClient for GiteaClient {
+    async fn list_projects(&self, _org: &str) -> Result<Vec<Project>> {
+        Err(projects_unsupported())
+    }
+
+    async fn get_project(&self, _org: &str, _project_number: u64) -> Result<Project> {
+        Err(projects_unsupported())
+    }
+
+    async fn list_project_items(&self, _project_node_id: &str) -> Result<Vec<ProjectItem>> {
+        Err(projects_unsupported())
+    }
+
+    async fn update_project_item_field(
+        &self,
+        _project_node_id: &str,
+        _item_node_id: &str,
+        _field_node_id: &str,
+        _update: &FieldUpdate,
+    ) -> Result<()> {
+        Err(projects_unsupported())
+    }
+
+    async fn add_project_item(
+        &self,
+        _project_node_id: &str,
+        _repo: &RepoId,
+        _number: u64,
+    ) -> Result<String> {
+        Err(projects_unsupported())
+    }
+
+    async fn archive_project_item(
+        &self,
+        _project_node_id: &str,
+        _item_node_id: &str,
+    ) -> Result<()> {
+        Err(projects_unsupported())
+    }
+}
+
+fn projects_unsupported() -> Error {
+    Error::custom("GitHub Projects are not available for Gitea organizations")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> GiteaClient {
+        GiteaClient::new("tea-token", &format!("{}/api/v1", server.uri()))
+            .unwrap()
+            .with_retry_config(RetryConfig {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            })
+    }
+
+    fn user(id: i64, login: &str) -> serde_json::Value {
+        serde_json::json!({"id": id, "login": login, "avatar_url": "", "html_url": ""})
+    }
+
+    fn pull(number: u64, head: &str, merged: bool) -> serde_json::Value {
+        serde_json::json!({
+            "id": 500 + number, "number": number, "title": "Change", "body": "",
+            "state": "closed", "user": user(1, "alice"), "labels": [], "milestone": null,
+            "head": {"ref": head}, "base": {"ref": "main"},
+            "merged": merged, "merged_at": if merged { Some("2024-02-03T00:00:00Z") } else { None },
+            "additions": 10, "deletions": 2, "changed_files": 1,
+            "created_at": "2024-02-01T00:00:00Z", "updated_at": "2024-02-03T00:00:00Z",
+            "closed_at": "2024-02-03T00:00:00Z",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_issues_map_to_github_models() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/acme/api/issues"))
+            .and(header("authorization", "token tea-token"))
+            .and(query_param("type", "issues"))
+            .and(query_param("state", "all"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "id": 11, "number": 1, "title": "Planned", "body": "", "state": "open",
+                    "labels": [{"id": 3, "name": "bug", "color": "ee0701", "description": ""}],
+                    "assignees": null,
+                    "milestone": {
+                        "id": 4, "title": "v1", "description": "", "state": "open",
+                        "open_issues": 1, "closed_issues": 0,
+                        "created_at": "2024-01-01T00:00:00Z", "closed_at": null, "due_on": null,
+                    },
+                    "user": user(1, "alice"), "comments": 2,
+                    "created_at": "2024-01-05T00:00:00Z", "updated_at": "2024-01-06T00:00:00Z",
+                    "closed_at": null,
+                },
+                {
+                    "id": 12, "number": 2, "title": "Unplanned", "body": "Text",
+                    "state": "closed", "labels": [], "assignees": [user(2, "bob")],
+                    "milestone": null, "user": user(1, "alice"), "comments": 0,
+                    "created_at": "2024-01-05T00:00:00Z", "updated_at": "2024-01-07T00:00:00Z",
+                    "closed_at": "2024-01-07T00:00:00Z",
+                },
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let repo = RepoId::new("acme", "api");
+        let issues = client.list_issues(&repo, IssueParams::all()).await.unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].labels[0].name, "bug");
+        assert!(issues[0].assignees.is_empty());
+        assert_eq!(issues[0].milestone.as_ref().unwrap().open_issues, 1);
+        assert_eq!(issues[1].state, IssueState::Closed);
+        assert_eq!(issues[1].assignee_logins(), vec!["bob"]);
+
+        let unplanned = client
+            .list_issues(&repo, IssueParams::all().with_milestone("none"))
+            .await
+            .unwrap();
+        assert_eq!(unplanned.len(), 1);
+        assert_eq!(unplanned[0].number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pulls_follow_link_pages_and_filter_branches() {
+        let server = MockServer::start().await;
+        let next = format!(
+            "<{}/api/v1/repos/acme/api/pulls?state=closed&page=2>; rel=\"next\"",
+            server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/acme/api/pulls"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([pull(2, "feature", false)])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/acme/api/pulls"))
+            .and(query_param("limit", "100"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("link", next.as_str())
+                    .set_body_json(serde_json::json!([
+                        pull(1, "feature", true),
+                        pull(3, "other", true)
+                    ])),
+            )
+            .mount(&server)
+            .await;
+
+        let params = PullParams {
+            head: Some("feature".to_string()),
+            ..PullParams::merged()
+        };
+        let pulls = client(&server)
+            .list_pulls(&RepoId::new("acme", "api"), params)
+            .await
+            .unwrap();
+        assert_eq!(
+            pulls.iter().map(|p| p.number).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(pulls[0].merged);
+        assert_eq!(pulls[0].lines_changed(), 12);
+        assert!(!pulls[1].merged);
+    }
+
+    #[tokio::test]
+    async fn test_timeline_events_and_reviews() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/acme/api/issues/1/timeline"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 1, "type": "label", "body": "1", "user": user(1, "alice"),
+                 "created_at": "2024-01-05T01:00:00Z", "label": {"name": "bug"}},
+                {"id": 2, "type": "comment", "body": "Looking", "user": user(2, "bob"),
+                 "created_at": "2024-01-05T02:00:00Z"},
+                {"id": 3, "type": "change_title", "body": "", "user": user(1, "alice"),
+                 "created_at": "2024-01-05T03:00:00Z"},
+                {"id": 4, "type": "close", "body": "", "user": user(2, "bob"),
+                 "created_at": "2024-01-05T04:00:00Z"},
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/acme/api/pulls/1/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 9, "user": user(2, "bob"), "body": "", "state": "REQUEST_CHANGES",
+                 "submitted_at": "2024-02-02T00:00:00Z"},
+                {"id": 10, "user": user(2, "bob"), "body": "", "state": "APPROVED",
+                 "submitted_at": "2024-02-03T00:00:00Z"},
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let repo = RepoId::new("acme", "api");
+        let events = client.list_issue_events(&repo, 1).await.unwrap();
+        let types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, vec!["labeled", "commented", "closed"]);
+        assert_eq!(events[0].label_name.as_deref(), Some("bug"));
+
+        let reviews = client.list_pull_reviews(&repo, 1).await.unwrap();
+        assert!(reviews[0].changes_requested());
+        assert!(reviews[1].is_approved());
+    }
+}
@@ -0,0 +1,957 @@
+//! GitLab backend
+//!
+//! Maps GitLab projects, issues, merge requests, milestones and releases onto
+//! the GitHub-shaped models, so metrics, reports and API routes work on an
+//! organization configured with `provider = "gitlab"`. A repository
+//! `group/project` is the GitLab project with that path; issue and merge
+//! request numbers are their project-scoped IIDs.
+//!
+//! GitLab has no Projects V2 and no sub-issue hierarchy, so Projects calls
+//! fail and no issue relations are listed. Merge request sizes only carry the
+//! changed file count, reported by [`GitHubClient::get_pull`].
+
+use super::rest::{encode_segment, Query, RestApi};
+use super::{
+    GitHubClient, IssueParams, IssueSort, IssueStateFilter, Pagination, ProjectClient, PullParams,
+    PullStateFilter, RateLimitInfo, RepoId, RetryConfig, SortDirection,
+};
+use crate::models::{
+    FieldUpdate, Issue, IssueEvent, IssueRelation, IssueState, Label, Milestone, MilestoneState,
+    Project, ProjectItem, PullRequest, PullState, Release, Repository, Review, User,
+};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{info, instrument};
+
+/// API root of GitLab.com
+pub const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Client for the GitLab REST API (v4)
+#[derive(Debug)]
+pub struct GitLabClient {
+    api: RestApi,
+    pagination: Pagination,
+}
+
+impl GitLabClient {
+    /// Create a client with a personal, group or project access token
+    ///
+    /// `base_url` is the API root, e.g. `https://gitlab.example.com/api/v4`
+    /// (GitLab.com when None).
+    pub fn new(token: &str, base_url: Option<&str>) -> Result<Self> {
+        let base_url = base_url.unwrap_or(GITLAB_API_URL);
+        info!(base_url = %base_url, "Creating GitLab client");
+        Ok(Self {
+            api: RestApi::new("GitLab", base_url, "private-token", token, "per_page")?,
+            pagination: Pagination::default(),
+        })
+    }
+
+    /// Set custom retry configuration
+    pub fn with_retry_config(mut self, config: RetryConfig) -> Self {
+        self.api.set_retry_config(config);
+        self
+    }
+
+    /// Set the item cap and progress callback for list calls
+    pub fn with_pagination(mut self, pagination: Pagination) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// API path of a repository's project
+    fn project(repo: &RepoId) -> String {
+        format!("/projects/{}", encode_segment(&repo.full_name()))
+    }
+
+    fn sort_query(query: &mut Query, sort: IssueSort, direction: SortDirection) {
+        let order_by = match sort {
+            IssueSort::Updated => "updated_at",
+            // GitLab cannot sort by comment count
+            IssueSort::Created | IssueSort::Comments => "created_at",
+        };
+        query.push(("order_by", order_by.to_string()));
+        query.push((
+            "sort",
+            match direction {
+                SortDirection::Asc => "asc",
+                SortDirection::Desc => "desc",
+            }
+            .to_string(),
+        ));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GitLab API types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct GlUser {
+    id: i64,
+    username: String,
+    avatar_url: Option<String>,
+    web_url: Option<String>,
+    /// Bot users of project and group access tokens
+    #[serde(default)]
+    bot: bool,
+}
+
+impl From<GlUser> for User {
+    fn from(user: GlUser) -> Self {
+        User {
+            id: user.id,
+            login: user.username,
+            avatar_url: user.avatar_url.unwrap_or_default(),
+            html_url: user.web_url.unwrap_or_default(),
+            is_bot: user.bot,
+        }
+    }
+}
+
+/// A label, as a name or (with `with_labels_details`) in full
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GlLabel {
+    Detail {
+        id: i64,
+        name: String,
+        color: String,
+        description: Option<String>,
+    },
+    Name(String),
+}
+
+impl From<GlLabel> for Label {
+    fn from(label: GlLabel) -> Self {
+        match label {
+            GlLabel::Detail {
+                id,
+                name,
+                color,
+                description,
+            } => Label {
+                id,
+                name,
+                color: color.trim_start_matches('#').to_string(),
+                description,
+            },
+            GlLabel::Name(name) => Label {
+                id: 0,
+                name,
+                color: String::new(),
+                description: None,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GlMilestone {
+    id: i64,
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    due_date: Option<NaiveDate>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<GlMilestone> for Milestone {
+    /// Issue counts are left at zero; `list_milestones` fills them in
+    fn from(milestone: GlMilestone) -> Self {
+        let closed = milestone.state == "closed";
+        Milestone {
+            id: milestone.id,
+            number: milestone.iid,
+            title: milestone.title,
+            description: milestone.description.filter(|d| !d.is_empty()),
+            state: if closed {
+                MilestoneState::Closed
+            } else {
+                MilestoneState::Open
+            },
+            open_issues: 0,
+            closed_issues: 0,
+            due_on: milestone
+                .due_date
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc()),
+            created_at: milestone.created_at,
+            // GitLab does not record when a milestone was closed
+            closed_at: closed.then_some(milestone.updated_at),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GlIssue {
+    id: i64,
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<GlLabel>,
+    #[serde(default)]
+    assignees: Vec<GlUser>,
+    milestone: Option<GlMilestone>,
+    author: GlUser,
+    #[serde(default)]
+    user_notes_count: u32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    closed_by: Option<GlUser>,
+}
+
+impl From<GlIssue> for Issue {
+    fn from(issue: GlIssue) -> Self {
+        Issue {
+            id: issue.id,
+            number: issue.iid,
+            title: issue.title,
+            body: issue.description,
+            state: if issue.state == "closed" {
+                IssueState::Closed
+            } else {
+                IssueState::Open
+            },
+            labels: issue.labels.into_iter().map(Label::from).collect(),
+            assignees: issue.assignees.into_iter().map(User::from).collect(),
+            milestone: issue.milestone.map(Milestone::from),
+            author: issue.author.into(),
+            comments_count: issue.user_notes_count,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            closed_at: issue.closed_at,
+            closed_by: issue.closed_by.map(User::from),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GlMergeRequest {
+    id: i64,
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    work_in_progress: bool,
+    author: GlUser,
+    #[serde(default)]
+    labels: Vec<GlLabel>,
+    milestone: Option<GlMilestone>,
+    source_branch: String,
+    target_branch: String,
+    merged_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    /// Only on single merge requests, e.g. "12" or "1000+"
+    changes_count: Option<String>,
+}
+
+impl From<GlMergeRequest> for PullRequest {
+    fn from(mr: GlMergeRequest) -> Self {
+        let merged = mr.state == "merged";
+        let state = match mr.state.as_str() {
+            "closed" | "merged" => PullState::Closed,
+            // "opened" and "locked"
+            _ => PullState::Open,
+        };
+        PullRequest {
+            id: mr.id,
+            number: mr.iid,
+            title: mr.title,
+            body: mr.description,
+            state,
+            draft: mr.draft || mr.work_in_progress,
+            author: mr.author.into(),
+            labels: mr.labels.into_iter().map(Label::from).collect(),
+            milestone: mr.milestone.map(Milestone::from),
+            head_ref: mr.source_branch,
+            base_ref: mr.target_branch,
+            merged,
+            merged_at: mr.merged_at,
+            additions: 0,
+            deletions: 0,
+            changed_files: mr
+                .changes_count
+                .and_then(|c| c.trim_end_matches('+').parse().ok())
+                .unwrap_or(0),
+            created_at: mr.created_at,
+            updated_at: mr.updated_at,
+            // GitLab leaves closed_at empty on merged merge requests
+            closed_at: mr.closed_at.or(if merged { mr.merged_at } else { None }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GlNamespace {
+    full_path: String,
+}
+
+#[derive(Deserialize)]
+struct GlProject {
+    id: i64,
+    path: String,
+    path_with_namespace: String,
+    namespace: GlNamespace,
+    description: Option<String>,
+    visibility: Option<String>,
+    default_branch: Option<String>,
+    created_at: DateTime<Utc>,
+    last_activity_at: DateTime<Utc>,
+}
+
+impl From<GlProject> for Repository {
+    fn from(project: GlProject) -> Self {
+        Repository {
+            id: project.id,
+            owner: project.namespace.full_path,
+            name: project.path,
+            full_name: project.path_with_namespace,
+            description: project.description.filter(|d| !d.is_empty()),
+            private: project.visibility.as_deref() != Some("public"),
+            default_branch: project.default_branch.unwrap_or_else(|| "main".to_string()),
+            created_at: project.created_at,
+            updated_at: project.last_activity_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GlRelease {
+    tag_name: String,
+    name: Option<String>,
+    description: Option<String>,
+    created_at: DateTime<Utc>,
+    released_at: Option<DateTime<Utc>>,
+    author: Option<GlUser>,
+    #[serde(default)]
+    upcoming_release: bool,
+}
+
+impl GlRelease {
+    fn into_release(self, repo: &RepoId) -> Release {
+        Release {
+            id: release_id(repo, &self.tag_name),
+            tag_name: self.tag_name,
+            name: self.name,
+            body: self.description,
+            draft: false,
+            prerelease: false,
+            author: self.author.map(User::from).unwrap_or_else(User::unknown),
+            created_at: self.created_at,
+            // An upcoming release is not published until its release date
+            published_at: self.released_at.filter(|_| !self.upcoming_release),
+        }
+    }
+}
+
+/// Stable ID for a release (GitLab releases have none)
+fn release_id(repo: &RepoId, tag: &str) -> i64 {
+    let key = format!("gitlab {} {}", repo.full_name(), tag);
+    (super::etag_cache::fnv1a(key.as_bytes()) >> 1) as i64
+}
+
+#[derive(Deserialize)]
+struct GlNote {
+    id: i64,
+    author: GlUser,
+    created_at: DateTime<Utc>,
+    system: bool,
+}
+
+#[derive(Deserialize)]
+struct GlStateEvent {
+    id: i64,
+    user: Option<GlUser>,
+    created_at: DateTime<Utc>,
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct GlLabelName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GlLabelEvent {
+    id: i64,
+    user: Option<GlUser>,
+    created_at: DateTime<Utc>,
+    label: Option<GlLabelName>,
+    action: String,
+}
+
+#[derive(Deserialize)]
+struct GlApprover {
+    user: GlUser,
+}
+
+#[derive(Deserialize)]
+struct GlApprovals {
+    #[serde(default)]
+    approved_by: Vec<GlApprover>,
+}
+
+fn event(
+    id: i64,
+    event_type: &str,
+    actor: Option<GlUser>,
+    created_at: DateTime<Utc>,
+    label_name: Option<String>,
+) -> IssueEvent {
+    IssueEvent {
+        id,
+        event_type: event_type.to_string(),
+        actor: actor.map(User::from),
+        created_at,
+        label_name,
+        assignee: None,
+    }
+}
+
+#[async_trait]
+impl GitHubClient for GitLabClient {
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_repository(&self, repo: &RepoId) -> Result<Repository> {
+        let project: GlProject = self
+            .api
+            .get("get_repository", &Self::project(repo), &Query::new())
+            .await?;
+        Ok(project.into())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_org_repos(&self, org: &str) -> Result<Vec<Repository>> {
+        let path = format!("/groups/{}/projects", encode_segment(org));
+        let query = vec![("archived", "false".to_string())];
+        let projects: Vec<GlProject> = self
+            .api
+            .list("list_org_repos", &path, &query, &self.pagination)
+            .await?;
+        Ok(projects.into_iter().map(Repository::from).collect())
+    }
+
+    #[instrument(skip(self, params), fields(repo = %repo))]
+    async fn list_issues(&self, repo: &RepoId, params: IssueParams) -> Result<Vec<Issue>> {
+        let mut query: Query = vec![("with_labels_details", "true".to_string())];
+        match params.state {
+            IssueStateFilter::Open => query.push(("state", "opened".to_string())),
+            IssueStateFilter::Closed => query.push(("state", "closed".to_string())),
+            IssueStateFilter::All => {}
+        }
+        if let Some(labels) = &params.labels {
+            query.push(("labels", labels.join(",")));
+        }
+        if let Some(assignee) = &params.assignee {
+            query.push(("assignee_username", assignee.clone()));
+        }
+        if let Some(milestone) = &params.milestone {
+            let milestone = match milestone.as_str() {
+                "none" => "None",
+                "*" => "Any",
+                title => title,
+            };
+            query.push(("milestone", milestone.to_string()));
+        }
+        if let Some(creator) = &params.creator {
+            query.push(("author_username", creator.clone()));
+        }
+        if let Some(since) = params.since {
+            query.push(("updated_after", since.to_rfc3339()));
+        }
+        Self::sort_query(&mut query, params.sort, params.direction);
+
+        let path = format!("{}/issues", Self::project(repo));
+        let issues: Vec<GlIssue> = self
+            .api
+            .list("list_issues", &path, &query, &self.pagination)
+            .await?;
+        Ok(issues.into_iter().map(Issue::from).collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_issue(&self, repo: &RepoId, number: u64) -> Result<Issue> {
+        let path = format!("{}/issues/{}", Self::project(repo), number);
+        let issue: GlIssue = self.api.get("get_issue", &path, &Query::new()).await?;
+        Ok(issue.into())
+    }
+
+    /// Comments, state changes and label changes, oldest first
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_issue_events(&self, repo: &RepoId, number: u64) -> Result<Vec<IssueEvent>> {
+        let issue = format!("{}/issues/{}", Self::project(repo), number);
+        let notes: Vec<GlNote> = self
+            .api
+            .list(
+                "list_issue_events",
+                &format!("{}/notes", issue),
+                &Query::new(),
+                &self.pagination,
+            )
+            .await?;
+        let states: Vec<GlStateEvent> = self
+            .api
+            .list(
+                "list_issue_events",
+                &format!("{}/resource_state_events", issue),
+                &Query::new(),
+                &self.pagination,
+            )
+            .await?;
+        let labels: Vec<GlLabelEvent> = self
+            .api
+            .list(
+                "list_issue_events",
+                &format!("{}/resource_label_events", issue),
+                &Query::new(),
+                &self.pagination,
+            )
+            .await?;
+
+        let mut events: Vec<IssueEvent> = notes
+            .into_iter()
+            .filter(|n| !n.system)
+            .map(|n| event(n.id, "commented", Some(n.author), n.created_at, None))
+            .chain(
+                states
+                    .into_iter()
+                    .map(|e| event(e.id, &e.state, e.user, e.created_at, None)),
+            )
+            .chain(labels.into_iter().map(|e| {
+                let event_type = if e.action == "remove" {
+                    "unlabeled"
+                } else {
+                    "labeled"
+                };
+                event(
+                    e.id,
+                    event_type,
+                    e.user,
+                    e.created_at,
+                    e.label.map(|l| l.name),
+                )
+            }))
+            .collect();
+        events.sort_by_key(|e| e.created_at);
+        Ok(events)
+    }
+
+    /// GitLab issue links have no parent/child or tracking direction, so no
+    /// relations are reported
+    async fn list_issue_relations(&self, _repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        Ok(Vec::new())
+    }
+
+    /// Milestones with issue counts tallied from the project's issues
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
+        let path = format!("{}/milestones", Self::project(repo));
+        let milestones: Vec<GlMilestone> = self
+            .api
+            .list("list_milestones", &path, &Query::new(), &self.pagination)
+            .await?;
+
+        let mut counts: HashMap<i64, (u32, u32)> = HashMap::new();
+        for issue in self.list_issues(repo, IssueParams::all()).await? {
+            if let Some(milestone) = issue.milestone {
+                let (open, closed) = counts.entry(milestone.id).or_default();
+                match issue.state {
+                    IssueState::Open => *open += 1,
+                    IssueState::Closed => *closed += 1,
+                }
+            }
+        }
+
+        Ok(milestones
+            .into_iter()
+            .map(|m| {
+                let mut milestone = Milestone::from(m);
+                let (open, closed) = counts.get(&milestone.id).copied().unwrap_or_default();
+                milestone.open_issues = open;
+                milestone.closed_issues = closed;
+                milestone
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self, params), fields(repo = %repo))]
+    async fn list_pulls(&self, repo: &RepoId, params: PullParams) -> Result<Vec<PullRequest>> {
+        let mut query: Query = vec![("with_labels_details", "true".to_string())];
+        // GitLab's "closed" leaves out merged merge requests, which GitHub
+        // counts as closed, so those are filtered here instead
+        if params.state == PullStateFilter::Open {
+            query.push(("state", "opened".to_string()));
+        }
+        if let Some(head) = &params.head {
+            query.push(("source_branch", head.clone()));
+        }
+        if let Some(base) = &params.base {
+            query.push(("target_branch", base.clone()));
+        }
+        Self::sort_query(&mut query, params.sort, params.direction);
+
+        let path = format!("{}/merge_requests", Self::project(repo));
+        let mrs: Vec<GlMergeRequest> = self
+            .api
+            .list("list_pulls", &path, &query, &self.pagination)
+            .await?;
+        Ok(mrs
+            .into_iter()
+            .map(PullRequest::from)
+            .filter(|p| params.state != PullStateFilter::Closed || p.state == PullState::Closed)
+            .collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_pull(&self, repo: &RepoId, number: u64) -> Result<PullRequest> {
+        let path = format!("{}/merge_requests/{}", Self::project(repo), number);
+        let mr: GlMergeRequest = self.api.get("get_pull", &path, &Query::new()).await?;
+        Ok(mr.into())
+    }
+
+    /// Approvals, as `APPROVED` reviews (GitLab does not date them)
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_pull_reviews(&self, repo: &RepoId, number: u64) -> Result<Vec<Review>> {
+        let path = format!(
+            "{}/merge_requests/{}/approvals",
+            Self::project(repo),
+            number
+        );
+        let approvals: GlApprovals = self
+            .api
+            .get("list_pull_reviews", &path, &Query::new())
+            .await?;
+        Ok(approvals
+            .approved_by
+            .into_iter()
+            .map(|a| Review {
+                id: a.user.id,
+                user: Some(a.user.into()),
+                body: None,
+                state: "APPROVED".to_string(),
+                submitted_at: None,
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_releases(&self, repo: &RepoId) -> Result<Vec<Release>> {
+        let path = format!("{}/releases", Self::project(repo));
+        let releases: Vec<GlRelease> = self
+            .api
+            .list("list_releases", &path, &Query::new(), &self.pagination)
+            .await?;
+        Ok(releases.into_iter().map(|r| r.into_release(repo)).collect())
+    }
+
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn get_release(&self, repo: &RepoId, tag: &str) -> Result<Release> {
+        let path = format!("{}/releases/{}", Self::project(repo), encode_segment(tag));
+        let release: GlRelease = self.api.get("get_release", &path, &Query::new()).await?;
+        Ok(release.into_release(repo))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user(&self, username: &str) -> Result<User> {
+        let query = vec![("username", username.to_string())];
+        let users: Vec<GlUser> = self.api.get("get_user", "/users", &query).await?;
+        users
+            .into_iter()
+            .next()
+            .map(User::from)
+            .ok_or_else(|| Error::NotFound(format!("GitLab user {}", username)))
+    }
+
+    /// Rate limit reported on a `GET /user` (which also checks the token);
+    /// instances without rate limiting report `u32::MAX`
+    async fn rate_limit(&self) -> Result<RateLimitInfo> {
+        let response = self
+            .api
+            .get_response("rate_limit", "/user", &Query::new())
+            .await?;
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+        };
+        Ok(RateLimitInfo {
+            remaining: header("ratelimit-remaining").map_or(u32::MAX, |v| v as u32),
+            limit: header("ratelimit-limit").map_or(u32::MAX, |v| v as u32),
+            reset: header("ratelimit-reset").unwrap_or(0),
+        })
+    }
+}
+
+/// GitLab has no GitHub Projects
+#[async_trait]
+impl ProjectClient for GitLabClient {
+    async fn list_projects(&self, _org: &str) -> Result<Vec<Project>> {
+        Err(projects_unsupported())
+    }
+
+    async fn get_project(&self, _org: &str, _project_number: u64) -> Result<Project> {
+        Err(projects_unsupported())
+    }
+
+    async fn list_project_items(&self, _project_node_id: &str) -> Result<Vec<ProjectItem>> {
+        Err(projects_unsupported())
+    }
+
+    async fn update_project_item_field(
+        &self,
+        _project_node_id: &str,
+        _item_node_id: &str,
+        _field_node_id: &str,
+        _update: &FieldUpdate,
+    ) -> Result<()> {
+        Err(projects_unsupported())
+    }
+
+    async fn add_project_item(
+        &self,
+        _project_node_id: &str,
+        _repo: &RepoId,
+        _number: u64,
+    ) -> Result<String> {
+        Err(projects_unsupported())
+    }
+
+    async fn archive_project_item(
+        &self,
+        _project_node_id: &str,
+        _item_node_id: &str,
+    ) -> Result<()> {
+        Err(projects_unsupported())
+    }
+}
+
+fn projects_unsupported() -> Error {
+    Error::custom("GitHub Projects are not available for GitLab organizations")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> GitLabClient {
+        GitLabClient::new("glpat-test", Some(&format!("{}/api/v4", server.uri())))
+            .unwrap()
+            .with_retry_config(RetryConfig {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            })
+    }
+
+    fn user(id: i64, username: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "username": username,
+            "avatar_url": format!("https://gitlab.example.com/{}.png", username),
+            "web_url": format!("https://gitlab.example.com/{}", username),
+        })
+    }
+
+    fn milestone() -> serde_json::Value {
+        serde_json::json!({
+            "id": 12, "iid": 3, "title": "v1.0", "description": "", "state": "active",
+            "due_date": "2024-03-31",
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-02T00:00:00Z",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_issues_map_to_github_models() {
+        let server = MockServer::start().await;
+        let next = format!(
+            "<{}/api/v4/projects/acme%2Fapi/issues?page=2>; rel=\"next\"",
+            server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fapi/issues"))
+            .and(header("private-token", "glpat-test"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fapi/issues"))
+            .and(query_param("state", "opened"))
+            .and(query_param("labels", "bug"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("link", next.as_str())
+                    .set_body_json(serde_json::json!([{
+                        "id": 901, "iid": 7, "title": "Crash on start",
+                        "description": "Steps...", "state": "opened",
+                        "labels": [{"id": 5, "name": "bug", "color": "#d9534f", "description": null}],
+                        "assignees": [user(2, "bob")],
+                        "milestone": milestone(),
+                        "author": user(1, "alice"),
+                        "user_notes_count": 4,
+                        "created_at": "2024-01-05T10:00:00Z",
+                        "updated_at": "2024-01-06T10:00:00Z",
+                        "closed_at": null, "closed_by": null,
+                    }])),
+            )
+            .mount(&server)
+            .await;
+
+        let issues = client(&server)
+            .list_issues(
+                &RepoId::new("acme", "api"),
+                IssueParams::open().with_labels(vec!["bug".to_string()]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.number, 7);
+        assert_eq!(issue.state, IssueState::Open);
+        assert_eq!(issue.author.login, "alice");
+        assert_eq!(issue.assignee_logins(), vec!["bob"]);
+        assert_eq!(issue.labels[0].color, "d9534f");
+        assert_eq!(issue.comments_count, 4);
+        let milestone = issue.milestone.as_ref().unwrap();
+        assert_eq!(milestone.number, 3);
+        assert_eq!(milestone.state, MilestoneState::Open);
+        assert_eq!(
+            milestone.due_on.unwrap().to_rfc3339(),
+            "2024-03-31T00:00:00+00:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merged_merge_requests_count_as_closed() {
+        let server = MockServer::start().await;
+        let mr = |iid: u64, state: &str, merged_at: Option<&str>| {
+            serde_json::json!({
+                "id": 1000 + iid, "iid": iid, "title": "Change", "description": null,
+                "state": state, "draft": false, "author": user(1, "alice"),
+                "labels": ["backend"], "milestone": null,
+                "source_branch": "feature", "target_branch": "main",
+                "merged_at": merged_at,
+                "created_at": "2024-02-01T00:00:00Z", "updated_at": "2024-02-02T00:00:00Z",
+                "closed_at": null,
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fapi/merge_requests"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                mr(1, "opened", None),
+                mr(2, "merged", Some("2024-02-03T00:00:00Z")),
+                mr(3, "closed", None),
+            ])))
+            .mount(&server)
+            .await;
+
+        let pulls = client(&server)
+            .list_pulls(&RepoId::new("acme", "api"), PullParams::merged())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pulls.iter().map(|p| p.number).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        let merged = &pulls[0];
+        assert!(merged.merged);
+        assert_eq!(merged.closed_at, merged.merged_at);
+        assert_eq!(merged.head_ref, "feature");
+        assert_eq!(merged.labels[0].name, "backend");
+    }
+
+    #[tokio::test]
+    async fn test_milestones_count_issues() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fapi/milestones"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([milestone()])),
+            )
+            .mount(&server)
+            .await;
+        let issue = |iid: u64, state: &str| {
+            serde_json::json!({
+                "id": iid, "iid": iid, "title": "Issue", "description": null, "state": state,
+                "labels": [], "assignees": [], "milestone": milestone(),
+                "author": user(1, "alice"),
+                "created_at": "2024-01-05T10:00:00Z", "updated_at": "2024-01-06T10:00:00Z",
+                "closed_at": null, "closed_by": null,
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fapi/issues"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                issue(1, "opened"),
+                issue(2, "closed"),
+                issue(3, "closed"),
+            ])))
+            .mount(&server)
+            .await;
+
+        let milestones = client(&server)
+            .list_milestones(&RepoId::new("acme", "api"))
+            .await
+            .unwrap();
+        assert_eq!(milestones[0].open_issues, 1);
+        assert_eq!(milestones[0].closed_issues, 2);
+    }
+
+    #[tokio::test]
+    async fn test_releases_and_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fapi/releases"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "tag_name": "v1.0.0", "name": "1.0", "description": "Notes",
+                    "created_at": "2024-03-01T00:00:00Z", "released_at": "2024-03-01T00:00:00Z",
+                    "author": user(1, "alice"), "upcoming_release": false,
+                }])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/acme%2Fmissing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let releases = client
+            .list_releases(&RepoId::new("acme", "api"))
+            .await
+            .unwrap();
+        assert!(releases[0].is_published());
+        assert_eq!(
+            releases[0].id,
+            release_id(&RepoId::new("acme", "api"), "v1.0.0")
+        );
+        assert!(releases[0].id > 0);
+
+        let err = client
+            .get_repository(&RepoId::new("acme", "missing"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+}
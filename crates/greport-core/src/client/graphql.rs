@@ -3,15 +3,27 @@
 //! This module provides a thin HTTP wrapper for issuing GraphQL queries
 //! against the GitHub API. It handles authentication, endpoint resolution
 //! (including GitHub Enterprise), and JSON deserialization.
+//!
+//! It also lists issues and pull requests in bulk, which is what
+//! `OctocrabClient` uses when the data source is [`DataSource::GraphQL`].
+//!
+//! [`DataSource::GraphQL`]: super::DataSource::GraphQL
 
 use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
 use tracing::{debug, warn};
 
+use super::app_auth::Credentials;
+use super::budget::{RateBudget, RateResource};
+use super::paginate::{PageCollector, Pagination};
+use super::retry::{with_retry, RetryConfig};
+use super::{IssueParams, IssueSort, IssueStateFilter, PullParams, PullStateFilter, RepoId};
 use crate::models::{
-    FieldValue, IterationValue, LabelInfo, Project, ProjectField, ProjectFieldType,
-    ProjectFieldValue, ProjectItem, ProjectItemContent, SelectOption,
+    FieldUpdate, FieldValue, Issue, IssueRef, IssueRelation, IssueState, IterationValue, Label,
+    LabelInfo, Milestone, Project, ProjectField, ProjectFieldType, ProjectFieldValue, ProjectItem,
+    ProjectItemContent, PullRequest, PullState, RelationKind, SelectOption, User,
 };
 use crate::{Error, Result};
 
@@ -106,6 +118,12 @@ query($org: String!, $number: Int!) {
                 startDate
                 duration
               }
+              completedIterations {
+                id
+                title
+                startDate
+                duration
+              }
             }
           }
         }
@@ -158,6 +176,12 @@ query($login: String!, $number: Int!) {
                 startDate
                 duration
               }
+              completedIterations {
+                id
+                title
+                startDate
+                duration
+              }
             }
           }
         }
@@ -238,6 +262,171 @@ query($nodeId: ID!, $first: Int!, $after: String) {
 }
 "#;
 
+// ---------------------------------------------------------------------------
+// GraphQL mutation constants
+// ---------------------------------------------------------------------------
+
+/// Set a field value on a project item.
+const UPDATE_ITEM_FIELD_VALUE: &str = r#"
+mutation($projectId: ID!, $itemId: ID!, $fieldId: ID!, $value: ProjectV2FieldValue!) {
+  updateProjectV2ItemFieldValue(
+    input: {projectId: $projectId, itemId: $itemId, fieldId: $fieldId, value: $value}
+  ) {
+    projectV2Item { id }
+  }
+}
+"#;
+
+/// Clear a field value on a project item.
+const CLEAR_ITEM_FIELD_VALUE: &str = r#"
+mutation($projectId: ID!, $itemId: ID!, $fieldId: ID!) {
+  clearProjectV2ItemFieldValue(
+    input: {projectId: $projectId, itemId: $itemId, fieldId: $fieldId}
+  ) {
+    projectV2Item { id }
+  }
+}
+"#;
+
+/// Add an issue or pull request to a project.
+const ADD_PROJECT_ITEM: &str = r#"
+mutation($projectId: ID!, $contentId: ID!) {
+  addProjectV2ItemById(input: {projectId: $projectId, contentId: $contentId}) {
+    item { id }
+  }
+}
+"#;
+
+/// Archive a project item.
+const ARCHIVE_PROJECT_ITEM: &str = r#"
+mutation($projectId: ID!, $itemId: ID!) {
+  archiveProjectV2Item(input: {projectId: $projectId, itemId: $itemId}) {
+    item { id }
+  }
+}
+"#;
+
+/// List tracked issues and sub-issues for every issue in a repository.
+const LIST_ISSUE_RELATIONS: &str = r#"
+query($owner: String!, $name: String!, $first: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    issues(first: $first, after: $after) {
+      nodes {
+        number
+        trackedIssues(first: 50) {
+          nodes { number repository { nameWithOwner } }
+        }
+        subIssues(first: 50) {
+          nodes { number repository { nameWithOwner } }
+        }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}
+"#;
+
+/// Resolve the node ID of an issue or pull request by number.
+const GET_CONTENT_NODE_ID: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    issueOrPullRequest(number: $number) {
+      ... on Issue { id }
+      ... on PullRequest { id }
+    }
+  }
+}
+"#;
+
+/// List issues with labels, assignees and who closed them.
+const LIST_ISSUES: &str = r#"
+query($owner: String!, $name: String!, $first: Int!, $after: String,
+      $states: [IssueState!], $labels: [String!], $filterBy: IssueFilters, $orderBy: IssueOrder) {
+  repository(owner: $owner, name: $name) {
+    issues(first: $first, after: $after, states: $states, labels: $labels,
+           filterBy: $filterBy, orderBy: $orderBy) {
+      nodes {
+        fullDatabaseId
+        number
+        title
+        body
+        state
+        author { ...ActorFields }
+        labels(first: 50) { nodes { id name color description } }
+        assignees(first: 20) { nodes { databaseId login avatarUrl url } }
+        milestone { number }
+        comments { totalCount }
+        createdAt
+        updatedAt
+        closedAt
+        timelineItems(last: 1, itemTypes: [CLOSED_EVENT]) {
+          nodes { ... on ClosedEvent { actor { ...ActorFields } } }
+        }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}
+
+fragment ActorFields on Actor {
+  __typename
+  login
+  avatarUrl
+  url
+  ... on User { databaseId }
+  ... on Bot { databaseId }
+}
+"#;
+
+/// List pull requests with size stats.
+const LIST_PULLS: &str = r#"
+query($owner: String!, $name: String!, $first: Int!, $after: String,
+      $states: [PullRequestState!], $baseRefName: String, $headRefName: String, $orderBy: IssueOrder) {
+  repository(owner: $owner, name: $name) {
+    pullRequests(first: $first, after: $after, states: $states, baseRefName: $baseRefName,
+                 headRefName: $headRefName, orderBy: $orderBy) {
+      nodes {
+        fullDatabaseId
+        number
+        title
+        body
+        state
+        isDraft
+        author { ...ActorFields }
+        labels(first: 50) { nodes { id name color description } }
+        milestone { number }
+        headRefName
+        baseRefName
+        merged
+        mergedAt
+        additions
+        deletions
+        changedFiles
+        createdAt
+        updatedAt
+        closedAt
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}
+
+fragment ActorFields on Actor {
+  __typename
+  login
+  avatarUrl
+  url
+  ... on User { databaseId }
+  ... on Bot { databaseId }
+}
+"#;
+
+/// Largest page for the issue query.
+const ISSUE_PAGE_SIZE: usize = 100;
+
+/// Largest page for the pull request query, which nests more connections.
+const PULL_PAGE_SIZE: usize = 50;
+
 // ---------------------------------------------------------------------------
 // Endpoint resolution
 // ---------------------------------------------------------------------------
@@ -271,13 +460,17 @@ pub fn derive_graphql_url(base_url: Option<&str>) -> String {
 pub struct GraphQLClient {
     http: Client,
     endpoint: String,
-    token: String,
+    credentials: Credentials,
+    budget: Arc<RateBudget>,
+    retry_config: RetryConfig,
 }
 
 impl std::fmt::Debug for GraphQLClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GraphQLClient")
             .field("endpoint", &self.endpoint)
+            .field("budget", &self.budget)
+            .field("retry_config", &self.retry_config)
             .finish_non_exhaustive()
     }
 }
@@ -298,13 +491,17 @@ struct GraphQLResponse<T> {
 struct GraphQLError {
     message: String,
     #[serde(rename = "type")]
-    #[allow(dead_code)]
     error_type: Option<String>,
 }
 
 impl GraphQLClient {
     /// Create a new GraphQL client.
     pub fn new(token: &str, base_url: Option<&str>) -> Result<Self> {
+        Self::from_credentials(Credentials::from(token), base_url)
+    }
+
+    /// Create a GraphQL client authenticating with `credentials`.
+    pub fn from_credentials(credentials: Credentials, base_url: Option<&str>) -> Result<Self> {
         let endpoint = derive_graphql_url(base_url);
         debug!(endpoint = %endpoint, "Creating GraphQL client");
 
@@ -316,30 +513,69 @@ impl GraphQLClient {
         Ok(Self {
             http,
             endpoint,
-            token: token.to_string(),
+            credentials,
+            budget: Arc::new(RateBudget::default()),
+            retry_config: RetryConfig::default(),
         })
     }
 
+    /// Share `budget` instead of tracking a separate one
+    pub fn with_budget(mut self, budget: Arc<RateBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Set custom retry configuration
+    pub fn with_retry_config(mut self, config: RetryConfig) -> Self {
+        self.retry_config = config;
+        self
+    }
+
+    /// Rate-limit budget this client draws on
+    pub fn budget(&self) -> &Arc<RateBudget> {
+        &self.budget
+    }
+
     /// Execute a GraphQL query and deserialize the response.
+    ///
+    /// Waits while the GraphQL point budget is nearly spent, and retries
+    /// network failures, server errors and rate limits.
     pub async fn query<T: DeserializeOwned>(
         &self,
         query: &str,
         variables: serde_json::Value,
     ) -> Result<T> {
         let request_body = GraphQLRequest { query, variables };
+        with_retry("graphql", &self.retry_config, || self.send(&request_body)).await
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request_body: &GraphQLRequest<'_>) -> Result<T> {
+        self.budget.acquire(RateResource::GraphQL).await;
+        let token = self.credentials.token().await?;
 
         let response = self
             .http
             .post(&self.endpoint)
-            .header("Authorization", format!("bearer {}", self.token))
-            .json(&request_body)
+            .header("Authorization", format!("bearer {}", token))
+            .json(request_body)
             .send()
             .await
             .map_err(|e| Error::Network(format!("GraphQL request failed: {}", e)))?;
 
         let status = response.status();
+        self.budget
+            .record(RateResource::GraphQL, status.as_u16(), response.headers());
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
+            if matches!(status.as_u16(), 403 | 429) && body.to_lowercase().contains("rate limit") {
+                return Err(self.rate_limited());
+            }
+            if status.is_server_error() {
+                return Err(Error::Network(format!(
+                    "GraphQL server error: HTTP {}",
+                    status.as_u16()
+                )));
+            }
             return Err(Error::GraphQL(format!(
                 "HTTP {}: {}",
                 status.as_u16(),
@@ -362,6 +598,12 @@ impl GraphQLClient {
         })?;
 
         if let Some(errors) = gql_response.errors {
+            if errors
+                .iter()
+                .any(|e| e.error_type.as_deref() == Some("RATE_LIMITED"))
+            {
+                return Err(self.rate_limited());
+            }
             if !errors.is_empty() {
                 return Err(classify_graphql_errors(&errors));
             }
@@ -372,6 +614,15 @@ impl GraphQLClient {
             .ok_or_else(|| Error::GraphQL("GraphQL response contained no data".to_string()))
     }
 
+    fn rate_limited(&self) -> Error {
+        let reset_at = self
+            .budget
+            .resume_at(RateResource::GraphQL)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_else(|| "an unknown time".to_string());
+        Error::RateLimitExceeded { reset_at }
+    }
+
     // -----------------------------------------------------------------------
     // High-level project operations
     // -----------------------------------------------------------------------
@@ -590,6 +841,322 @@ impl GraphQLClient {
         );
         Ok(all_items)
     }
+
+    // -----------------------------------------------------------------------
+    // Project mutations
+    // -----------------------------------------------------------------------
+
+    /// Set or clear a field value on a project item.
+    pub async fn update_item_field(
+        &self,
+        project_node_id: &str,
+        item_node_id: &str,
+        field_node_id: &str,
+        update: &FieldUpdate,
+    ) -> Result<()> {
+        let mut variables = serde_json::json!({
+            "projectId": project_node_id,
+            "itemId": item_node_id,
+            "fieldId": field_node_id,
+        });
+
+        let _: serde_json::Value = match field_update_value(update) {
+            Some(value) => {
+                variables["value"] = value;
+                self.query(UPDATE_ITEM_FIELD_VALUE, variables).await?
+            }
+            None => self.query(CLEAR_ITEM_FIELD_VALUE, variables).await?,
+        };
+
+        debug!(
+            item = item_node_id,
+            field = field_node_id,
+            "Updated project item field"
+        );
+        Ok(())
+    }
+
+    /// Add an issue or pull request to a project, returning the new item ID.
+    pub async fn add_item(&self, project_node_id: &str, content_node_id: &str) -> Result<String> {
+        let variables = serde_json::json!({
+            "projectId": project_node_id,
+            "contentId": content_node_id,
+        });
+
+        let data: AddItemData = self.query(ADD_PROJECT_ITEM, variables).await?;
+        data.add_project_v2_item_by_id
+            .item
+            .map(|i| i.id)
+            .ok_or_else(|| Error::GraphQL("addProjectV2ItemById returned no item".to_string()))
+    }
+
+    /// Archive a project item.
+    pub async fn archive_item(&self, project_node_id: &str, item_node_id: &str) -> Result<()> {
+        let variables = serde_json::json!({
+            "projectId": project_node_id,
+            "itemId": item_node_id,
+        });
+
+        let _: serde_json::Value = self.query(ARCHIVE_PROJECT_ITEM, variables).await?;
+        debug!(item = item_node_id, "Archived project item");
+        Ok(())
+    }
+
+    /// Resolve the GraphQL node ID of an issue or pull request.
+    pub async fn content_node_id(&self, owner: &str, name: &str, number: u64) -> Result<String> {
+        let variables = serde_json::json!({
+            "owner": owner,
+            "name": name,
+            "number": number as i64,
+        });
+
+        let data: ContentNodeData = self.query(GET_CONTENT_NODE_ID, variables).await?;
+        data.repository
+            .and_then(|r| r.issue_or_pull_request)
+            .map(|n| n.id)
+            .ok_or_else(|| {
+                Error::NotFound(format!(
+                    "Issue or pull request {}/{}#{} not found",
+                    owner, name, number
+                ))
+            })
+    }
+
+    // -----------------------------------------------------------------------
+    // Issue relations
+    // -----------------------------------------------------------------------
+
+    /// List tracked-issue and sub-issue relations for a repository.
+    ///
+    /// Relations are reported from the parent side, so a sub-issue in another
+    /// repository still appears as long as its parent lives here.
+    pub async fn list_issue_relations(
+        &self,
+        owner: &str,
+        name: &str,
+    ) -> Result<Vec<IssueRelation>> {
+        let repo = format!("{}/{}", owner, name);
+        let mut relations = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let variables = serde_json::json!({
+                "owner": owner,
+                "name": name,
+                "first": 50,
+                "after": cursor,
+            });
+
+            let data: RelationsData = self.query(LIST_ISSUE_RELATIONS, variables).await?;
+            let connection = data
+                .repository
+                .ok_or_else(|| Error::NotFound(format!("Repository '{}' not found", repo)))?
+                .issues;
+
+            for node in connection.nodes.into_iter().flatten() {
+                let issue = IssueRef::new(&repo, node.number);
+                let related = [
+                    (RelationKind::Tracks, node.tracked_issues),
+                    (RelationKind::ParentOf, node.sub_issues),
+                ];
+                for (kind, children) in related {
+                    for child in children.into_iter().flat_map(|c| c.nodes).flatten() {
+                        relations.push(IssueRelation {
+                            issue: issue.clone(),
+                            depends_on: IssueRef::new(
+                                child.repository.name_with_owner,
+                                child.number,
+                            ),
+                            kind,
+                        });
+                    }
+                }
+            }
+
+            if connection.page_info.has_next_page {
+                cursor = connection.page_info.end_cursor;
+            } else {
+                break;
+            }
+        }
+
+        debug!(repo = %repo, count = relations.len(), "Fetched issue relations");
+        Ok(relations)
+    }
+
+    // -----------------------------------------------------------------------
+    // Bulk issue and pull request listing
+    // -----------------------------------------------------------------------
+
+    /// List issues matching `params` in bulk queries.
+    ///
+    /// GraphQL does not expose milestone database IDs, so milestones are
+    /// looked up by number in `milestones` (the REST milestone list).
+    pub async fn list_issues(
+        &self,
+        repo: &RepoId,
+        params: &IssueParams,
+        milestones: &[Milestone],
+        pagination: &Pagination,
+    ) -> Result<Vec<Issue>> {
+        let mut issues = PageCollector::new(pagination, "list_issues");
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut variables = issue_variables(params);
+            variables["owner"] = repo.owner.clone().into();
+            variables["name"] = repo.name.clone().into();
+            variables["after"] = cursor.clone().into();
+
+            let data: IssuesData = self.query(LIST_ISSUES, variables).await?;
+            let connection = data
+                .repository
+                .ok_or_else(|| Error::NotFound(format!("Repository '{}' not found", repo)))?
+                .issues;
+
+            let nodes = connection.nodes.into_iter().flatten();
+            let page_info = connection.page_info;
+            let more = issues.push_page(
+                nodes.map(|node| convert_issue(node, milestones)),
+                page_info.has_next_page,
+                None,
+            );
+            if !more {
+                break;
+            }
+            cursor = page_info.end_cursor;
+        }
+
+        let issues = issues.into_items()?;
+        debug!(repo = %repo, count = issues.len(), "Fetched issues via GraphQL");
+        Ok(issues)
+    }
+
+    /// List pull requests matching `params` in bulk queries, with their size
+    /// stats.
+    ///
+    /// Milestones are resolved from `milestones` as in [`Self::list_issues`].
+    pub async fn list_pulls(
+        &self,
+        repo: &RepoId,
+        params: &PullParams,
+        milestones: &[Milestone],
+        pagination: &Pagination,
+    ) -> Result<Vec<PullRequest>> {
+        let mut pulls = PageCollector::new(pagination, "list_pulls");
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut variables = pull_variables(params);
+            variables["owner"] = repo.owner.clone().into();
+            variables["name"] = repo.name.clone().into();
+            variables["after"] = cursor.clone().into();
+
+            let data: PullsData = self.query(LIST_PULLS, variables).await?;
+            let connection = data
+                .repository
+                .ok_or_else(|| Error::NotFound(format!("Repository '{}' not found", repo)))?
+                .pull_requests;
+
+            let nodes = connection.nodes.into_iter().flatten();
+            let page_info = connection.page_info;
+            let more = pulls.push_page(
+                nodes.map(|node| convert_pull(node, milestones)),
+                page_info.has_next_page,
+                None,
+            );
+            if !more {
+                break;
+            }
+            cursor = page_info.end_cursor;
+        }
+
+        let pulls = pulls.into_items()?;
+        debug!(repo = %repo, count = pulls.len(), "Fetched pull requests via GraphQL");
+        Ok(pulls)
+    }
+}
+
+/// Page size for a query: `per_page` if set, capped at `max`.
+fn page_size(per_page: usize, max: usize) -> usize {
+    if per_page == 0 {
+        max
+    } else {
+        per_page.min(max)
+    }
+}
+
+/// GraphQL `IssueOrder` for a sort field and direction.
+fn issue_order(sort: IssueSort, direction: super::SortDirection) -> serde_json::Value {
+    let field = match sort {
+        IssueSort::Created => "CREATED_AT",
+        IssueSort::Updated => "UPDATED_AT",
+        IssueSort::Comments => "COMMENTS",
+    };
+    let direction = match direction {
+        super::SortDirection::Asc => "ASC",
+        super::SortDirection::Desc => "DESC",
+    };
+    serde_json::json!({ "field": field, "direction": direction })
+}
+
+/// Query variables for `LIST_ISSUES`, without the repository and cursor.
+fn issue_variables(params: &IssueParams) -> serde_json::Value {
+    let states = match params.state {
+        IssueStateFilter::Open => serde_json::json!(["OPEN"]),
+        IssueStateFilter::Closed => serde_json::json!(["CLOSED"]),
+        IssueStateFilter::All => serde_json::Value::Null,
+    };
+    serde_json::json!({
+        "first": page_size(params.per_page, ISSUE_PAGE_SIZE),
+        "states": states,
+        "labels": params.labels,
+        "filterBy": {
+            "assignee": params.assignee,
+            "createdBy": params.creator,
+            "mentioned": params.mentioned,
+            "milestoneNumber": params.milestone,
+            "since": params.since,
+        },
+        "orderBy": issue_order(params.sort, params.direction),
+    })
+}
+
+/// Query variables for `LIST_PULLS`, without the repository and cursor.
+fn pull_variables(params: &PullParams) -> serde_json::Value {
+    let states = match params.state {
+        PullStateFilter::Open => serde_json::json!(["OPEN"]),
+        PullStateFilter::Closed => serde_json::json!(["CLOSED", "MERGED"]),
+        PullStateFilter::All => serde_json::Value::Null,
+    };
+    // REST accepts `user:branch` for head; GraphQL takes the branch name
+    let head = params
+        .head
+        .as_deref()
+        .map(|h| h.split_once(':').map_or(h, |(_, branch)| branch));
+    serde_json::json!({
+        "first": page_size(params.per_page, PULL_PAGE_SIZE),
+        "states": states,
+        "baseRefName": params.base,
+        "headRefName": head,
+        "orderBy": issue_order(params.sort, params.direction),
+    })
+}
+
+/// GraphQL `ProjectV2FieldValue` input for an update, or `None` to clear.
+fn field_update_value(update: &FieldUpdate) -> Option<serde_json::Value> {
+    match update {
+        FieldUpdate::Text { value } => Some(serde_json::json!({ "text": value })),
+        FieldUpdate::Number { value } => Some(serde_json::json!({ "number": value })),
+        FieldUpdate::Date { value } => Some(serde_json::json!({ "date": value })),
+        FieldUpdate::SingleSelect { option_id } => {
+            Some(serde_json::json!({ "singleSelectOptionId": option_id }))
+        }
+        FieldUpdate::Iteration { iteration_id } => {
+            Some(serde_json::json!({ "iterationId": iteration_id }))
+        }
+        FieldUpdate::Clear => None,
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -750,6 +1317,8 @@ struct GqlSelectOption {
 #[derive(Deserialize)]
 struct GqlIterationConfig {
     iterations: Vec<GqlIteration>,
+    #[serde(rename = "completedIterations", default)]
+    completed_iterations: Vec<GqlIteration>,
 }
 
 #[derive(Deserialize)]
@@ -761,6 +1330,184 @@ struct GqlIteration {
     duration: u32,
 }
 
+// -- Mutation responses --
+
+#[derive(Deserialize)]
+struct AddItemData {
+    #[serde(rename = "addProjectV2ItemById")]
+    add_project_v2_item_by_id: AddItemPayload,
+}
+
+#[derive(Deserialize)]
+struct AddItemPayload {
+    item: Option<NodeId>,
+}
+
+#[derive(Deserialize)]
+struct NodeId {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct ContentNodeData {
+    repository: Option<ContentNodeRepo>,
+}
+
+#[derive(Deserialize)]
+struct ContentNodeRepo {
+    #[serde(rename = "issueOrPullRequest")]
+    issue_or_pull_request: Option<NodeId>,
+}
+
+// -- Issue relations response --
+
+#[derive(Deserialize)]
+struct RelationsData {
+    repository: Option<RelationsRepo>,
+}
+
+#[derive(Deserialize)]
+struct RelationsRepo {
+    issues: Connection<GqlRelationIssue>,
+}
+
+#[derive(Deserialize)]
+struct GqlRelationIssue {
+    number: u64,
+    #[serde(rename = "trackedIssues")]
+    tracked_issues: Option<RelatedIssues>,
+    #[serde(rename = "subIssues")]
+    sub_issues: Option<RelatedIssues>,
+}
+
+#[derive(Deserialize)]
+struct RelatedIssues {
+    nodes: Vec<Option<GqlRelatedIssue>>,
+}
+
+#[derive(Deserialize)]
+struct GqlRelatedIssue {
+    number: u64,
+    repository: GqlRepoName,
+}
+
+#[derive(Deserialize)]
+struct GqlRepoName {
+    #[serde(rename = "nameWithOwner")]
+    name_with_owner: String,
+}
+
+// -- List issues and pull requests response --
+
+#[derive(Deserialize)]
+struct IssuesData {
+    repository: Option<IssuesRepo>,
+}
+
+#[derive(Deserialize)]
+struct IssuesRepo {
+    issues: Connection<GqlIssue>,
+}
+
+#[derive(Deserialize)]
+struct PullsData {
+    repository: Option<PullsRepo>,
+}
+
+#[derive(Deserialize)]
+struct PullsRepo {
+    #[serde(rename = "pullRequests")]
+    pull_requests: Connection<GqlPull>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlIssue {
+    full_database_id: Option<String>,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    author: Option<GqlActor>,
+    labels: Option<Nodes<GqlIssueLabel>>,
+    assignees: Option<Nodes<GqlActor>>,
+    milestone: Option<GqlMilestoneRef>,
+    comments: Option<TotalCount>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    timeline_items: Option<Nodes<GqlClosedEvent>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlPull {
+    full_database_id: Option<String>,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    is_draft: bool,
+    author: Option<GqlActor>,
+    labels: Option<Nodes<GqlIssueLabel>>,
+    milestone: Option<GqlMilestoneRef>,
+    head_ref_name: String,
+    base_ref_name: String,
+    merged: bool,
+    merged_at: Option<DateTime<Utc>>,
+    additions: u32,
+    deletions: u32,
+    changed_files: u32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+/// A connection read without pagination (nested lists).
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<Option<T>>,
+}
+
+impl<T> Nodes<T> {
+    fn into_vec(nodes: Option<Self>) -> Vec<T> {
+        nodes
+            .map(|n| n.nodes.into_iter().flatten().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlActor {
+    login: String,
+    #[serde(default)]
+    avatar_url: String,
+    #[serde(default)]
+    url: String,
+    database_id: Option<i64>,
+    #[serde(rename = "__typename", default)]
+    typename: String,
+}
+
+#[derive(Deserialize)]
+struct GqlIssueLabel {
+    id: String,
+    name: String,
+    color: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GqlMilestoneRef {
+    number: u64,
+}
+
+#[derive(Deserialize)]
+struct GqlClosedEvent {
+    actor: Option<GqlActor>,
+}
+
 // -- List items response --
 
 #[derive(Deserialize)]
@@ -917,15 +1664,21 @@ fn convert_field(gql: &GqlField) -> ProjectField {
                 .configuration
                 .as_ref()
                 .map(|cfg| {
-                    cfg.iterations
+                    // Completed iterations are listed separately; merge them
+                    // so sprint history is available for reporting.
+                    let mut iterations: Vec<IterationValue> = cfg
+                        .completed_iterations
                         .iter()
+                        .chain(cfg.iterations.iter())
                         .map(|it| IterationValue {
                             id: it.id.clone(),
                             title: it.title.clone(),
                             start_date: it.start_date.clone(),
                             duration: it.duration,
                         })
-                        .collect()
+                        .collect();
+                    iterations.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+                    iterations
                 })
                 .unwrap_or_default();
             ProjectFieldType::Iteration { iterations }
@@ -1075,6 +1828,113 @@ fn convert_field_value(gql: GqlFieldValueNode) -> Option<ProjectFieldValue> {
     Some(ProjectFieldValue { field_name, value })
 }
 
+fn convert_actor(gql: Option<GqlActor>) -> User {
+    match gql {
+        Some(actor) => User {
+            id: actor.database_id.unwrap_or(0),
+            login: actor.login,
+            avatar_url: actor.avatar_url,
+            html_url: actor.url,
+            is_bot: actor.typename == "Bot",
+        },
+        // Deleted accounts have no author
+        None => User::unknown(),
+    }
+}
+
+/// Stable numeric ID for a label, which GraphQL only identifies by node ID.
+fn label_id(node_id: &str) -> i64 {
+    // FNV-1a, shifted to stay positive
+    let hash = node_id.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    (hash >> 1) as i64
+}
+
+fn convert_labels(gql: Option<Nodes<GqlIssueLabel>>) -> Vec<Label> {
+    Nodes::into_vec(gql)
+        .into_iter()
+        .map(|l| Label {
+            id: label_id(&l.id),
+            name: l.name,
+            color: l.color,
+            description: l.description,
+        })
+        .collect()
+}
+
+fn resolve_milestone(gql: Option<GqlMilestoneRef>, milestones: &[Milestone]) -> Option<Milestone> {
+    let number = gql?.number;
+    let milestone = milestones.iter().find(|m| m.number == number).cloned();
+    if milestone.is_none() {
+        debug!(milestone = number, "Milestone missing from milestone list");
+    }
+    milestone
+}
+
+fn parse_database_id(id: Option<&str>) -> i64 {
+    id.and_then(|id| id.parse().ok()).unwrap_or(0)
+}
+
+fn convert_issue(gql: GqlIssue, milestones: &[Milestone]) -> Issue {
+    let closed_by = Nodes::into_vec(gql.timeline_items)
+        .into_iter()
+        .last()
+        .and_then(|event| event.actor)
+        .map(|actor| convert_actor(Some(actor)));
+    Issue {
+        id: parse_database_id(gql.full_database_id.as_deref()),
+        number: gql.number,
+        title: gql.title,
+        body: gql.body,
+        state: if gql.state == "OPEN" {
+            IssueState::Open
+        } else {
+            IssueState::Closed
+        },
+        labels: convert_labels(gql.labels),
+        assignees: Nodes::into_vec(gql.assignees)
+            .into_iter()
+            .map(|a| convert_actor(Some(a)))
+            .collect(),
+        milestone: resolve_milestone(gql.milestone, milestones),
+        author: convert_actor(gql.author),
+        comments_count: gql.comments.map(|c| c.total_count).unwrap_or(0),
+        created_at: gql.created_at,
+        updated_at: gql.updated_at,
+        closed_at: gql.closed_at,
+        closed_by,
+    }
+}
+
+fn convert_pull(gql: GqlPull, milestones: &[Milestone]) -> PullRequest {
+    PullRequest {
+        id: parse_database_id(gql.full_database_id.as_deref()),
+        number: gql.number,
+        title: gql.title,
+        body: gql.body,
+        state: if gql.state == "OPEN" {
+            PullState::Open
+        } else {
+            PullState::Closed
+        },
+        draft: gql.is_draft,
+        author: convert_actor(gql.author),
+        labels: convert_labels(gql.labels),
+        milestone: resolve_milestone(gql.milestone, milestones),
+        head_ref: gql.head_ref_name,
+        base_ref: gql.base_ref_name,
+        merged: gql.merged,
+        merged_at: gql.merged_at,
+        additions: gql.additions,
+        deletions: gql.deletions,
+        changed_files: gql.changed_files,
+        created_at: gql.created_at,
+        updated_at: gql.updated_at,
+        closed_at: gql.closed_at,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1229,6 +2089,9 @@ mod tests {
                                         "iterations": [
                                             { "id": "it1", "title": "Sprint 1", "startDate": "2026-01-06", "duration": 14 },
                                             { "id": "it2", "title": "Sprint 2", "startDate": "2026-01-20", "duration": 14 }
+                                        ],
+                                        "completedIterations": [
+                                            { "id": "it0", "title": "Sprint 0", "startDate": "2025-12-23", "duration": 14 }
                                         ]
                                     }
                                 },
@@ -1274,14 +2137,15 @@ mod tests {
             other => panic!("Expected SingleSelect, got {:?}", other),
         }
 
-        // Sprint -> Iteration with 2 iterations
+        // Sprint -> Iteration with 2 active + 1 completed iteration
         assert_eq!(fields[2].name, "Sprint");
         match &fields[2].field_type {
             ProjectFieldType::Iteration { iterations } => {
-                assert_eq!(iterations.len(), 2);
-                assert_eq!(iterations[0].title, "Sprint 1");
-                assert_eq!(iterations[0].duration, 14);
-                assert_eq!(iterations[1].title, "Sprint 2");
+                assert_eq!(iterations.len(), 3);
+                assert_eq!(iterations[0].title, "Sprint 0");
+                assert_eq!(iterations[1].title, "Sprint 1");
+                assert_eq!(iterations[1].duration, 14);
+                assert_eq!(iterations[2].title, "Sprint 2");
             }
             other => panic!("Expected Iteration, got {:?}", other),
         }
@@ -1575,4 +2439,273 @@ mod tests {
 
         assert!(convert_field_value(node).is_none());
     }
+
+    #[test]
+    fn test_field_update_value() {
+        let v = field_update_value(&FieldUpdate::SingleSelect {
+            option_id: "opt1".to_string(),
+        })
+        .unwrap();
+        assert_eq!(v, serde_json::json!({ "singleSelectOptionId": "opt1" }));
+
+        let v = field_update_value(&FieldUpdate::Number { value: 5.0 }).unwrap();
+        assert_eq!(v, serde_json::json!({ "number": 5.0 }));
+
+        let v = field_update_value(&FieldUpdate::Iteration {
+            iteration_id: "it1".to_string(),
+        })
+        .unwrap();
+        assert_eq!(v, serde_json::json!({ "iterationId": "it1" }));
+
+        assert!(field_update_value(&FieldUpdate::Clear).is_none());
+    }
+
+    #[test]
+    fn test_parse_add_item_response() {
+        let json = r#"{
+            "data": {
+                "addProjectV2ItemById": { "item": { "id": "PVTI_new" } }
+            }
+        }"#;
+        let resp: GraphQLResponse<AddItemData> = serde_json::from_str(json).unwrap();
+        let item = resp.data.unwrap().add_project_v2_item_by_id.item.unwrap();
+        assert_eq!(item.id, "PVTI_new");
+    }
+
+    #[test]
+    fn test_parse_issue_relations_response() {
+        let json = r#"{
+            "data": {
+                "repository": {
+                    "issues": {
+                        "nodes": [
+                            {
+                                "number": 1,
+                                "trackedIssues": {
+                                    "nodes": [
+                                        { "number": 2, "repository": { "nameWithOwner": "acme/app" } }
+                                    ]
+                                },
+                                "subIssues": {
+                                    "nodes": [
+                                        { "number": 9, "repository": { "nameWithOwner": "acme/api" } }
+                                    ]
+                                }
+                            },
+                            { "number": 2, "trackedIssues": { "nodes": [] }, "subIssues": null }
+                        ],
+                        "pageInfo": { "hasNextPage": false, "endCursor": null }
+                    }
+                }
+            }
+        }"#;
+
+        let resp: GraphQLResponse<RelationsData> = serde_json::from_str(json).unwrap();
+        let issues = resp.data.unwrap().repository.unwrap().issues;
+        let nodes: Vec<_> = issues.nodes.into_iter().flatten().collect();
+        assert_eq!(nodes.len(), 2);
+
+        let sub = nodes[0].sub_issues.as_ref().unwrap().nodes[0]
+            .as_ref()
+            .unwrap();
+        assert_eq!(sub.number, 9);
+        assert_eq!(sub.repository.name_with_owner, "acme/api");
+        assert!(nodes[1].sub_issues.is_none());
+    }
+
+    fn milestone(number: u64) -> Milestone {
+        Milestone {
+            id: 4200 + number as i64,
+            number,
+            title: format!("v{}", number),
+            description: None,
+            state: crate::models::MilestoneState::Open,
+            open_issues: 1,
+            closed_issues: 1,
+            due_on: None,
+            created_at: Utc::now(),
+            closed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_issues_response() {
+        let json = r#"{
+            "data": {
+                "repository": {
+                    "issues": {
+                        "nodes": [
+                            {
+                                "fullDatabaseId": "3000000001",
+                                "number": 7,
+                                "title": "Crash on start",
+                                "body": null,
+                                "state": "CLOSED",
+                                "author": { "login": "alice", "avatarUrl": "a", "url": "u", "databaseId": 11 },
+                                "labels": { "nodes": [
+                                    { "id": "LA_1", "name": "bug", "color": "d73a4a", "description": null },
+                                    { "id": "LA_2", "name": "p1", "color": "000000", "description": "Urgent" }
+                                ] },
+                                "assignees": { "nodes": [
+                                    { "login": "bob", "avatarUrl": "", "url": "", "databaseId": 12 }
+                                ] },
+                                "milestone": { "number": 2 },
+                                "comments": { "totalCount": 3 },
+                                "createdAt": "2024-01-01T00:00:00Z",
+                                "updatedAt": "2024-01-03T00:00:00Z",
+                                "closedAt": "2024-01-03T00:00:00Z",
+                                "timelineItems": { "nodes": [
+                                    { "actor": { "login": "carol", "avatarUrl": "", "url": "", "databaseId": 13 } }
+                                ] }
+                            },
+                            {
+                                "fullDatabaseId": "3000000002",
+                                "number": 8,
+                                "title": "Orphan",
+                                "body": "text",
+                                "state": "OPEN",
+                                "author": null,
+                                "labels": { "nodes": [] },
+                                "assignees": { "nodes": [] },
+                                "milestone": { "number": 9 },
+                                "comments": { "totalCount": 0 },
+                                "createdAt": "2024-01-01T00:00:00Z",
+                                "updatedAt": "2024-01-01T00:00:00Z",
+                                "closedAt": null,
+                                "timelineItems": { "nodes": [] }
+                            }
+                        ],
+                        "pageInfo": { "hasNextPage": false, "endCursor": null }
+                    }
+                }
+            }
+        }"#;
+
+        let resp: GraphQLResponse<IssuesData> = serde_json::from_str(json).unwrap();
+        let milestones = [milestone(2)];
+        let issues: Vec<Issue> = resp
+            .data
+            .unwrap()
+            .repository
+            .unwrap()
+            .issues
+            .nodes
+            .into_iter()
+            .flatten()
+            .map(|node| convert_issue(node, &milestones))
+            .collect();
+
+        let closed = &issues[0];
+        assert_eq!(closed.id, 3_000_000_001);
+        assert_eq!(closed.state, IssueState::Closed);
+        assert_eq!(closed.author.id, 11);
+        assert_eq!(closed.assignees[0].login, "bob");
+        assert_eq!(closed.labels.len(), 2);
+        assert_ne!(closed.labels[0].id, closed.labels[1].id);
+        assert_eq!(closed.milestone.as_ref().unwrap().id, 4202);
+        assert_eq!(closed.comments_count, 3);
+        assert_eq!(closed.closed_by.as_ref().unwrap().login, "carol");
+
+        // Unknown milestones and deleted authors degrade instead of failing
+        let open = &issues[1];
+        assert!(open.milestone.is_none());
+        assert_eq!(open.author.login, "unknown");
+        assert!(open.closed_by.is_none());
+    }
+
+    #[test]
+    fn test_parse_pulls_response() {
+        let json = r#"{
+            "data": {
+                "repository": {
+                    "pullRequests": {
+                        "nodes": [
+                            {
+                                "fullDatabaseId": "99",
+                                "number": 12,
+                                "title": "Add cache",
+                                "body": null,
+                                "state": "MERGED",
+                                "isDraft": false,
+                                "author": { "login": "dependabot", "avatarUrl": "", "url": "", "databaseId": 49699333 },
+                                "labels": { "nodes": [] },
+                                "milestone": null,
+                                "headRefName": "cache",
+                                "baseRefName": "main",
+                                "merged": true,
+                                "mergedAt": "2024-02-02T00:00:00Z",
+                                "additions": 120,
+                                "deletions": 30,
+                                "changedFiles": 4,
+                                "createdAt": "2024-02-01T00:00:00Z",
+                                "updatedAt": "2024-02-02T00:00:00Z",
+                                "closedAt": "2024-02-02T00:00:00Z"
+                            }
+                        ],
+                        "pageInfo": { "hasNextPage": true, "endCursor": "Y3Vyc29y" }
+                    }
+                }
+            }
+        }"#;
+
+        let resp: GraphQLResponse<PullsData> = serde_json::from_str(json).unwrap();
+        let connection = resp.data.unwrap().repository.unwrap().pull_requests;
+        assert!(connection.page_info.has_next_page);
+        let pull = convert_pull(connection.nodes.into_iter().flatten().next().unwrap(), &[]);
+
+        assert_eq!(pull.id, 99);
+        assert_eq!(pull.state, PullState::Closed);
+        assert!(pull.merged);
+        assert_eq!(pull.lines_changed(), 150);
+        assert_eq!(pull.changed_files, 4);
+        assert_eq!(pull.size_category(), crate::models::PrSize::Medium);
+    }
+
+    #[test]
+    fn test_issue_variables() {
+        let params = IssueParams {
+            labels: Some(vec!["bug".to_string()]),
+            assignee: Some("bob".to_string()),
+            milestone: Some("3".to_string()),
+            ..IssueParams::closed()
+        };
+        let vars = issue_variables(&params);
+        assert_eq!(vars["first"], 100);
+        assert_eq!(vars["states"], serde_json::json!(["CLOSED"]));
+        assert_eq!(vars["labels"], serde_json::json!(["bug"]));
+        assert_eq!(vars["filterBy"]["assignee"], "bob");
+        assert_eq!(vars["filterBy"]["milestoneNumber"], "3");
+        assert_eq!(vars["orderBy"]["field"], "CREATED_AT");
+        assert_eq!(vars["orderBy"]["direction"], "DESC");
+
+        assert!(issue_variables(&IssueParams::all())["states"].is_null());
+    }
+
+    #[test]
+    fn test_pull_variables() {
+        let params = PullParams {
+            head: Some("acme:feature".to_string()),
+            base: Some("main".to_string()),
+            ..PullParams::merged()
+        };
+        let vars = pull_variables(&params);
+        assert_eq!(vars["first"], 50);
+        assert_eq!(vars["states"], serde_json::json!(["CLOSED", "MERGED"]));
+        assert_eq!(vars["headRefName"], "feature");
+        assert_eq!(vars["baseRefName"], "main");
+
+        let vars = pull_variables(&PullParams {
+            per_page: 10,
+            ..Default::default()
+        });
+        assert_eq!(vars["first"], 10);
+        assert_eq!(vars["states"], serde_json::json!(["OPEN"]));
+    }
+
+    #[test]
+    fn test_label_id_is_stable() {
+        assert_eq!(label_id("LA_kwDOA"), label_id("LA_kwDOA"));
+        assert_ne!(label_id("LA_kwDOA"), label_id("LA_kwDOB"));
+        assert!(label_id("LA_kwDOA") >= 0);
+    }
 }
@@ -1,9 +1,11 @@
 //! Mock GitHub client for testing
 
-use super::{GitHubClient, IssueParams, PullParams, RateLimitInfo, RepoId};
+use super::{GitHubClient, IssueParams, ProjectClient, PullParams, RateLimitInfo, RepoId};
 use crate::models::{
-    Issue, IssueEvent, IssueState, Label, Milestone, MilestoneState, PullRequest, PullState,
-    Release, Repository, Review, User,
+    FieldUpdate, FieldValue, Issue, IssueEvent, IssueRelation, IssueState, Label, LabelInfo,
+    Milestone, MilestoneState, Project, ProjectField, ProjectFieldType, ProjectFieldValue,
+    ProjectItem, ProjectItemContent, PullRequest, PullState, Release, Repository, Review,
+    SelectOption, User,
 };
 use crate::Result;
 use async_trait::async_trait;
@@ -16,11 +18,16 @@ pub struct MockData {
     pub repositories: HashMap<String, Repository>,
     pub issues: HashMap<String, Vec<Issue>>,
     pub issue_events: HashMap<(String, u64), Vec<IssueEvent>>,
+    pub issue_relations: HashMap<String, Vec<IssueRelation>>,
     pub milestones: HashMap<String, Vec<Milestone>>,
     pub pulls: HashMap<String, Vec<PullRequest>>,
     pub pull_reviews: HashMap<(String, u64), Vec<Review>>,
     pub releases: HashMap<String, Vec<Release>>,
     pub users: HashMap<String, User>,
+    /// Projects keyed by owning organization
+    pub projects: HashMap<String, Vec<Project>>,
+    /// Project items keyed by project node ID
+    pub project_items: HashMap<String, Vec<ProjectItem>>,
 }
 
 impl MockData {
@@ -42,6 +49,12 @@ impl MockData {
         self
     }
 
+    /// Add tracked-issue/sub-issue relations to a repository
+    pub fn with_issue_relations(mut self, repo: &str, relations: Vec<IssueRelation>) -> Self {
+        self.issue_relations.insert(repo.to_string(), relations);
+        self
+    }
+
     /// Add milestones to a repository
     pub fn with_milestones(mut self, repo: &str, milestones: Vec<Milestone>) -> Self {
         self.milestones.insert(repo.to_string(), milestones);
@@ -65,6 +78,16 @@ impl MockData {
         self.users.insert(user.login.clone(), user);
         self
     }
+
+    /// Add a project and its items
+    pub fn with_project(mut self, project: Project, items: Vec<ProjectItem>) -> Self {
+        self.project_items.insert(project.node_id.clone(), items);
+        self.projects
+            .entry(project.owner.clone())
+            .or_default()
+            .push(project);
+        self
+    }
 }
 
 /// Mock GitHub client for testing
@@ -92,6 +115,7 @@ impl MockGitHubClient {
             login: "test-user".to_string(),
             avatar_url: "https://example.com/avatar.png".to_string(),
             html_url: "https://github.com/test-user".to_string(),
+            is_bot: false,
         };
 
         let bug_label = Label {
@@ -241,13 +265,74 @@ impl MockGitHubClient {
             updated_at: now,
         };
 
+        let status_field = ProjectField {
+            node_id: "PVTSSF_status".to_string(),
+            name: "Status".to_string(),
+            field_type: ProjectFieldType::SingleSelect {
+                options: vec![
+                    SelectOption {
+                        id: "opt_todo".to_string(),
+                        name: "Todo".to_string(),
+                        color: None,
+                        description: None,
+                    },
+                    SelectOption {
+                        id: "opt_done".to_string(),
+                        name: "Done".to_string(),
+                        color: None,
+                        description: None,
+                    },
+                ],
+            },
+        };
+
+        let project_items = vec![ProjectItem {
+            node_id: "PVTI_1".to_string(),
+            content: ProjectItemContent::Issue {
+                number: 1,
+                title: "Fix login bug".to_string(),
+                state: "OPEN".to_string(),
+                url: "https://github.com/test-owner/test-repo/issues/1".to_string(),
+                repository: "test-owner/test-repo".to_string(),
+                assignees: vec![test_user.login.clone()],
+                labels: vec![LabelInfo {
+                    name: bug_label.name.clone(),
+                    color: bug_label.color.clone(),
+                }],
+            },
+            field_values: vec![ProjectFieldValue {
+                field_name: "Status".to_string(),
+                value: FieldValue::SingleSelect {
+                    name: "Todo".to_string(),
+                    option_id: "opt_todo".to_string(),
+                },
+            }],
+            created_at: one_week_ago,
+            updated_at: one_day_ago,
+        }];
+
+        let project = Project {
+            node_id: "PVT_1".to_string(),
+            number: 1,
+            title: "Roadmap".to_string(),
+            description: Some("Test project".to_string()),
+            url: "https://github.com/orgs/test-owner/projects/1".to_string(),
+            closed: false,
+            owner: "test-owner".to_string(),
+            created_at: two_weeks_ago,
+            updated_at: one_day_ago,
+            fields: vec![status_field],
+            total_items: project_items.len() as u32,
+        };
+
         let data = MockData::new()
             .with_repository(repo)
             .with_issues("test-owner/test-repo", issues)
             .with_milestones("test-owner/test-repo", vec![milestone])
             .with_pulls("test-owner/test-repo", pulls)
             .with_releases("test-owner/test-repo", releases)
-            .with_user(test_user);
+            .with_user(test_user)
+            .with_project(project, project_items);
 
         Self::new(data)
     }
@@ -354,6 +439,15 @@ impl GitHubClient for MockGitHubClient {
             .unwrap_or_default())
     }
 
+    async fn list_issue_relations(&self, repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        let data = self.data.read().unwrap();
+        Ok(data
+            .issue_relations
+            .get(&repo.full_name())
+            .cloned()
+            .unwrap_or_default())
+    }
+
     async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
         let data = self.data.read().unwrap();
         Ok(data
@@ -449,6 +543,141 @@ impl GitHubClient for MockGitHubClient {
     }
 }
 
+#[async_trait]
+impl ProjectClient for MockGitHubClient {
+    async fn list_projects(&self, org: &str) -> Result<Vec<Project>> {
+        let data = self.data.read().unwrap();
+        Ok(data.projects.get(org).cloned().unwrap_or_default())
+    }
+
+    async fn get_project(&self, org: &str, project_number: u64) -> Result<Project> {
+        let data = self.data.read().unwrap();
+        data.projects
+            .get(org)
+            .and_then(|projects| projects.iter().find(|p| p.number == project_number))
+            .cloned()
+            .ok_or_else(|| {
+                crate::Error::NotFound(format!("Project #{} not found in {}", project_number, org))
+            })
+    }
+
+    async fn list_project_items(&self, project_node_id: &str) -> Result<Vec<ProjectItem>> {
+        let data = self.data.read().unwrap();
+        Ok(data
+            .project_items
+            .get(project_node_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn update_project_item_field(
+        &self,
+        project_node_id: &str,
+        item_node_id: &str,
+        field_node_id: &str,
+        update: &FieldUpdate,
+    ) -> Result<()> {
+        let mut data = self.data.write().unwrap();
+        let field_name = data
+            .projects
+            .values()
+            .flatten()
+            .find(|p| p.node_id == project_node_id)
+            .and_then(|p| p.fields.iter().find(|f| f.node_id == field_node_id))
+            .map(|f| f.name.clone())
+            .ok_or_else(|| crate::Error::NotFound(format!("Field {} not found", field_node_id)))?;
+        let item = data
+            .project_items
+            .get_mut(project_node_id)
+            .and_then(|items| items.iter_mut().find(|i| i.node_id == item_node_id))
+            .ok_or_else(|| crate::Error::NotFound(format!("Item {} not found", item_node_id)))?;
+
+        let value = match update {
+            FieldUpdate::Text { value } => FieldValue::Text {
+                value: value.clone(),
+            },
+            FieldUpdate::Number { value } => FieldValue::Number { value: *value },
+            FieldUpdate::Date { value } => FieldValue::Date {
+                value: value.clone(),
+            },
+            FieldUpdate::SingleSelect { option_id } => FieldValue::SingleSelect {
+                name: option_id.clone(),
+                option_id: option_id.clone(),
+            },
+            FieldUpdate::Iteration { iteration_id } => FieldValue::Iteration {
+                title: iteration_id.clone(),
+                start_date: String::new(),
+                duration: 0,
+                iteration_id: iteration_id.clone(),
+            },
+            FieldUpdate::Clear => FieldValue::Empty,
+        };
+        item.field_values.retain(|v| v.field_name != field_name);
+        item.field_values
+            .push(ProjectFieldValue { field_name, value });
+        Ok(())
+    }
+
+    async fn add_project_item(
+        &self,
+        project_node_id: &str,
+        repo: &RepoId,
+        number: u64,
+    ) -> Result<String> {
+        let mut data = self.data.write().unwrap();
+        let issue = data
+            .issues
+            .get(&repo.full_name())
+            .and_then(|issues| issues.iter().find(|i| i.number == number))
+            .cloned()
+            .ok_or_else(|| crate::Error::NotFound(format!("Issue #{} not found", number)))?;
+        let items = data.project_items.get_mut(project_node_id).ok_or_else(|| {
+            crate::Error::NotFound(format!("Project {} not found", project_node_id))
+        })?;
+
+        let node_id = format!("PVTI_{}", items.len() + 1);
+        items.push(ProjectItem {
+            node_id: node_id.clone(),
+            content: ProjectItemContent::Issue {
+                number,
+                title: issue.title,
+                state: format!("{:?}", issue.state).to_uppercase(),
+                url: format!("https://github.com/{}/issues/{}", repo, number),
+                repository: repo.full_name(),
+                assignees: issue.assignees.into_iter().map(|a| a.login).collect(),
+                labels: issue
+                    .labels
+                    .into_iter()
+                    .map(|l| LabelInfo {
+                        name: l.name,
+                        color: l.color,
+                    })
+                    .collect(),
+            },
+            field_values: Vec::new(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        });
+        Ok(node_id)
+    }
+
+    async fn archive_project_item(&self, project_node_id: &str, item_node_id: &str) -> Result<()> {
+        let mut data = self.data.write().unwrap();
+        let items = data.project_items.get_mut(project_node_id).ok_or_else(|| {
+            crate::Error::NotFound(format!("Project {} not found", project_node_id))
+        })?;
+        let before = items.len();
+        items.retain(|i| i.node_id != item_node_id);
+        if items.len() == before {
+            return Err(crate::Error::NotFound(format!(
+                "Item {} not found",
+                item_node_id
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -509,4 +738,36 @@ mod tests {
         assert_eq!(rate_limit.remaining, 5000);
         assert_eq!(rate_limit.limit, 5000);
     }
+
+    #[tokio::test]
+    async fn test_mock_client_projects() {
+        let client = MockGitHubClient::with_sample_data();
+
+        let projects = client.list_projects("test-owner").await.unwrap();
+        assert_eq!(projects.len(), 1);
+        let project = client.get_project("test-owner", 1).await.unwrap();
+        assert!(client.get_project("test-owner", 99).await.is_err());
+
+        let repo_id = RepoId::new("test-owner", "test-repo");
+        let node_id = client
+            .add_project_item(&project.node_id, &repo_id, 2)
+            .await
+            .unwrap();
+        let (field, update) = project.resolve_field_update("Status", "Done").unwrap();
+        client
+            .update_project_item_field(&project.node_id, &node_id, &field.node_id, &update)
+            .await
+            .unwrap();
+
+        let items = client.list_project_items(&project.node_id).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].field_values[0].field_name, "Status");
+
+        client
+            .archive_project_item(&project.node_id, &node_id)
+            .await
+            .unwrap();
+        let items = client.list_project_items(&project.node_id).await.unwrap();
+        assert_eq!(items.len(), 1);
+    }
 }
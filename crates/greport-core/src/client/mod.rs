@@ -1,9 +1,17 @@
 //! GitHub API client abstraction
 
+mod app_auth;
+mod budget;
+mod etag_cache;
+mod fixtures;
+mod gitea;
+mod gitlab;
 pub mod graphql;
 mod octocrab_client;
+mod paginate;
 mod params;
 mod registry;
+mod rest;
 mod retry;
 
 #[cfg(any(test, feature = "mock"))]
@@ -11,8 +19,19 @@ mod mock_client;
 
 pub use retry::RetryConfig;
 
+pub use app_auth::{AppCredentials, Credentials, InstallationTokens};
+
+pub use budget::{
+    RateBudget, RateBudgetSnapshot, RateResource, ResourceBudget, DEFAULT_RATE_LIMIT_RESERVE,
+};
+
+pub use etag_cache::{CachedResponse, EtagStore, FileEtagStore};
+pub use fixtures::{RecordingClient, ReplayClient};
+pub use gitea::GiteaClient;
+pub use gitlab::{GitLabClient, GITLAB_API_URL};
 pub use graphql::GraphQLClient;
 pub use octocrab_client::OctocrabClient;
+pub use paginate::{Listing, PageProgress, Pagination};
 pub use params::*;
 pub use registry::{GitHubClientRegistry, OrgEntry};
 
@@ -20,8 +39,8 @@ pub use registry::{GitHubClientRegistry, OrgEntry};
 pub use mock_client::{MockData, MockGitHubClient};
 
 use crate::models::{
-    Issue, IssueEvent, Milestone, Project, ProjectItem, PullRequest, Release, Repository, Review,
-    User,
+    FieldUpdate, Issue, IssueEvent, IssueRelation, Milestone, Project, ProjectItem, PullRequest,
+    Release, Repository, Review, User,
 };
 use crate::Result;
 use async_trait::async_trait;
@@ -87,6 +106,9 @@ pub trait GitHubClient: Send + Sync {
     /// List events for an issue
     async fn list_issue_events(&self, repo: &RepoId, number: u64) -> Result<Vec<IssueEvent>>;
 
+    /// List tracked-issue and sub-issue relations for issues in a repository
+    async fn list_issue_relations(&self, repo: &RepoId) -> Result<Vec<IssueRelation>>;
+
     /// List milestones for a repository
     async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>>;
 
@@ -118,10 +140,17 @@ pub trait GitHubClient: Send + Sync {
 
     /// Get current rate limit status
     async fn rate_limit(&self) -> Result<RateLimitInfo>;
+
+    /// Live rate-limit budget, as tracked from recent responses
+    ///
+    /// `None` for clients that do not talk to GitHub directly.
+    fn rate_budget(&self) -> Option<RateBudgetSnapshot> {
+        None
+    }
 }
 
 /// Rate limit information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RateLimitInfo {
     /// Remaining requests
     pub remaining: u32,
@@ -145,4 +174,33 @@ pub trait ProjectClient: Send + Sync {
 
     /// List all items in a project.
     async fn list_project_items(&self, project_node_id: &str) -> Result<Vec<ProjectItem>>;
+
+    /// Set or clear a field value on a project item.
+    async fn update_project_item_field(
+        &self,
+        project_node_id: &str,
+        item_node_id: &str,
+        field_node_id: &str,
+        update: &FieldUpdate,
+    ) -> Result<()>;
+
+    /// Add an issue or pull request to a project, returning the new item's node ID.
+    async fn add_project_item(
+        &self,
+        project_node_id: &str,
+        repo: &RepoId,
+        number: u64,
+    ) -> Result<String>;
+
+    /// Archive a project item.
+    async fn archive_project_item(&self, project_node_id: &str, item_node_id: &str) -> Result<()>;
 }
+
+/// A client for both the REST and Projects V2 APIs.
+///
+/// Implemented for every type that implements `GitHubClient` and
+/// `ProjectClient`, so `Arc<dyn GitHubApi>` can hold a real `OctocrabClient`
+/// or a `MockGitHubClient` interchangeably.
+pub trait GitHubApi: GitHubClient + ProjectClient {}
+
+impl<T: GitHubClient + ProjectClient + ?Sized> GitHubApi for T {}
@@ -1,18 +1,30 @@
 //! Octocrab-based GitHub client implementation
 
+use super::app_auth::Credentials;
+use super::budget::{unix_time, RateBudget, RateBudgetSnapshot, RateResource, ResourceBudget};
+use super::etag_cache::{store_key, CachedResponse, EtagStore, FileEtagStore};
 use super::graphql::GraphQLClient;
-use super::retry::RetryConfig;
+use super::paginate::{PageCollector, Pagination};
+use super::retry::{with_retry, RetryConfig};
 use super::{
-    GitHubClient, IssueParams, IssueStateFilter, ProjectClient, PullParams, PullStateFilter,
-    RateLimitInfo, RepoId,
+    DataSource, GitHubClient, IssueParams, IssueStateFilter, ProjectClient, PullParams,
+    PullStateFilter, RateLimitInfo, RepoId,
 };
 use crate::models::{
-    Issue, IssueEvent, IssueState, Label, Milestone, MilestoneState, Project, ProjectItem,
-    PullRequest, PullState, Release, Repository, Review, User,
+    FieldUpdate, Issue, IssueEvent, IssueRelation, IssueState, Label, Milestone, MilestoneState,
+    Project, ProjectItem, PullRequest, PullState, Release, Repository, Review, User,
 };
 use crate::{Error, Result};
 use async_trait::async_trait;
-use octocrab::Octocrab;
+use bytes::Bytes;
+use http::header::{AUTHORIZATION, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LINK};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full};
+use octocrab::{FromResponse, Octocrab, Page};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
 use tracing::{debug, error, info, instrument, warn};
 
 /// Log detailed error information for debugging
@@ -116,18 +128,63 @@ fn log_api_error(operation: &str, endpoint: &str, err: &octocrab::Error) {
     }
 }
 
+/// Add the validators of a stored response as conditional request headers
+fn add_conditional_headers(headers: &mut HeaderMap, cached: &CachedResponse) {
+    let values = [
+        (IF_NONE_MATCH, &cached.etag),
+        (IF_MODIFIED_SINCE, &cached.last_modified),
+    ];
+    for (name, value) in values {
+        if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+            headers.insert(name, value);
+        }
+    }
+}
+
+/// Rebuild a successful response from a stored one
+fn replay(cached: &CachedResponse) -> http::Response<BoxBody<Bytes, octocrab::Error>> {
+    let mut response = http::Response::new(full_body(Bytes::from(cached.body.clone())));
+    if let Some(link) = cached
+        .link
+        .as_deref()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        response.headers_mut().insert(LINK, link);
+    }
+    response
+}
+
+fn full_body(body: Bytes) -> BoxBody<Bytes, octocrab::Error> {
+    Full::new(body).map_err(|never| match never {}).boxed()
+}
+
 /// GitHub client using octocrab with retry support
+///
+/// Every request waits on a [`RateBudget`] shared with the GraphQL client,
+/// so a token's calls pause near exhaustion instead of failing.
 pub struct OctocrabClient {
     client: Octocrab,
     retry_config: RetryConfig,
+    budget: Arc<RateBudget>,
+    credentials: Credentials,
+    etag_store: Option<Arc<dyn EtagStore>>,
+    /// Credentials and base URL, separating stored responses per token
+    cache_scope: String,
     graphql: GraphQLClient,
+    data_source: DataSource,
+    pagination: Pagination,
 }
 
 impl std::fmt::Debug for OctocrabClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OctocrabClient")
             .field("retry_config", &self.retry_config)
+            .field("budget", &self.budget)
+            .field("credentials", &self.credentials)
+            .field("etag_store", &self.etag_store)
             .field("graphql", &self.graphql)
+            .field("data_source", &self.data_source)
+            .field("pagination", &self.pagination)
             .finish_non_exhaustive()
     }
 }
@@ -139,19 +196,20 @@ impl OctocrabClient {
     /// * `token` - GitHub personal access token
     /// * `base_url` - Optional base URL for GitHub Enterprise (e.g., `https://github.mycompany.com/api/v3`)
     pub fn new(token: &str, base_url: Option<&str>) -> Result<Self> {
-        let mut builder = Octocrab::builder().personal_token(token.to_string());
-
-        // Log token type (without exposing the actual token)
-        let token_type = if token.starts_with("ghp_") {
-            "classic PAT"
-        } else if token.starts_with("github_pat_") {
-            "fine-grained PAT"
-        } else if token.starts_with("gho_") {
-            "OAuth token"
-        } else {
-            "unknown token type"
-        };
-        debug!(token_type = token_type, "Creating GitHub client");
+        Self::from_credentials(Credentials::from(token), base_url)
+    }
+
+    /// Create a client authenticating with `credentials` (a token or a
+    /// GitHub App installation) and optional base URL
+    pub fn from_credentials(credentials: Credentials, base_url: Option<&str>) -> Result<Self> {
+        // Retries are ours (see `get`): octocrab's own layer repeats 5xx and
+        // 429 responses immediately, without backoff or honouring Retry-After.
+        // Authentication is ours too, since installation tokens expire.
+        let mut builder = Octocrab::builder()
+            .add_retry_config(octocrab::service::middleware::retry::RetryConfig::None);
+
+        // Log credential type (without exposing the actual token)
+        debug!(token_type = credentials.kind(), "Creating GitHub client");
 
         if let Some(url) = base_url {
             debug!(base_url = url, "Using GitHub Enterprise base URL");
@@ -166,22 +224,234 @@ impl OctocrabClient {
             .build()
             .map_err(|e| Error::Custom(format!("Failed to create GitHub client: {}", e)))?;
 
-        let graphql = GraphQLClient::new(token, base_url)?;
+        let graphql = GraphQLClient::from_credentials(credentials.clone(), base_url)?;
+        let budget = graphql.budget().clone();
 
         debug!("GitHub client created successfully");
         Ok(Self {
             client,
             retry_config: RetryConfig::default(),
+            budget,
+            cache_scope: format!(
+                "{} {}",
+                credentials.scope(),
+                base_url.unwrap_or("https://api.github.com")
+            ),
+            credentials,
+            etag_store: None,
             graphql,
+            data_source: DataSource::default(),
+            pagination: Pagination::default(),
         })
     }
 
     /// Set custom retry configuration
     pub fn with_retry_config(mut self, config: RetryConfig) -> Self {
+        self.graphql = self.graphql.with_retry_config(config.clone());
         self.retry_config = config;
         self
     }
 
+    /// Pause calls once only `reserve` requests are left in a rate-limit
+    /// window (see [`crate::Config::rate_limit_reserve`])
+    pub fn with_rate_limit_reserve(self, reserve: u32) -> Self {
+        self.budget.set_reserve(reserve);
+        self
+    }
+
+    /// Send conditional requests, keeping responses in `store`
+    ///
+    /// Unchanged resources then come back as free `304 Not Modified`
+    /// responses and are served from the store.
+    pub fn with_etag_store(mut self, store: impl EtagStore + 'static) -> Self {
+        self.etag_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Apply the client settings from `config`: data source, rate-limit
+    /// reserve and the conditional-request cache
+    pub fn with_config(self, config: &crate::config::Config) -> Result<Self> {
+        let client = self
+            .with_data_source(config.data_source()?)
+            .with_rate_limit_reserve(config.rate_limit_reserve());
+        Ok(match config.etag_cache_dir() {
+            Some(dir) => client.with_etag_store(FileEtagStore::new(dir)),
+            None => client,
+        })
+    }
+
+    /// Rate-limit budget shared by this client's REST and GraphQL calls
+    pub fn budget(&self) -> &Arc<RateBudget> {
+        &self.budget
+    }
+
+    /// Set the API used to list issues and pull requests
+    pub fn with_data_source(mut self, data_source: DataSource) -> Self {
+        self.data_source = data_source;
+        self
+    }
+
+    /// Set the item cap and progress callback for list calls
+    pub fn with_pagination(mut self, pagination: Pagination) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// `Authorization` header for the next request
+    async fn auth_headers(&self) -> std::result::Result<HeaderMap, octocrab::Error> {
+        let token = self
+            .credentials
+            .token()
+            .await
+            .map_err(|e| octocrab::Error::Other {
+                source: Box::new(e),
+                backtrace: std::backtrace::Backtrace::capture(),
+            })?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|e| {
+            octocrab::Error::InvalidHeaderValue {
+                source: e,
+                backtrace: std::backtrace::Backtrace::capture(),
+            }
+        })?;
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, value);
+        Ok(headers)
+    }
+
+    /// Send a REST `GET` for `route` with optional query parameters
+    ///
+    /// Waits on the core rate budget, records the budget headers of every
+    /// response, and retries transient failures and rate limits. With an
+    /// ETag store the request is conditional, and a 304 is answered from
+    /// the stored body.
+    async fn get<R, P>(
+        &self,
+        operation: &'static str,
+        route: &str,
+        parameters: Option<&P>,
+    ) -> std::result::Result<R, octocrab::Error>
+    where
+        R: FromResponse,
+        P: Serialize + ?Sized,
+    {
+        let uri = match parameters {
+            Some(parameters) => {
+                let query = serde_urlencoded::to_string(parameters).map_err(|e| {
+                    octocrab::Error::Other {
+                        source: Box::new(e),
+                        backtrace: std::backtrace::Backtrace::capture(),
+                    }
+                })?;
+                let separator = if route.contains('?') { '&' } else { '?' };
+                format!("{route}{separator}{query}")
+            }
+            None => route.to_string(),
+        };
+
+        let store = self
+            .etag_store
+            .as_ref()
+            .map(|store| (store, store_key(&self.cache_scope, &uri)));
+        let cached = store
+            .as_ref()
+            .and_then(|(store, key)| store.load(key))
+            .filter(|c| c.url == uri && c.is_conditional());
+
+        with_retry(operation, &self.retry_config, || async {
+            self.budget.acquire(RateResource::Core).await;
+            let mut headers = self.auth_headers().await?;
+            if let Some(cached) = &cached {
+                add_conditional_headers(&mut headers, cached);
+            }
+            let response = self
+                .client
+                ._get_with_headers(uri.as_str(), Some(headers))
+                .await?;
+            self.budget.record(
+                RateResource::Core,
+                response.status().as_u16(),
+                response.headers(),
+            );
+
+            if let (StatusCode::NOT_MODIFIED, Some(cached)) = (response.status(), &cached) {
+                debug!(operation = operation, "Not modified, using stored response");
+                return R::from_response(replay(cached)).await;
+            }
+
+            let response = octocrab::map_github_error(response).await?;
+            let Some((store, key)) = &store else {
+                return R::from_response(response).await;
+            };
+
+            let (parts, body) = response.into_parts();
+            let body = body.collect().await?.to_bytes();
+            let header = |name| {
+                parts
+                    .headers
+                    .get(name)
+                    .and_then(|v: &HeaderValue| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            let entry = CachedResponse {
+                url: uri.clone(),
+                etag: header(ETAG),
+                last_modified: header(LAST_MODIFIED),
+                link: header(LINK),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            };
+            if entry.is_conditional() {
+                store.store(key, &entry);
+            }
+            R::from_response(http::Response::from_parts(parts, full_body(body))).await
+        })
+        .await
+    }
+
+    /// Collect `first` and every following page, converting each item with
+    /// `convert` (items it maps to `None` are skipped)
+    async fn collect_pages<T, U>(
+        &self,
+        operation: &'static str,
+        endpoint: &str,
+        first: Page<T>,
+        mut convert: impl FnMut(T) -> Option<U>,
+    ) -> Result<Vec<U>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut collector = PageCollector::new(&self.pagination, operation);
+        let mut page = first;
+        loop {
+            debug!(
+                operation = operation,
+                items_in_page = page.items.len(),
+                has_next = page.next.is_some(),
+                "Received page"
+            );
+            let total_pages = page.number_of_pages();
+            let next = page.next.take();
+            let more = collector.push_page(
+                page.items.into_iter().filter_map(&mut convert),
+                next.is_some(),
+                total_pages,
+            );
+            if !more {
+                break;
+            }
+
+            let Some(next) = next else { break };
+            page = match self.get(operation, &next.to_string(), None::<&()>).await {
+                Ok(p) => p,
+                Err(e) => {
+                    log_api_error(operation, endpoint, &e);
+                    return Err(e.into());
+                }
+            };
+        }
+        collector.into_items()
+    }
+
     /// Create a client with only a token (uses default GitHub.com API)
     pub fn with_token(token: &str) -> Result<Self> {
         Self::new(token, None)
@@ -208,8 +478,65 @@ impl OctocrabClient {
     /// Create a client from a Config object
     pub fn from_config(config: &crate::config::Config) -> Result<Self> {
         debug!("Creating GitHub client from config");
-        let token = config.github_token()?;
-        Self::new(&token, config.github.base_url.as_deref())
+        let credentials = config.github_credentials()?;
+        Self::from_credentials(credentials, config.github.base_url.as_deref())
+    }
+
+    /// Fetch the user the token belongs to (`GET /user`)
+    #[instrument(skip(self))]
+    pub async fn authenticated_user(&self) -> Result<User> {
+        let endpoint = "/user";
+        debug!(endpoint = %endpoint, "Fetching authenticated user");
+
+        let user = match self
+            .get::<octocrab::models::Author, ()>("authenticated_user", endpoint, None)
+            .await
+        {
+            Ok(u) => u,
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 401 => {
+                // A bad token supplied by a caller, not a server fault
+                debug!(endpoint = %endpoint, "Token rejected by GitHub");
+                return Err(Error::InvalidToken);
+            }
+            Err(e) => {
+                log_api_error("authenticated_user", endpoint, &e);
+                return Err(e.into());
+            }
+        };
+
+        Ok(Self::convert_user(user))
+    }
+
+    /// List organization logins for the token's user (`GET /user/orgs`).
+    ///
+    /// Only organizations visible to the token are returned; private
+    /// memberships need the `read:org` scope.
+    #[instrument(skip(self))]
+    pub async fn authenticated_user_orgs(&self) -> Result<Vec<String>> {
+        #[derive(serde::Deserialize)]
+        struct OrgLogin {
+            login: String,
+        }
+
+        let endpoint = "/user/orgs";
+        debug!(endpoint = %endpoint, "Fetching authenticated user organizations");
+
+        let orgs: Vec<OrgLogin> = match self
+            .get(
+                "authenticated_user_orgs",
+                endpoint,
+                Some(&[("per_page", "100")]),
+            )
+            .await
+        {
+            Ok(orgs) => orgs,
+            Err(e) => {
+                debug!(endpoint = %endpoint, error = %e, "Failed to list organizations");
+                return Err(e.into());
+            }
+        };
+
+        Ok(orgs.into_iter().map(|o| o.login).collect())
     }
 
     /// Convert octocrab issue to our Issue model
@@ -253,12 +580,23 @@ impl OctocrabClient {
     fn convert_user(user: octocrab::models::Author) -> User {
         User {
             id: user.id.0 as i64,
+            is_bot: user.r#type == "Bot",
             login: user.login,
             avatar_url: user.avatar_url.to_string(),
             html_url: user.html_url.to_string(),
         }
     }
 
+    /// Convert an octocrab `/rate_limit` entry to our budget model
+    fn convert_rate(rate: &octocrab::models::Rate) -> ResourceBudget {
+        ResourceBudget {
+            limit: rate.limit as u32,
+            remaining: rate.remaining as u32,
+            used: rate.used as u32,
+            reset: unix_time(rate.reset),
+        }
+    }
+
     /// Convert octocrab milestone to our Milestone model
     fn convert_milestone(ms: octocrab::models::Milestone) -> Milestone {
         Milestone {
@@ -287,7 +625,10 @@ impl GitHubClient for OctocrabClient {
         let endpoint = format!("/repos/{}/{}", repo.owner, repo.name);
         info!(endpoint = %endpoint, "Fetching repository info");
 
-        let r = match self.client.repos(&repo.owner, &repo.name).get().await {
+        let r = match self
+            .get::<octocrab::models::Repository, ()>("get_repository", &endpoint, None)
+            .await
+        {
             Ok(r) => {
                 debug!(
                     repo_id = r.id.0,
@@ -321,47 +662,55 @@ impl GitHubClient for OctocrabClient {
         info!(endpoint = %endpoint, org = org, "Fetching organization repositories");
 
         let page = match self
-            .client
-            .orgs(org)
-            .list_repos()
-            .per_page(100)
-            .send()
+            .get::<Page<octocrab::models::Repository>, _>(
+                "list_org_repos",
+                &endpoint,
+                Some(&[("per_page", "100")]),
+            )
             .await
         {
-            Ok(p) => {
-                debug!(
-                    repos_count = p.items.len(),
-                    "Fetched organization repositories"
-                );
-                p
-            }
+            Ok(p) => p,
             Err(e) => {
                 log_api_error("list_org_repos", &endpoint, &e);
                 return Err(e.into());
             }
         };
 
-        let repos = page
-            .items
-            .into_iter()
-            .map(|r| Repository {
-                id: r.id.0 as i64,
-                owner: org.to_string(),
-                name: r.name.clone(),
-                full_name: r.full_name.unwrap_or_else(|| format!("{}/{}", org, r.name)),
-                description: r.description,
-                private: r.private.unwrap_or(false),
-                default_branch: r.default_branch.unwrap_or_else(|| "main".to_string()),
-                created_at: r.created_at.unwrap_or_else(chrono::Utc::now),
-                updated_at: r.updated_at.unwrap_or_else(chrono::Utc::now),
+        let repos = self
+            .collect_pages("list_org_repos", &endpoint, page, |r| {
+                Some(Repository {
+                    id: r.id.0 as i64,
+                    owner: org.to_string(),
+                    name: r.name.clone(),
+                    full_name: r.full_name.unwrap_or_else(|| format!("{}/{}", org, r.name)),
+                    description: r.description,
+                    private: r.private.unwrap_or(false),
+                    default_branch: r.default_branch.unwrap_or_else(|| "main".to_string()),
+                    created_at: r.created_at.unwrap_or_else(chrono::Utc::now),
+                    updated_at: r.updated_at.unwrap_or_else(chrono::Utc::now),
+                })
             })
-            .collect();
+            .await?;
 
+        debug!(
+            repos_count = repos.len(),
+            "Fetched organization repositories"
+        );
         Ok(repos)
     }
 
     #[instrument(skip(self), fields(repo = %repo))]
     async fn list_issues(&self, repo: &RepoId, params: IssueParams) -> Result<Vec<Issue>> {
+        if self.data_source == DataSource::GraphQL {
+            // GraphQL has no milestone database IDs, so issues take them
+            // from the REST milestone list
+            let milestones = self.list_milestones(repo).await?;
+            return self
+                .graphql
+                .list_issues(repo, &params, &milestones, &self.pagination)
+                .await;
+        }
+
         let endpoint = format!("/repos/{}/{}/issues", repo.owner, repo.name);
         info!(
             endpoint = %endpoint,
@@ -372,103 +721,62 @@ impl GitHubClient for OctocrabClient {
             "Starting to fetch issues"
         );
 
-        let state = match params.state {
-            IssueStateFilter::Open => octocrab::params::State::Open,
-            IssueStateFilter::Closed => octocrab::params::State::Closed,
-            IssueStateFilter::All => octocrab::params::State::All,
-        };
+        #[derive(Serialize)]
+        struct ListIssuesQuery<'a> {
+            state: IssueStateFilter,
+            per_page: usize,
+            labels: Option<String>,
+            assignee: Option<&'a str>,
+            since: Option<String>,
+        }
 
-        let issues_handler = self.client.issues(&repo.owner, &repo.name);
-        let mut builder = issues_handler
-            .list()
-            .state(state)
-            .per_page(params.per_page.min(100) as u8);
+        let mut query = ListIssuesQuery {
+            state: params.state,
+            per_page: params.per_page.min(100),
+            labels: None,
+            assignee: None,
+            since: None,
+        };
 
         if let Some(labels) = &params.labels {
             debug!(labels = ?labels, "Filtering by labels");
-            builder = builder.labels(labels);
+            query.labels = Some(labels.join(","));
         }
 
         if let Some(assignee) = &params.assignee {
             debug!(assignee = %assignee, "Filtering by assignee");
-            builder = builder.assignee(assignee.as_str());
+            query.assignee = Some(assignee.as_str());
         }
 
         if let Some(since) = params.since {
             debug!(since = %since, "Filtering by since date");
-            builder = builder.since(since);
+            query.since = Some(since.to_rfc3339());
         }
 
         debug!("Sending initial issues request");
-        let mut all_issues = Vec::new();
-        let mut page = match builder.send().await {
-            Ok(p) => {
-                debug!(
-                    items_in_page = p.items.len(),
-                    has_next = p.next.is_some(),
-                    "Received first page of issues"
-                );
-                p
-            }
+        let page = match self
+            .get::<Page<octocrab::models::issues::Issue>, _>("list_issues", &endpoint, Some(&query))
+            .await
+        {
+            Ok(p) => p,
             Err(e) => {
                 log_api_error("list_issues", &endpoint, &e);
                 return Err(e.into());
             }
         };
 
-        let mut page_num = 1;
-        loop {
-            debug!(
-                page = page_num,
-                items = page.items.len(),
-                "Processing issues page"
-            );
-
-            for issue in page.items {
-                // Skip pull requests (GitHub API includes them in issues)
-                if issue.pull_request.is_none() {
-                    all_issues.push(Self::convert_issue(issue));
-                }
-            }
-
-            if page.next.is_none() {
-                debug!("No more pages, finished fetching issues");
-                break;
-            }
-
-            page_num += 1;
-            debug!(page = page_num, "Fetching next page of issues");
-
-            page = match self
-                .client
-                .get_page::<octocrab::models::issues::Issue>(&page.next)
-                .await
-            {
-                Ok(Some(next)) => {
-                    debug!(
-                        items_in_page = next.items.len(),
-                        has_next = next.next.is_some(),
-                        "Received next page of issues"
-                    );
-                    next
-                }
-                Ok(None) => {
-                    debug!("No more pages available");
-                    break;
-                }
-                Err(e) => {
-                    log_api_error("list_issues (pagination)", &endpoint, &e);
-                    return Err(e.into());
-                }
-            };
-        }
+        // The issues endpoint also returns pull requests; skip them
+        let issues = self
+            .collect_pages("list_issues", &endpoint, page, |issue| {
+                issue
+                    .pull_request
+                    .is_none()
+                    .then(|| Self::convert_issue(issue))
+            })
+            .await?;
 
-        info!(
-            total_issues = all_issues.len(),
-            pages_fetched = page_num,
-            "Completed fetching issues"
-        );
-        Ok(all_issues)
+        info!(total_issues = issues.len(), "Completed fetching issues");
+        Ok(issues)
     }
 
     #[instrument(skip(self), fields(repo = %repo, issue_number = number))]
@@ -477,9 +785,7 @@ impl GitHubClient for OctocrabClient {
         info!(endpoint = %endpoint, "Fetching single issue");
 
         let issue = match self
-            .client
-            .issues(&repo.owner, &repo.name)
-            .get(number)
+            .get::<octocrab::models::issues::Issue, ()>("get_issue", &endpoint, None)
             .await
         {
             Ok(i) => {
@@ -524,32 +830,29 @@ impl GitHubClient for OctocrabClient {
             assignee: Option<octocrab::models::Author>,
         }
 
-        let events: Vec<ApiEvent> = match self
-            .client
-            .get::<Vec<ApiEvent>, _, _>(&route, None::<&()>)
+        let page = match self
+            .get::<Page<ApiEvent>, ()>("list_issue_events", &route, None)
             .await
         {
-            Ok(e) => {
-                debug!(events_count = e.len(), "Successfully fetched issue events");
-                e
-            }
+            Ok(p) => p,
             Err(e) => {
                 log_api_error("list_issue_events", &route, &e);
                 return Err(e.into());
             }
         };
 
-        let result: Vec<IssueEvent> = events
-            .into_iter()
-            .map(|e| IssueEvent {
-                id: e.id,
-                event_type: e.event,
-                actor: e.actor.map(Self::convert_user),
-                created_at: e.created_at,
-                label_name: e.label.map(|l| l.name),
-                assignee: e.assignee.map(Self::convert_user),
+        let result: Vec<IssueEvent> = self
+            .collect_pages("list_issue_events", &route, page, |e| {
+                Some(IssueEvent {
+                    id: e.id,
+                    event_type: e.event,
+                    actor: e.actor.map(Self::convert_user),
+                    created_at: e.created_at,
+                    label_name: e.label.map(|l| l.name),
+                    assignee: e.assignee.map(Self::convert_user),
+                })
             })
-            .collect();
+            .await?;
 
         info!(
             total_events = result.len(),
@@ -558,6 +861,13 @@ impl GitHubClient for OctocrabClient {
         Ok(result)
     }
 
+    #[instrument(skip(self), fields(repo = %repo))]
+    async fn list_issue_relations(&self, repo: &RepoId) -> Result<Vec<IssueRelation>> {
+        self.graphql
+            .list_issue_relations(&repo.owner, &repo.name)
+            .await
+    }
+
     #[instrument(skip(self), fields(repo = %repo))]
     async fn list_milestones(&self, repo: &RepoId) -> Result<Vec<Milestone>> {
         // Use the REST API directly for milestones
@@ -567,28 +877,22 @@ impl GitHubClient for OctocrabClient {
         );
         info!(endpoint = %route, "Fetching milestones");
 
-        let milestones: Vec<octocrab::models::Milestone> = match self
-            .client
-            .get::<Vec<octocrab::models::Milestone>, _, _>(&route, None::<&()>)
+        let page = match self
+            .get::<Page<octocrab::models::Milestone>, ()>("list_milestones", &route, None)
             .await
         {
-            Ok(m) => {
-                debug!(
-                    milestones_count = m.len(),
-                    "Successfully fetched milestones"
-                );
-                m
-            }
+            Ok(p) => p,
             Err(e) => {
                 log_api_error("list_milestones", &route, &e);
                 return Err(e.into());
             }
         };
 
-        let result: Vec<Milestone> = milestones
-            .into_iter()
-            .map(Self::convert_milestone)
-            .collect();
+        let result: Vec<Milestone> = self
+            .collect_pages("list_milestones", &route, page, |m| {
+                Some(Self::convert_milestone(m))
+            })
+            .await?;
 
         info!(
             total_milestones = result.len(),
@@ -599,6 +903,14 @@ impl GitHubClient for OctocrabClient {
 
     #[instrument(skip(self), fields(repo = %repo))]
     async fn list_pulls(&self, repo: &RepoId, params: PullParams) -> Result<Vec<PullRequest>> {
+        if self.data_source == DataSource::GraphQL {
+            let milestones = self.list_milestones(repo).await?;
+            return self
+                .graphql
+                .list_pulls(repo, &params, &milestones, &self.pagination)
+                .await;
+        }
+
         let endpoint = format!("/repos/{}/{}/pulls", repo.owner, repo.name);
         info!(
             endpoint = %endpoint,
@@ -607,46 +919,41 @@ impl GitHubClient for OctocrabClient {
             "Starting to fetch pull requests"
         );
 
-        let state = match params.state {
-            PullStateFilter::Open => octocrab::params::State::Open,
-            PullStateFilter::Closed => octocrab::params::State::Closed,
-            PullStateFilter::All => octocrab::params::State::All,
+        #[derive(Serialize)]
+        struct ListPullsQuery {
+            state: PullStateFilter,
+            per_page: usize,
+        }
+
+        let query = ListPullsQuery {
+            state: params.state,
+            per_page: params.per_page.min(100),
         };
 
         debug!("Sending pull requests request");
         let page = match self
-            .client
-            .pulls(&repo.owner, &repo.name)
-            .list()
-            .state(state)
-            .per_page(params.per_page.min(100) as u8)
-            .send()
+            .get::<Page<octocrab::models::pulls::PullRequest>, _>(
+                "list_pulls",
+                &endpoint,
+                Some(&query),
+            )
             .await
         {
-            Ok(p) => {
-                debug!(
-                    items_in_page = p.items.len(),
-                    has_next = p.next.is_some(),
-                    "Successfully fetched pull requests page"
-                );
-                p
-            }
+            Ok(p) => p,
             Err(e) => {
                 log_api_error("list_pulls", &endpoint, &e);
                 return Err(e.into());
             }
         };
 
-        let prs: Vec<PullRequest> = page
-            .items
-            .into_iter()
-            .map(|pr| {
+        let prs: Vec<PullRequest> = self
+            .collect_pages("list_pulls", &endpoint, page, |pr| {
                 let is_open = pr
                     .state
                     .as_ref()
                     .map(|s| format!("{:?}", s).to_lowercase().contains("open"))
                     .unwrap_or(false);
-                PullRequest {
+                Some(PullRequest {
                     id: pr.id.0 as i64,
                     number: pr.number,
                     title: pr.title.unwrap_or_default(),
@@ -665,6 +972,7 @@ impl GitHubClient for OctocrabClient {
                             login: "unknown".to_string(),
                             avatar_url: String::new(),
                             html_url: String::new(),
+                            is_bot: false,
                         }),
                     labels: pr
                         .labels
@@ -688,9 +996,9 @@ impl GitHubClient for OctocrabClient {
                     created_at: pr.created_at.unwrap_or_else(chrono::Utc::now),
                     updated_at: pr.updated_at.unwrap_or_else(chrono::Utc::now),
                     closed_at: pr.closed_at,
-                }
+                })
             })
-            .collect();
+            .await?;
 
         info!(total_prs = prs.len(), "Completed fetching pull requests");
         Ok(prs)
@@ -701,7 +1009,10 @@ impl GitHubClient for OctocrabClient {
         let endpoint = format!("/repos/{}/{}/pulls/{}", repo.owner, repo.name, number);
         info!(endpoint = %endpoint, "Fetching single pull request");
 
-        let pr = match self.client.pulls(&repo.owner, &repo.name).get(number).await {
+        let pr = match self
+            .get::<octocrab::models::pulls::PullRequest, ()>("get_pull", &endpoint, None)
+            .await
+        {
             Ok(p) => {
                 debug!(
                     pr_id = p.id.0,
@@ -742,6 +1053,7 @@ impl GitHubClient for OctocrabClient {
                     login: "unknown".to_string(),
                     avatar_url: String::new(),
                     html_url: String::new(),
+                    is_bot: false,
                 }),
             labels: pr
                 .labels
@@ -777,36 +1089,31 @@ impl GitHubClient for OctocrabClient {
         info!(endpoint = %endpoint, "Fetching pull request reviews");
 
         let reviews = match self
-            .client
-            .pulls(&repo.owner, &repo.name)
-            .list_reviews(number)
-            .send()
+            .get::<Page<octocrab::models::pulls::Review>, _>(
+                "list_pull_reviews",
+                &endpoint,
+                Some(&[("per_page", "100")]),
+            )
             .await
         {
-            Ok(r) => {
-                debug!(
-                    reviews_count = r.items.len(),
-                    "Successfully fetched reviews"
-                );
-                r
-            }
+            Ok(r) => r,
             Err(e) => {
                 log_api_error("list_pull_reviews", &endpoint, &e);
                 return Err(e.into());
             }
         };
 
-        let result: Vec<Review> = reviews
-            .items
-            .into_iter()
-            .map(|r| Review {
-                id: r.id.0 as i64,
-                user: r.user.map(Self::convert_user),
-                body: r.body,
-                state: r.state.map(|s| format!("{:?}", s)).unwrap_or_default(),
-                submitted_at: r.submitted_at,
+        let result: Vec<Review> = self
+            .collect_pages("list_pull_reviews", &endpoint, reviews, |r| {
+                Some(Review {
+                    id: r.id.0 as i64,
+                    user: r.user.map(Self::convert_user),
+                    body: r.body,
+                    state: r.state.map(|s| format!("{:?}", s)).unwrap_or_default(),
+                    submitted_at: r.submitted_at,
+                })
             })
-            .collect();
+            .await?;
 
         info!(total_reviews = result.len(), "Completed fetching reviews");
         Ok(result)
@@ -818,47 +1125,41 @@ impl GitHubClient for OctocrabClient {
         info!(endpoint = %endpoint, "Fetching releases");
 
         let releases = match self
-            .client
-            .repos(&repo.owner, &repo.name)
-            .releases()
-            .list()
-            .per_page(100)
-            .send()
+            .get::<Page<octocrab::models::repos::Release>, _>(
+                "list_releases",
+                &endpoint,
+                Some(&[("per_page", "100")]),
+            )
             .await
         {
-            Ok(r) => {
-                debug!(
-                    releases_count = r.items.len(),
-                    "Successfully fetched releases"
-                );
-                r
-            }
+            Ok(r) => r,
             Err(e) => {
                 log_api_error("list_releases", &endpoint, &e);
                 return Err(e.into());
             }
         };
 
-        let result: Vec<Release> = releases
-            .items
-            .into_iter()
-            .map(|r| Release {
-                id: r.id.0 as i64,
-                tag_name: r.tag_name,
-                name: r.name,
-                body: r.body,
-                draft: r.draft,
-                prerelease: r.prerelease,
-                author: r.author.map(Self::convert_user).unwrap_or_else(|| User {
-                    id: 0,
-                    login: "unknown".to_string(),
-                    avatar_url: String::new(),
-                    html_url: String::new(),
-                }),
-                created_at: r.created_at.unwrap_or_else(chrono::Utc::now),
-                published_at: r.published_at,
+        let result: Vec<Release> = self
+            .collect_pages("list_releases", &endpoint, releases, |r| {
+                Some(Release {
+                    id: r.id.0 as i64,
+                    tag_name: r.tag_name,
+                    name: r.name,
+                    body: r.body,
+                    draft: r.draft,
+                    prerelease: r.prerelease,
+                    author: r.author.map(Self::convert_user).unwrap_or_else(|| User {
+                        id: 0,
+                        login: "unknown".to_string(),
+                        avatar_url: String::new(),
+                        html_url: String::new(),
+                        is_bot: false,
+                    }),
+                    created_at: r.created_at.unwrap_or_else(chrono::Utc::now),
+                    published_at: r.published_at,
+                })
             })
-            .collect();
+            .await?;
 
         info!(total_releases = result.len(), "Completed fetching releases");
         Ok(result)
@@ -870,10 +1171,7 @@ impl GitHubClient for OctocrabClient {
         info!(endpoint = %endpoint, "Fetching release by tag");
 
         let r = match self
-            .client
-            .repos(&repo.owner, &repo.name)
-            .releases()
-            .get_by_tag(tag)
+            .get::<octocrab::models::repos::Release, ()>("get_release", &endpoint, None)
             .await
         {
             Ok(rel) => {
@@ -905,6 +1203,7 @@ impl GitHubClient for OctocrabClient {
                 login: "unknown".to_string(),
                 avatar_url: String::new(),
                 html_url: String::new(),
+                is_bot: false,
             }),
             created_at: r.created_at.unwrap_or_else(chrono::Utc::now),
             published_at: r.published_at,
@@ -916,7 +1215,10 @@ impl GitHubClient for OctocrabClient {
         let endpoint = format!("/users/{}", username);
         info!(endpoint = %endpoint, "Fetching user profile");
 
-        let user = match self.client.users(username).profile().await {
+        let user = match self
+            .get::<octocrab::models::UserProfile, ()>("get_user", &endpoint, None)
+            .await
+        {
             Ok(u) => {
                 debug!(
                     user_id = u.id.0,
@@ -933,6 +1235,7 @@ impl GitHubClient for OctocrabClient {
 
         Ok(User {
             id: user.id.0 as i64,
+            is_bot: user.r#type == "Bot",
             login: user.login,
             avatar_url: user.avatar_url.to_string(),
             html_url: user.html_url.to_string(),
@@ -944,7 +1247,17 @@ impl GitHubClient for OctocrabClient {
         let endpoint = "/rate_limit";
         info!(endpoint = %endpoint, "Fetching rate limit status");
 
-        let rate_limit = match self.client.ratelimit().get().await {
+        // Sent directly rather than through `get`, which would charge it to
+        // the budget
+        let result = async {
+            let response = self
+                .client
+                ._get_with_headers(endpoint, Some(self.auth_headers().await?))
+                .await?;
+            let response = octocrab::map_github_error(response).await?;
+            octocrab::models::RateLimit::from_response(response).await
+        };
+        let rate_limit = match result.await {
             Ok(rl) => {
                 debug!(
                     remaining = rl.resources.core.remaining,
@@ -960,12 +1273,26 @@ impl GitHubClient for OctocrabClient {
             }
         };
 
+        // /rate_limit does not count against the limit, so it is a free way
+        // to seed the budget before the first real request
+        let resources = &rate_limit.resources;
+        self.budget
+            .update(RateResource::Core, Self::convert_rate(&resources.core));
+        if let Some(graphql) = &resources.graphql {
+            self.budget
+                .update(RateResource::GraphQL, Self::convert_rate(graphql));
+        }
+
         Ok(RateLimitInfo {
             remaining: rate_limit.resources.core.remaining as u32,
             limit: rate_limit.resources.core.limit as u32,
             reset: rate_limit.resources.core.reset,
         })
     }
+
+    fn rate_budget(&self) -> Option<RateBudgetSnapshot> {
+        Some(self.budget.snapshot())
+    }
 }
 
 #[async_trait]
@@ -981,4 +1308,207 @@ impl ProjectClient for OctocrabClient {
     async fn list_project_items(&self, project_node_id: &str) -> Result<Vec<ProjectItem>> {
         self.graphql.list_items(project_node_id).await
     }
+
+    async fn update_project_item_field(
+        &self,
+        project_node_id: &str,
+        item_node_id: &str,
+        field_node_id: &str,
+        update: &FieldUpdate,
+    ) -> Result<()> {
+        self.graphql
+            .update_item_field(project_node_id, item_node_id, field_node_id, update)
+            .await
+    }
+
+    async fn add_project_item(
+        &self,
+        project_node_id: &str,
+        repo: &RepoId,
+        number: u64,
+    ) -> Result<String> {
+        let content_id = self
+            .graphql
+            .content_node_id(&repo.owner, &repo.name, number)
+            .await?;
+        self.graphql.add_item(project_node_id, &content_id).await
+    }
+
+    async fn archive_project_item(&self, project_node_id: &str, item_node_id: &str) -> Result<()> {
+        self.graphql
+            .archive_item(project_node_id, item_node_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{AppCredentials, InstallationTokens};
+    use std::time::Duration;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> OctocrabClient {
+        OctocrabClient::new("ghp_test", Some(&server.uri()))
+            .unwrap()
+            .with_retry_config(RetryConfig {
+                initial_backoff: Duration::from_millis(10),
+                ..Default::default()
+            })
+    }
+
+    fn orgs_response(remaining: u32) -> ResponseTemplate {
+        let reset = chrono::Utc::now().timestamp() + 600;
+        ResponseTemplate::new(200)
+            .insert_header("x-ratelimit-resource", "core")
+            .insert_header("x-ratelimit-limit", "5000")
+            .insert_header("x-ratelimit-remaining", remaining.to_string().as_str())
+            .insert_header("x-ratelimit-used", (5000 - remaining).to_string().as_str())
+            .insert_header("x-ratelimit-reset", reset.to_string().as_str())
+            .set_body_json(serde_json::json!([{ "login": "octo-org" }]))
+    }
+
+    #[tokio::test]
+    async fn test_app_installation_token_is_sent() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/42/access_tokens"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "token": "ghs_installation",
+                "expires_at": (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339(),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .and(header("authorization", "Bearer ghs_installation"))
+            .respond_with(orgs_response(4999))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let key = include_bytes!("../../tests/fixtures/github_app_key.pem");
+        let tokens = InstallationTokens::new(
+            AppCredentials::new(1234, 42, key).unwrap(),
+            Some(&server.uri()),
+        )
+        .unwrap();
+        let client = OctocrabClient::from_credentials(
+            Credentials::App(Arc::new(tokens)),
+            Some(&server.uri()),
+        )
+        .unwrap();
+
+        // One token serves both requests
+        client.authenticated_user_orgs().await.unwrap();
+        client.authenticated_user_orgs().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_responses_update_the_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .respond_with(orgs_response(4321))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        assert!(client.rate_budget().unwrap().core.is_none());
+
+        let orgs = client.authenticated_user_orgs().await.unwrap();
+        assert_eq!(orgs, vec!["octo-org"]);
+        let core = client.rate_budget().unwrap().core.unwrap();
+        assert_eq!(core.remaining, 4321);
+        assert_eq!(core.used, 679);
+    }
+
+    #[tokio::test]
+    async fn test_secondary_rate_limit_is_retried_after_pause() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("retry-after", "1")
+                    .set_body_json(serde_json::json!({
+                        "message": "You have exceeded a secondary rate limit"
+                    })),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .respond_with(orgs_response(4000))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let started = std::time::Instant::now();
+        let orgs = client.authenticated_user_orgs().await.unwrap();
+        assert_eq!(orgs, vec!["octo-org"]);
+        assert!(started.elapsed() >= Duration::from_millis(500));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_is_served_from_store() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .respond_with(orgs_response(4999).insert_header("etag", "\"v1\""))
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("greport-etag-client-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let client = client(&server).with_etag_store(FileEtagStore::new(&dir));
+
+        let first = client.authenticated_user_orgs().await.unwrap();
+        let second = client.authenticated_user_orgs().await.unwrap();
+        assert_eq!(first, vec!["octo-org"]);
+        assert_eq!(second, first);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].headers.get("if-none-match").is_none());
+        assert_eq!(requests[1].headers.get("if-none-match").unwrap(), "\"v1\"");
+
+        // Another token does not see the stored response
+        let other = OctocrabClient::new("ghp_other", Some(&server.uri()))
+            .unwrap()
+            .with_etag_store(FileEtagStore::new(&dir));
+        other.authenticated_user_orgs().await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[2].headers.get("if-none-match").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_permission_errors_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .set_body_json(serde_json::json!({ "message": "Must have admin rights" })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        assert!(client.authenticated_user_orgs().await.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,258 @@
+//! Shared pagination for list calls
+//!
+//! Every `OctocrabClient` list method, REST or GraphQL, collects its pages
+//! through [`PageCollector`], so they all honour the same item cap and report
+//! progress the same way.
+
+use crate::{Error, Result};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Progress of a paginated list call, reported after each page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageProgress {
+    /// List call being paginated (e.g. "list_issues")
+    pub operation: &'static str,
+    /// Pages fetched so far
+    pub page: u32,
+    /// Total pages, when the API reports it
+    pub total_pages: Option<u32>,
+    /// Items collected so far
+    pub items: usize,
+    /// Whether this was the last page fetched
+    pub done: bool,
+    /// Whether the item cap stopped the call before the last page
+    pub truncated: bool,
+}
+
+type PageCallback = Arc<dyn Fn(&PageProgress) + Send + Sync>;
+
+/// Item cap and progress callback for paginated list calls
+///
+/// The default fetches every page and reports nothing.
+#[derive(Clone, Default)]
+pub struct Pagination {
+    /// Stop after this many items (`None` fetches everything)
+    pub max_items: Option<usize>,
+    /// Fail list calls the item cap cuts short instead of returning them
+    pub require_complete: bool,
+    on_page: Option<PageCallback>,
+}
+
+impl std::fmt::Debug for Pagination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pagination")
+            .field("max_items", &self.max_items)
+            .field("require_complete", &self.require_complete)
+            .field("on_page", &self.on_page.is_some())
+            .finish()
+    }
+}
+
+impl Pagination {
+    /// Limit each list call to `max_items` items
+    pub fn with_max_items(mut self, max_items: Option<usize>) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
+    /// Call `callback` after every page, e.g. to drive a progress bar
+    ///
+    /// The last call for a list has `done` set, and `truncated` if the item
+    /// cap cut the list short.
+    pub fn on_page(mut self, callback: impl Fn(&PageProgress) + Send + Sync + 'static) -> Self {
+        self.on_page = Some(Arc::new(callback));
+        self
+    }
+
+    /// Fail a list call the item cap would cut short with
+    /// [`Error::Truncated`], for callers that store or serve lists as if
+    /// they were whole (the API server and its sync)
+    pub fn require_complete(mut self) -> Self {
+        self.require_complete = true;
+        self
+    }
+
+    /// Items of a finished list call, or an error when the list was cut
+    /// short and complete lists are required
+    pub(crate) fn accept<T>(&self, listing: Listing<T>) -> Result<Vec<T>> {
+        match self.max_items {
+            Some(max_items) if listing.truncated && self.require_complete => {
+                Err(Error::Truncated {
+                    operation: listing.operation.to_string(),
+                    max_items,
+                })
+            }
+            _ => Ok(listing.items),
+        }
+    }
+}
+
+/// Items collected by one list call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing<T> {
+    /// List call the items came from (e.g. "list_issues")
+    pub operation: &'static str,
+    /// Items, in API order
+    pub items: Vec<T>,
+    /// Whether the item cap stopped the call before the last page
+    pub truncated: bool,
+}
+
+/// Collects the pages of one list call
+pub(crate) struct PageCollector<'a, T> {
+    pagination: &'a Pagination,
+    operation: &'static str,
+    items: Vec<T>,
+    page: u32,
+    truncated: bool,
+}
+
+impl<'a, T> PageCollector<'a, T> {
+    pub(crate) fn new(pagination: &'a Pagination, operation: &'static str) -> Self {
+        Self {
+            pagination,
+            operation,
+            items: Vec::new(),
+            page: 0,
+            truncated: false,
+        }
+    }
+
+    /// Add one page of items, returning whether to fetch the next page
+    pub(crate) fn push_page(
+        &mut self,
+        items: impl IntoIterator<Item = T>,
+        has_next: bool,
+        total_pages: Option<u32>,
+    ) -> bool {
+        self.page += 1;
+        let mut truncated = false;
+        for item in items {
+            if self.is_full() {
+                truncated = true;
+                break;
+            }
+            self.items.push(item);
+        }
+        truncated |= has_next && self.is_full();
+        let done = truncated || !has_next;
+        self.truncated |= truncated;
+
+        if truncated {
+            warn!(
+                operation = self.operation,
+                max_items = ?self.pagination.max_items,
+                "List truncated at the item cap"
+            );
+        }
+        if let Some(callback) = &self.pagination.on_page {
+            callback(&PageProgress {
+                operation: self.operation,
+                page: self.page,
+                total_pages,
+                items: self.items.len(),
+                done,
+                truncated,
+            });
+        }
+        !done
+    }
+
+    fn is_full(&self) -> bool {
+        self.pagination
+            .max_items
+            .is_some_and(|max| self.items.len() >= max)
+    }
+
+    pub(crate) fn finish(self) -> Listing<T> {
+        Listing {
+            operation: self.operation,
+            items: self.items,
+            truncated: self.truncated,
+        }
+    }
+
+    /// Finish the call, applying the pagination's completeness requirement
+    pub(crate) fn into_items(self) -> Result<Vec<T>> {
+        let pagination = self.pagination;
+        pagination.accept(self.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn recording(max_items: Option<usize>) -> (Pagination, Arc<Mutex<Vec<PageProgress>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let pagination = Pagination::default()
+            .with_max_items(max_items)
+            .on_page(move |p| sink.lock().unwrap().push(p.clone()));
+        (pagination, seen)
+    }
+
+    #[test]
+    fn test_collects_every_page() {
+        let (pagination, seen) = recording(None);
+        let mut collector = PageCollector::new(&pagination, "list_test");
+        assert!(collector.push_page(0..100, true, Some(2)));
+        assert!(!collector.push_page(100..150, false, None));
+        let listing = collector.finish();
+        assert_eq!(listing.items.len(), 150);
+        assert!(!listing.truncated);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].total_pages, Some(2));
+        assert!(!seen[0].done);
+        assert!(seen[1].done && !seen[1].truncated);
+        assert_eq!(seen[1].items, 150);
+    }
+
+    #[test]
+    fn test_cap_truncates_and_reports() {
+        let (pagination, seen) = recording(Some(120));
+        let mut collector = PageCollector::new(&pagination, "list_test");
+        assert!(collector.push_page(0..100, true, Some(3)));
+        assert!(!collector.push_page(100..200, true, Some(3)));
+        let listing = collector.finish();
+        assert_eq!(listing.items, (0..120).collect::<Vec<_>>());
+        assert!(listing.truncated);
+
+        let last = seen.lock().unwrap().last().cloned().unwrap();
+        assert!(last.done && last.truncated);
+        assert_eq!(last.page, 2);
+    }
+
+    #[test]
+    fn test_cap_on_exact_last_page_is_not_truncation() {
+        let (pagination, seen) = recording(Some(100));
+        let mut collector = PageCollector::new(&pagination, "list_test");
+        assert!(!collector.push_page(0..100, false, None));
+        assert!(!seen.lock().unwrap()[0].truncated);
+
+        // A full cap with more pages left is
+        let mut collector = PageCollector::new(&pagination, "list_test");
+        assert!(!collector.push_page(0..100, true, None));
+        assert!(seen.lock().unwrap()[1].truncated);
+    }
+
+    #[test]
+    fn test_require_complete_rejects_truncated_lists() {
+        let pagination = Pagination::default()
+            .with_max_items(Some(100))
+            .require_complete();
+        let mut collector = PageCollector::new(&pagination, "list_test");
+        collector.push_page(0..100, true, None);
+        let err = collector.into_items().unwrap_err();
+        assert!(matches!(err, Error::Truncated { max_items: 100, .. }));
+
+        // A list that fits the cap is still returned
+        let mut collector = PageCollector::new(&pagination, "list_test");
+        collector.push_page(0..100, false, None);
+        assert_eq!(collector.into_items().unwrap().len(), 100);
+    }
+}
@@ -55,6 +55,66 @@ impl std::str::FromStr for PullStateFilter {
     }
 }
 
+/// API used to list issues and pull requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataSource {
+    /// REST list endpoints (pull request sizes are not included)
+    #[default]
+    Rest,
+    /// Bulk GraphQL queries, including sizes, reviews and linked issues
+    #[serde(rename = "graphql")]
+    GraphQL,
+}
+
+impl std::str::FromStr for DataSource {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rest" => Ok(DataSource::Rest),
+            "graphql" => Ok(DataSource::GraphQL),
+            _ => Err(crate::Error::Custom(format!("Invalid data source: {}", s))),
+        }
+    }
+}
+
+/// Forge hosting an organization's repositories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// GitHub.com or GitHub Enterprise Server
+    #[default]
+    GitHub,
+    /// GitLab.com or self-managed GitLab
+    GitLab,
+    /// Gitea or Forgejo
+    Gitea,
+}
+
+impl std::fmt::Display for Provider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Provider::GitHub => "github",
+            Provider::GitLab => "gitlab",
+            Provider::Gitea => "gitea",
+        })
+    }
+}
+
+impl std::str::FromStr for Provider {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(Provider::GitHub),
+            "gitlab" => Ok(Provider::GitLab),
+            "gitea" | "forgejo" => Ok(Provider::Gitea),
+            _ => Err(crate::Error::Custom(format!("Invalid provider: {}", s))),
+        }
+    }
+}
+
 /// Issue sort field
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -1,26 +1,35 @@
 //! GitHub client registry for multi-organization support
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use serde::Serialize;
 use tracing::{debug, info, warn};
 
-use crate::client::GitHubClient;
+use crate::client::GitHubApi;
 use crate::client::OctocrabClient;
+use crate::client::Pagination;
+use crate::client::Provider;
+use crate::client::RateBudgetSnapshot;
+use crate::client::RecordingClient;
+use crate::client::ReplayClient;
 use crate::client::RepoId;
-use crate::config::Config;
+use crate::client::{GitLabClient, GiteaClient, GITLAB_API_URL};
+use crate::config::{Config, OrgConfig};
 use crate::{Error, Result};
 
 /// Derive the web (non-API) URL from a GitHub API base URL.
 ///
-/// Strips `/api/v3` or `/api` suffixes to produce the browsable web URL.
-/// For example, `https://github.mycompany.com/api/v3` becomes
-/// `https://github.mycompany.com`.
+/// Strips `/api/v3` or `/api` suffixes (and GitLab's `/api/v4` or Gitea's
+/// `/api/v1`) to produce the browsable web URL. For example,
+/// `https://github.mycompany.com/api/v3` becomes `https://github.mycompany.com`.
 fn derive_web_url(api_url: &str) -> String {
     let trimmed = api_url.trim_end_matches('/');
     trimmed
         .strip_suffix("/api/v3")
+        .or_else(|| trimmed.strip_suffix("/api/v4"))
+        .or_else(|| trimmed.strip_suffix("/api/v1"))
         .or_else(|| trimmed.strip_suffix("/api"))
         .unwrap_or(trimmed)
         .to_string()
@@ -37,8 +46,68 @@ pub struct OrgEntry {
     pub repo_count: usize,
     /// Configured repository names
     pub repo_names: Vec<String>,
-    /// Whether a token is configured
+    /// Whether a token or GitHub App installation is configured
     pub has_token: bool,
+    /// Forge hosting the organization
+    pub provider: Provider,
+}
+
+/// Build the client for one `[[organizations]]` entry, by its provider
+fn org_client(
+    org: &OrgConfig,
+    config: &Config,
+    pagination: Pagination,
+) -> Result<Arc<dyn GitHubApi>> {
+    if org.provider != Provider::GitHub && org.app.is_some() {
+        return Err(Error::Config(format!(
+            "Organization {}: a GitHub App cannot authenticate to {}",
+            org.name, org.provider
+        )));
+    }
+    Ok(match org.provider {
+        Provider::GitHub => Arc::new(
+            OctocrabClient::from_credentials(
+                config.org_credentials(org)?,
+                org.base_url.as_deref(),
+            )?
+            .with_config(config)?
+            .with_pagination(pagination),
+        ),
+        Provider::GitLab => Arc::new(
+            GitLabClient::new(&config.org_token(org)?, org.base_url.as_deref())?
+                .with_pagination(pagination),
+        ),
+        Provider::Gitea => {
+            let base_url = org.base_url.as_deref().ok_or_else(|| {
+                Error::Config(format!(
+                    "Organization {}: Gitea needs a base_url (e.g. https://gitea.example.com/api/v1)",
+                    org.name
+                ))
+            })?;
+            Arc::new(
+                GiteaClient::new(&config.org_token(org)?, base_url)?.with_pagination(pagination),
+            )
+        }
+    })
+}
+
+/// Metadata of every `[[organizations]]` entry
+fn org_entries(config: &Config) -> Vec<OrgEntry> {
+    config
+        .organizations
+        .iter()
+        .map(|org| {
+            let repos = org.repos.as_deref().unwrap_or_default();
+            OrgEntry {
+                name: org.name.clone(),
+                base_url: org.base_url.clone(),
+                repo_count: repos.len(),
+                repo_names: repos.iter().map(|r| r.to_string()).collect(),
+                has_token: !org.token.is_empty() || org.app.is_some(),
+                provider: org.provider,
+            }
+        })
+        .collect()
 }
 
 /// Registry that manages multiple GitHub clients, one per organization.
@@ -49,49 +118,126 @@ pub struct OrgEntry {
 /// - Case-insensitive fallback matching
 /// - Default client fallback for unconfigured orgs
 pub struct GitHubClientRegistry {
-    clients: HashMap<String, Arc<OctocrabClient>>,
-    default_client: Option<Arc<OctocrabClient>>,
+    clients: HashMap<String, Arc<dyn GitHubApi>>,
+    default_client: Option<Arc<dyn GitHubApi>>,
     default_base_url: Option<String>,
     org_entries: Vec<OrgEntry>,
+    /// Organizations whose credentials could not be read, with the error,
+    /// reported when the organization is used
+    unavailable: HashMap<String, String>,
 }
 
 impl GitHubClientRegistry {
     /// Create a registry with only a default client (no per-org entries).
-    pub fn with_default(client: OctocrabClient, base_url: Option<String>) -> Self {
+    ///
+    /// Any `GitHubApi` implementation works, including `MockGitHubClient`.
+    pub fn with_default(client: impl GitHubApi + 'static, base_url: Option<String>) -> Self {
         Self {
             clients: HashMap::new(),
             default_client: Some(Arc::new(client)),
             default_base_url: base_url,
             org_entries: Vec::new(),
+            unavailable: HashMap::new(),
+        }
+    }
+
+    /// Register a client for an organization, alongside its metadata.
+    pub fn with_org(mut self, entry: OrgEntry, client: impl GitHubApi + 'static) -> Self {
+        self.clients
+            .insert(entry.name.to_lowercase(), Arc::new(client));
+        self.org_entries.push(entry);
+        self
+    }
+
+    /// Record every client's calls to fixtures under `dir`.
+    ///
+    /// Each client is wrapped in a [`RecordingClient`]; all of them share
+    /// the directory, so a `ReplayClient` on it serves a whole run.
+    pub fn recording(mut self, dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let record = |client: Arc<dyn GitHubApi>| {
+            Arc::new(RecordingClient::new(client, dir.clone())) as Arc<dyn GitHubApi>
+        };
+        self.clients = self
+            .clients
+            .into_iter()
+            .map(|(name, client)| (name, record(client)))
+            .collect();
+        self.default_client = self.default_client.map(record);
+        self
+    }
+
+    /// Build a registry answering every call from fixtures under `dir`.
+    ///
+    /// Keeps the organizations of `config` (base URLs, providers) so links
+    /// and routing match the recorded run, but needs no credentials.
+    pub fn replaying(config: &Config, dir: impl Into<PathBuf>) -> Self {
+        let replay = ReplayClient::new(dir);
+        let org_entries = org_entries(config);
+        let clients = org_entries
+            .iter()
+            .map(|entry| {
+                let client: Arc<dyn GitHubApi> = Arc::new(replay.clone());
+                (entry.name.to_lowercase(), client)
+            })
+            .collect();
+        Self {
+            clients,
+            default_client: Some(Arc::new(replay)),
+            default_base_url: config.github.base_url.clone(),
+            org_entries,
+            unavailable: HashMap::new(),
         }
     }
 
     /// Build a registry from the application config.
     ///
-    /// Creates one `OctocrabClient` per `[[organizations]]` entry and
-    /// optionally a default client from `[github]` token.
+    /// Creates one client per `[[organizations]]` entry (an `OctocrabClient`,
+    /// or a GitLab or Gitea client per its `provider`) and optionally a
+    /// default client from `[github]` token.
     pub fn from_config(config: &Config) -> Result<Self> {
+        let pagination = Pagination::default().with_max_items(config.max_items());
+        Self::from_config_with_pagination(config, pagination)
+    }
+
+    /// Build a registry from the application config, giving every client
+    /// `pagination` (e.g. with a progress callback).
+    pub fn from_config_with_pagination(config: &Config, pagination: Pagination) -> Result<Self> {
         let mut clients = HashMap::new();
+        let mut unavailable = HashMap::new();
 
         for org in &config.organizations {
-            debug!(org = %org.name, "Creating client for organization");
-            let client = OctocrabClient::new(&org.token, org.base_url.as_deref())?;
-            clients.insert(org.name.to_lowercase(), Arc::new(client));
+            debug!(org = %org.name, provider = %org.provider, "Creating client for organization");
+            // A broken secret reference only matters once the organization
+            // is used; resolved secrets are memoized for `org_client`
+            if let Err(Error::Config(e)) = config.org_credentials(org) {
+                warn!(org = %org.name, error = %e, "Organization credentials unavailable");
+                unavailable.insert(org.name.to_lowercase(), e);
+                continue;
+            }
+            let client = org_client(org, config, pagination.clone())?;
+            clients.insert(org.name.to_lowercase(), client);
         }
 
-        let default_client = match config.github_token() {
-            Ok(token) => {
+        let default_client = match config.github_credentials() {
+            Ok(credentials) => {
                 debug!("Creating default client from [github] config");
-                let client = OctocrabClient::new(&token, config.github.base_url.as_deref())?;
-                Some(Arc::new(client))
+                let client = OctocrabClient::from_credentials(
+                    credentials,
+                    config.github.base_url.as_deref(),
+                )?
+                .with_config(config)?
+                .with_pagination(pagination);
+                Some(Arc::new(client) as Arc<dyn GitHubApi>)
             }
-            Err(_) => {
-                if clients.is_empty() {
+            Err(Error::MissingToken) => {
+                if config.organizations.is_empty() {
                     return Err(Error::MissingToken);
                 }
                 warn!("No default [github] token configured; only org-specific tokens available");
                 None
             }
+            Err(e) => return Err(e),
         };
 
         info!(
@@ -100,26 +246,12 @@ impl GitHubClientRegistry {
             "GitHub client registry initialized"
         );
 
-        let org_entries: Vec<OrgEntry> = config
-            .organizations
-            .iter()
-            .map(|org| {
-                let repos = org.repos.as_deref().unwrap_or_default();
-                OrgEntry {
-                    name: org.name.clone(),
-                    base_url: org.base_url.clone(),
-                    repo_count: repos.len(),
-                    repo_names: repos.iter().map(|r| r.to_string()).collect(),
-                    has_token: !org.token.is_empty(),
-                }
-            })
-            .collect();
-
         Ok(Self {
             clients,
             default_client,
             default_base_url: config.github.base_url.clone(),
-            org_entries,
+            org_entries: org_entries(config),
+            unavailable,
         })
     }
 
@@ -129,7 +261,7 @@ impl GitHubClientRegistry {
     /// 1. Exact (case-insensitive) match in configured organizations
     /// 2. Fallback to default client
     /// 3. Error if no match and no default
-    pub fn client_for_org(&self, org: &str) -> Result<&Arc<OctocrabClient>> {
+    pub fn client_for_org(&self, org: &str) -> Result<&Arc<dyn GitHubApi>> {
         let org_lower = org.to_lowercase();
 
         // Exact case-insensitive match
@@ -137,6 +269,9 @@ impl GitHubClientRegistry {
             debug!(org = org, "Found org-specific client");
             return Ok(client);
         }
+        if let Some(e) = self.unavailable.get(&org_lower) {
+            return Err(Error::Config(e.clone()));
+        }
 
         // Fallback to default
         if let Some(ref default) = self.default_client {
@@ -152,15 +287,20 @@ impl GitHubClientRegistry {
     }
 
     /// Get the client for a repository, resolving by its owner (organization).
-    pub fn client_for_repo(&self, repo: &RepoId) -> Result<&Arc<OctocrabClient>> {
+    pub fn client_for_repo(&self, repo: &RepoId) -> Result<&Arc<dyn GitHubApi>> {
         self.client_for_org(&repo.owner)
     }
 
     /// Get the default client (from `[github]` config).
-    pub fn default_client(&self) -> Result<&Arc<OctocrabClient>> {
+    pub fn default_client(&self) -> Result<&Arc<dyn GitHubApi>> {
         self.default_client.as_ref().ok_or(Error::MissingToken)
     }
 
+    /// Get the API base URL of the default client (None = public github.com).
+    pub fn default_base_url(&self) -> Option<&str> {
+        self.default_base_url.as_deref()
+    }
+
     /// List all configured organization names.
     pub fn org_names(&self) -> Vec<&str> {
         self.clients.keys().map(|s| s.as_str()).collect()
@@ -180,7 +320,8 @@ impl GitHubClientRegistry {
     ///
     /// Resolution order:
     /// 1. If the org is found in `org_entries` with a `base_url`, derive the web URL.
-    /// 2. If the org is found but has no `base_url`, return `https://github.com`.
+    /// 2. If the org is found but has no `base_url`, return `https://github.com`
+    ///    (`https://gitlab.com` for GitLab orgs).
     /// 3. If the org is not found, fall back to `default_base_url` (legacy mode).
     /// 4. Last resort: `https://github.com`.
     pub fn web_url_for_owner(&self, owner: &str) -> String {
@@ -189,6 +330,7 @@ impl GitHubClientRegistry {
             if entry.name.to_lowercase() == owner_lower {
                 return match entry.base_url {
                     Some(ref api_url) => derive_web_url(api_url),
+                    None if entry.provider == Provider::GitLab => derive_web_url(GITLAB_API_URL),
                     None => "https://github.com".to_string(),
                 };
             }
@@ -210,6 +352,37 @@ impl GitHubClientRegistry {
         }
     }
 
+    /// Live rate-limit budget of the client serving `org`.
+    ///
+    /// Tracked from the headers of the client's recent responses. `None` if
+    /// no client serves the org or the client does not track a budget
+    /// (e.g. `MockGitHubClient`).
+    pub fn rate_budget_for_org(&self, org: &str) -> Option<RateBudgetSnapshot> {
+        self.client_for_org(org).ok()?.rate_budget()
+    }
+
+    /// Live rate-limit budget of the default client.
+    pub fn default_rate_budget(&self) -> Option<RateBudgetSnapshot> {
+        self.default_client.as_ref()?.rate_budget()
+    }
+
+    /// Refresh every client's budget from the GitHub rate_limit API.
+    ///
+    /// That endpoint does not count against the limit, so this is a free
+    /// way to show budgets before any other request. Failures are logged.
+    pub async fn refresh_rate_budgets(&self) {
+        let clients = self
+            .clients
+            .iter()
+            .map(|(name, client)| (name.as_str(), client))
+            .chain(self.default_client.iter().map(|c| ("(default)", c)));
+        for (name, client) in clients {
+            if let Err(e) = client.rate_limit().await {
+                warn!(org = %name, error = %e, "Failed to fetch rate limit");
+            }
+        }
+    }
+
     /// Validate all configured tokens by calling the GitHub rate_limit API.
     ///
     /// Returns the count of tokens that validated successfully.
@@ -254,7 +427,7 @@ impl GitHubClientRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{Config, GitHubConfig, OrgConfig};
+    use crate::config::GitHubConfig;
 
     /// Helper to build a Config with org entries.
     /// Note: Tests that call `from_config` will actually create OctocrabClient
@@ -267,6 +440,7 @@ mod tests {
             github: GitHubConfig {
                 token: default_token.map(|t| t.to_string()),
                 base_url: None,
+                ..Default::default()
             },
             organizations: orgs
                 .into_iter()
@@ -275,12 +449,34 @@ mod tests {
                     token: token.to_string(),
                     base_url: base_url.map(|u| u.to_string()),
                     repos: None,
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 })
                 .collect(),
             ..Default::default()
         }
     }
 
+    #[tokio::test]
+    async fn test_registry_defers_broken_secret_references() {
+        let config = config_with_orgs(
+            Some("ghp_default"),
+            vec![
+                ("org-alpha", "ghp_alpha", None),
+                ("org-broken", "env:GREPORT_TEST_UNSET_ORG_TOKEN", None),
+            ],
+        );
+
+        // The broken org does not stop the others from working
+        let registry = GitHubClientRegistry::from_config(&config).unwrap();
+        assert!(registry.client_for_org("org-alpha").is_ok());
+        let Err(err) = registry.client_for_org("org-broken") else {
+            panic!("expected an error for org-broken");
+        };
+        assert!(err.to_string().contains("organizations.org-broken.token"));
+    }
+
     #[tokio::test]
     async fn test_registry_client_for_known_org() {
         let config = config_with_orgs(
@@ -327,7 +523,7 @@ mod tests {
         // Unknown org with no default should error
         let result = registry.client_for_org("unknown-org");
         assert!(result.is_err());
-        let err = result.unwrap_err();
+        let err = result.err().unwrap();
         match err {
             Error::OrgNotConfigured { org, env_var } => {
                 assert_eq!(org, "unknown-org");
@@ -484,6 +680,7 @@ mod tests {
             github: GitHubConfig {
                 token: Some("ghp_default".to_string()),
                 base_url: Some("https://ghe.corp.com/api/v3".to_string()),
+                ..Default::default()
             },
             organizations: vec![],
             ..Default::default()
@@ -497,4 +694,74 @@ mod tests {
         );
         assert_eq!(registry.default_web_url(), "https://ghe.corp.com");
     }
+
+    #[tokio::test]
+    async fn test_registry_with_mock_client() {
+        use crate::client::MockGitHubClient;
+
+        let registry =
+            GitHubClientRegistry::with_default(MockGitHubClient::with_sample_data(), None);
+        let client = registry.client_for_org("test-owner").unwrap();
+
+        let repo = client
+            .get_repository(&RepoId::new("test-owner", "test-repo"))
+            .await
+            .unwrap();
+        assert_eq!(repo.full_name, "test-owner/test-repo");
+        assert_eq!(client.list_projects("test-owner").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registry_rate_budgets() {
+        let config = config_with_orgs(Some("ghp_default"), vec![("org-alpha", "ghp_alpha", None)]);
+        let registry = GitHubClientRegistry::from_config(&config).unwrap();
+
+        // Real clients track a budget, empty until the first response
+        let budget = registry.rate_budget_for_org("org-alpha").unwrap();
+        assert!(budget.core.is_none() && budget.graphql.is_none());
+        assert!(registry.default_rate_budget().is_some());
+
+        let mock = GitHubClientRegistry::with_default(
+            crate::client::MockGitHubClient::with_sample_data(),
+            None,
+        );
+        assert!(mock.rate_budget_for_org("test-owner").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_registry_providers() {
+        let mut config = config_with_orgs(
+            Some("ghp_default"),
+            vec![
+                ("gl-group", "glpat_x", None),
+                ("tea-org", "tea_x", Some("https://git.corp.com/api/v1")),
+            ],
+        );
+        config.organizations[0].provider = Provider::GitLab;
+        config.organizations[1].provider = Provider::Gitea;
+
+        let registry = GitHubClientRegistry::from_config(&config).unwrap();
+        assert!(registry.has_org("gl-group") && registry.has_org("tea-org"));
+        // Only GitHub clients track a rate budget
+        assert!(registry.rate_budget_for_org("gl-group").is_none());
+        assert_eq!(registry.web_url_for_owner("gl-group"), "https://gitlab.com");
+        assert_eq!(
+            registry.web_url_for_owner("tea-org"),
+            "https://git.corp.com"
+        );
+
+        // Replay keeps the organizations without needing their tokens
+        config.organizations[0].token = "env:GREPORT_UNSET_REPLAY_TOKEN".into();
+        let replay = GitHubClientRegistry::replaying(&config, "/nonexistent");
+        assert!(replay.has_org("gl-group") && replay.has_org("tea-org"));
+        assert_eq!(replay.web_url_for_owner("gl-group"), "https://gitlab.com");
+        assert_eq!(replay.web_url_for_owner("tea-org"), "https://git.corp.com");
+
+        // Gitea has no public default instance
+        config.organizations[1].base_url = None;
+        assert!(matches!(
+            GitHubClientRegistry::from_config(&config),
+            Err(Error::Config(_))
+        ));
+    }
 }
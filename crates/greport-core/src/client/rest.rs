@@ -0,0 +1,255 @@
+//! Plain REST plumbing shared by the GitLab and Gitea clients
+//!
+//! Both APIs page with a `Link: <...>; rel="next"` header, authenticate with
+//! a single header and answer errors with ordinary status codes, so one
+//! small helper covers the requests, retries and pagination of both.
+
+use super::paginate::{PageCollector, Pagination};
+use super::retry::{with_retry, RetryConfig};
+use crate::{Error, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use tracing::debug;
+
+/// Query parameters of a request
+pub(crate) type Query = Vec<(&'static str, String)>;
+
+/// Authenticated JSON API at one base URL
+pub(crate) struct RestApi {
+    http: Client,
+    base_url: String,
+    /// Name of the forge, for error messages
+    provider: &'static str,
+    /// Query parameter holding the page size
+    page_size_param: &'static str,
+    retry_config: RetryConfig,
+}
+
+impl std::fmt::Debug for RestApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestApi")
+            .field("base_url", &self.base_url)
+            .field("provider", &self.provider)
+            .field("retry_config", &self.retry_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RestApi {
+    /// Create an API client sending `auth_header: auth_value` on every request
+    pub(crate) fn new(
+        provider: &'static str,
+        base_url: &str,
+        auth_header: &'static str,
+        auth_value: &str,
+        page_size_param: &'static str,
+    ) -> Result<Self> {
+        let mut value = HeaderValue::from_str(auth_value)
+            .map_err(|e| Error::Config(format!("Invalid {} token: {}", provider, e)))?;
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(HeaderName::from_static(auth_header), value);
+
+        let http = Client::builder()
+            .user_agent("greport")
+            .default_headers(headers)
+            .build()
+            .map_err(|e| Error::Custom(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            provider,
+            page_size_param,
+            retry_config: RetryConfig::default(),
+        })
+    }
+
+    /// Set custom retry configuration
+    pub(crate) fn set_retry_config(&mut self, config: RetryConfig) {
+        self.retry_config = config;
+    }
+
+    /// `GET` `path` and deserialize the body
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        path: &str,
+        query: &Query,
+    ) -> Result<T> {
+        let url = self.url(path, query);
+        let response = self.send(operation, &url).await?;
+        self.json(response).await
+    }
+
+    /// `GET` `path` and return the raw response (e.g. to read its headers)
+    pub(crate) async fn get_response(
+        &self,
+        operation: &'static str,
+        path: &str,
+        query: &Query,
+    ) -> Result<Response> {
+        self.send(operation, &self.url(path, query)).await
+    }
+
+    /// `GET` every page of the list at `path`
+    pub(crate) async fn list<T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        path: &str,
+        query: &Query,
+        pagination: &Pagination,
+    ) -> Result<Vec<T>> {
+        let mut query = query.clone();
+        query.push((self.page_size_param, "100".to_string()));
+        let mut url = self.url(path, &query);
+        let mut collector = PageCollector::new(pagination, operation);
+
+        loop {
+            let response = self.send(operation, &url).await?;
+            let next = next_link(response.headers());
+            let total_pages = response
+                .headers()
+                .get("x-total-pages")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
+            let items: Vec<T> = self.json(response).await?;
+            debug!(
+                operation = operation,
+                items_in_page = items.len(),
+                has_next = next.is_some(),
+                "Received page"
+            );
+
+            if !collector.push_page(items, next.is_some(), total_pages) {
+                break;
+            }
+            match next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+        collector.into_items()
+    }
+
+    fn url(&self, path: &str, query: &Query) -> String {
+        let url = format!("{}{}", self.base_url, path);
+        if query.is_empty() {
+            return url;
+        }
+        let query = serde_urlencoded::to_string(query).unwrap_or_default();
+        format!("{}?{}", url, query)
+    }
+
+    async fn send(&self, operation: &'static str, url: &str) -> Result<Response> {
+        with_retry(operation, &self.retry_config, || async {
+            let response =
+                self.http.get(url).send().await.map_err(|e| {
+                    Error::Network(format!("{} request failed: {}", self.provider, e))
+                })?;
+            self.check(response).await
+        })
+        .await
+    }
+
+    /// Map an error status to an [`Error`]
+    async fn check(&self, response: Response) -> Result<Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let reset_at = response
+            .headers()
+            .get("ratelimit-reset")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|secs| super::budget::unix_time(secs).to_rfc3339());
+        let path = response.url().path().to_string();
+        let body = response.text().await.unwrap_or_default();
+        Err(match status.as_u16() {
+            401 => Error::InvalidToken,
+            404 => Error::NotFound(format!("{} {}", self.provider, path)),
+            429 => Error::RateLimitExceeded {
+                reset_at: reset_at.unwrap_or_else(|| "an unknown time".to_string()),
+            },
+            s if s >= 500 => Error::Network(format!("{} server error: HTTP {}", self.provider, s)),
+            s => Error::GitHubApi(format!("{} {}: {}", self.provider, s, body.trim())),
+        })
+    }
+
+    async fn json<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
+        let body = response.text().await.map_err(|e| {
+            Error::Network(format!("Failed to read {} response: {}", self.provider, e))
+        })?;
+        serde_json::from_str(&body)
+            .map_err(|e| Error::GitHubApi(format!("Unexpected {} response: {}", self.provider, e)))
+    }
+}
+
+/// URL of the `rel="next"` entry of a `Link` header
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// Percent-encode one path segment (e.g. a GitLab project path or a tag)
+pub(crate) fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for b in segment.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            HeaderValue::from_static(
+                "<https://git.example.com/api/v4/projects/1/issues?page=1>; rel=\"prev\", \
+                 <https://git.example.com/api/v4/projects/1/issues?page=3>; rel=\"next\"",
+            ),
+        );
+        assert_eq!(
+            next_link(&headers).as_deref(),
+            Some("https://git.example.com/api/v4/projects/1/issues?page=3")
+        );
+
+        headers.insert(
+            "link",
+            HeaderValue::from_static("<https://git.example.com/x?page=1>; rel=\"first\""),
+        );
+        assert_eq!(next_link(&headers), None);
+        assert_eq!(next_link(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_encode_segment() {
+        assert_eq!(encode_segment("acme/api"), "acme%2Fapi");
+        assert_eq!(encode_segment("v1.2.0-rc.1"), "v1.2.0-rc.1");
+        assert_eq!(encode_segment("a b"), "a%20b");
+    }
+}
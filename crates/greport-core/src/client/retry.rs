@@ -1,4 +1,4 @@
-//! Retry logic for transient network errors
+//! Retry logic for transient network errors and rate limits
 
 use std::time::Duration;
 use tokio::time::sleep;
@@ -29,7 +29,6 @@ impl Default for RetryConfig {
 }
 
 /// Check if an error is retryable (transient network error)
-#[allow(dead_code)]
 pub fn is_retryable_error(err: &octocrab::Error) -> bool {
     match err {
         // Network/connection errors are retryable
@@ -48,22 +47,45 @@ pub fn is_retryable_error(err: &octocrab::Error) -> bool {
             (500..600).contains(&status) // 5xx errors
                 || status == 429 // Too Many Requests
                 || status == 408 // Request Timeout
+                // GitHub answers an exhausted or secondary rate limit with 403
+                || (status == 403 && source.message.to_lowercase().contains("rate limit"))
         }
         // Other errors are generally not retryable
         _ => false,
     }
 }
 
+/// Errors that [`with_retry`] knows how to classify
+pub(crate) trait Retryable: std::fmt::Display {
+    /// Whether the failed call may succeed if repeated
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for octocrab::Error {
+    fn is_retryable(&self) -> bool {
+        is_retryable_error(self)
+    }
+}
+
+impl Retryable for crate::Error {
+    fn is_retryable(&self) -> bool {
+        crate::Error::is_retryable(self)
+    }
+}
+
 /// Execute an async operation with retry logic
-#[allow(dead_code)]
-pub async fn with_retry<F, Fut, T>(
+///
+/// Rate-limit waits happen inside `operation` (see `RateBudget::acquire`),
+/// so a retried rate-limited call sleeps until the budget allows it.
+pub(crate) async fn with_retry<F, Fut, T, E>(
     operation_name: &str,
     config: &RetryConfig,
     mut operation: F,
-) -> Result<T, octocrab::Error>
+) -> Result<T, E>
 where
     F: FnMut() -> Fut,
-    Fut: std::future::Future<Output = Result<T, octocrab::Error>>,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    E: Retryable,
 {
     let mut attempt = 0;
     let mut backoff = config.initial_backoff;
@@ -83,7 +105,7 @@ where
                 return Ok(result);
             }
             Err(err) => {
-                if attempt > config.max_retries || !is_retryable_error(&err) {
+                if attempt > config.max_retries || !err.is_retryable() {
                     if attempt > 1 {
                         warn!(
                             operation = operation_name,
@@ -127,4 +149,50 @@ mod tests {
         assert_eq!(config.max_backoff, Duration::from_secs(30));
         assert!((config.multiplier - 2.0).abs() < f64::EPSILON);
     }
+
+    fn fast() -> RetryConfig {
+        RetryConfig {
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_repeats_retryable_errors() {
+        let mut calls = 0;
+        let result = with_retry("test", &fast(), || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 {
+                    Err(crate::Error::Network("connection reset".into()))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_gives_up() {
+        let mut calls = 0;
+        let result: Result<(), _> = with_retry("test", &fast(), || {
+            calls += 1;
+            async { Err(crate::Error::NotFound("issue".into())) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let result: Result<(), _> = with_retry("test", &fast(), || {
+            calls += 1;
+            async { Err(crate::Error::Network("timeout".into())) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 4);
+    }
 }
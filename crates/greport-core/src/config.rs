@@ -1,8 +1,12 @@
 //! Configuration management for greport
 
+use crate::client::{Credentials, Provider};
+use crate::identities::Identities;
+use crate::metrics::BotFilter;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 /// Main configuration structure
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -23,6 +27,14 @@ pub struct Config {
     #[serde(default)]
     pub sla: SlaConfig,
 
+    /// Bot and automation accounts to keep out of metrics
+    #[serde(default)]
+    pub bots: BotsConfig,
+
+    /// Contributor reporting configuration
+    #[serde(default)]
+    pub contributors: ContributorsConfig,
+
     /// Database configuration
     #[serde(default)]
     pub database: DatabaseConfig,
@@ -34,6 +46,15 @@ pub struct Config {
     /// Logging configuration
     #[serde(default)]
     pub logging: LoggingConfig,
+
+    /// Dashboard/API login configuration
+    #[serde(default)]
+    pub oauth: OAuthConfig,
+
+    /// Secrets already read from their references, so each `cmd:` runs at
+    /// most once
+    #[serde(skip)]
+    pub resolved_secrets: SecretCache,
 }
 
 /// GitHub-specific configuration
@@ -44,6 +65,36 @@ pub struct GitHubConfig {
 
     /// GitHub API base URL (for GitHub Enterprise)
     pub base_url: Option<String>,
+
+    /// API used to list issues and pull requests: "rest" or "graphql"
+    pub data_source: Option<String>,
+
+    /// Maximum items fetched by each list call (unlimited when unset)
+    pub max_items: Option<usize>,
+
+    /// Requests held back in each rate-limit window before calls pause
+    pub rate_limit_reserve: Option<u32>,
+
+    /// Send conditional requests and keep responses on disk (default true,
+    /// false for the API server)
+    pub etag_cache: Option<bool>,
+
+    /// Directory for stored responses (default `<cache dir>/greport/github`)
+    pub etag_cache_dir: Option<PathBuf>,
+
+    /// GitHub App installation to authenticate as instead of `token`
+    pub app: Option<GitHubAppConfig>,
+}
+
+/// GitHub App installation credentials
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitHubAppConfig {
+    /// App ID, shown on the App's settings page
+    pub app_id: u64,
+    /// Path to the App's PEM private key
+    pub private_key_path: PathBuf,
+    /// Installation ID for the account the App is installed on
+    pub installation_id: u64,
 }
 
 /// Single organization configuration
@@ -52,12 +103,24 @@ pub struct OrgConfig {
     /// GitHub organization name (e.g., "my-org")
     pub name: String,
     /// GitHub personal access token scoped to this organization
+    /// (empty when `app` is set)
+    #[serde(default)]
     pub token: String,
     /// Optional GitHub API base URL (for GitHub Enterprise)
     pub base_url: Option<String>,
     /// Optional list of repository names (without org prefix) to report on
     #[serde(default)]
     pub repos: Option<Vec<String>>,
+    /// GitHub App installation to authenticate as instead of `token`
+    #[serde(default)]
+    pub app: Option<GitHubAppConfig>,
+    /// Forge hosting the organization (`github`, `gitlab` or `gitea`)
+    #[serde(default)]
+    pub provider: Provider,
+    /// Bot logins or globs excluded for this organization, on top of
+    /// `bots.exclude`
+    #[serde(default)]
+    pub exclude_bots: Vec<String>,
 }
 
 /// Default settings
@@ -101,6 +164,42 @@ pub struct SlaPriority {
     pub resolution_time_hours: i64,
 }
 
+/// Bot and automation account configuration
+///
+/// Nothing is excluded unless configured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BotsConfig {
+    /// Logins or globs (e.g. `*[bot]`) treated as bots in every organization
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Also treat accounts GitHub reports as `type: Bot` as bots
+    #[serde(default)]
+    pub github_apps: bool,
+
+    /// Report bot activity separately instead of hiding it
+    #[serde(default)]
+    pub report_separately: bool,
+}
+
+impl BotsConfig {
+    /// Bot filter for the global patterns plus `extra` (e.g. an
+    /// organization's `exclude_bots`)
+    pub fn filter(&self, extra: &[String]) -> BotFilter {
+        BotFilter::new(self.exclude.iter().chain(extra).cloned())
+            .with_github_apps(self.github_apps)
+            .with_separate_report(self.report_separately)
+    }
+}
+
+/// Contributor reporting configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContributorsConfig {
+    /// TOML file mapping login aliases to people (and teams), so stats roll
+    /// up per person. Can also be set via GREPORT_IDENTITIES_FILE.
+    pub identities_file: Option<PathBuf>,
+}
+
 /// Database configuration (used by API server)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -132,6 +231,10 @@ pub struct ServerConfig {
     /// Rate limit per minute per client
     pub rate_limit_per_minute: Option<u32>,
 
+    /// Rate limit counter storage: "memory" (per process) or "postgres"
+    /// (shared by all replicas)
+    pub rate_limit_backend: Option<String>,
+
     /// Cache TTL in seconds for API responses
     pub cache_ttl_seconds: Option<u64>,
 
@@ -140,6 +243,76 @@ pub struct ServerConfig {
 
     /// Require API key authentication
     pub require_auth: Option<bool>,
+
+    /// Use a caller's validated GitHub token for GitHub fetches
+    pub use_caller_token: Option<bool>,
+
+    /// Maximum nesting depth of a GraphQL query
+    pub graphql_max_depth: Option<usize>,
+
+    /// Maximum complexity of a GraphQL query (one per field, multiplied by
+    /// the page size for list fields)
+    pub graphql_max_complexity: Option<usize>,
+}
+
+/// OAuth2/OIDC login configuration (used by API server)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OAuthConfig {
+    /// Identity provider: "github" (OAuth App, default) or "oidc"
+    pub provider: Option<String>,
+
+    /// OAuth client ID; login is disabled when unset
+    pub client_id: Option<String>,
+
+    /// OAuth client secret
+    /// Can also be set via OAUTH_CLIENT_SECRET env var (takes priority)
+    pub client_secret: Option<String>,
+
+    /// Authorization endpoint (defaults to GitHub's for the github provider)
+    pub authorize_url: Option<String>,
+
+    /// Token endpoint (defaults to GitHub's for the github provider)
+    pub token_url: Option<String>,
+
+    /// User info endpoint (defaults to `/user` on the GitHub API)
+    pub userinfo_url: Option<String>,
+
+    /// Callback URL registered with the provider
+    /// (e.g. "http://localhost:9423/api/v1/auth/callback")
+    pub redirect_url: Option<String>,
+
+    /// Scopes to request from the provider
+    pub scopes: Option<Vec<String>>,
+
+    /// OIDC claim listing the user's organizations or groups (default "groups")
+    pub org_claim: Option<String>,
+
+    /// Secret used to sign session tokens
+    /// Can also be set via SESSION_SECRET env var (takes priority)
+    pub session_secret: Option<String>,
+
+    /// Session lifetime in hours (default 12)
+    pub session_ttl_hours: Option<i64>,
+
+    /// Dashboard URL: where to return after login, and the origin allowed
+    /// to make credentialed requests
+    pub dashboard_url: Option<String>,
+
+    /// Only members of these organizations may log in; sessions are
+    /// limited to them. Required when login is enabled.
+    #[serde(default)]
+    pub allowed_orgs: Vec<String>,
+
+    /// Scopes granted to every logged-in user (default ["read"])
+    pub default_scopes: Option<Vec<String>>,
+
+    /// Additional scopes granted to members of an organization
+    #[serde(default)]
+    pub org_scopes: HashMap<String, Vec<String>>,
+
+    /// Users granted the admin scope
+    #[serde(default)]
+    pub admin_users: Vec<String>,
 }
 
 /// Logging configuration
@@ -192,6 +365,116 @@ fn default_resolution_time() -> i64 {
     168 // 7 days
 }
 
+/// Reference to a secret kept outside the config file
+///
+/// Secret fields (`token`, `client_secret`, `session_secret`) accept
+/// `env:VAR`, `file:/path` or `cmd:command` in place of the value. The
+/// reference is kept in the config and only read when the credential is
+/// first needed (see [`Config::secret`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    /// `env:VAR` - an environment variable
+    Env(String),
+    /// `file:/path` - a file, with trailing whitespace removed
+    File(PathBuf),
+    /// `cmd:command` - the first line a shell command prints
+    Command(String),
+}
+
+/// Secrets read from their references, keyed by reference
+#[derive(Clone, Default)]
+pub struct SecretCache(Arc<Mutex<HashMap<String, String>>>);
+
+impl std::fmt::Debug for SecretCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the secrets themselves
+        let len = self.0.lock().map(|m| m.len()).unwrap_or_default();
+        write!(f, "SecretCache({} resolved)", len)
+    }
+}
+
+impl SecretSource {
+    /// Parse a config value, or None when it is the secret itself
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Some(var) = value.strip_prefix("env:") {
+            Some(SecretSource::Env(var.trim().to_string()))
+        } else if let Some(path) = value.strip_prefix("file:") {
+            Some(SecretSource::File(PathBuf::from(path.trim())))
+        } else {
+            value
+                .strip_prefix("cmd:")
+                .map(|command| SecretSource::Command(command.trim().to_string()))
+        }
+    }
+
+    /// Read the secret
+    pub fn read(&self) -> crate::Result<String> {
+        self.read_with(|var| std::env::var(var).ok())
+    }
+
+    /// Read the secret, looking environment variables up with `env`
+    pub fn read_with(&self, env: impl Fn(&str) -> Option<String>) -> crate::Result<String> {
+        let secret = match self {
+            SecretSource::Env(var) => env(var).ok_or_else(|| {
+                crate::Error::Config(format!("environment variable {} is not set", var))
+            })?,
+            SecretSource::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| {
+                    crate::Error::Config(format!("failed to read {}: {}", path.display(), e))
+                })?
+                .trim_end()
+                .to_string(),
+            SecretSource::Command(command) => {
+                let output = shell_command(command).output().map_err(|e| {
+                    crate::Error::Config(format!("failed to run '{}': {}", command, e))
+                })?;
+                if !output.status.success() {
+                    return Err(crate::Error::Config(format!(
+                        "'{}' failed ({}): {}",
+                        command,
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    )));
+                }
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            }
+        };
+        if secret.is_empty() {
+            return Err(crate::Error::Config(format!("{} is empty", self)));
+        }
+        Ok(secret)
+    }
+}
+
+impl std::fmt::Display for SecretSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretSource::Env(var) => write!(f, "env:{}", var),
+            SecretSource::File(path) => write!(f, "file:{}", path.display()),
+            SecretSource::Command(command) => write!(f, "cmd:{}", command),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
 /// Mask a token for display: shows first 4 chars + `****` + last 4 chars.
 /// For tokens with 8 or fewer characters, shows first 4 + `****`.
 pub fn mask_token(token: &str) -> String {
@@ -230,11 +513,68 @@ impl Config {
         let mut config: Config = toml::from_str(&content)
             .map_err(|e| crate::Error::Config(format!("Failed to parse config: {}", e)))?;
 
-        config.merge_org_env_vars();
+        config.merge_org_env_vars()?;
 
         Ok(config)
     }
 
+    /// The secret a secret field holds: the value itself, or what its
+    /// `env:`, `file:` or `cmd:` reference points to. `field` names the
+    /// setting in errors (e.g. `organizations.acme.token`).
+    pub fn secret(&self, field: &str, value: &str) -> crate::Result<String> {
+        let Some(source) = SecretSource::parse(value) else {
+            return Ok(value.to_string());
+        };
+        let mut resolved = self
+            .resolved_secrets
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(secret) = resolved.get(value) {
+            return Ok(secret.clone());
+        }
+        let secret = source
+            .read()
+            .map_err(|e| crate::Error::Config(format!("{}: {}", field, e)))?;
+        resolved.insert(value.to_string(), secret.clone());
+        Ok(secret)
+    }
+
+    /// How to show a secret field: the reference when it was given as one,
+    /// otherwise the masked value
+    pub fn display_secret(value: &str) -> String {
+        match SecretSource::parse(value) {
+            Some(source) => source.to_string(),
+            None => mask_token(value),
+        }
+    }
+
+    /// Copy of the config with every secret replaced by
+    /// [`Config::display_secret`], for printing; no reference is read
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        for value in config.secrets_mut() {
+            if !value.is_empty() {
+                *value = Self::display_secret(value);
+            }
+        }
+        config
+    }
+
+    /// Secret fields that are set
+    fn secrets_mut(&mut self) -> Vec<&mut String> {
+        let optional = [
+            &mut self.github.token,
+            &mut self.oauth.client_secret,
+            &mut self.oauth.session_secret,
+        ];
+        let mut fields: Vec<&mut String> = optional.into_iter().flatten().collect();
+        for org in &mut self.organizations {
+            fields.push(&mut org.token);
+        }
+        fields
+    }
+
     /// Get the default configuration file path
     pub fn default_config_path() -> crate::Result<PathBuf> {
         let home = dirs::home_dir()
@@ -246,41 +586,134 @@ impl Config {
     /// Get GitHub token from config or environment
     pub fn github_token(&self) -> crate::Result<String> {
         if let Some(token) = &self.github.token {
-            return Ok(token.clone());
+            return self.secret("github.token", token);
         }
 
         std::env::var("GITHUB_TOKEN").map_err(|_| crate::Error::MissingToken)
     }
 
+    /// Resolve the default GitHub App installation (env vars > config file)
+    ///
+    /// The environment overrides the `[github.app]` table only when
+    /// `GITHUB_APP_ID`, `GITHUB_APP_PRIVATE_KEY_PATH` and
+    /// `GITHUB_APP_INSTALLATION_ID` are all set.
+    pub fn github_app(&self) -> Option<GitHubAppConfig> {
+        let var = |name| std::env::var(name).ok();
+        let from_env = match (
+            var("GITHUB_APP_ID").and_then(|v| v.parse().ok()),
+            var("GITHUB_APP_PRIVATE_KEY_PATH"),
+            var("GITHUB_APP_INSTALLATION_ID").and_then(|v| v.parse().ok()),
+        ) {
+            (Some(app_id), Some(path), Some(installation_id)) => Some(GitHubAppConfig {
+                app_id,
+                private_key_path: PathBuf::from(path),
+                installation_id,
+            }),
+            _ => None,
+        };
+        from_env.or_else(|| self.github.app.clone())
+    }
+
+    /// Resolve the default credentials: the GitHub App installation when
+    /// one is configured, otherwise the token
+    pub fn github_credentials(&self) -> crate::Result<Credentials> {
+        match self.github_app() {
+            Some(app) => Credentials::app(&app, self.github.base_url.as_deref()),
+            None => self.github_token().map(Credentials::Token),
+        }
+    }
+
+    /// Resolve an organization's token
+    pub fn org_token(&self, org: &OrgConfig) -> crate::Result<String> {
+        self.secret(&format!("organizations.{}.token", org.name), &org.token)
+    }
+
+    /// Credentials for an organization: the GitHub App installation when
+    /// `app` is set, otherwise its token
+    pub fn org_credentials(&self, org: &OrgConfig) -> crate::Result<Credentials> {
+        match &org.app {
+            Some(app) => Credentials::app(app, org.base_url.as_deref()),
+            None => self.org_token(org).map(Credentials::Token),
+        }
+    }
+
     /// Scan environment for GREPORT_ORG_*_TOKEN variables and merge into config
-    pub fn merge_org_env_vars(&mut self) {
-        for (key, value) in std::env::vars() {
+    ///
+    /// The tokens are merged as `env:` references, so they display as such.
+    pub fn merge_org_env_vars(&mut self) -> crate::Result<()> {
+        self.merge_org_vars(std::env::vars().collect())
+    }
+
+    /// [`Self::merge_org_env_vars`] over the given variables
+    fn merge_org_vars(&mut self, vars: HashMap<String, String>) -> crate::Result<()> {
+        let mut keys: Vec<&String> = vars.keys().collect();
+        keys.sort();
+        for key in keys {
             if let Some(org_suffix) = key.strip_prefix("GREPORT_ORG_") {
                 if let Some(org_upper) = org_suffix.strip_suffix("_TOKEN") {
                     let org_name = org_upper.to_lowercase().replace('_', "-");
+                    let value = SecretSource::Env(key.clone()).to_string();
                     if let Some(org) = self.organizations.iter_mut().find(|o| o.name == org_name) {
                         org.token = value;
                     } else {
                         let base_url_key = format!("GREPORT_ORG_{}_BASE_URL", org_upper);
+                        let provider_key = format!("GREPORT_ORG_{}_PROVIDER", org_upper);
+                        let provider = match vars.get(&provider_key) {
+                            Some(p) => p.parse().map_err(|e| {
+                                crate::Error::Config(format!("{}: {}", provider_key, e))
+                            })?,
+                            None => Provider::default(),
+                        };
                         self.organizations.push(OrgConfig {
                             name: org_name,
                             token: value,
-                            base_url: std::env::var(base_url_key).ok(),
+                            base_url: vars.get(&base_url_key).cloned(),
                             repos: None,
+                            app: None,
+                            provider,
+                            exclude_bots: Vec::new(),
                         });
                     }
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Bot filter for `org`: the global `bots.exclude` patterns plus the
+    /// organization's `exclude_bots`
+    pub fn bot_filter(&self, org: &str) -> BotFilter {
+        let org_lower = org.to_lowercase();
+        let org_patterns: Vec<String> = self
+            .organizations
+            .iter()
+            .filter(|o| o.name.to_lowercase() == org_lower)
+            .flat_map(|o| o.exclude_bots.iter().cloned())
+            .collect();
+        self.bots.filter(&org_patterns)
+    }
+
+    /// Load contributor identities (env var > config file); none are mapped
+    /// when no identities file is configured
+    pub fn identities(&self) -> crate::Result<Identities> {
+        let path = std::env::var("GREPORT_IDENTITIES_FILE")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| self.contributors.identities_file.clone());
+        match path {
+            Some(path) => Identities::load(&path),
+            None => Ok(Identities::default()),
+        }
     }
 
     /// Get GitHub token for a specific organization
-    pub fn github_token_for_org(&self, org: &str) -> Option<String> {
+    pub fn github_token_for_org(&self, org: &str) -> crate::Result<Option<String>> {
         let org_lower = org.to_lowercase();
         self.organizations
             .iter()
             .find(|o| o.name.to_lowercase() == org_lower)
-            .map(|o| o.token.clone())
+            .map(|o| self.org_token(o))
+            .transpose()
     }
 
     /// Resolve database URL (env var > config file)
@@ -350,6 +783,14 @@ impl Config {
             .unwrap_or(60)
     }
 
+    /// Resolve rate limit backend (env var > config file > "memory")
+    pub fn rate_limit_backend(&self) -> String {
+        std::env::var("RATE_LIMIT_BACKEND")
+            .ok()
+            .or_else(|| self.server.rate_limit_backend.clone())
+            .unwrap_or_else(|| "memory".to_string())
+    }
+
     /// Resolve cache TTL seconds (env var > config file > 300)
     pub fn cache_ttl_seconds(&self) -> u64 {
         std::env::var("CACHE_TTL_SECONDS")
@@ -376,6 +817,110 @@ impl Config {
         self.server.require_auth.unwrap_or(false)
     }
 
+    /// Resolve use caller token (env var > config file > false)
+    pub fn use_caller_token(&self) -> bool {
+        if let Ok(v) = std::env::var("USE_CALLER_TOKEN") {
+            return v == "true" || v == "1";
+        }
+        self.server.use_caller_token.unwrap_or(false)
+    }
+
+    /// Resolve GraphQL query depth limit (env var > config file > 12)
+    pub fn graphql_max_depth(&self) -> usize {
+        std::env::var("GRAPHQL_MAX_DEPTH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .or(self.server.graphql_max_depth)
+            .unwrap_or(12)
+    }
+
+    /// Resolve GraphQL query complexity limit (env var > config file > 5000)
+    pub fn graphql_max_complexity(&self) -> usize {
+        std::env::var("GRAPHQL_MAX_COMPLEXITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .or(self.server.graphql_max_complexity)
+            .unwrap_or(5000)
+    }
+
+    /// Resolve the issue and pull request data source
+    /// (env var > config file > REST); an unknown value is an error
+    pub fn data_source(&self) -> crate::Result<crate::client::DataSource> {
+        match std::env::var("GREPORT_DATA_SOURCE")
+            .ok()
+            .or_else(|| self.github.data_source.clone())
+        {
+            Some(value) => value.parse().map_err(|_| {
+                crate::Error::Config(format!(
+                    "Invalid data_source '{}' (expected 'rest' or 'graphql')",
+                    value
+                ))
+            }),
+            None => Ok(crate::client::DataSource::default()),
+        }
+    }
+
+    /// Resolve the per-list item cap (env var > config file > unlimited)
+    pub fn max_items(&self) -> Option<usize> {
+        std::env::var("GREPORT_MAX_ITEMS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .or(self.github.max_items)
+    }
+
+    /// Resolve the rate-limit reserve (env var > config file > 50)
+    pub fn rate_limit_reserve(&self) -> u32 {
+        std::env::var("GREPORT_RATE_LIMIT_RESERVE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .or(self.github.rate_limit_reserve)
+            .unwrap_or(crate::client::DEFAULT_RATE_LIMIT_RESERVE)
+    }
+
+    /// Resolve the directory for conditional-request responses
+    /// (env var > config file > user cache dir), or None when disabled
+    pub fn etag_cache_dir(&self) -> Option<PathBuf> {
+        let enabled = std::env::var("GREPORT_ETAG_CACHE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .or(self.github.etag_cache)
+            .unwrap_or(true);
+        if !enabled {
+            return None;
+        }
+        std::env::var("GREPORT_ETAG_CACHE_DIR")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| self.github.etag_cache_dir.clone())
+            .or_else(crate::client::FileEtagStore::default_dir)
+    }
+
+    /// Resolve OAuth client secret (env var > config file)
+    pub fn oauth_client_secret(&self) -> crate::Result<Option<String>> {
+        match std::env::var("OAUTH_CLIENT_SECRET") {
+            Ok(secret) => Ok(Some(secret)),
+            Err(_) => self
+                .oauth
+                .client_secret
+                .as_deref()
+                .map(|value| self.secret("oauth.client_secret", value))
+                .transpose(),
+        }
+    }
+
+    /// Resolve session signing secret (env var > config file)
+    pub fn session_secret(&self) -> crate::Result<Option<String>> {
+        match std::env::var("SESSION_SECRET") {
+            Ok(secret) => Ok(Some(secret)),
+            Err(_) => self
+                .oauth
+                .session_secret
+                .as_deref()
+                .map(|value| self.secret("oauth.session_secret", value))
+                .transpose(),
+        }
+    }
+
     /// Collect repos from all configured organizations.
     ///
     /// Returns `RepoId` for each repo listed in each org's `repos` field.
@@ -462,6 +1007,72 @@ format = "json"
         assert_eq!(config.defaults.format, "json");
     }
 
+    #[test]
+    fn test_config_data_source() {
+        let config: Config = toml::from_str("[github]\ndata_source = \"GraphQL\"\n").unwrap();
+        assert_eq!(config.github.data_source.as_deref(), Some("GraphQL"));
+        assert_eq!(
+            config.data_source().unwrap(),
+            crate::client::DataSource::GraphQL
+        );
+        assert_eq!(
+            Config::default().data_source().unwrap(),
+            crate::client::DataSource::Rest
+        );
+
+        let config: Config = toml::from_str("[github]\ndata_source = \"grapql\"\n").unwrap();
+        let err = config.data_source().unwrap_err();
+        assert!(err.to_string().contains("Invalid data_source 'grapql'"));
+    }
+
+    #[test]
+    fn test_config_rate_limit_reserve() {
+        let config: Config = toml::from_str("[github]\nrate_limit_reserve = 200\n").unwrap();
+        assert_eq!(config.rate_limit_reserve(), 200);
+        assert_eq!(Config::default().rate_limit_reserve(), 50);
+    }
+
+    #[test]
+    fn test_config_etag_cache_dir() {
+        let config: Config =
+            toml::from_str("[github]\netag_cache_dir = \"/tmp/greport-etags\"\n").unwrap();
+        assert_eq!(
+            config.etag_cache_dir(),
+            Some(PathBuf::from("/tmp/greport-etags"))
+        );
+
+        let config: Config = toml::from_str("[github]\netag_cache = false\n").unwrap();
+        assert_eq!(config.etag_cache_dir(), None);
+    }
+
+    #[test]
+    fn test_config_org_github_app() {
+        let toml_str = r#"
+[[organizations]]
+name = "app-org"
+
+[organizations.app]
+app_id = 1234
+private_key_path = "/nonexistent/greport-app.pem"
+installation_id = 42
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let org = &config.organizations[0];
+        assert!(org.token.is_empty());
+        assert_eq!(
+            org.app,
+            Some(GitHubAppConfig {
+                app_id: 1234,
+                private_key_path: PathBuf::from("/nonexistent/greport-app.pem"),
+                installation_id: 42,
+            })
+        );
+
+        // The key is read when the credentials are built
+        let err = config.org_credentials(org).unwrap_err();
+        assert!(err.to_string().contains("greport-app.pem"));
+    }
+
     #[test]
     fn test_config_org_token_lookup() {
         let config = Config {
@@ -471,26 +1082,32 @@ format = "json"
                     token: "ghp_myorg".to_string(),
                     base_url: None,
                     repos: None,
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 },
                 OrgConfig {
                     name: "other-org".to_string(),
                     token: "ghp_other".to_string(),
                     base_url: None,
                     repos: None,
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 },
             ],
             ..Default::default()
         };
 
         assert_eq!(
-            config.github_token_for_org("my-org"),
+            config.github_token_for_org("my-org").unwrap(),
             Some("ghp_myorg".to_string())
         );
         assert_eq!(
-            config.github_token_for_org("other-org"),
+            config.github_token_for_org("other-org").unwrap(),
             Some("ghp_other".to_string())
         );
-        assert_eq!(config.github_token_for_org("unknown-org"), None);
+        assert_eq!(config.github_token_for_org("unknown-org").unwrap(), None);
     }
 
     #[test]
@@ -501,20 +1118,23 @@ format = "json"
                 token: "ghp_myorg".to_string(),
                 base_url: None,
                 repos: None,
+                app: None,
+                provider: Provider::GitHub,
+                exclude_bots: Vec::new(),
             }],
             ..Default::default()
         };
 
         assert_eq!(
-            config.github_token_for_org("my-org"),
+            config.github_token_for_org("my-org").unwrap(),
             Some("ghp_myorg".to_string())
         );
         assert_eq!(
-            config.github_token_for_org("MY-ORG"),
+            config.github_token_for_org("MY-ORG").unwrap(),
             Some("ghp_myorg".to_string())
         );
         assert_eq!(
-            config.github_token_for_org("My-Org"),
+            config.github_token_for_org("My-Org").unwrap(),
             Some("ghp_myorg".to_string())
         );
     }
@@ -555,12 +1175,18 @@ token = "ghp_beta"
                     token: "ghp_alpha".to_string(),
                     base_url: None,
                     repos: Some(vec!["api".to_string(), "web".to_string()]),
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 },
                 OrgConfig {
                     name: "org-beta".to_string(),
                     token: "ghp_beta".to_string(),
                     base_url: None,
                     repos: Some(vec!["sdk".to_string()]),
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 },
             ],
             ..Default::default()
@@ -593,12 +1219,18 @@ token = "ghp_beta"
                     token: "ghp_alpha".to_string(),
                     base_url: None,
                     repos: Some(vec!["api".to_string()]),
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 },
                 OrgConfig {
                     name: "org-beta".to_string(),
                     token: "ghp_beta".to_string(),
                     base_url: None,
                     repos: Some(vec!["sdk".to_string(), "cli".to_string()]),
+                    app: None,
+                    provider: Provider::GitHub,
+                    exclude_bots: Vec::new(),
                 },
             ],
             ..Default::default()
@@ -629,6 +1261,158 @@ token = "ghp_beta"
         assert_eq!(masked, "abc****");
     }
 
+    #[test]
+    fn test_secret_source_parse() {
+        assert_eq!(
+            SecretSource::parse("env:GITHUB_ACME_TOKEN"),
+            Some(SecretSource::Env("GITHUB_ACME_TOKEN".to_string()))
+        );
+        assert_eq!(
+            SecretSource::parse("file:/run/secrets/x"),
+            Some(SecretSource::File(PathBuf::from("/run/secrets/x")))
+        );
+        assert_eq!(
+            SecretSource::parse("cmd:pass show github/acme"),
+            Some(SecretSource::Command("pass show github/acme".to_string()))
+        );
+        assert_eq!(SecretSource::parse("ghp_plain"), None);
+        assert_eq!(
+            SecretSource::parse("cmd:pass show github/acme")
+                .unwrap()
+                .to_string(),
+            "cmd:pass show github/acme"
+        );
+    }
+
+    #[test]
+    fn test_resolve_secret_references() {
+        let dir = std::env::temp_dir().join(format!("greport-secrets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let secret_file = dir.join("acme");
+        std::fs::write(&secret_file, "ghp_fromfile\n").unwrap();
+
+        let toml_str = format!(
+            r#"
+[github]
+token = "file:{}"
+
+[[organizations]]
+name = "acme"
+token = "env:GREPORT_TEST_SECRET_UNSET"
+
+[[organizations]]
+name = "plain"
+token = "ghp_plaintext1234"
+"#,
+            secret_file.display()
+        );
+        let config: Config = toml::from_str(&toml_str).unwrap();
+
+        // Only the secrets asked for are read; the broken reference on an
+        // unused org does not get in the way
+        assert_eq!(config.github_token().unwrap(), "ghp_fromfile");
+        let plain = &config.organizations[1];
+        assert_eq!(config.org_token(plain).unwrap(), "ghp_plaintext1234");
+        let err = config.org_token(&config.organizations[0]).unwrap_err();
+        assert!(err.to_string().contains("organizations.acme.token"));
+        assert!(err
+            .to_string()
+            .contains("GREPORT_TEST_SECRET_UNSET is not set"));
+
+        // Display shows the reference, or the masked value for plain secrets
+        let shown = config.redacted();
+        assert_eq!(
+            shown.github.token,
+            Some(format!("file:{}", secret_file.display()))
+        );
+        assert_eq!(
+            shown.organizations[0].token,
+            "env:GREPORT_TEST_SECRET_UNSET"
+        );
+        assert_eq!(shown.organizations[1].token, "ghp_****1234");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_env_secret_reference() {
+        let source = SecretSource::parse("env:GREPORT_TOKEN").unwrap();
+        let env = |var: &str| (var == "GREPORT_TOKEN").then(|| "ghp_fromenv".to_string());
+        assert_eq!(source.read_with(env).unwrap(), "ghp_fromenv");
+
+        let err = source.read_with(|_| None).unwrap_err();
+        assert!(err.to_string().contains("GREPORT_TOKEN is not set"));
+        let err = source.read_with(|_| Some(String::new())).unwrap_err();
+        assert!(err.to_string().contains("env:GREPORT_TOKEN is empty"));
+    }
+
+    #[test]
+    fn test_merge_org_env_vars() {
+        let vars = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let mut config = Config::default();
+        config
+            .merge_org_vars(vars(&[
+                ("GREPORT_ORG_MY_GROUP_TOKEN", "glpat_x"),
+                ("GREPORT_ORG_MY_GROUP_PROVIDER", "GitLab"),
+                ("GREPORT_ORG_MY_GROUP_BASE_URL", "https://gitlab.corp.com"),
+            ]))
+            .unwrap();
+        let org = &config.organizations[0];
+        assert_eq!(org.name, "my-group");
+        assert_eq!(org.token, "env:GREPORT_ORG_MY_GROUP_TOKEN");
+        assert_eq!(org.provider, Provider::GitLab);
+        assert_eq!(org.base_url.as_deref(), Some("https://gitlab.corp.com"));
+
+        let err = Config::default()
+            .merge_org_vars(vars(&[
+                ("GREPORT_ORG_ACME_TOKEN", "ghp_x"),
+                ("GREPORT_ORG_ACME_PROVIDER", "gitlba"),
+            ]))
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::Config(_)));
+        assert!(err.to_string().contains("GREPORT_ORG_ACME_PROVIDER"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_command_secret() {
+        let dir = std::env::temp_dir().join(format!("greport-secret-cmd-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let runs = dir.join("runs");
+        let command = format!(
+            "echo run >> {}; printf 'ghp_fromcmd\\nmetadata'",
+            runs.display()
+        );
+        let config = Config {
+            github: GitHubConfig {
+                token: Some(format!("cmd:{}", command)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Loading and displaying the config runs nothing
+        assert!(config.redacted().github.token.unwrap().starts_with("cmd:"));
+        assert!(!runs.exists());
+
+        // The command runs once, when the token is first needed
+        assert_eq!(config.github_token().unwrap(), "ghp_fromcmd");
+        assert_eq!(config.github_token().unwrap(), "ghp_fromcmd");
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 1);
+
+        let config: Config = toml::from_str("[github]\ntoken = \"cmd:false\"\n").unwrap();
+        let err = config.github_token().unwrap_err();
+        assert!(err.to_string().contains("github.token"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_resolved_repos_empty_when_no_repos() {
         let config = Config {
@@ -637,6 +1421,9 @@ token = "ghp_beta"
                 token: "ghp_alpha".to_string(),
                 base_url: None,
                 repos: None,
+                app: None,
+                provider: Provider::GitHub,
+                exclude_bots: Vec::new(),
             }],
             ..Default::default()
         };
@@ -644,4 +1431,55 @@ token = "ghp_beta"
         assert!(config.resolved_repos().is_empty());
         assert!(config.resolved_repos_for_org("org-alpha").is_empty());
     }
+
+    #[test]
+    fn test_bot_filter_merges_global_and_org_patterns() {
+        let config: Config = toml::from_str(
+            r#"
+[bots]
+exclude = ["*[bot]"]
+github_apps = true
+
+[[organizations]]
+name = "acme"
+token = "ghp_acme"
+exclude_bots = ["acme-ci"]
+"#,
+        )
+        .unwrap();
+
+        let acme = config.bot_filter("Acme");
+        assert!(acme.is_bot_login("dependabot[bot]"));
+        assert!(acme.is_bot_login("acme-ci"));
+        assert!(!acme.separate());
+
+        let other = config.bot_filter("other");
+        assert!(other.is_bot_login("renovate[bot]"));
+        assert!(!other.is_bot_login("acme-ci"));
+
+        assert!(!Config::default()
+            .bot_filter("acme")
+            .is_bot_login("dependabot[bot]"));
+    }
+
+    #[test]
+    fn test_identities_file() {
+        let path = std::env::temp_dir().join(format!("greport-ids-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[[person]]\nname = \"alice\"\nlogins = [\"asmith\"]\n",
+        )
+        .unwrap();
+        let config = Config {
+            contributors: ContributorsConfig {
+                identities_file: Some(path.clone()),
+            },
+            ..Default::default()
+        };
+        let identities = config.identities().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(identities.canonical("ASmith"), "alice");
+
+        assert!(Config::default().identities().unwrap().is_empty());
+    }
 }
@@ -24,6 +24,10 @@ pub enum Error {
     #[error("Missing GitHub token. Set GITHUB_TOKEN environment variable")]
     MissingToken,
 
+    /// Token was rejected by GitHub (401)
+    #[error("GitHub rejected the token (bad credentials or expired)")]
+    InvalidToken,
+
     /// No token configured for the specified organization
     #[error(
         "No GitHub token configured for organization '{org}'. \
@@ -94,6 +98,19 @@ pub enum Error {
     )]
     ProjectsNotAvailable,
 
+    /// A list had more items than the `max_items` cap allows, and a
+    /// complete list was required
+    #[error(
+        "{operation} returned more than {max_items} items (the max_items cap); \
+         raise or unset max_items to fetch the whole list"
+    )]
+    Truncated {
+        /// List call that was cut short
+        operation: String,
+        /// Item cap in effect
+        max_items: usize,
+    },
+
     /// Custom error
     #[error("{0}")]
     Custom(String),
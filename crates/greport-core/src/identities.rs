@@ -0,0 +1,161 @@
+//! Contributor identities
+//!
+//! People often contribute from several accounts: a work and a personal
+//! login, or different logins on GitHub Enterprise and github.com. An
+//! identity file maps those login aliases to one person (and optionally a
+//! team) so contributor stats roll up per human.
+//!
+//! ```toml
+//! [[person]]
+//! name = "alice"
+//! team = "platform"
+//! logins = ["alice", "alice-acme", "asmith"]
+//!
+//! [[person]]
+//! name = "bob"
+//! logins = ["bob", "bob-ghe"]
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One person and the logins they contribute under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Person {
+    /// Canonical name, reported in place of any of the logins
+    pub name: String,
+    /// Team the person belongs to
+    #[serde(default)]
+    pub team: Option<String>,
+    /// Login aliases (matching ignores case)
+    #[serde(default)]
+    pub logins: Vec<String>,
+}
+
+/// Mapping of login aliases to people
+///
+/// Logins not listed stand for themselves.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Identities {
+    /// People, each with their logins
+    #[serde(default, rename = "person")]
+    pub people: Vec<Person>,
+    /// Lowercased login -> index into `people`
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+impl Identities {
+    /// Load identities from a TOML file
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            crate::Error::Config(format!(
+                "Failed to read identities file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// Parse identities from a TOML string
+    pub fn from_toml(content: &str) -> crate::Result<Self> {
+        let identities: Identities = toml::from_str(content)
+            .map_err(|e| crate::Error::Config(format!("Failed to parse identities: {}", e)))?;
+        Self::new(identities.people)
+    }
+
+    /// Build the mapping, rejecting a login claimed by two people
+    pub fn new(people: Vec<Person>) -> crate::Result<Self> {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (i, person) in people.iter().enumerate() {
+            // A person's name doubles as one of their logins
+            let logins = std::iter::once(&person.name).chain(&person.logins);
+            for login in logins {
+                let key = login.to_lowercase();
+                match index.get(&key) {
+                    Some(&other) if other != i => {
+                        return Err(crate::Error::Config(format!(
+                            "Login '{}' is mapped to both '{}' and '{}'",
+                            login, people[other].name, person.name
+                        )));
+                    }
+                    _ => {
+                        index.insert(key, i);
+                    }
+                }
+            }
+        }
+        Ok(Self { people, index })
+    }
+
+    /// Whether no people are mapped
+    pub fn is_empty(&self) -> bool {
+        self.people.is_empty()
+    }
+
+    /// The person `login` belongs to, if mapped
+    pub fn person(&self, login: &str) -> Option<&Person> {
+        self.index
+            .get(&login.to_lowercase())
+            .map(|&i| &self.people[i])
+    }
+
+    /// Name to report `login` under: the person's name, or the login itself
+    pub fn canonical<'a>(&'a self, login: &'a str) -> &'a str {
+        self.person(login).map_or(login, |p| p.name.as_str())
+    }
+
+    /// Team of the person `login` belongs to
+    pub fn team(&self, login: &str) -> Option<&str> {
+        self.person(login).and_then(|p| p.team.as_deref())
+    }
+
+    /// Whether `login` is one of the accounts of `person` (a login or a
+    /// person's name)
+    pub fn same_person(&self, login: &str, person: &str) -> bool {
+        self.canonical(login)
+            .eq_ignore_ascii_case(self.canonical(person))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITIES: &str = r#"
+[[person]]
+name = "alice"
+team = "platform"
+logins = ["alice-acme", "ASmith"]
+
+[[person]]
+name = "bob"
+logins = ["bob-ghe"]
+"#;
+
+    #[test]
+    fn test_canonical_and_team() {
+        let ids = Identities::from_toml(IDENTITIES).unwrap();
+        assert_eq!(ids.canonical("asmith"), "alice");
+        assert_eq!(ids.canonical("alice"), "alice");
+        assert_eq!(ids.canonical("bob-ghe"), "bob");
+        assert_eq!(ids.canonical("carol"), "carol");
+        assert_eq!(ids.team("alice-acme"), Some("platform"));
+        assert_eq!(ids.team("bob"), None);
+        assert!(ids.same_person("asmith", "alice-acme"));
+        assert!(!ids.same_person("asmith", "bob"));
+        assert!(Identities::default().same_person("Carol", "carol"));
+    }
+
+    #[test]
+    fn test_login_mapped_twice_is_an_error() {
+        let err = Identities::from_toml(
+            "[[person]]\nname = \"alice\"\nlogins = [\"shared\"]\n\n\
+             [[person]]\nname = \"bob\"\nlogins = [\"Shared\"]\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("'Shared' is mapped to both"));
+    }
+}
@@ -5,16 +5,20 @@
 //! - Data models for issues, pull requests, releases
 //! - Metrics calculations (velocity, SLA, burndown)
 //! - Report generation
+//! - Contributor identity merging
+//! - Projects (V2) bulk update rules
 
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod identities;
 pub mod metrics;
 pub mod models;
 pub mod reports;
+pub mod rules;
 
 pub use client::{
-    GitHubClient, GitHubClientRegistry, OctocrabClient, OrgEntry, ProjectClient, RepoId,
+    GitHubApi, GitHubClient, GitHubClientRegistry, OctocrabClient, OrgEntry, ProjectClient, RepoId,
 };
 pub use config::{Config, OrgConfig};
 pub use error::{Error, Result};
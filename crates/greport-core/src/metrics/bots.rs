@@ -0,0 +1,165 @@
+//! Bot and automation account filtering
+
+use crate::models::User;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Decides which accounts are bots, and whether their activity is hidden or
+/// reported separately
+///
+/// The default filter treats no account as a bot.
+#[derive(Debug, Clone, Default)]
+pub struct BotFilter {
+    patterns: Vec<String>,
+    github_apps: bool,
+    separate: bool,
+}
+
+impl BotFilter {
+    /// Treat logins matching any of `patterns` as bots
+    ///
+    /// A pattern is a login, or a glob where `*` matches any run of
+    /// characters (e.g. `*[bot]`). Matching ignores case.
+    pub fn new(patterns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|p| p.into().to_lowercase())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Also treat accounts GitHub reports as `type: Bot` as bots
+    pub fn with_github_apps(mut self, github_apps: bool) -> Self {
+        self.github_apps = github_apps;
+        self
+    }
+
+    /// Report bot activity separately instead of hiding it
+    pub fn with_separate_report(mut self, separate: bool) -> Self {
+        self.separate = separate;
+        self
+    }
+
+    /// Whether bot activity is reported separately
+    pub fn separate(&self) -> bool {
+        self.separate
+    }
+
+    /// Whether `user` is a bot
+    pub fn is_bot(&self, user: &User) -> bool {
+        (self.github_apps && user.is_bot) || self.is_bot_login(&user.login)
+    }
+
+    /// Whether `login` matches one of the patterns
+    pub fn is_bot_login(&self, login: &str) -> bool {
+        let login = login.to_lowercase();
+        self.patterns.iter().any(|p| glob_match(p, &login))
+    }
+
+    /// Split `items` into those by people and those by bots
+    pub fn partition<'a, T>(
+        &self,
+        items: impl IntoIterator<Item = &'a T>,
+        user: impl Fn(&T) -> &User,
+    ) -> (Vec<&'a T>, Vec<&'a T>) {
+        items.into_iter().partition(|item| !self.is_bot(user(item)))
+    }
+
+    /// Bot activity to report for `bot_authors`: `None` when bot activity is
+    /// hidden or there was none
+    pub fn activity<'a>(
+        &self,
+        bot_authors: impl IntoIterator<Item = &'a User>,
+    ) -> Option<BotActivity> {
+        if !self.separate {
+            return None;
+        }
+        let mut activity = BotActivity::default();
+        for author in bot_authors {
+            activity.total += 1;
+            *activity.by_author.entry(author.login.clone()).or_insert(0) += 1;
+        }
+        (activity.total > 0).then_some(activity)
+    }
+}
+
+/// Activity of bot accounts, reported apart from the metrics of people
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BotActivity {
+    /// Number of items authored by bots
+    pub total: usize,
+    /// Items by bot login
+    pub by_author: HashMap<String, usize>,
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match remaining.find(part) {
+            Some(at) => remaining = &remaining[at + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(login: &str, is_bot: bool) -> User {
+        User {
+            login: login.to_string(),
+            is_bot,
+            ..User::unknown()
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*[bot]", "dependabot[bot]"));
+        assert!(!glob_match("*[bot]", "robot"));
+        assert!(glob_match("ci-*", "ci-runner"));
+        assert!(glob_match("*-bot-*", "acme-bot-deploy"));
+        assert!(glob_match("renovate", "renovate"));
+        assert!(!glob_match("renovate", "renovate-bot"));
+        assert!(glob_match("*", "anyone"));
+        assert!(!glob_match("a*a", "a"));
+    }
+
+    #[test]
+    fn test_is_bot() {
+        let filter = BotFilter::new(["*[bot]", "Jenkins"]);
+        assert!(filter.is_bot(&user("dependabot[bot]", false)));
+        assert!(filter.is_bot(&user("jenkins", false)));
+        assert!(!filter.is_bot(&user("alice", false)));
+        // `type: Bot` only counts when enabled
+        assert!(!filter.is_bot(&user("dependabot", true)));
+        assert!(filter
+            .with_github_apps(true)
+            .is_bot(&user("dependabot", true)));
+        assert!(!BotFilter::default().is_bot(&user("renovate[bot]", true)));
+    }
+
+    #[test]
+    fn test_activity_only_when_separate() {
+        let bots = [user("renovate[bot]", false), user("renovate[bot]", false)];
+        let filter = BotFilter::new(["*[bot]"]);
+        assert!(filter.activity(&bots).is_none());
+
+        let activity = filter.with_separate_report(true).activity(&bots).unwrap();
+        assert_eq!(activity.total, 2);
+        assert_eq!(activity.by_author["renovate[bot]"], 2);
+    }
+}
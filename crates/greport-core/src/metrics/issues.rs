@@ -1,11 +1,13 @@
 //! Issue metrics calculations
 
+use super::{BotActivity, BotFilter};
 use crate::models::{Issue, IssueState};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Aggregated issue metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct IssueMetrics {
     /// Total number of issues
     pub total: usize,
@@ -27,10 +29,14 @@ pub struct IssueMetrics {
     pub age_distribution: AgeDistribution,
     /// Number of stale issues
     pub stale_count: usize,
+    /// Issues opened by bots, when bot activity is reported separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_activity: Option<BotActivity>,
 }
 
 /// Age distribution for issues
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AgeDistribution {
     /// Age buckets
     pub buckets: Vec<AgeBucket>,
@@ -38,6 +44,7 @@ pub struct AgeDistribution {
 
 /// Single age bucket
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AgeBucket {
     /// Display label
     pub label: String,
@@ -52,18 +59,31 @@ pub struct AgeBucket {
 /// Calculator for issue metrics
 pub struct IssueMetricsCalculator {
     stale_days: i64,
+    bots: BotFilter,
 }
 
 impl IssueMetricsCalculator {
     /// Create a new calculator with the given stale threshold
     pub fn new(stale_days: i64) -> Self {
-        Self { stale_days }
+        Self {
+            stale_days,
+            bots: BotFilter::default(),
+        }
+    }
+
+    /// Leave out issues opened by bots (or report them separately) and
+    /// bot assignees
+    pub fn with_bot_filter(mut self, bots: BotFilter) -> Self {
+        self.bots = bots;
+        self
     }
 
     /// Calculate metrics from a list of issues
     pub fn calculate(&self, issues: &[Issue]) -> IssueMetrics {
-        let open: Vec<_> = issues
+        let (issues, bot_issues) = self.bots.partition(issues, |i| &i.author);
+        let open: Vec<&Issue> = issues
             .iter()
+            .copied()
             .filter(|i| i.state == IssueState::Open)
             .collect();
         let closed: Vec<_> = issues
@@ -87,7 +107,7 @@ impl IssueMetricsCalculator {
 
         // Group by label
         let mut by_label: HashMap<String, usize> = HashMap::new();
-        for issue in issues {
+        for issue in &issues {
             for label in &issue.labels {
                 *by_label.entry(label.name.clone()).or_insert(0) += 1;
             }
@@ -95,11 +115,12 @@ impl IssueMetricsCalculator {
 
         // Group by assignee
         let mut by_assignee: HashMap<String, usize> = HashMap::new();
-        for issue in issues {
-            if issue.assignees.is_empty() {
+        for issue in &issues {
+            let (assignees, _) = self.bots.partition(&issue.assignees, |u| u);
+            if assignees.is_empty() {
                 *by_assignee.entry("Unassigned".to_string()).or_insert(0) += 1;
             } else {
-                for assignee in &issue.assignees {
+                for assignee in assignees {
                     *by_assignee.entry(assignee.login.clone()).or_insert(0) += 1;
                 }
             }
@@ -107,7 +128,7 @@ impl IssueMetricsCalculator {
 
         // Group by milestone
         let mut by_milestone: HashMap<String, usize> = HashMap::new();
-        for issue in issues {
+        for issue in &issues {
             let milestone_name = issue
                 .milestone
                 .as_ref()
@@ -133,6 +154,7 @@ impl IssueMetricsCalculator {
             by_milestone,
             age_distribution,
             stale_count,
+            bot_activity: self.bots.activity(bot_issues.iter().map(|i| &i.author)),
         }
     }
 
@@ -258,6 +280,26 @@ mod tests {
         assert_eq!(metrics.closed, 1);
     }
 
+    #[test]
+    fn test_issue_metrics_excludes_bots() {
+        let mut bot_issue = create_issue(IssueState::Open, 5);
+        bot_issue.author.login = "renovate[bot]".to_string();
+        let issues = vec![bot_issue, create_issue(IssueState::Open, 15)];
+
+        let bots = BotFilter::new(["*[bot]"]);
+        let metrics = IssueMetricsCalculator::new(30)
+            .with_bot_filter(bots.clone())
+            .calculate(&issues);
+        assert_eq!(metrics.total, 1);
+        assert!(metrics.bot_activity.is_none());
+
+        let metrics = IssueMetricsCalculator::new(30)
+            .with_bot_filter(bots.with_separate_report(true))
+            .calculate(&issues);
+        assert_eq!(metrics.total, 1);
+        assert_eq!(metrics.bot_activity.unwrap().total, 1);
+    }
+
     #[test]
     fn test_median_calculation() {
         assert_eq!(
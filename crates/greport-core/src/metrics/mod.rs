@@ -1,11 +1,15 @@
 //! Metrics calculations for GitHub data
 
+mod bots;
 mod issues;
+mod projects;
 mod pulls;
 mod sla;
 mod velocity;
 
+pub use bots::*;
 pub use issues::*;
+pub use projects::*;
 pub use pulls::*;
 pub use sla::*;
 pub use velocity::*;
@@ -0,0 +1,174 @@
+//! GitHub Projects V2 metrics calculations
+
+use crate::models::{FieldValue, ProjectItem};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Aggregated metrics for the items on a project board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectMetrics {
+    /// Total number of items
+    pub total_items: usize,
+    /// Items grouped by the value of the status field ("No Status" when unset)
+    pub by_status: Vec<FieldCount>,
+    /// Items grouped by content type (issue, pull_request, draft_issue)
+    pub by_content_type: Vec<FieldCount>,
+    /// Items grouped by assignee ("Unassigned" when none)
+    pub by_assignee: Vec<FieldCount>,
+    /// Items grouped by iteration title, across all iteration fields
+    pub by_iteration: Vec<FieldCount>,
+    /// Number of linked issues/PRs that are closed or merged
+    pub closed_items: usize,
+}
+
+/// A value and the number of items carrying it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldCount {
+    /// Field value
+    pub value: String,
+    /// Number of items
+    pub count: usize,
+}
+
+/// Calculator for project board metrics
+pub struct ProjectMetricsCalculator;
+
+impl ProjectMetricsCalculator {
+    /// Calculate metrics, grouping status by the given single-select field
+    pub fn calculate<'a>(
+        items: impl IntoIterator<Item = &'a ProjectItem>,
+        status_field: &str,
+    ) -> ProjectMetrics {
+        let mut total_items = 0;
+        let mut closed_items = 0;
+        let mut by_status: HashMap<String, usize> = HashMap::new();
+        let mut by_content_type: HashMap<String, usize> = HashMap::new();
+        let mut by_assignee: HashMap<String, usize> = HashMap::new();
+        let mut by_iteration: HashMap<String, usize> = HashMap::new();
+
+        for item in items {
+            total_items += 1;
+
+            let status = item
+                .field_display(status_field)
+                .unwrap_or_else(|| "No Status".to_string());
+            *by_status.entry(status).or_insert(0) += 1;
+
+            *by_content_type
+                .entry(item.content_type().to_string())
+                .or_insert(0) += 1;
+
+            let assignees = item.assignees();
+            if assignees.is_empty() {
+                *by_assignee.entry("Unassigned".to_string()).or_insert(0) += 1;
+            }
+            for login in assignees {
+                *by_assignee.entry(login.to_string()).or_insert(0) += 1;
+            }
+
+            for fv in &item.field_values {
+                if let FieldValue::Iteration { title, .. } = &fv.value {
+                    *by_iteration.entry(title.clone()).or_insert(0) += 1;
+                }
+            }
+
+            if item.state().is_some_and(|s| {
+                s.eq_ignore_ascii_case("closed") || s.eq_ignore_ascii_case("merged")
+            }) {
+                closed_items += 1;
+            }
+        }
+
+        ProjectMetrics {
+            total_items,
+            by_status: Self::sorted_counts(by_status),
+            by_content_type: Self::sorted_counts(by_content_type),
+            by_assignee: Self::sorted_counts(by_assignee),
+            by_iteration: Self::sorted_counts(by_iteration),
+            closed_items,
+        }
+    }
+
+    /// Sort counts descending, breaking ties by value name
+    fn sorted_counts(map: HashMap<String, usize>) -> Vec<FieldCount> {
+        let mut counts: Vec<FieldCount> = map
+            .into_iter()
+            .map(|(value, count)| FieldCount { value, count })
+            .collect();
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectFieldValue, ProjectItemContent};
+    use chrono::Utc;
+
+    fn make_item(status: Option<&str>, state: &str, assignees: &[&str]) -> ProjectItem {
+        let now = Utc::now();
+        let field_values = status
+            .map(|s| {
+                vec![ProjectFieldValue {
+                    field_name: "Status".to_string(),
+                    value: FieldValue::SingleSelect {
+                        name: s.to_string(),
+                        option_id: "opt".to_string(),
+                    },
+                }]
+            })
+            .unwrap_or_default();
+        ProjectItem {
+            node_id: "PVTI_1".to_string(),
+            content: ProjectItemContent::Issue {
+                number: 1,
+                title: "Item".to_string(),
+                state: state.to_string(),
+                url: String::new(),
+                repository: "acme/repo".to_string(),
+                assignees: assignees.iter().map(|a| a.to_string()).collect(),
+                labels: vec![],
+            },
+            field_values,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_project_metrics_counts() {
+        let items = vec![
+            make_item(Some("Done"), "CLOSED", &["alice"]),
+            make_item(Some("Done"), "CLOSED", &["bob"]),
+            make_item(Some("Todo"), "OPEN", &["alice"]),
+            make_item(None, "OPEN", &[]),
+        ];
+
+        let metrics = ProjectMetricsCalculator::calculate(&items, "Status");
+        assert_eq!(metrics.total_items, 4);
+        assert_eq!(metrics.closed_items, 2);
+        assert_eq!(metrics.by_status[0].value, "Done");
+        assert_eq!(metrics.by_status[0].count, 2);
+        assert!(metrics
+            .by_status
+            .iter()
+            .any(|c| c.value == "No Status" && c.count == 1));
+        assert_eq!(metrics.by_assignee[0].value, "alice");
+        assert!(metrics
+            .by_assignee
+            .iter()
+            .any(|c| c.value == "Unassigned" && c.count == 1));
+        assert_eq!(metrics.by_content_type[0].count, 4);
+    }
+
+    #[test]
+    fn test_project_metrics_empty() {
+        let items: Vec<ProjectItem> = vec![];
+        let metrics = ProjectMetricsCalculator::calculate(&items, "Status");
+        assert_eq!(metrics.total_items, 0);
+        assert!(metrics.by_status.is_empty());
+    }
+}
@@ -1,11 +1,13 @@
 //! Pull request metrics calculations
 
+use super::{BotActivity, BotFilter};
 use crate::models::{PullRequest, PullState};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Aggregated pull request metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PullMetrics {
     /// Total number of PRs
     pub total: usize,
@@ -27,6 +29,9 @@ pub struct PullMetrics {
     pub by_base_branch: HashMap<String, usize>,
     /// Number of draft PRs
     pub draft_count: usize,
+    /// PRs by bots, when bot activity is reported separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_activity: Option<BotActivity>,
 }
 
 /// Calculator for pull request metrics
@@ -35,6 +40,15 @@ pub struct PullMetricsCalculator;
 impl PullMetricsCalculator {
     /// Calculate metrics from a list of pull requests
     pub fn calculate(prs: &[PullRequest]) -> PullMetrics {
+        Self::calculate_with_bots(prs, &BotFilter::default())
+    }
+
+    /// Calculate metrics from the pull requests not authored by bots
+    ///
+    /// Bot PRs are left out, or counted in `bot_activity` when `bots`
+    /// reports them separately.
+    pub fn calculate_with_bots(prs: &[PullRequest], bots: &BotFilter) -> PullMetrics {
+        let (prs, bot_prs) = bots.partition(prs, |p| &p.author);
         let open: Vec<_> = prs.iter().filter(|p| p.state == PullState::Open).collect();
         let merged: Vec<_> = prs.iter().filter(|p| p.merged).collect();
         let closed_unmerged: Vec<_> = prs
@@ -58,20 +72,20 @@ impl PullMetricsCalculator {
 
         // Group by size
         let mut by_size: HashMap<String, usize> = HashMap::new();
-        for pr in prs {
+        for pr in &prs {
             let size = pr.size_category();
             *by_size.entry(size.label().to_string()).or_insert(0) += 1;
         }
 
         // Group by author
         let mut by_author: HashMap<String, usize> = HashMap::new();
-        for pr in prs {
+        for pr in &prs {
             *by_author.entry(pr.author.login.clone()).or_insert(0) += 1;
         }
 
         // Group by base branch
         let mut by_base_branch: HashMap<String, usize> = HashMap::new();
-        for pr in prs {
+        for pr in &prs {
             *by_base_branch.entry(pr.base_ref.clone()).or_insert(0) += 1;
         }
 
@@ -89,6 +103,7 @@ impl PullMetricsCalculator {
             by_author,
             by_base_branch,
             draft_count,
+            bot_activity: bots.activity(bot_prs.iter().map(|p| &p.author)),
         }
     }
 
@@ -109,6 +124,7 @@ impl PullMetricsCalculator {
 
 /// List of PRs without reviews
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UnreviewedPrs {
     /// PRs without any reviews
     pub prs: Vec<UnreviewedPrSummary>,
@@ -118,6 +134,7 @@ pub struct UnreviewedPrs {
 
 /// Summary of an unreviewed PR
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UnreviewedPrSummary {
     /// PR number
     pub number: u64,
@@ -143,6 +160,7 @@ mod tests {
             login: login.to_string(),
             avatar_url: "".to_string(),
             html_url: "".to_string(),
+            is_bot: false,
         }
     }
 
@@ -214,6 +232,46 @@ mod tests {
         assert_eq!(*metrics.by_author.get("bob").unwrap(), 1);
     }
 
+    #[test]
+    fn test_pull_metrics_excludes_bots() {
+        let prs = vec![
+            create_test_pr(1, PullState::Open, false, None, 10, 5, "alice", false),
+            create_test_pr(
+                2,
+                PullState::Open,
+                false,
+                None,
+                2,
+                2,
+                "dependabot[bot]",
+                false,
+            ),
+            create_test_pr(
+                3,
+                PullState::Open,
+                false,
+                None,
+                2,
+                2,
+                "renovate[bot]",
+                false,
+            ),
+        ];
+
+        let bots = BotFilter::new(["*[bot]"]);
+        let metrics = PullMetricsCalculator::calculate_with_bots(&prs, &bots);
+        assert_eq!(metrics.total, 1);
+        assert!(!metrics.by_author.contains_key("dependabot[bot]"));
+        assert!(metrics.bot_activity.is_none());
+
+        let metrics =
+            PullMetricsCalculator::calculate_with_bots(&prs, &bots.with_separate_report(true));
+        assert_eq!(metrics.total, 1);
+        let activity = metrics.bot_activity.unwrap();
+        assert_eq!(activity.total, 2);
+        assert_eq!(activity.by_author["renovate[bot]"], 1);
+    }
+
     #[test]
     fn test_pull_metrics_by_size() {
         let prs = vec![
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 
 /// SLA compliance report
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SlaReport {
     /// Total issues evaluated
     pub total_issues: usize,
@@ -28,6 +29,7 @@ pub struct SlaReport {
 
 /// SLA violation details
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SlaViolation {
     /// Issue number
     pub issue_number: u64,
@@ -45,6 +47,7 @@ pub struct SlaViolation {
 
 /// Type of SLA violation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum ViolationType {
     /// First response time violation
     Response,
@@ -172,6 +175,7 @@ mod tests {
             login: "test".to_string(),
             avatar_url: "".to_string(),
             html_url: "".to_string(),
+            is_bot: false,
         }
     }
 
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 /// Time period for velocity calculations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Period {
     /// Daily
@@ -51,6 +52,7 @@ impl std::str::FromStr for Period {
 
 /// Velocity trend direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Trend {
     /// Issue count is increasing
@@ -74,6 +76,7 @@ impl Trend {
 
 /// Velocity metrics over time
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VelocityMetrics {
     /// Time period used
     pub period: Period,
@@ -89,6 +92,7 @@ pub struct VelocityMetrics {
 
 /// Single velocity data point
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VelocityDataPoint {
     /// Period start date
     pub period_start: DateTime<Utc>,
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 
 /// Type of calendar event
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CalendarEventType {
     /// Issue was created
@@ -24,6 +25,7 @@ pub enum CalendarEventType {
 
 /// A single calendar event
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CalendarEvent {
     /// Unique event identifier
     pub id: String,
@@ -49,6 +51,7 @@ pub struct CalendarEvent {
 
 /// Summary of calendar events by type
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CalendarSummary {
     /// Total number of events
     pub total_events: usize,
@@ -58,6 +61,7 @@ pub struct CalendarSummary {
 
 /// Calendar data for a date range
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CalendarData {
     /// Start of date range
     pub start_date: NaiveDate,
@@ -0,0 +1,272 @@
+//! Issue dependency models and reference parsing
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Reference to an issue in a repository
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IssueRef {
+    /// Repository full name (owner/repo)
+    pub repo: String,
+    /// Issue number
+    pub number: u64,
+}
+
+impl IssueRef {
+    /// Create a new issue reference
+    pub fn new(repo: impl Into<String>, number: u64) -> Self {
+        Self {
+            repo: repo.into(),
+            number,
+        }
+    }
+
+    /// Parse `#N`, `owner/repo#N` or an issue/pull request URL.
+    ///
+    /// `#N` resolves against `default_repo`.
+    pub fn parse(s: &str, default_repo: &str) -> Option<Self> {
+        if s.starts_with("http://") || s.starts_with("https://") {
+            // https://host/owner/repo/issues/N (or /pull/N)
+            let path = s.split("://").nth(1)?;
+            let parts: Vec<&str> = path.split('/').collect();
+            if parts.len() < 5 || !matches!(parts[3], "issues" | "pull") {
+                return None;
+            }
+            let number = parts[4].split(['#', '?']).next()?.parse().ok()?;
+            return Some(Self::new(format!("{}/{}", parts[1], parts[2]), number));
+        }
+
+        let (repo, number) = s.split_once('#')?;
+        let number: u64 = number.parse().ok()?;
+        if repo.is_empty() {
+            return Some(Self::new(default_repo, number));
+        }
+
+        let (owner, name) = repo.split_once('/')?;
+        let valid = |p: &str| {
+            !p.is_empty()
+                && p.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        if !valid(owner) || !valid(name) {
+            return None;
+        }
+        Some(Self::new(repo, number))
+    }
+}
+
+impl fmt::Display for IssueRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.repo, self.number)
+    }
+}
+
+/// Kind of dependency between two issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    /// "blocked by" reference in the issue body
+    BlockedBy,
+    /// "depends on" reference in the issue body
+    DependsOn,
+    /// Tracking issue (task list) tracks the other issue
+    Tracks,
+    /// Parent issue of a sub-issue
+    ParentOf,
+}
+
+impl RelationKind {
+    /// Get display label
+    pub fn label(&self) -> &'static str {
+        match self {
+            RelationKind::BlockedBy => "blocked by",
+            RelationKind::DependsOn => "depends on",
+            RelationKind::Tracks => "tracks",
+            RelationKind::ParentOf => "parent of",
+        }
+    }
+}
+
+/// A directed dependency: `issue` cannot be completed before `depends_on`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IssueRelation {
+    /// Dependent issue
+    pub issue: IssueRef,
+    /// Prerequisite issue
+    pub depends_on: IssueRef,
+    /// How the dependency was declared
+    pub kind: RelationKind,
+}
+
+/// Find "blocked by" / "depends on" references in an issue body.
+///
+/// Each keyword may be followed by a list of references separated by
+/// commas, whitespace or "and", e.g. `Blocked by: #12, acme/api#7 and #15`.
+pub fn parse_dependency_references(body: &str, repo: &str) -> Vec<(RelationKind, IssueRef)> {
+    const KEYWORDS: [(&str, RelationKind); 3] = [
+        ("blocked by", RelationKind::BlockedBy),
+        ("depends on", RelationKind::DependsOn),
+        ("depend on", RelationKind::DependsOn),
+    ];
+
+    let mut refs = Vec::new();
+    for line in body.lines() {
+        // ASCII lowercasing keeps byte offsets aligned with the original line
+        let lower = line.to_ascii_lowercase();
+        let mut pos = 0;
+        while pos < lower.len() {
+            let next = KEYWORDS
+                .iter()
+                .filter_map(|(kw, kind)| lower[pos..].find(kw).map(|i| (pos + i, *kw, *kind)))
+                .min_by_key(|(i, kw, _)| (*i, std::cmp::Reverse(kw.len())));
+            let Some((start, kw, kind)) = next else {
+                break;
+            };
+
+            pos = start + kw.len();
+            let rest = line[pos..].trim_start_matches([':', ' ', '\t']);
+            for token in rest.split([' ', ',', '\t']).filter(|t| !t.is_empty()) {
+                if matches!(token.to_ascii_lowercase().as_str(), "and" | "&" | "or") {
+                    continue;
+                }
+                let token = token.trim_end_matches(['.', ';', ')', ']']);
+                let token = token.trim_start_matches(['(', '[']);
+                match IssueRef::parse(token, repo) {
+                    Some(r) => refs.push((kind, r)),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    refs
+}
+
+/// A `- [ ]` / `- [x]` task list entry in an issue body
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TaskListItem {
+    /// Whether the box is checked
+    pub checked: bool,
+    /// Item text after the checkbox
+    pub text: String,
+    /// Issue the item refers to, when the text starts with a reference
+    pub issue: Option<IssueRef>,
+}
+
+/// Parse task list items from an issue body.
+///
+/// Items inside fenced code blocks are ignored.
+pub fn parse_task_list(body: &str, repo: &str) -> Vec<TaskListItem> {
+    let mut items = Vec::new();
+    let mut in_code = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        let Some(rest) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        else {
+            continue;
+        };
+        let (checked, text) = match rest.get(..3) {
+            Some("[ ]") => (false, &rest[3..]),
+            Some("[x]") | Some("[X]") => (true, &rest[3..]),
+            _ => continue,
+        };
+        let text = text.trim();
+
+        let issue = text
+            .split_whitespace()
+            .next()
+            .and_then(|token| IssueRef::parse(token.trim_end_matches([',', ':']), repo));
+
+        items.push(TaskListItem {
+            checked,
+            text: text.to_string(),
+            issue,
+        });
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_ref_parse() {
+        assert_eq!(
+            IssueRef::parse("#12", "acme/app"),
+            Some(IssueRef::new("acme/app", 12))
+        );
+        assert_eq!(
+            IssueRef::parse("acme/api#7", "acme/app"),
+            Some(IssueRef::new("acme/api", 7))
+        );
+        assert_eq!(
+            IssueRef::parse("https://github.com/acme/api/issues/9", "acme/app"),
+            Some(IssueRef::new("acme/api", 9))
+        );
+        assert_eq!(IssueRef::parse("#abc", "acme/app"), None);
+        assert_eq!(IssueRef::parse("release", "acme/app"), None);
+        assert_eq!(IssueRef::new("acme/app", 3).to_string(), "acme/app#3");
+    }
+
+    #[test]
+    fn test_parse_dependency_references() {
+        let body = "Some context.\n\nBlocked by: #12, acme/api#7 and #15.\nDepends on https://github.com/acme/web/issues/3";
+        let refs = parse_dependency_references(body, "acme/app");
+
+        assert_eq!(
+            refs,
+            vec![
+                (RelationKind::BlockedBy, IssueRef::new("acme/app", 12)),
+                (RelationKind::BlockedBy, IssueRef::new("acme/api", 7)),
+                (RelationKind::BlockedBy, IssueRef::new("acme/app", 15)),
+                (RelationKind::DependsOn, IssueRef::new("acme/web", 3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dependency_references_stops_at_text() {
+        let refs =
+            parse_dependency_references("This is blocked by #4 until the release", "acme/app");
+        assert_eq!(
+            refs,
+            vec![(RelationKind::BlockedBy, IssueRef::new("acme/app", 4))]
+        );
+
+        assert!(parse_dependency_references("blocked by the API team", "acme/app").is_empty());
+    }
+
+    #[test]
+    fn test_parse_task_list() {
+        let body = "## Tasks\n\n- [x] #10\n- [ ] acme/api#4 backend support\n  - [ ] Write docs\n* [X] https://github.com/acme/web/issues/2\n- not a task\n\n```\n- [ ] #99\n```";
+        let items = parse_task_list(body, "acme/app");
+
+        assert_eq!(items.len(), 4);
+        assert!(items[0].checked);
+        assert_eq!(items[0].issue, Some(IssueRef::new("acme/app", 10)));
+        assert!(!items[1].checked);
+        assert_eq!(items[1].issue, Some(IssueRef::new("acme/api", 4)));
+        assert_eq!(items[2].text, "Write docs");
+        assert_eq!(items[2].issue, None);
+        assert!(items[3].checked);
+        assert_eq!(items[3].issue, Some(IssueRef::new("acme/web", 2)));
+    }
+}
@@ -1,11 +1,12 @@
 //! Issue model and related types
 
-use super::User;
+use super::{parse_task_list, TaskListItem, User};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Issue state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum IssueState {
     /// Issue is open
@@ -16,6 +17,7 @@ pub enum IssueState {
 
 /// Issue label
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Label {
     /// Label ID
     pub id: i64,
@@ -29,6 +31,7 @@ pub struct Label {
 
 /// Milestone state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum MilestoneState {
     /// Milestone is open
@@ -39,6 +42,7 @@ pub enum MilestoneState {
 
 /// Milestone
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Milestone {
     /// Milestone ID
     pub id: i64,
@@ -83,6 +87,7 @@ impl Milestone {
 
 /// GitHub issue
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Issue {
     /// Issue ID
     pub id: i64,
@@ -160,10 +165,19 @@ impl Issue {
     pub fn is_assigned(&self) -> bool {
         !self.assignees.is_empty()
     }
+
+    /// Parse the task list in the issue body (`#N` resolves against `repo`)
+    pub fn task_list(&self, repo: &str) -> Vec<TaskListItem> {
+        self.body
+            .as_deref()
+            .map(|body| parse_task_list(body, repo))
+            .unwrap_or_default()
+    }
 }
 
 /// Issue timeline event
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct IssueEvent {
     /// Event ID
     pub id: i64,
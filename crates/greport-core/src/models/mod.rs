@@ -1,6 +1,7 @@
 //! Data models for greport
 
 mod calendar;
+mod dependency;
 mod issue;
 mod project;
 mod pull_request;
@@ -10,6 +11,7 @@ mod repository;
 mod user;
 
 pub use calendar::*;
+pub use dependency::*;
 pub use issue::*;
 pub use project::*;
 pub use pull_request::*;
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// A GitHub Project (V2).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Project {
     /// GitHub GraphQL node ID (opaque string like "PVT_kwDO...")
     pub node_id: String,
@@ -32,6 +33,7 @@ pub struct Project {
 
 /// A field definition on a project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProjectField {
     /// GraphQL node ID
     pub node_id: String,
@@ -43,6 +45,7 @@ pub struct ProjectField {
 
 /// Discriminated field type.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ProjectFieldType {
     /// Plain text field
@@ -61,6 +64,7 @@ pub enum ProjectFieldType {
 
 /// A single-select option.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SelectOption {
     /// Option ID
     pub id: String,
@@ -74,6 +78,7 @@ pub struct SelectOption {
 
 /// An iteration/sprint value.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct IterationValue {
     /// Iteration ID
     pub id: String,
@@ -87,6 +92,7 @@ pub struct IterationValue {
 
 /// An item on a project board.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProjectItem {
     /// GraphQL node ID
     pub node_id: String,
@@ -102,6 +108,7 @@ pub struct ProjectItem {
 
 /// The content linked to a project item (union type).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ProjectItemContent {
     /// Linked issue
@@ -134,6 +141,7 @@ pub enum ProjectItemContent {
 
 /// Label summary for project items.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LabelInfo {
     /// Label name
     pub name: String,
@@ -143,6 +151,7 @@ pub struct LabelInfo {
 
 /// A field value on a project item.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProjectFieldValue {
     /// The field name this value belongs to
     pub field_name: String,
@@ -152,6 +161,7 @@ pub struct ProjectFieldValue {
 
 /// Typed field value (mirrors GraphQL union).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FieldValue {
     /// Text value
@@ -172,3 +182,493 @@ pub enum FieldValue {
     /// No value set
     Empty,
 }
+
+/// A new value to write to a project item field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FieldUpdate {
+    /// Set a text value
+    Text { value: String },
+    /// Set a numeric value
+    Number { value: f64 },
+    /// Set a date value (YYYY-MM-DD)
+    Date { value: String },
+    /// Select a single-select option by ID
+    SingleSelect { option_id: String },
+    /// Assign an iteration by ID
+    Iteration { iteration_id: String },
+    /// Clear the field
+    Clear,
+}
+
+impl Project {
+    /// Look up a field definition by name (case-insensitive).
+    pub fn field(&self, name: &str) -> Option<&ProjectField> {
+        self.fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Resolve a human-readable value (option name, iteration title, number,
+    /// date) into an update for the named field. An empty value clears it.
+    pub fn resolve_field_update(
+        &self,
+        field_name: &str,
+        value: &str,
+    ) -> crate::Result<(&ProjectField, FieldUpdate)> {
+        let field = self.field(field_name).ok_or_else(|| {
+            crate::Error::NotFound(format!(
+                "Field '{}' not found in project #{}",
+                field_name, self.number
+            ))
+        })?;
+
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok((field, FieldUpdate::Clear));
+        }
+
+        let update = match &field.field_type {
+            ProjectFieldType::Text => FieldUpdate::Text {
+                value: value.to_string(),
+            },
+            ProjectFieldType::Number => FieldUpdate::Number {
+                value: value.parse().map_err(|_| {
+                    crate::Error::custom(format!(
+                        "Field '{}' expects a number, got '{}'",
+                        field.name, value
+                    ))
+                })?,
+            },
+            ProjectFieldType::Date => {
+                chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                    crate::Error::custom(format!(
+                        "Field '{}' expects a date (YYYY-MM-DD), got '{}'",
+                        field.name, value
+                    ))
+                })?;
+                FieldUpdate::Date {
+                    value: value.to_string(),
+                }
+            }
+            ProjectFieldType::SingleSelect { options } => {
+                let option = options
+                    .iter()
+                    .find(|o| o.name.eq_ignore_ascii_case(value))
+                    .ok_or_else(|| {
+                        crate::Error::NotFound(format!(
+                            "Option '{}' not found for field '{}'",
+                            value, field.name
+                        ))
+                    })?;
+                FieldUpdate::SingleSelect {
+                    option_id: option.id.clone(),
+                }
+            }
+            ProjectFieldType::Iteration { iterations } => {
+                let iteration = iterations
+                    .iter()
+                    .find(|i| i.title.eq_ignore_ascii_case(value))
+                    .ok_or_else(|| {
+                        crate::Error::NotFound(format!(
+                            "Iteration '{}' not found for field '{}'",
+                            value, field.name
+                        ))
+                    })?;
+                FieldUpdate::Iteration {
+                    iteration_id: iteration.id.clone(),
+                }
+            }
+            ProjectFieldType::BuiltIn => {
+                return Err(crate::Error::custom(format!(
+                    "Field '{}' is built-in and cannot be updated",
+                    field.name
+                )))
+            }
+        };
+
+        Ok((field, update))
+    }
+}
+
+impl ProjectFieldType {
+    /// Short label for display (e.g. "single_select").
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectFieldType::Text => "text",
+            ProjectFieldType::Number => "number",
+            ProjectFieldType::Date => "date",
+            ProjectFieldType::SingleSelect { .. } => "single_select",
+            ProjectFieldType::Iteration { .. } => "iteration",
+            ProjectFieldType::BuiltIn => "built_in",
+        }
+    }
+}
+
+impl FieldValue {
+    /// Human-readable value, or `None` when the field is empty.
+    pub fn display(&self) -> Option<String> {
+        match self {
+            FieldValue::Text { value } => Some(value.clone()),
+            FieldValue::Number { value } => Some(value.to_string()),
+            FieldValue::Date { value } => Some(value.clone()),
+            FieldValue::SingleSelect { name, .. } => Some(name.clone()),
+            FieldValue::Iteration { title, .. } => Some(title.clone()),
+            FieldValue::Empty => None,
+        }
+    }
+}
+
+impl ProjectItem {
+    /// Look up a field value by field name (case-insensitive).
+    pub fn field_value(&self, field_name: &str) -> Option<&FieldValue> {
+        self.field_values
+            .iter()
+            .find(|fv| fv.field_name.eq_ignore_ascii_case(field_name))
+            .map(|fv| &fv.value)
+    }
+
+    /// Display value of a field by name (case-insensitive).
+    pub fn field_display(&self, field_name: &str) -> Option<String> {
+        self.field_value(field_name).and_then(FieldValue::display)
+    }
+
+    /// Value of the built-in "Status" single-select field.
+    pub fn status(&self) -> Option<String> {
+        self.field_display("Status")
+    }
+
+    /// Title of the first iteration value on the item, if any.
+    pub fn iteration(&self) -> Option<&str> {
+        self.field_values.iter().find_map(|fv| match &fv.value {
+            FieldValue::Iteration { title, .. } => Some(title.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Content type label ("issue", "pull_request" or "draft_issue").
+    pub fn content_type(&self) -> &'static str {
+        match self.content {
+            ProjectItemContent::Issue { .. } => "issue",
+            ProjectItemContent::PullRequest { .. } => "pull_request",
+            ProjectItemContent::DraftIssue { .. } => "draft_issue",
+        }
+    }
+
+    /// Title of the linked content.
+    pub fn title(&self) -> &str {
+        match &self.content {
+            ProjectItemContent::Issue { title, .. }
+            | ProjectItemContent::PullRequest { title, .. }
+            | ProjectItemContent::DraftIssue { title, .. } => title,
+        }
+    }
+
+    /// Issue/PR number of the linked content (None for drafts).
+    pub fn number(&self) -> Option<u64> {
+        match &self.content {
+            ProjectItemContent::Issue { number, .. }
+            | ProjectItemContent::PullRequest { number, .. } => Some(*number),
+            ProjectItemContent::DraftIssue { .. } => None,
+        }
+    }
+
+    /// Repository (owner/repo) of the linked content (None for drafts).
+    pub fn repository(&self) -> Option<&str> {
+        match &self.content {
+            ProjectItemContent::Issue { repository, .. }
+            | ProjectItemContent::PullRequest { repository, .. } => Some(repository),
+            ProjectItemContent::DraftIssue { .. } => None,
+        }
+    }
+
+    /// Short reference: "owner/repo#number", or the node ID for drafts.
+    pub fn reference(&self) -> String {
+        match (self.repository(), self.number()) {
+            (Some(repo), Some(number)) => format!("{}#{}", repo, number),
+            _ => self.node_id.clone(),
+        }
+    }
+
+    /// State of the linked content (None for drafts).
+    pub fn state(&self) -> Option<&str> {
+        match &self.content {
+            ProjectItemContent::Issue { state, .. }
+            | ProjectItemContent::PullRequest { state, .. } => Some(state),
+            ProjectItemContent::DraftIssue { .. } => None,
+        }
+    }
+
+    /// Assignee logins. Pull requests report their author.
+    pub fn assignees(&self) -> Vec<&str> {
+        match &self.content {
+            ProjectItemContent::Issue { assignees, .. }
+            | ProjectItemContent::DraftIssue { assignees, .. } => {
+                assignees.iter().map(String::as_str).collect()
+            }
+            ProjectItemContent::PullRequest { author, .. } => vec![author.as_str()],
+        }
+    }
+}
+
+/// Filters applied to project items.
+///
+/// All comparisons are case-insensitive. An unset filter matches every item.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectItemFilter {
+    /// Value of the status field
+    pub status: Option<String>,
+    /// Single-select field `status` is matched against ("Status" when unset)
+    pub status_field: Option<String>,
+    /// Iteration title (matched against any iteration field)
+    pub iteration: Option<String>,
+    /// Assignee login
+    pub assignee: Option<String>,
+    /// Custom field matches as (field name, value) pairs
+    pub fields: Vec<(String, String)>,
+}
+
+impl ProjectItemFilter {
+    /// Parse a `Field=Value` expression into a field filter pair.
+    pub fn parse_field(expr: &str) -> crate::Result<(String, String)> {
+        match expr.split_once('=') {
+            Some((field, value)) if !field.trim().is_empty() => {
+                Ok((field.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(crate::Error::Custom(format!(
+                "Invalid field filter '{}': expected Field=Value",
+                expr
+            ))),
+        }
+    }
+
+    /// Check whether an item passes every configured filter.
+    pub fn matches(&self, item: &ProjectItem) -> bool {
+        if let Some(ref status) = self.status {
+            let field = self.status_field.as_deref().unwrap_or("Status");
+            match item.field_display(field) {
+                Some(s) if s.eq_ignore_ascii_case(status) => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(ref iteration) = self.iteration {
+            let in_iteration = item.field_values.iter().any(|fv| {
+                matches!(&fv.value, FieldValue::Iteration { title, .. } if title.eq_ignore_ascii_case(iteration))
+            });
+            if !in_iteration {
+                return false;
+            }
+        }
+
+        if let Some(ref assignee) = self.assignee {
+            if !item
+                .assignees()
+                .iter()
+                .any(|a| a.eq_ignore_ascii_case(assignee))
+            {
+                return false;
+            }
+        }
+
+        self.fields.iter().all(|(field, value)| {
+            item.field_display(field)
+                .is_some_and(|v| v.eq_ignore_ascii_case(value))
+        })
+    }
+
+    /// Return the items that pass the filter.
+    pub fn apply<'a>(&self, items: &'a [ProjectItem]) -> Vec<&'a ProjectItem> {
+        items.iter().filter(|i| self.matches(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_item(status: &str, sprint: &str, assignees: &[&str], priority: &str) -> ProjectItem {
+        let now = Utc::now();
+        ProjectItem {
+            node_id: "PVTI_1".to_string(),
+            content: ProjectItemContent::Issue {
+                number: 1,
+                title: "Test issue".to_string(),
+                state: "OPEN".to_string(),
+                url: "https://github.com/acme/repo/issues/1".to_string(),
+                repository: "acme/repo".to_string(),
+                assignees: assignees.iter().map(|a| a.to_string()).collect(),
+                labels: vec![],
+            },
+            field_values: vec![
+                ProjectFieldValue {
+                    field_name: "Status".to_string(),
+                    value: FieldValue::SingleSelect {
+                        name: status.to_string(),
+                        option_id: "opt".to_string(),
+                    },
+                },
+                ProjectFieldValue {
+                    field_name: "Sprint".to_string(),
+                    value: FieldValue::Iteration {
+                        title: sprint.to_string(),
+                        start_date: "2025-01-06".to_string(),
+                        duration: 14,
+                        iteration_id: "it".to_string(),
+                    },
+                },
+                ProjectFieldValue {
+                    field_name: "Priority".to_string(),
+                    value: FieldValue::SingleSelect {
+                        name: priority.to_string(),
+                        option_id: "p".to_string(),
+                    },
+                },
+            ],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_item_accessors() {
+        let item = make_item("In Progress", "Sprint 1", &["alice"], "High");
+        assert_eq!(item.status().as_deref(), Some("In Progress"));
+        assert_eq!(item.field_display("priority").as_deref(), Some("High"));
+        assert_eq!(item.content_type(), "issue");
+        assert_eq!(item.number(), Some(1));
+        assert_eq!(item.repository(), Some("acme/repo"));
+        assert_eq!(item.assignees(), vec!["alice"]);
+    }
+
+    #[test]
+    fn test_filter_default_matches_all() {
+        let item = make_item("Todo", "Sprint 1", &[], "Low");
+        assert!(ProjectItemFilter::default().matches(&item));
+    }
+
+    #[test]
+    fn test_filter_status_iteration_assignee() {
+        let item = make_item("Done", "Sprint 2", &["bob"], "High");
+
+        let filter = ProjectItemFilter {
+            status: Some("done".to_string()),
+            iteration: Some("sprint 2".to_string()),
+            assignee: Some("BOB".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&item));
+
+        let filter = ProjectItemFilter {
+            status: Some("Todo".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&item));
+
+        let filter = ProjectItemFilter {
+            assignee: Some("alice".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&item));
+    }
+
+    #[test]
+    fn test_filter_status_field() {
+        // "Priority" stands in for a board whose status field is renamed
+        let item = make_item("Todo", "Sprint 1", &[], "Blocked");
+        let filter = ProjectItemFilter {
+            status: Some("blocked".to_string()),
+            status_field: Some("Priority".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&item));
+
+        let filter = ProjectItemFilter {
+            status: Some("todo".to_string()),
+            status_field: Some("Priority".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&item));
+    }
+
+    #[test]
+    fn test_filter_custom_fields() {
+        let items = vec![
+            make_item("Todo", "Sprint 1", &[], "High"),
+            make_item("Todo", "Sprint 1", &[], "Low"),
+        ];
+        let filter = ProjectItemFilter {
+            fields: vec![ProjectItemFilter::parse_field("Priority=high").unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(filter.apply(&items).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_field_invalid() {
+        assert!(ProjectItemFilter::parse_field("Priority").is_err());
+        assert!(ProjectItemFilter::parse_field("=High").is_err());
+    }
+
+    fn make_project() -> Project {
+        let now = Utc::now();
+        let field = |name: &str, field_type| ProjectField {
+            node_id: format!("F_{}", name),
+            name: name.to_string(),
+            field_type,
+        };
+        Project {
+            node_id: "PVT_1".to_string(),
+            number: 1,
+            title: "Board".to_string(),
+            description: None,
+            url: String::new(),
+            closed: false,
+            owner: "acme".to_string(),
+            created_at: now,
+            updated_at: now,
+            fields: vec![
+                field(
+                    "Status",
+                    ProjectFieldType::SingleSelect {
+                        options: vec![SelectOption {
+                            id: "opt_done".to_string(),
+                            name: "Done".to_string(),
+                            color: None,
+                            description: None,
+                        }],
+                    },
+                ),
+                field("Estimate", ProjectFieldType::Number),
+                field("Title", ProjectFieldType::BuiltIn),
+            ],
+            total_items: 0,
+        }
+    }
+
+    #[test]
+    fn test_resolve_field_update() {
+        let project = make_project();
+
+        let (field, update) = project.resolve_field_update("status", "done").unwrap();
+        assert_eq!(field.node_id, "F_Status");
+        assert_eq!(
+            update,
+            FieldUpdate::SingleSelect {
+                option_id: "opt_done".to_string()
+            }
+        );
+
+        let (_, update) = project.resolve_field_update("Estimate", "3").unwrap();
+        assert_eq!(update, FieldUpdate::Number { value: 3.0 });
+
+        let (_, update) = project.resolve_field_update("Status", "").unwrap();
+        assert_eq!(update, FieldUpdate::Clear);
+
+        assert!(project.resolve_field_update("Status", "Blocked").is_err());
+        assert!(project.resolve_field_update("Estimate", "lots").is_err());
+        assert!(project.resolve_field_update("Title", "x").is_err());
+        assert!(project.resolve_field_update("Missing", "x").is_err());
+    }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 /// Pull request state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum PullState {
     /// PR is open
@@ -16,6 +17,7 @@ pub enum PullState {
 
 /// Pull request size category
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum PrSize {
     /// 0-10 lines
     XSmall,
@@ -44,6 +46,7 @@ impl PrSize {
 
 /// GitHub pull request
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PullRequest {
     /// PR ID
     pub id: i64,
@@ -122,6 +125,7 @@ impl PullRequest {
 
 /// Pull request review
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Review {
     /// Review ID
     pub id: i64,
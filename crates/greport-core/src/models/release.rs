@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 /// GitHub release
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Release {
     /// Release ID
     pub id: i64,
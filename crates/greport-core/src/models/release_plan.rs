@@ -7,6 +7,7 @@ use super::{Milestone, Release};
 
 /// Status of an upcoming release milestone
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReleasePlanStatus {
     /// Milestone is progressing normally
@@ -19,6 +20,7 @@ pub enum ReleasePlanStatus {
 
 /// An upcoming release (open milestone with due date)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UpcomingRelease {
     /// The milestone
     pub milestone: Milestone,
@@ -36,6 +38,7 @@ pub struct UpcomingRelease {
 
 /// A recently published release
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RecentRelease {
     /// The release
     pub release: Release,
@@ -47,6 +50,7 @@ pub struct RecentRelease {
 
 /// A timeline entry (milestone or release plotted on timeline)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TimelineEntry {
     /// Date of the event
     pub date: DateTime<Utc>,
@@ -64,6 +68,7 @@ pub struct TimelineEntry {
 
 /// Complete release plan data
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReleasePlan {
     /// Upcoming milestones sorted by due date
     pub upcoming: Vec<UpcomingRelease>,
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// GitHub repository
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Repository {
     /// Repository ID
     pub id: i64,
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// GitHub user
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct User {
     /// User ID
     pub id: i64,
@@ -13,6 +14,9 @@ pub struct User {
     pub avatar_url: String,
     /// Profile URL
     pub html_url: String,
+    /// Whether GitHub reports the account as `type: Bot` (a GitHub App)
+    #[serde(default)]
+    pub is_bot: bool,
 }
 
 impl User {
@@ -23,6 +27,7 @@ impl User {
             login: "unknown".to_string(),
             avatar_url: String::new(),
             html_url: String::new(),
+            is_bot: false,
         }
     }
 }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 /// Burndown chart report
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BurndownReport {
     /// Milestone name
     pub milestone: String,
@@ -25,6 +26,7 @@ pub struct BurndownReport {
 
 /// Single burndown data point
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BurndownDataPoint {
     /// Date
     pub date: DateTime<Utc>,
@@ -141,6 +143,7 @@ impl BurndownCalculator {
 
 /// Burnup chart report (inverse of burndown)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BurnupReport {
     /// Milestone name
     pub milestone: String,
@@ -156,6 +159,7 @@ pub struct BurnupReport {
 
 /// Scope data point for burnup chart
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ScopeDataPoint {
     /// Date
     pub date: DateTime<Utc>,
@@ -227,6 +231,7 @@ mod tests {
             login: "test".to_string(),
             avatar_url: "".to_string(),
             html_url: "".to_string(),
+            is_bot: false,
         }
     }
 
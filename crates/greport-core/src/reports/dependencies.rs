@@ -0,0 +1,605 @@
+//! Cross-repository issue dependency graph
+
+use crate::models::{parse_dependency_references, Issue, IssueRef, IssueRelation, IssueState};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// An issue in the dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DependencyNode {
+    /// Issue reference
+    pub issue: IssueRef,
+    /// Issue title (empty when the issue was not fetched)
+    pub title: String,
+    /// Whether the issue is open. Issues that were not fetched count as open.
+    pub open: bool,
+    /// Milestone title
+    pub milestone: Option<String>,
+    /// Whether the issue was fetched from a tracked repository
+    pub known: bool,
+}
+
+/// An open issue blocking other open work
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BlockerSummary {
+    /// The blocking issue
+    pub node: DependencyNode,
+    /// Open issues waiting on it
+    pub blocks: Vec<IssueRef>,
+}
+
+/// Dependency report for a milestone (or all open issues)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DependencyReport {
+    /// Milestone the report is scoped to
+    pub milestone: Option<String>,
+    /// Number of issues in the graph
+    pub total_issues: usize,
+    /// Number of dependencies in the graph
+    pub total_relations: usize,
+    /// Dependency cycles
+    pub cycles: Vec<Vec<IssueRef>>,
+    /// Longest chain of open dependencies, first prerequisite first
+    pub critical_path: Vec<DependencyNode>,
+    /// Open blockers, most blocking first
+    pub open_blockers: Vec<BlockerSummary>,
+}
+
+/// Directed graph of issue dependencies across repositories
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    nodes: BTreeMap<IssueRef, DependencyNode>,
+    edges: Vec<IssueRelation>,
+    seen: HashSet<(IssueRef, IssueRef)>,
+}
+
+impl DependencyGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a repository's issues and the dependencies declared in their bodies
+    pub fn add_issues(&mut self, repo: &str, issues: &[Issue]) {
+        for issue in issues {
+            let node = IssueRef::new(repo, issue.number);
+            self.nodes.insert(
+                node.clone(),
+                DependencyNode {
+                    issue: node.clone(),
+                    title: issue.title.clone(),
+                    open: issue.state == IssueState::Open,
+                    milestone: issue.milestone.as_ref().map(|m| m.title.clone()),
+                    known: true,
+                },
+            );
+
+            let body = issue.body.as_deref().unwrap_or_default();
+            for (kind, target) in parse_dependency_references(body, repo) {
+                self.add_relation(IssueRelation {
+                    issue: node.clone(),
+                    depends_on: target,
+                    kind,
+                });
+            }
+        }
+    }
+
+    /// Add relations (e.g. tracked issues and sub-issues)
+    pub fn add_relations(&mut self, relations: impl IntoIterator<Item = IssueRelation>) {
+        for relation in relations {
+            self.add_relation(relation);
+        }
+    }
+
+    fn add_relation(&mut self, relation: IssueRelation) {
+        if relation.issue == relation.depends_on {
+            return;
+        }
+        if self
+            .seen
+            .insert((relation.issue.clone(), relation.depends_on.clone()))
+        {
+            self.edges.push(relation);
+        }
+    }
+
+    /// All nodes, including referenced issues that were not fetched
+    pub fn nodes(&self) -> Vec<DependencyNode> {
+        let mut nodes = self.nodes.clone();
+        for edge in &self.edges {
+            for r in [&edge.issue, &edge.depends_on] {
+                nodes.entry(r.clone()).or_insert_with(|| DependencyNode {
+                    issue: r.clone(),
+                    title: String::new(),
+                    open: true,
+                    milestone: None,
+                    known: false,
+                });
+            }
+        }
+        nodes.into_values().collect()
+    }
+
+    /// All dependencies
+    pub fn edges(&self) -> &[IssueRelation] {
+        &self.edges
+    }
+
+    fn node(&self, r: &IssueRef) -> DependencyNode {
+        self.nodes
+            .get(r)
+            .cloned()
+            .unwrap_or_else(|| DependencyNode {
+                issue: r.clone(),
+                title: String::new(),
+                open: true,
+                milestone: None,
+                known: false,
+            })
+    }
+
+    fn is_open(&self, r: &IssueRef) -> bool {
+        self.nodes.get(r).is_none_or(|n| n.open)
+    }
+
+    /// Prerequisites of each issue
+    fn adjacency(&self) -> HashMap<&IssueRef, Vec<&IssueRef>> {
+        let mut adj: HashMap<&IssueRef, Vec<&IssueRef>> = HashMap::new();
+        for edge in &self.edges {
+            adj.entry(&edge.issue).or_default().push(&edge.depends_on);
+        }
+        adj
+    }
+
+    /// Find dependency cycles (strongly connected components with more than
+    /// one issue). Self-references are dropped when the graph is built.
+    pub fn cycles(&self) -> Vec<Vec<IssueRef>> {
+        let adj = self.adjacency();
+        let nodes: BTreeSet<&IssueRef> = self
+            .edges
+            .iter()
+            .flat_map(|e| [&e.issue, &e.depends_on])
+            .collect();
+
+        // Tarjan's algorithm
+        struct State<'a> {
+            index: usize,
+            indices: HashMap<&'a IssueRef, usize>,
+            lowlink: HashMap<&'a IssueRef, usize>,
+            stack: Vec<&'a IssueRef>,
+            on_stack: HashSet<&'a IssueRef>,
+            components: Vec<Vec<IssueRef>>,
+        }
+
+        fn connect<'a>(
+            v: &'a IssueRef,
+            adj: &HashMap<&'a IssueRef, Vec<&'a IssueRef>>,
+            st: &mut State<'a>,
+        ) {
+            st.indices.insert(v, st.index);
+            st.lowlink.insert(v, st.index);
+            st.index += 1;
+            st.stack.push(v);
+            st.on_stack.insert(v);
+
+            for &w in adj.get(v).map(Vec::as_slice).unwrap_or_default() {
+                if !st.indices.contains_key(w) {
+                    connect(w, adj, st);
+                    let low = st.lowlink[v].min(st.lowlink[w]);
+                    st.lowlink.insert(v, low);
+                } else if st.on_stack.contains(w) {
+                    let low = st.lowlink[v].min(st.indices[w]);
+                    st.lowlink.insert(v, low);
+                }
+            }
+
+            if st.lowlink[v] == st.indices[v] {
+                let mut component = Vec::new();
+                while let Some(w) = st.stack.pop() {
+                    st.on_stack.remove(w);
+                    component.push(w.clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort();
+                    st.components.push(component);
+                }
+            }
+        }
+
+        let mut st = State {
+            index: 0,
+            indices: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        for v in nodes {
+            if !st.indices.contains_key(v) {
+                connect(v, &adj, &mut st);
+            }
+        }
+
+        st.components.sort();
+        st.components
+    }
+
+    /// Build a report scoped to open issues in `milestone` (or all open
+    /// issues when `None`) and everything they transitively depend on.
+    pub fn report(&self, milestone: Option<&str>) -> DependencyReport {
+        let adj = self.adjacency();
+        let cycles = self.cycles();
+        let cycle_of: HashMap<&IssueRef, usize> = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, c)| c.iter().map(move |r| (r, i)))
+            .collect();
+
+        let roots: Vec<&IssueRef> = self
+            .nodes
+            .values()
+            .filter(|n| n.open)
+            .filter(|n| match milestone {
+                Some(m) => n
+                    .milestone
+                    .as_deref()
+                    .is_some_and(|t| t.eq_ignore_ascii_case(m)),
+                None => true,
+            })
+            .map(|n| &n.issue)
+            .collect();
+
+        // Open issues in scope: roots plus their open prerequisites
+        let mut scope: HashSet<&IssueRef> = HashSet::new();
+        let mut stack = roots.clone();
+        while let Some(r) = stack.pop() {
+            if !scope.insert(r) {
+                continue;
+            }
+            for &dep in adj.get(r).map(Vec::as_slice).unwrap_or_default() {
+                if self.is_open(dep) {
+                    stack.push(dep);
+                }
+            }
+        }
+
+        // Open blockers: open prerequisites of open issues in scope
+        let mut blocks: BTreeMap<&IssueRef, BTreeSet<IssueRef>> = BTreeMap::new();
+        for edge in &self.edges {
+            if scope.contains(&edge.issue) && self.is_open(&edge.depends_on) {
+                blocks
+                    .entry(&edge.depends_on)
+                    .or_default()
+                    .insert(edge.issue.clone());
+            }
+        }
+        let mut open_blockers: Vec<BlockerSummary> = blocks
+            .into_iter()
+            .map(|(r, b)| BlockerSummary {
+                node: self.node(r),
+                blocks: b.into_iter().collect(),
+            })
+            .collect();
+        open_blockers.sort_by(|a, b| {
+            b.blocks
+                .len()
+                .cmp(&a.blocks.len())
+                .then_with(|| a.node.issue.cmp(&b.node.issue))
+        });
+
+        // Critical path: longest chain of open dependencies from a root,
+        // ignoring edges inside cycles.
+        let mut memo: HashMap<&IssueRef, Vec<&IssueRef>> = HashMap::new();
+        fn longest<'a>(
+            r: &'a IssueRef,
+            graph: &DependencyGraph,
+            adj: &HashMap<&'a IssueRef, Vec<&'a IssueRef>>,
+            cycle_of: &HashMap<&IssueRef, usize>,
+            memo: &mut HashMap<&'a IssueRef, Vec<&'a IssueRef>>,
+        ) -> Vec<&'a IssueRef> {
+            if let Some(path) = memo.get(r) {
+                return path.clone();
+            }
+            let mut best: Vec<&IssueRef> = Vec::new();
+            for &dep in adj.get(r).map(Vec::as_slice).unwrap_or_default() {
+                let same_cycle = cycle_of.get(r).is_some() && cycle_of.get(r) == cycle_of.get(dep);
+                if !graph.is_open(dep) || same_cycle {
+                    continue;
+                }
+                let path = longest(dep, graph, adj, cycle_of, memo);
+                if path.len() > best.len() {
+                    best = path;
+                }
+            }
+            best.push(r);
+            memo.insert(r, best.clone());
+            best
+        }
+
+        let mut critical: Vec<&IssueRef> = Vec::new();
+        let mut sorted_roots = roots;
+        sorted_roots.sort();
+        for root in sorted_roots {
+            let path = longest(root, self, &adj, &cycle_of, &mut memo);
+            if path.len() > critical.len() {
+                critical = path;
+            }
+        }
+        let critical_path = if critical.len() > 1 {
+            critical.into_iter().map(|r| self.node(r)).collect()
+        } else {
+            Vec::new()
+        };
+
+        DependencyReport {
+            milestone: milestone.map(String::from),
+            total_issues: self.nodes().len(),
+            total_relations: self.edges.len(),
+            cycles,
+            critical_path,
+            open_blockers,
+        }
+    }
+
+    /// Nodes and edges that touch at least one dependency
+    fn connected_nodes(&self) -> Vec<DependencyNode> {
+        let connected: HashSet<&IssueRef> = self
+            .edges
+            .iter()
+            .flat_map(|e| [&e.issue, &e.depends_on])
+            .collect();
+        self.nodes()
+            .into_iter()
+            .filter(|n| connected.contains(&n.issue))
+            .collect()
+    }
+
+    /// Export as Graphviz DOT. Edges point from an issue to its prerequisite.
+    pub fn to_dot(&self) -> String {
+        let in_cycle: HashSet<IssueRef> = self.cycles().into_iter().flatten().collect();
+        let mut out =
+            String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for node in self.connected_nodes() {
+            let label = if node.title.is_empty() {
+                node.issue.to_string()
+            } else {
+                format!("{}\\n{}", node.issue, node.title)
+            };
+            let mut attrs = vec![format!("label=\"{}\"", dot_escape(&label))];
+            if !node.open {
+                attrs.push("style=dashed".to_string());
+                attrs.push("color=gray".to_string());
+            }
+            if !node.known {
+                attrs.push("style=dotted".to_string());
+            }
+            if in_cycle.contains(&node.issue) {
+                attrs.push("color=red".to_string());
+            }
+            let _ = writeln!(out, "    \"{}\" [{}];", node.issue, attrs.join(", "));
+        }
+
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                edge.issue,
+                edge.depends_on,
+                edge.kind.label()
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Export as a Mermaid flowchart. Edges point from an issue to its prerequisite.
+    pub fn to_mermaid(&self) -> String {
+        let nodes = self.connected_nodes();
+        let ids: HashMap<&IssueRef, String> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (&n.issue, format!("n{}", i)))
+            .collect();
+        let in_cycle: HashSet<IssueRef> = self.cycles().into_iter().flatten().collect();
+
+        let mut out = String::from("graph LR\n");
+        for node in &nodes {
+            let label = if node.title.is_empty() {
+                node.issue.to_string()
+            } else {
+                format!("{}: {}", node.issue, node.title)
+            };
+            let _ = writeln!(
+                out,
+                "    {}[\"{}\"]",
+                ids[&node.issue],
+                mermaid_escape(&label)
+            );
+        }
+
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    {} -->|{}| {}",
+                ids[&edge.issue],
+                edge.kind.label(),
+                ids[&edge.depends_on]
+            );
+        }
+
+        let closed: Vec<&str> = nodes
+            .iter()
+            .filter(|n| !n.open)
+            .map(|n| ids[&n.issue].as_str())
+            .collect();
+        if !closed.is_empty() {
+            out.push_str("    classDef closed fill:#eee,stroke:#999,color:#999\n");
+            let _ = writeln!(out, "    class {} closed", closed.join(","));
+        }
+
+        let cyclic: Vec<&str> = nodes
+            .iter()
+            .filter(|n| in_cycle.contains(&n.issue))
+            .map(|n| ids[&n.issue].as_str())
+            .collect();
+        if !cyclic.is_empty() {
+            out.push_str("    classDef cycle stroke:#d00,stroke-width:2px\n");
+            let _ = writeln!(out, "    class {} cycle", cyclic.join(","));
+        }
+
+        out
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('"', "\\\"")
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('#', "#35;").replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Milestone, MilestoneState, RelationKind, User};
+    use chrono::Utc;
+
+    fn create_test_issue(number: u64, state: IssueState, body: &str, milestone: bool) -> Issue {
+        let now = Utc::now();
+        Issue {
+            id: number as i64,
+            number,
+            title: format!("Issue {}", number),
+            body: Some(body.to_string()),
+            state,
+            labels: vec![],
+            assignees: vec![],
+            milestone: milestone.then(|| Milestone {
+                id: 1,
+                number: 1,
+                title: "v1.0".to_string(),
+                description: None,
+                state: MilestoneState::Open,
+                open_issues: 0,
+                closed_issues: 0,
+                due_on: None,
+                created_at: now,
+                closed_at: None,
+            }),
+            author: User::unknown(),
+            comments_count: 0,
+            created_at: now,
+            updated_at: now,
+            closed_at: None,
+            closed_by: None,
+        }
+    }
+
+    fn create_test_graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        graph.add_issues(
+            "acme/app",
+            &[
+                create_test_issue(1, IssueState::Open, "Blocked by #2", true),
+                create_test_issue(2, IssueState::Open, "Depends on acme/api#5", false),
+                create_test_issue(3, IssueState::Closed, "", false),
+                create_test_issue(4, IssueState::Open, "Blocked by #3", true),
+            ],
+        );
+        graph.add_issues(
+            "acme/api",
+            &[create_test_issue(5, IssueState::Open, "", false)],
+        );
+        graph
+    }
+
+    #[test]
+    fn test_dependency_report() {
+        let report = create_test_graph().report(Some("v1.0"));
+
+        assert_eq!(report.total_issues, 5);
+        assert_eq!(report.total_relations, 3);
+        assert!(report.cycles.is_empty());
+
+        let path: Vec<String> = report
+            .critical_path
+            .iter()
+            .map(|n| n.issue.to_string())
+            .collect();
+        assert_eq!(path, vec!["acme/api#5", "acme/app#2", "acme/app#1"]);
+
+        // #3 is closed, so it does not block #4
+        let blockers: Vec<String> = report
+            .open_blockers
+            .iter()
+            .map(|b| b.node.issue.to_string())
+            .collect();
+        assert_eq!(blockers, vec!["acme/api#5", "acme/app#2"]);
+    }
+
+    #[test]
+    fn test_dependency_cycles() {
+        let mut graph = DependencyGraph::new();
+        graph.add_issues(
+            "acme/app",
+            &[
+                create_test_issue(1, IssueState::Open, "Blocked by #2", false),
+                create_test_issue(2, IssueState::Open, "Blocked by #3", false),
+                create_test_issue(3, IssueState::Open, "Blocked by #1", false),
+                create_test_issue(4, IssueState::Open, "Blocked by #1", false),
+            ],
+        );
+
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 3);
+
+        // Critical path stops at the cycle instead of looping
+        let report = graph.report(None);
+        assert!(report.critical_path.len() <= 3);
+    }
+
+    #[test]
+    fn test_relations_and_unknown_nodes() {
+        let mut graph = DependencyGraph::new();
+        graph.add_issues(
+            "acme/app",
+            &[create_test_issue(1, IssueState::Open, "", false)],
+        );
+        graph.add_relations([IssueRelation {
+            issue: IssueRef::new("acme/app", 1),
+            depends_on: IssueRef::new("acme/other", 8),
+            kind: RelationKind::ParentOf,
+        }]);
+
+        let report = graph.report(None);
+        assert_eq!(report.open_blockers.len(), 1);
+        assert!(!report.open_blockers[0].node.known);
+    }
+
+    #[test]
+    fn test_exports() {
+        let graph = create_test_graph();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"acme/app#1\" -> \"acme/app#2\" [label=\"blocked by\"];"));
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("-->|depends on|"));
+        assert!(mermaid.contains("acme/app#35;1"));
+        assert!(mermaid.contains("class "));
+    }
+}
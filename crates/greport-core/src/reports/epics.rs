@@ -0,0 +1,432 @@
+//! Epic progress roll-ups from sub-issues, tracked issues and task lists
+
+use crate::models::{Issue, IssueRef, IssueRelation, IssueState, RelationKind};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// How a child is attached to its epic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ChildSource {
+    /// GitHub sub-issue
+    SubIssue,
+    /// Issue tracked by the epic
+    TrackedIssue,
+    /// Task list item in the epic body
+    TaskList,
+}
+
+/// A child of an epic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EpicChild {
+    /// Referenced issue (None for plain task list items)
+    pub issue: Option<IssueRef>,
+    /// Issue title, or task list text
+    pub title: String,
+    /// Whether the child is done (issue closed or box checked)
+    pub completed: bool,
+    /// How the child is attached
+    pub source: ChildSource,
+    /// Whether the child has children of its own
+    pub is_epic: bool,
+    /// Days since the child issue was last updated
+    pub days_inactive: Option<i64>,
+    /// Open child with no activity within the stale threshold
+    pub stalled: bool,
+}
+
+/// Progress of a single epic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EpicProgress {
+    /// Epic issue
+    pub issue: IssueRef,
+    /// Epic title
+    pub title: String,
+    /// Whether the epic is open
+    pub open: bool,
+    /// Completed direct children
+    pub completed: usize,
+    /// Total direct children
+    pub total: usize,
+    /// Completed leaf items across all nested epics
+    pub nested_completed: usize,
+    /// Total leaf items across all nested epics
+    pub nested_total: usize,
+    /// Completion percentage of leaf items
+    pub percent: f64,
+    /// Levels of nesting below the epic (1 when no child is an epic)
+    pub depth: usize,
+    /// Direct children
+    pub children: Vec<EpicChild>,
+}
+
+impl EpicProgress {
+    /// Children that are open and inactive
+    pub fn stalled_children(&self) -> impl Iterator<Item = &EpicChild> {
+        self.children.iter().filter(|c| c.stalled)
+    }
+}
+
+/// Epic progress report for a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EpicReport {
+    /// Repository full name
+    pub repository: String,
+    /// Days without activity before an open child counts as stalled
+    pub stale_days: i64,
+    /// Epics ordered open first, then by number
+    pub epics: Vec<EpicProgress>,
+    /// Stalled children across all epics
+    pub total_stalled: usize,
+}
+
+/// Unresolved child entry before issue state is looked up
+struct ChildEntry {
+    issue: Option<IssueRef>,
+    text: String,
+    checked: bool,
+    source: ChildSource,
+}
+
+/// Epic progress calculator
+pub struct EpicReportCalculator {
+    stale_days: i64,
+    include_closed: bool,
+}
+
+impl EpicReportCalculator {
+    /// Create a new calculator
+    pub fn new(stale_days: i64) -> Self {
+        Self {
+            stale_days,
+            include_closed: false,
+        }
+    }
+
+    /// Also report closed epics
+    pub fn with_closed(mut self, include_closed: bool) -> Self {
+        self.include_closed = include_closed;
+        self
+    }
+
+    /// Calculate epic progress.
+    ///
+    /// `relations` supplies sub-issue and tracked-issue links; task lists are
+    /// read from the issue bodies. Other relation kinds are ignored.
+    pub fn calculate(
+        &self,
+        repo: &str,
+        issues: &[Issue],
+        relations: &[IssueRelation],
+        now: DateTime<Utc>,
+    ) -> EpicReport {
+        let by_ref: HashMap<IssueRef, &Issue> = issues
+            .iter()
+            .map(|i| (IssueRef::new(repo, i.number), i))
+            .collect();
+
+        let mut children: BTreeMap<IssueRef, Vec<ChildEntry>> = BTreeMap::new();
+        for relation in relations {
+            let source = match relation.kind {
+                RelationKind::ParentOf => ChildSource::SubIssue,
+                RelationKind::Tracks => ChildSource::TrackedIssue,
+                _ => continue,
+            };
+            let entries = children.entry(relation.issue.clone()).or_default();
+            if !entries
+                .iter()
+                .any(|e| e.issue.as_ref() == Some(&relation.depends_on))
+            {
+                entries.push(ChildEntry {
+                    issue: Some(relation.depends_on.clone()),
+                    text: String::new(),
+                    checked: false,
+                    source,
+                });
+            }
+        }
+
+        for issue in issues {
+            let parent = IssueRef::new(repo, issue.number);
+            for item in issue.task_list(repo) {
+                if item.issue.as_ref() == Some(&parent) {
+                    continue;
+                }
+                let entries = children.entry(parent.clone()).or_default();
+                // Tracked issues are usually declared through the task list
+                if let Some(existing) = entries
+                    .iter_mut()
+                    .find(|e| item.issue.is_some() && e.issue == item.issue)
+                {
+                    existing.checked |= item.checked;
+                    continue;
+                }
+                entries.push(ChildEntry {
+                    issue: item.issue,
+                    text: item.text,
+                    checked: item.checked,
+                    source: ChildSource::TaskList,
+                });
+            }
+        }
+        children.retain(|_, entries| !entries.is_empty());
+
+        let stale_cutoff = now - Duration::days(self.stale_days);
+        let mut epics = Vec::new();
+
+        for (epic_ref, entries) in &children {
+            let (title, open) = match by_ref.get(epic_ref) {
+                Some(issue) => (issue.title.clone(), issue.state == IssueState::Open),
+                None => continue,
+            };
+            if !open && !self.include_closed {
+                continue;
+            }
+
+            let children_out: Vec<EpicChild> = entries
+                .iter()
+                .map(|entry| {
+                    let known = entry.issue.as_ref().and_then(|r| by_ref.get(r));
+                    let completed = match known {
+                        Some(issue) => issue.state == IssueState::Closed,
+                        None => entry.checked,
+                    };
+                    let title = match known {
+                        Some(issue) => issue.title.clone(),
+                        None if entry.text.is_empty() => entry
+                            .issue
+                            .as_ref()
+                            .map(|r| r.to_string())
+                            .unwrap_or_default(),
+                        None => entry.text.clone(),
+                    };
+                    EpicChild {
+                        title,
+                        completed,
+                        source: entry.source,
+                        is_epic: entry
+                            .issue
+                            .as_ref()
+                            .is_some_and(|r| children.contains_key(r)),
+                        days_inactive: known.map(|i| (now - i.updated_at).num_days()),
+                        stalled: known.is_some_and(|i| !completed && i.updated_at < stale_cutoff),
+                        issue: entry.issue.clone(),
+                    }
+                })
+                .collect();
+
+            let (nested_completed, nested_total) =
+                leaf_totals(epic_ref, &children, &by_ref, &mut HashSet::new());
+            let percent = if nested_total == 0 {
+                0.0
+            } else {
+                nested_completed as f64 / nested_total as f64 * 100.0
+            };
+
+            epics.push(EpicProgress {
+                issue: epic_ref.clone(),
+                title,
+                open,
+                completed: children_out.iter().filter(|c| c.completed).count(),
+                total: children_out.len(),
+                nested_completed,
+                nested_total,
+                percent,
+                depth: depth(epic_ref, &children, &mut HashSet::new()),
+                children: children_out,
+            });
+        }
+
+        epics.sort_by_key(|e| (!e.open, e.issue.clone()));
+        let total_stalled = epics.iter().map(|e| e.stalled_children().count()).sum();
+
+        EpicReport {
+            repository: repo.to_string(),
+            stale_days: self.stale_days,
+            epics,
+            total_stalled,
+        }
+    }
+}
+
+/// Completed and total leaf items below an epic, counting shared children once
+fn leaf_totals(
+    epic: &IssueRef,
+    children: &BTreeMap<IssueRef, Vec<ChildEntry>>,
+    by_ref: &HashMap<IssueRef, &Issue>,
+    visited: &mut HashSet<IssueRef>,
+) -> (usize, usize) {
+    if !visited.insert(epic.clone()) {
+        return (0, 0);
+    }
+
+    let mut completed = 0;
+    let mut total = 0;
+    for entry in children.get(epic).into_iter().flatten() {
+        match &entry.issue {
+            Some(child) if children.contains_key(child) => {
+                let (c, t) = leaf_totals(child, children, by_ref, visited);
+                completed += c;
+                total += t;
+            }
+            Some(child) => {
+                if !visited.insert(child.clone()) {
+                    continue;
+                }
+                total += 1;
+                let done = match by_ref.get(child) {
+                    Some(issue) => issue.state == IssueState::Closed,
+                    None => entry.checked,
+                };
+                if done {
+                    completed += 1;
+                }
+            }
+            None => {
+                total += 1;
+                if entry.checked {
+                    completed += 1;
+                }
+            }
+        }
+    }
+
+    (completed, total)
+}
+
+/// Nesting depth below an epic; cycles are cut at the first repeat
+fn depth(
+    epic: &IssueRef,
+    children: &BTreeMap<IssueRef, Vec<ChildEntry>>,
+    visiting: &mut HashSet<IssueRef>,
+) -> usize {
+    if !visiting.insert(epic.clone()) {
+        return 0;
+    }
+    let nested = children
+        .get(epic)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.issue.as_ref())
+        .filter(|c| children.contains_key(*c))
+        .map(|c| depth(c, children, visiting))
+        .max()
+        .unwrap_or(0);
+    visiting.remove(epic);
+    1 + nested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::User;
+
+    fn create_test_issue(number: u64, state: IssueState, body: &str, idle_days: i64) -> Issue {
+        let now = Utc::now();
+        Issue {
+            id: number as i64,
+            number,
+            title: format!("Issue {}", number),
+            body: Some(body.to_string()),
+            state,
+            labels: vec![],
+            assignees: vec![],
+            milestone: None,
+            author: User::unknown(),
+            comments_count: 0,
+            created_at: now - Duration::days(60),
+            updated_at: now - Duration::days(idle_days),
+            closed_at: None,
+            closed_by: None,
+        }
+    }
+
+    fn sub_issue(parent: u64, child: u64) -> IssueRelation {
+        IssueRelation {
+            issue: IssueRef::new("acme/app", parent),
+            depends_on: IssueRef::new("acme/app", child),
+            kind: RelationKind::ParentOf,
+        }
+    }
+
+    #[test]
+    fn test_epic_rollup() {
+        let issues = vec![
+            create_test_issue(1, IssueState::Open, "- [x] #2\n- [ ] #3\n- [ ] Docs", 1),
+            create_test_issue(2, IssueState::Closed, "", 1),
+            create_test_issue(3, IssueState::Open, "", 40),
+            create_test_issue(4, IssueState::Open, "", 1),
+        ];
+
+        let report = EpicReportCalculator::new(14).calculate(
+            "acme/app",
+            &issues,
+            &[sub_issue(1, 4)],
+            Utc::now(),
+        );
+
+        assert_eq!(report.epics.len(), 1);
+        let epic = &report.epics[0];
+        assert_eq!(epic.issue, IssueRef::new("acme/app", 1));
+        assert_eq!(epic.total, 4);
+        assert_eq!(epic.completed, 1);
+        assert_eq!(epic.depth, 1);
+        assert_eq!(epic.children[0].source, ChildSource::SubIssue);
+        assert_eq!(report.total_stalled, 1);
+        assert_eq!(
+            epic.stalled_children().next().unwrap().issue,
+            Some(IssueRef::new("acme/app", 3))
+        );
+    }
+
+    #[test]
+    fn test_nested_epics() {
+        let issues = vec![
+            create_test_issue(1, IssueState::Open, "", 1),
+            create_test_issue(2, IssueState::Open, "- [x] Design\n- [ ] Build", 1),
+            create_test_issue(3, IssueState::Closed, "", 1),
+            create_test_issue(4, IssueState::Closed, "", 1),
+        ];
+        let relations = vec![sub_issue(1, 2), sub_issue(1, 3), sub_issue(2, 4)];
+
+        let report =
+            EpicReportCalculator::new(14).calculate("acme/app", &issues, &relations, Utc::now());
+
+        let top = &report.epics[0];
+        assert_eq!(top.issue.number, 1);
+        assert_eq!(top.depth, 2);
+        assert_eq!((top.completed, top.total), (1, 2));
+        // #4, Design, Build from epic #2 plus #3
+        assert_eq!((top.nested_completed, top.nested_total), (3, 4));
+        assert!(top.children[0].is_epic);
+        assert_eq!(report.epics[1].depth, 1);
+    }
+
+    #[test]
+    fn test_closed_epics_and_cycles() {
+        let issues = vec![
+            create_test_issue(1, IssueState::Closed, "", 1),
+            create_test_issue(2, IssueState::Open, "", 1),
+        ];
+        let relations = vec![sub_issue(1, 2), sub_issue(2, 1)];
+
+        let report =
+            EpicReportCalculator::new(14).calculate("acme/app", &issues, &relations, Utc::now());
+        assert_eq!(report.epics.len(), 1);
+
+        let report = EpicReportCalculator::new(14).with_closed(true).calculate(
+            "acme/app",
+            &issues,
+            &relations,
+            Utc::now(),
+        );
+        assert_eq!(report.epics.len(), 2);
+        assert!(report.epics[0].open);
+        assert_eq!(report.epics[0].depth, 2);
+    }
+}
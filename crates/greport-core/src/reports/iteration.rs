@@ -0,0 +1,595 @@
+//! Iteration (sprint) report generation from Projects V2 iteration fields
+
+use crate::models::{FieldValue, IterationValue, Project, ProjectFieldType, ProjectItem};
+use crate::{Error, Result};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// How items are attributed to iterations, shown with every report.
+///
+/// Projects V2 keeps no history of field values, so carry-over can only be
+/// derived from where items sit now.
+pub const ITERATION_ATTRIBUTION_NOTE: &str = "Items count toward their current iteration only: \
+     an unfinished item moved to a later iteration no longer appears in the \
+     earlier iteration's committed or carry-over totals.";
+
+/// Unit used for velocity and completion ratios
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum VelocityUnit {
+    /// Sum of a numeric estimate field
+    Points,
+    /// Number of items
+    Count,
+}
+
+impl VelocityUnit {
+    /// Get display label
+    pub fn label(&self) -> &'static str {
+        match self {
+            VelocityUnit::Points => "points",
+            VelocityUnit::Count => "items",
+        }
+    }
+}
+
+/// Where an iteration sits relative to today
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum IterationState {
+    /// Iteration has ended
+    Completed,
+    /// Today falls within the iteration
+    Current,
+    /// Iteration has not started yet
+    Upcoming,
+}
+
+/// Iteration report for a single project iteration field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IterationReport {
+    /// Project title
+    pub project: String,
+    /// Iteration field name (e.g. "Sprint")
+    pub iteration_field: String,
+    /// Numeric field used for points, if any
+    pub points_field: Option<String>,
+    /// Unit used for velocity and completion ratio
+    pub unit: VelocityUnit,
+    /// Iterations ordered by start date
+    pub iterations: Vec<IterationSummary>,
+    /// Average velocity across completed iterations
+    pub avg_velocity: f64,
+    /// Items with no value for the iteration field
+    pub unscheduled_items: usize,
+    /// How items are attributed to iterations
+    pub note: String,
+}
+
+/// Summary of a single iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IterationSummary {
+    /// Iteration ID
+    pub iteration_id: String,
+    /// Display title (e.g. "Sprint 12")
+    pub title: String,
+    /// First day of the iteration
+    pub start_date: NaiveDate,
+    /// Last day of the iteration (inclusive)
+    pub end_date: NaiveDate,
+    /// Where the iteration sits relative to today
+    pub state: IterationState,
+    /// Items committed to the iteration
+    pub committed: ItemTally,
+    /// Committed items that are done
+    pub completed: ItemTally,
+    /// Unfinished items of an ended iteration
+    pub carry_over: ItemTally,
+    /// Title of the iteration that carry-over rolls into
+    pub carried_to: Option<String>,
+    /// Completed / committed in the report unit (0.0 - 1.0)
+    pub completion_ratio: f64,
+    /// Completed work in the report unit
+    pub velocity: f64,
+    /// Unfinished items of an ended iteration
+    pub carry_over_items: Vec<IterationItemRef>,
+}
+
+/// Item and point totals
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ItemTally {
+    /// Number of items
+    pub items: usize,
+    /// Sum of points
+    pub points: f64,
+}
+
+impl ItemTally {
+    fn add(&mut self, points: f64) {
+        self.items += 1;
+        self.points += points;
+    }
+
+    /// Value in the given unit
+    pub fn value(&self, unit: VelocityUnit) -> f64 {
+        match unit {
+            VelocityUnit::Points => self.points,
+            VelocityUnit::Count => self.items as f64,
+        }
+    }
+}
+
+/// Lightweight reference to a project item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IterationItemRef {
+    /// Issue/PR number (None for drafts)
+    pub number: Option<u64>,
+    /// Item title
+    pub title: String,
+    /// Repository (owner/repo)
+    pub repository: Option<String>,
+    /// Current status value
+    pub status: Option<String>,
+    /// Points, when a points field is configured
+    pub points: Option<f64>,
+}
+
+/// Calculator for iteration reports
+#[derive(Debug, Clone)]
+pub struct IterationReportCalculator {
+    iteration_field: String,
+    points_field: Option<String>,
+    status_field: String,
+    done_values: Vec<String>,
+}
+
+impl IterationReportCalculator {
+    /// Create a calculator for the given iteration field
+    pub fn new(iteration_field: impl Into<String>) -> Self {
+        Self {
+            iteration_field: iteration_field.into(),
+            points_field: None,
+            status_field: "Status".to_string(),
+            done_values: vec!["Done".to_string()],
+        }
+    }
+
+    /// Measure velocity in points taken from a numeric field
+    pub fn with_points_field(mut self, field: Option<String>) -> Self {
+        self.points_field = field;
+        self
+    }
+
+    /// Set the single-select field holding the item status
+    pub fn with_status_field(mut self, field: impl Into<String>) -> Self {
+        self.status_field = field.into();
+        self
+    }
+
+    /// Set the status values that count as done
+    pub fn with_done_values(mut self, values: Vec<String>) -> Self {
+        if !values.is_empty() {
+            self.done_values = values;
+        }
+        self
+    }
+
+    /// Generate the report.
+    ///
+    /// Iterations come from the field configuration on `project`, merged with
+    /// any iteration values found on items. An item counts as completed when
+    /// its status is one of the done values or its linked issue/PR is closed.
+    /// Items are attributed to their current iteration only (see
+    /// [`ITERATION_ATTRIBUTION_NOTE`]).
+    ///
+    /// Fails with [`Error::NotFound`] when neither the project nor any item
+    /// has an iteration field of that name.
+    pub fn calculate(
+        &self,
+        project: &Project,
+        items: &[ProjectItem],
+        today: NaiveDate,
+    ) -> Result<IterationReport> {
+        self.check_field(project, items)?;
+
+        let unit = if self.points_field.is_some() {
+            VelocityUnit::Points
+        } else {
+            VelocityUnit::Count
+        };
+
+        let mut iterations = self.collect_iterations(project, items);
+        iterations.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+
+        let mut summaries: Vec<IterationSummary> = iterations
+            .iter()
+            .filter_map(|it| {
+                let start_date = NaiveDate::parse_from_str(&it.start_date, "%Y-%m-%d").ok()?;
+                let end_date = start_date + Duration::days(it.duration.max(1) as i64 - 1);
+                let state = if end_date < today {
+                    IterationState::Completed
+                } else if start_date <= today {
+                    IterationState::Current
+                } else {
+                    IterationState::Upcoming
+                };
+                Some(IterationSummary {
+                    iteration_id: it.id.clone(),
+                    title: it.title.clone(),
+                    start_date,
+                    end_date,
+                    state,
+                    committed: ItemTally::default(),
+                    completed: ItemTally::default(),
+                    carry_over: ItemTally::default(),
+                    carried_to: None,
+                    completion_ratio: 0.0,
+                    velocity: 0.0,
+                    carry_over_items: Vec::new(),
+                })
+            })
+            .collect();
+
+        let mut unscheduled_items = 0;
+        for item in items {
+            let Some(iteration_id) = self.iteration_id(item) else {
+                unscheduled_items += 1;
+                continue;
+            };
+            let Some(summary) = summaries
+                .iter_mut()
+                .find(|s| s.iteration_id == iteration_id)
+            else {
+                continue;
+            };
+
+            let points = self.points(item);
+            summary.committed.add(points.unwrap_or(0.0));
+            if self.is_done(item) {
+                summary.completed.add(points.unwrap_or(0.0));
+            } else if summary.state == IterationState::Completed {
+                summary.carry_over.add(points.unwrap_or(0.0));
+                summary.carry_over_items.push(IterationItemRef {
+                    number: item.number(),
+                    title: item.title().to_string(),
+                    repository: item.repository().map(String::from),
+                    status: item.field_display(&self.status_field),
+                    points,
+                });
+            }
+        }
+
+        let next_titles: Vec<Option<String>> = (0..summaries.len())
+            .map(|i| summaries.get(i + 1).map(|s| s.title.clone()))
+            .collect();
+        for (summary, next) in summaries.iter_mut().zip(next_titles) {
+            let committed = summary.committed.value(unit);
+            summary.velocity = summary.completed.value(unit);
+            summary.completion_ratio = if committed > 0.0 {
+                summary.velocity / committed
+            } else {
+                0.0
+            };
+            if summary.carry_over.items > 0 {
+                summary.carried_to = next;
+            }
+        }
+
+        let completed: Vec<f64> = summaries
+            .iter()
+            .filter(|s| s.state == IterationState::Completed)
+            .map(|s| s.velocity)
+            .collect();
+        let avg_velocity = if completed.is_empty() {
+            0.0
+        } else {
+            completed.iter().sum::<f64>() / completed.len() as f64
+        };
+
+        Ok(IterationReport {
+            project: project.title.clone(),
+            iteration_field: self.iteration_field.clone(),
+            points_field: self.points_field.clone(),
+            unit,
+            iterations: summaries,
+            avg_velocity,
+            unscheduled_items,
+            note: ITERATION_ATTRIBUTION_NOTE.to_string(),
+        })
+    }
+
+    /// Check that the iteration field exists, naming the project's
+    /// iteration fields when it does not
+    fn check_field(&self, project: &Project, items: &[ProjectItem]) -> Result<()> {
+        let iteration_fields: Vec<&str> = project
+            .fields
+            .iter()
+            .filter(|f| matches!(f.field_type, ProjectFieldType::Iteration { .. }))
+            .map(|f| f.name.as_str())
+            .collect();
+        let known = iteration_fields
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&self.iteration_field))
+            || items.iter().any(|item| self.iteration_id(item).is_some());
+        if known {
+            return Ok(());
+        }
+        Err(Error::NotFound(format!(
+            "Iteration field '{}' on project '{}' (iteration fields: {})",
+            self.iteration_field,
+            project.title,
+            if iteration_fields.is_empty() {
+                "none".to_string()
+            } else {
+                iteration_fields.join(", ")
+            }
+        )))
+    }
+
+    /// Iterations from the field config plus any only seen on items.
+    fn collect_iterations(&self, project: &Project, items: &[ProjectItem]) -> Vec<IterationValue> {
+        let mut iterations: Vec<IterationValue> = project
+            .fields
+            .iter()
+            .filter(|f| f.name.eq_ignore_ascii_case(&self.iteration_field))
+            .find_map(|f| match &f.field_type {
+                ProjectFieldType::Iteration { iterations } => Some(iterations.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        for item in items {
+            if let Some(FieldValue::Iteration {
+                title,
+                start_date,
+                duration,
+                iteration_id,
+            }) = item.field_value(&self.iteration_field)
+            {
+                if !iterations.iter().any(|it| &it.id == iteration_id) {
+                    iterations.push(IterationValue {
+                        id: iteration_id.clone(),
+                        title: title.clone(),
+                        start_date: start_date.clone(),
+                        duration: *duration,
+                    });
+                }
+            }
+        }
+
+        iterations
+    }
+
+    fn iteration_id<'a>(&self, item: &'a ProjectItem) -> Option<&'a str> {
+        match item.field_value(&self.iteration_field) {
+            Some(FieldValue::Iteration { iteration_id, .. }) => Some(iteration_id),
+            _ => None,
+        }
+    }
+
+    fn points(&self, item: &ProjectItem) -> Option<f64> {
+        let field = self.points_field.as_ref()?;
+        match item.field_value(field) {
+            Some(FieldValue::Number { value }) => Some(*value),
+            Some(FieldValue::Text { value }) => value.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn is_done(&self, item: &ProjectItem) -> bool {
+        let status_done = item
+            .field_display(&self.status_field)
+            .is_some_and(|s| self.done_values.iter().any(|d| d.eq_ignore_ascii_case(&s)));
+        let content_done = item
+            .state()
+            .is_some_and(|s| s.eq_ignore_ascii_case("closed") || s.eq_ignore_ascii_case("merged"));
+        status_done || content_done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectField, ProjectFieldValue, ProjectItemContent};
+    use chrono::Utc;
+
+    fn iteration(id: &str, title: &str, start: &str) -> IterationValue {
+        IterationValue {
+            id: id.to_string(),
+            title: title.to_string(),
+            start_date: start.to_string(),
+            duration: 14,
+        }
+    }
+
+    fn create_test_project(iterations: Vec<IterationValue>) -> Project {
+        let now = Utc::now();
+        Project {
+            node_id: "PVT_1".to_string(),
+            number: 1,
+            title: "Roadmap".to_string(),
+            description: None,
+            url: String::new(),
+            closed: false,
+            owner: "acme".to_string(),
+            created_at: now,
+            updated_at: now,
+            fields: vec![ProjectField {
+                node_id: "F_1".to_string(),
+                name: "Sprint".to_string(),
+                field_type: ProjectFieldType::Iteration { iterations },
+            }],
+            total_items: 0,
+        }
+    }
+
+    fn create_test_item(it: Option<&IterationValue>, status: &str, points: f64) -> ProjectItem {
+        let now = Utc::now();
+        let mut field_values = vec![
+            ProjectFieldValue {
+                field_name: "Status".to_string(),
+                value: FieldValue::SingleSelect {
+                    name: status.to_string(),
+                    option_id: "o".to_string(),
+                },
+            },
+            ProjectFieldValue {
+                field_name: "Estimate".to_string(),
+                value: FieldValue::Number { value: points },
+            },
+        ];
+        if let Some(it) = it {
+            field_values.push(ProjectFieldValue {
+                field_name: "Sprint".to_string(),
+                value: FieldValue::Iteration {
+                    title: it.title.clone(),
+                    start_date: it.start_date.clone(),
+                    duration: it.duration,
+                    iteration_id: it.id.clone(),
+                },
+            });
+        }
+        ProjectItem {
+            node_id: "PVTI".to_string(),
+            content: ProjectItemContent::Issue {
+                number: 1,
+                title: "Item".to_string(),
+                state: "OPEN".to_string(),
+                url: String::new(),
+                repository: "acme/app".to_string(),
+                assignees: vec![],
+                labels: vec![],
+            },
+            field_values,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 25).unwrap()
+    }
+
+    #[test]
+    fn test_iteration_report_counts() {
+        let s1 = iteration("i1", "Sprint 1", "2025-01-01");
+        let s2 = iteration("i2", "Sprint 2", "2025-01-15");
+        let project = create_test_project(vec![s2.clone(), s1.clone()]);
+        let items = vec![
+            create_test_item(Some(&s1), "Done", 3.0),
+            create_test_item(Some(&s1), "In Progress", 5.0),
+            create_test_item(Some(&s2), "Todo", 2.0),
+            create_test_item(None, "Todo", 1.0),
+        ];
+
+        let report = IterationReportCalculator::new("Sprint")
+            .calculate(&project, &items, today())
+            .unwrap();
+
+        assert_eq!(report.unit, VelocityUnit::Count);
+        assert_eq!(report.iterations.len(), 2);
+        assert_eq!(report.unscheduled_items, 1);
+
+        let first = &report.iterations[0];
+        assert_eq!(first.title, "Sprint 1");
+        assert_eq!(first.state, IterationState::Completed);
+        assert_eq!(first.committed.items, 2);
+        assert_eq!(first.completed.items, 1);
+        assert_eq!(first.carry_over.items, 1);
+        assert_eq!(first.carried_to.as_deref(), Some("Sprint 2"));
+        assert!((first.completion_ratio - 0.5).abs() < f64::EPSILON);
+
+        let second = &report.iterations[1];
+        assert_eq!(second.state, IterationState::Current);
+        assert_eq!(second.carry_over.items, 0);
+        assert!((report.avg_velocity - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_iteration_report_attributes_items_to_current_iteration() {
+        // An unfinished item moved from Sprint 1 to Sprint 2 counts only in
+        // Sprint 2; with no field history, Sprint 1 shows no carry-over
+        let s1 = iteration("i1", "Sprint 1", "2025-01-01");
+        let s2 = iteration("i2", "Sprint 2", "2025-01-15");
+        let project = create_test_project(vec![s1, s2.clone()]);
+        let items = vec![create_test_item(Some(&s2), "In Progress", 1.0)];
+
+        let report = IterationReportCalculator::new("Sprint")
+            .calculate(&project, &items, today())
+            .unwrap();
+
+        let first = &report.iterations[0];
+        assert_eq!(first.committed.items, 0);
+        assert_eq!(first.carry_over.items, 0);
+        assert!(first.carried_to.is_none());
+        assert_eq!(report.iterations[1].committed.items, 1);
+        assert_eq!(report.note, ITERATION_ATTRIBUTION_NOTE);
+    }
+
+    #[test]
+    fn test_iteration_report_unknown_field() {
+        let s1 = iteration("i1", "Sprint 1", "2025-01-01");
+        let project = create_test_project(vec![s1.clone()]);
+        let items = vec![create_test_item(Some(&s1), "Done", 1.0)];
+
+        let err = IterationReportCalculator::new("Iteration")
+            .calculate(&project, &items, today())
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+        assert!(err.to_string().contains("'Iteration'"));
+        assert!(err.to_string().contains("iteration fields: Sprint"));
+    }
+
+    #[test]
+    fn test_iteration_report_points() {
+        let s1 = iteration("i1", "Sprint 1", "2025-01-01");
+        let project = create_test_project(vec![s1.clone()]);
+        let items = vec![
+            create_test_item(Some(&s1), "Done", 3.0),
+            create_test_item(Some(&s1), "Todo", 5.0),
+        ];
+
+        let report = IterationReportCalculator::new("Sprint")
+            .with_points_field(Some("Estimate".to_string()))
+            .calculate(&project, &items, today())
+            .unwrap();
+
+        let first = &report.iterations[0];
+        assert_eq!(report.unit, VelocityUnit::Points);
+        assert!((first.committed.points - 8.0).abs() < f64::EPSILON);
+        assert!((first.velocity - 3.0).abs() < f64::EPSILON);
+        assert_eq!(first.carry_over_items[0].points, Some(5.0));
+    }
+
+    #[test]
+    fn test_iteration_report_custom_done_values() {
+        let s1 = iteration("i1", "Sprint 1", "2025-01-01");
+        let project = create_test_project(vec![s1.clone()]);
+        let items = vec![create_test_item(Some(&s1), "Shipped", 1.0)];
+
+        let report = IterationReportCalculator::new("Sprint")
+            .with_done_values(vec!["Shipped".to_string()])
+            .calculate(&project, &items, today())
+            .unwrap();
+        assert_eq!(report.iterations[0].completed.items, 1);
+    }
+
+    #[test]
+    fn test_iteration_report_iterations_from_items() {
+        let s1 = iteration("i1", "Sprint 1", "2025-01-01");
+        let project = create_test_project(vec![]);
+        let items = vec![create_test_item(Some(&s1), "Done", 1.0)];
+
+        let report = IterationReportCalculator::new("sprint")
+            .calculate(&project, &items, today())
+            .unwrap();
+        assert_eq!(report.iterations.len(), 1);
+        assert_eq!(report.iterations[0].end_date.to_string(), "2025-01-14");
+    }
+}
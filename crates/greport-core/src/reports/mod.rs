@@ -1,7 +1,13 @@
 //! Report generation
 
 mod burndown;
+mod dependencies;
+mod epics;
+mod iteration;
 mod release_notes;
 
 pub use burndown::*;
+pub use dependencies::*;
+pub use epics::*;
+pub use iteration::*;
 pub use release_notes::*;
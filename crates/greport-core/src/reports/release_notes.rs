@@ -1,11 +1,13 @@
 //! Release notes generation
 
-use crate::models::{Issue, Label, PullRequest};
+use crate::metrics::BotFilter;
+use crate::models::{Issue, Label, PullRequest, User};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Generated release notes
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReleaseNotes {
     /// Version/release name
     pub version: String,
@@ -17,12 +19,17 @@ pub struct ReleaseNotes {
     pub sections: Vec<ReleaseSection>,
     /// List of contributors
     pub contributors: Vec<String>,
+    /// Bot accounts that contributed, when bot activity is reported
+    /// separately (otherwise bots are left out)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bot_contributors: Vec<String>,
     /// Release statistics
     pub stats: ReleaseStats,
 }
 
 /// Section within release notes
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReleaseSection {
     /// Section title
     pub title: String,
@@ -32,6 +39,7 @@ pub struct ReleaseSection {
 
 /// Single item in release notes
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReleaseItem {
     /// Issue/PR number
     pub number: u64,
@@ -45,6 +53,7 @@ pub struct ReleaseItem {
 
 /// Release statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReleaseStats {
     /// Number of issues closed
     pub issues_closed: usize,
@@ -85,12 +94,22 @@ impl Default for ReleaseNotesConfig {
 /// Generator for release notes
 pub struct ReleaseNotesGenerator {
     config: ReleaseNotesConfig,
+    bots: BotFilter,
 }
 
 impl ReleaseNotesGenerator {
     /// Create a new generator with the given config
     pub fn new(config: ReleaseNotesConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            bots: BotFilter::default(),
+        }
+    }
+
+    /// Keep bots out of the contributors (or list them separately)
+    pub fn with_bot_filter(mut self, bots: BotFilter) -> Self {
+        self.bots = bots;
+        self
     }
 
     /// Create a generator with default config
@@ -102,6 +121,17 @@ impl ReleaseNotesGenerator {
     pub fn generate(&self, version: &str, issues: &[Issue], prs: &[PullRequest]) -> ReleaseNotes {
         let mut sections: HashMap<String, Vec<ReleaseItem>> = HashMap::new();
         let mut contributors: Vec<String> = Vec::new();
+        let mut bot_contributors: Vec<String> = Vec::new();
+        let mut add_contributor = |user: &User| {
+            let list = if self.bots.is_bot(user) {
+                &mut bot_contributors
+            } else {
+                &mut contributors
+            };
+            if !list.contains(&user.login) {
+                list.push(user.login.clone());
+            }
+        };
 
         // Process issues
         for issue in issues {
@@ -113,17 +143,12 @@ impl ReleaseNotesGenerator {
                 labels: issue.labels.iter().map(|l| l.name.clone()).collect(),
             };
             sections.entry(section).or_default().push(item);
-
-            if !contributors.contains(&issue.author.login) {
-                contributors.push(issue.author.login.clone());
-            }
+            add_contributor(&issue.author);
         }
 
         // Process PRs
         for pr in prs {
-            if !contributors.contains(&pr.author.login) {
-                contributors.push(pr.author.login.clone());
-            }
+            add_contributor(&pr.author);
         }
 
         // Convert to ordered sections
@@ -151,6 +176,10 @@ impl ReleaseNotesGenerator {
             .collect();
 
         contributors.sort();
+        bot_contributors.sort();
+        if !self.bots.separate() {
+            bot_contributors.clear();
+        }
 
         ReleaseNotes {
             version: version.to_string(),
@@ -162,6 +191,7 @@ impl ReleaseNotesGenerator {
             ),
             sections: ordered_sections,
             contributors: contributors.clone(),
+            bot_contributors,
             stats: ReleaseStats {
                 issues_closed: issues.len(),
                 prs_merged: prs.len(),
@@ -205,6 +235,13 @@ impl ReleaseNotesGenerator {
             md.push_str(&format!("- @{}\n", contributor));
         }
 
+        if !notes.bot_contributors.is_empty() {
+            md.push_str("\n## Automation\n\n");
+            for bot in &notes.bot_contributors {
+                md.push_str(&format!("- @{}\n", bot));
+            }
+        }
+
         md
     }
 }
@@ -227,6 +264,7 @@ mod tests {
             login: login.to_string(),
             avatar_url: "".to_string(),
             html_url: "".to_string(),
+            is_bot: false,
         }
     }
 
@@ -402,6 +440,25 @@ mod tests {
         assert!(notes.contributors.contains(&"charlie".to_string()));
     }
 
+    #[test]
+    fn test_release_notes_bot_contributors() {
+        let issues = vec![create_test_issue(1, "Fix login bug", "alice", vec![])];
+        let prs = vec![create_test_pr(2, "dependabot[bot]")];
+        let bots = BotFilter::new(["*[bot]"]);
+
+        let generator = ReleaseNotesGenerator::with_defaults().with_bot_filter(bots.clone());
+        let notes = generator.generate("v1.0.0", &issues, &prs);
+        assert_eq!(notes.contributors, vec!["alice".to_string()]);
+        assert!(notes.bot_contributors.is_empty());
+
+        let generator =
+            ReleaseNotesGenerator::with_defaults().with_bot_filter(bots.with_separate_report(true));
+        let notes = generator.generate("v1.0.0", &issues, &prs);
+        assert_eq!(notes.contributors, vec!["alice".to_string()]);
+        assert_eq!(notes.bot_contributors, vec!["dependabot[bot]".to_string()]);
+        assert!(generator.to_markdown(&notes).contains("## Automation"));
+    }
+
     #[test]
     fn test_release_notes_to_markdown() {
         let generator = ReleaseNotesGenerator::with_defaults();
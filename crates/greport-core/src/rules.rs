@@ -0,0 +1,424 @@
+//! Bulk update rules for GitHub Projects (V2)
+//!
+//! Rules are loaded from a TOML file and evaluated against project items to
+//! produce a list of planned changes, which can be printed (dry run) or
+//! applied through [`ProjectClient`](crate::client::ProjectClient).
+//!
+//! ```toml
+//! [[rule]]
+//! name = "Done when closed"
+//! when = { content_state = ["closed", "merged"], status_not = ["Done"] }
+//! set = { Status = "Done" }
+//!
+//! [[rule]]
+//! name = "Stale to backlog"
+//! when = { status = ["In Progress"], stale_days = 30 }
+//! set = { Status = "Backlog" }
+//! ```
+
+use crate::models::{FieldUpdate, Project, ProjectItem};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// A set of project rules
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectRules {
+    /// Rules, evaluated in order
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<ProjectRule>,
+}
+
+/// A single rule: a condition and the actions to take on matching items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRule {
+    /// Rule name, shown in planned changes
+    pub name: String,
+    /// Condition an item must satisfy
+    #[serde(default)]
+    pub when: RuleCondition,
+    /// Field values to set (field name -> value; empty clears the field)
+    #[serde(default)]
+    pub set: BTreeMap<String, String>,
+    /// Archive matching items
+    #[serde(default)]
+    pub archive: bool,
+}
+
+/// Item condition. All set criteria must match; comparisons are case-insensitive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleCondition {
+    /// Content type is one of these ("issue", "pull_request", "draft_issue")
+    #[serde(default)]
+    pub content_type: Vec<String>,
+    /// Linked issue/PR state is one of these ("open", "closed", "merged")
+    #[serde(default)]
+    pub content_state: Vec<String>,
+    /// Status is one of these
+    #[serde(default)]
+    pub status: Vec<String>,
+    /// Status is none of these
+    #[serde(default)]
+    pub status_not: Vec<String>,
+    /// Iteration title
+    pub iteration: Option<String>,
+    /// Field values that must match (field name -> value)
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Item has not been updated for at least this many days
+    pub stale_days: Option<i64>,
+}
+
+/// A change produced by evaluating rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedChange {
+    /// Name of the rule that produced the change
+    pub rule: String,
+    /// Project item node ID
+    pub item_node_id: String,
+    /// Item reference (owner/repo#number, or node ID for drafts)
+    pub item: String,
+    /// Item title
+    pub title: String,
+    /// Action to perform
+    pub action: PlannedAction,
+}
+
+/// Action of a planned change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlannedAction {
+    /// Set or clear a field value
+    SetField {
+        /// Field name
+        field: String,
+        /// Field node ID
+        field_node_id: String,
+        /// Current display value
+        from: Option<String>,
+        /// New display value (empty when clearing)
+        to: String,
+        /// Resolved update
+        update: FieldUpdate,
+    },
+    /// Archive the item
+    Archive,
+}
+
+impl PlannedAction {
+    /// Short description for display (e.g. "Status: Todo -> Done")
+    pub fn describe(&self) -> String {
+        match self {
+            PlannedAction::SetField {
+                field, from, to, ..
+            } => {
+                let to = if to.is_empty() { "(clear)" } else { to };
+                format!("{}: {} -> {}", field, from.as_deref().unwrap_or("-"), to)
+            }
+            PlannedAction::Archive => "archive".to_string(),
+        }
+    }
+}
+
+impl ProjectRules {
+    /// Load rules from a TOML file
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            crate::Error::Config(format!(
+                "Failed to read rules file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// Parse rules from a TOML string
+    pub fn from_toml(content: &str) -> crate::Result<Self> {
+        let rules: ProjectRules = toml::from_str(content)
+            .map_err(|e| crate::Error::Config(format!("Failed to parse rules: {}", e)))?;
+
+        for rule in &rules.rules {
+            if rule.set.is_empty() && !rule.archive {
+                return Err(crate::Error::Config(format!(
+                    "Rule '{}' has no actions (expected 'set' or 'archive')",
+                    rule.name
+                )));
+            }
+        }
+
+        Ok(rules)
+    }
+
+    /// Evaluate rules against items and return the changes to make.
+    ///
+    /// Rules see the items as fetched, so one rule's changes do not feed into
+    /// another. When several rules set the same field on an item, the first
+    /// wins. Values already in place produce no change.
+    pub fn plan(
+        &self,
+        project: &Project,
+        items: &[ProjectItem],
+        now: DateTime<Utc>,
+    ) -> crate::Result<Vec<PlannedChange>> {
+        let mut changes = Vec::new();
+        let mut touched: HashSet<(String, String)> = HashSet::new();
+        let mut archived: HashSet<String> = HashSet::new();
+
+        for item in items {
+            for rule in &self.rules {
+                if !rule.when.matches(item, now) {
+                    continue;
+                }
+
+                for (field_name, value) in &rule.set {
+                    let (field, update) = project.resolve_field_update(field_name, value)?;
+                    let current = item.field_display(&field.name);
+                    let unchanged = match &current {
+                        Some(c) => c.eq_ignore_ascii_case(value.trim()),
+                        None => value.trim().is_empty(),
+                    };
+                    if unchanged || !touched.insert((item.node_id.clone(), field.node_id.clone())) {
+                        continue;
+                    }
+
+                    changes.push(PlannedChange {
+                        rule: rule.name.clone(),
+                        item_node_id: item.node_id.clone(),
+                        item: item.reference(),
+                        title: item.title().to_string(),
+                        action: PlannedAction::SetField {
+                            field: field.name.clone(),
+                            field_node_id: field.node_id.clone(),
+                            from: current,
+                            to: value.trim().to_string(),
+                            update,
+                        },
+                    });
+                }
+
+                if rule.archive && archived.insert(item.node_id.clone()) {
+                    changes.push(PlannedChange {
+                        rule: rule.name.clone(),
+                        item_node_id: item.node_id.clone(),
+                        item: item.reference(),
+                        title: item.title().to_string(),
+                        action: PlannedAction::Archive,
+                    });
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+impl RuleCondition {
+    /// Check whether an item satisfies this condition
+    pub fn matches(&self, item: &ProjectItem, now: DateTime<Utc>) -> bool {
+        let any_eq = |values: &[String], v: &str| values.iter().any(|x| x.eq_ignore_ascii_case(v));
+
+        if !self.content_type.is_empty() && !any_eq(&self.content_type, item.content_type()) {
+            return false;
+        }
+
+        if !self.content_state.is_empty()
+            && !item
+                .state()
+                .is_some_and(|state| any_eq(&self.content_state, state))
+        {
+            return false;
+        }
+
+        let status = item.status();
+        if !self.status.is_empty() && !status.as_deref().is_some_and(|s| any_eq(&self.status, s)) {
+            return false;
+        }
+        if status
+            .as_deref()
+            .is_some_and(|s| any_eq(&self.status_not, s))
+        {
+            return false;
+        }
+
+        if let Some(iteration) = &self.iteration {
+            if !item
+                .iteration()
+                .is_some_and(|i| i.eq_ignore_ascii_case(iteration))
+            {
+                return false;
+            }
+        }
+
+        for (field, value) in &self.fields {
+            if !item
+                .field_display(field)
+                .is_some_and(|v| v.eq_ignore_ascii_case(value))
+            {
+                return false;
+            }
+        }
+
+        if let Some(days) = self.stale_days {
+            if now - item.updated_at < Duration::days(days) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        FieldValue, ProjectField, ProjectFieldType, ProjectFieldValue, ProjectItemContent,
+        SelectOption,
+    };
+
+    const RULES: &str = r#"
+[[rule]]
+name = "Done when closed"
+when = { content_state = ["closed", "merged"], status_not = ["Done"] }
+set = { Status = "Done" }
+
+[[rule]]
+name = "Stale to backlog"
+when = { status = ["In Progress"], stale_days = 30 }
+set = { Status = "Backlog" }
+
+[[rule]]
+name = "Archive old"
+when = { status = ["Done"], stale_days = 90 }
+archive = true
+"#;
+
+    fn option(id: &str, name: &str) -> SelectOption {
+        SelectOption {
+            id: id.to_string(),
+            name: name.to_string(),
+            color: None,
+            description: None,
+        }
+    }
+
+    fn create_test_project() -> Project {
+        Project {
+            node_id: "PVT_1".to_string(),
+            number: 1,
+            title: "Board".to_string(),
+            description: None,
+            url: String::new(),
+            closed: false,
+            owner: "acme".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            fields: vec![ProjectField {
+                node_id: "F_status".to_string(),
+                name: "Status".to_string(),
+                field_type: ProjectFieldType::SingleSelect {
+                    options: vec![
+                        option("o_backlog", "Backlog"),
+                        option("o_progress", "In Progress"),
+                        option("o_done", "Done"),
+                    ],
+                },
+            }],
+            total_items: 0,
+        }
+    }
+
+    fn create_test_item(number: u64, state: &str, status: &str, age_days: i64) -> ProjectItem {
+        let updated = Utc::now() - Duration::days(age_days);
+        ProjectItem {
+            node_id: format!("PVTI_{}", number),
+            content: ProjectItemContent::Issue {
+                number,
+                title: format!("Issue {}", number),
+                state: state.to_string(),
+                url: String::new(),
+                repository: "acme/app".to_string(),
+                assignees: vec![],
+                labels: vec![],
+            },
+            field_values: vec![ProjectFieldValue {
+                field_name: "Status".to_string(),
+                value: FieldValue::SingleSelect {
+                    name: status.to_string(),
+                    option_id: String::new(),
+                },
+            }],
+            created_at: updated,
+            updated_at: updated,
+        }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = ProjectRules::from_toml(RULES).unwrap();
+        assert_eq!(rules.rules.len(), 3);
+        assert_eq!(rules.rules[0].when.content_state, vec!["closed", "merged"]);
+        assert_eq!(rules.rules[1].when.stale_days, Some(30));
+        assert!(rules.rules[2].archive);
+    }
+
+    #[test]
+    fn test_rule_without_actions_rejected() {
+        let err = ProjectRules::from_toml("[[rule]]\nname = \"noop\"\n").unwrap_err();
+        assert!(err.to_string().contains("no actions"));
+    }
+
+    #[test]
+    fn test_plan_changes() {
+        let rules = ProjectRules::from_toml(RULES).unwrap();
+        let items = vec![
+            create_test_item(1, "CLOSED", "In Progress", 1),
+            create_test_item(2, "OPEN", "In Progress", 45),
+            create_test_item(3, "OPEN", "In Progress", 2),
+            create_test_item(4, "CLOSED", "Done", 120),
+        ];
+
+        let changes = rules
+            .plan(&create_test_project(), &items, Utc::now())
+            .unwrap();
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].item, "acme/app#1");
+        assert_eq!(changes[0].action.describe(), "Status: In Progress -> Done");
+        assert!(matches!(
+            &changes[0].action,
+            PlannedAction::SetField { update: FieldUpdate::SingleSelect { option_id }, .. }
+                if option_id == "o_done"
+        ));
+        assert_eq!(changes[1].rule, "Stale to backlog");
+        assert_eq!(changes[1].item, "acme/app#2");
+        assert_eq!(changes[2].item, "acme/app#4");
+        assert!(matches!(changes[2].action, PlannedAction::Archive));
+    }
+
+    #[test]
+    fn test_plan_first_rule_wins() {
+        let rules = ProjectRules::from_toml(RULES).unwrap();
+        // Closed and stale: both rules match, only the first sets Status.
+        let items = vec![create_test_item(1, "CLOSED", "In Progress", 45)];
+
+        let changes = rules
+            .plan(&create_test_project(), &items, Utc::now())
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].rule, "Done when closed");
+    }
+
+    #[test]
+    fn test_plan_unknown_option_errors() {
+        let rules =
+            ProjectRules::from_toml("[[rule]]\nname = \"x\"\nset = { Status = \"Shipped\" }\n")
+                .unwrap();
+        let items = vec![create_test_item(1, "OPEN", "Done", 1)];
+        assert!(rules
+            .plan(&create_test_project(), &items, Utc::now())
+            .is_err());
+    }
+}
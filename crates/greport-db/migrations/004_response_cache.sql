@@ -0,0 +1,15 @@
+-- Store cached API responses and track sync generations for cache keys
+
+ALTER TABLE cache_metadata ADD COLUMN IF NOT EXISTS etag VARCHAR(64);
+ALTER TABLE cache_metadata ADD COLUMN IF NOT EXISTS body TEXT;
+
+-- Global counter bumped by every sync or tracked-repo change
+CREATE TABLE IF NOT EXISTS sync_generation (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    generation BIGINT NOT NULL DEFAULT 0
+);
+
+INSERT INTO sync_generation (id, generation) VALUES (TRUE, 0) ON CONFLICT DO NOTHING;
+
+-- Global generation at the repository's last sync
+ALTER TABLE sync_status ADD COLUMN IF NOT EXISTS generation BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,10 @@
+-- Per-client request counters shared by all API replicas
+
+CREATE TABLE IF NOT EXISTS rate_limit_counters (
+    client_id VARCHAR(255) NOT NULL,
+    window_start TIMESTAMPTZ NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (client_id, window_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_counters_window ON rate_limit_counters(window_start);
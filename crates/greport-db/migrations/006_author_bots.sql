@@ -0,0 +1,5 @@
+-- Whether the author is a GitHub App or other bot account (`type: Bot`)
+
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS author_is_bot BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE pull_requests ADD COLUMN IF NOT EXISTS author_is_bot BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE releases ADD COLUMN IF NOT EXISTS author_is_bot BOOLEAN NOT NULL DEFAULT FALSE;
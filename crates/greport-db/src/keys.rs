@@ -0,0 +1,142 @@
+//! API key generation and hashing
+//!
+//! Only the SHA-256 hash of a key is stored; the plaintext key is shown once
+//! when it is created.
+
+use crate::models::ApiKeyInput;
+use crate::DbError;
+use chrono::{DateTime, Utc};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+
+/// Prefix for generated API keys
+pub const API_KEY_PREFIX: &str = "grp_";
+
+/// Length of the random part of a generated key
+const API_KEY_RANDOM_LEN: usize = 40;
+
+/// Generate a new random API key
+pub fn generate_api_key() -> String {
+    let random = Alphanumeric.sample_string(&mut rand::thread_rng(), API_KEY_RANDOM_LEN);
+    format!("{API_KEY_PREFIX}{random}")
+}
+
+/// Hash an API key for storage/lookup
+pub fn hash_api_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Check that `scope` is one the server understands: `read`, `sync`,
+/// `admin` or `org:<name>`
+pub fn validate_scope(scope: &str) -> Result<(), DbError> {
+    match scope {
+        "read" | "sync" | "admin" => Ok(()),
+        _ if scope
+            .strip_prefix("org:")
+            .is_some_and(|org| !org.trim().is_empty()) =>
+        {
+            Ok(())
+        }
+        _ => Err(DbError::InvalidInput(format!(
+            "Unknown scope '{}' (expected read, sync, admin or org:<name>)",
+            scope
+        ))),
+    }
+}
+
+/// Validate key settings, generate a key and build the insert input.
+///
+/// Returns the plaintext key together with the input holding its hash.
+pub fn new_api_key(
+    name: &str,
+    owner: &str,
+    scopes: Vec<String>,
+    rate_limit: i32,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(String, ApiKeyInput), DbError> {
+    if name.trim().is_empty() {
+        return Err(DbError::InvalidInput("API key name is required".into()));
+    }
+    if owner.trim().is_empty() {
+        return Err(DbError::InvalidInput("API key owner is required".into()));
+    }
+    if scopes.is_empty() {
+        return Err(DbError::InvalidInput(
+            "API key needs at least one scope".into(),
+        ));
+    }
+    let scopes: Vec<String> = scopes.iter().map(|s| s.trim().to_string()).collect();
+    for scope in &scopes {
+        validate_scope(scope)?;
+    }
+    if rate_limit <= 0 {
+        return Err(DbError::InvalidInput(
+            "Rate limit must be a positive number of requests per minute".into(),
+        ));
+    }
+    if expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(DbError::InvalidInput("Expiry must be in the future".into()));
+    }
+
+    let key = generate_api_key();
+    let input = ApiKeyInput {
+        name: name.trim().to_string(),
+        key_hash: hash_api_key(&key),
+        owner: owner.trim().to_string(),
+        scopes,
+        rate_limit,
+        expires_at,
+    };
+    Ok((key, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_new_api_key() {
+        let (key, input) = new_api_key("ci", "alice", vec!["read".into()], 60, None).unwrap();
+
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert_eq!(key.len(), API_KEY_PREFIX.len() + API_KEY_RANDOM_LEN);
+        assert_eq!(input.key_hash, hash_api_key(&key));
+        assert_ne!(input.key_hash, key);
+        assert_ne!(generate_api_key(), key);
+    }
+
+    #[test]
+    fn test_validate_scope() {
+        for scope in ["read", "sync", "admin", "org:acme"] {
+            assert!(validate_scope(scope).is_ok(), "{}", scope);
+        }
+        for scope in ["", "amdin", "Read", "*", "org:", "org: "] {
+            assert!(validate_scope(scope).is_err(), "{}", scope);
+        }
+
+        let (_, input) = new_api_key(
+            "ci",
+            "alice",
+            vec![" read".into(), "org:acme".into()],
+            60,
+            None,
+        )
+        .unwrap();
+        assert_eq!(input.scopes, vec!["read", "org:acme"]);
+    }
+
+    #[test]
+    fn test_new_api_key_validation() {
+        let past = Some(Utc::now() - Duration::days(1));
+        assert!(new_api_key("", "alice", vec!["read".into()], 60, None).is_err());
+        assert!(new_api_key("ci", "alice", vec![], 60, None).is_err());
+        assert!(new_api_key("ci", "alice", vec!["amdin".into()], 60, None).is_err());
+        assert!(new_api_key("ci", "alice", vec!["org:".into()], 60, None).is_err());
+        assert!(new_api_key("ci", "alice", vec![" ".into()], 60, None).is_err());
+        assert!(new_api_key("ci", "alice", vec!["read".into()], 0, None).is_err());
+        assert!(new_api_key("ci", "alice", vec!["read".into()], 60, past).is_err());
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides database models, queries, and migrations for caching
 //! GitHub data and storing user configuration.
 
+pub mod keys;
 pub mod models;
 pub mod queries;
 
@@ -27,6 +28,9 @@ pub enum DbError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 }
 
 /// Database configuration
@@ -123,5 +127,6 @@ pub async fn check_health(pool: &DbPool) -> Result<(), DbError> {
 }
 
 /// Re-export commonly used types
+pub use keys::{generate_api_key, hash_api_key};
 pub use models::*;
 pub use queries::RepositoryStats;
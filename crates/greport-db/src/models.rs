@@ -50,6 +50,7 @@ pub struct IssueRow {
     pub milestone_id: Option<i64>,
     pub author_login: String,
     pub author_id: i64,
+    pub author_is_bot: bool,
     pub comments_count: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -88,6 +89,7 @@ pub struct PullRequestRow {
     pub milestone_id: Option<i64>,
     pub author_login: String,
     pub author_id: i64,
+    pub author_is_bot: bool,
     pub head_ref: String,
     pub base_ref: String,
     pub merged: bool,
@@ -113,6 +115,7 @@ pub struct ReleaseRow {
     pub prerelease: bool,
     pub author_login: String,
     pub author_id: i64,
+    pub author_is_bot: bool,
     pub created_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
     pub synced_at: DateTime<Utc>,
@@ -143,6 +146,8 @@ pub struct SyncStatusRow {
     pub milestones_synced_at: Option<DateTime<Utc>>,
     pub last_error: Option<String>,
     pub last_error_at: Option<DateTime<Utc>>,
+    /// Global sync generation at the last sync
+    pub generation: i64,
 }
 
 /// Cache metadata record
@@ -153,6 +158,19 @@ pub struct CacheMetadataRow {
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
     pub hit_count: i32,
+    /// Entity tag of a cached response
+    pub etag: Option<String>,
+    /// Cached response body
+    pub body: Option<String>,
+}
+
+/// Request counts for a client's current and previous rate limit windows
+#[derive(Debug, Clone, FromRow)]
+pub struct RateLimitWindow {
+    pub window_start: DateTime<Utc>,
+    pub current_count: i32,
+    pub previous_count: i32,
+    pub elapsed_seconds: f64,
 }
 
 /// Input for creating/updating a repository
@@ -198,6 +216,7 @@ pub struct IssueInput {
     pub milestone_id: Option<i64>,
     pub author_login: String,
     pub author_id: i64,
+    pub author_is_bot: bool,
     pub comments_count: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -218,6 +237,7 @@ pub struct PullRequestInput {
     pub milestone_id: Option<i64>,
     pub author_login: String,
     pub author_id: i64,
+    pub author_is_bot: bool,
     pub head_ref: String,
     pub base_ref: String,
     pub merged: bool,
@@ -242,10 +262,39 @@ pub struct ReleaseInput {
     pub prerelease: bool,
     pub author_login: String,
     pub author_id: i64,
+    pub author_is_bot: bool,
     pub created_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
 }
 
+/// Filters for [`search_issues`](crate::queries::search_issues)
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilter {
+    /// `open` or `closed`
+    pub state: Option<String>,
+    pub milestone_id: Option<i64>,
+    /// Label names the issue must all carry (case-insensitive)
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    pub author: Option<String>,
+    /// Only issues updated at or after this time
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+/// Filters for [`search_pull_requests`](crate::queries::search_pull_requests)
+#[derive(Debug, Clone, Default)]
+pub struct PullRequestFilter {
+    /// `open` or `closed`
+    pub state: Option<String>,
+    pub merged: Option<bool>,
+    pub draft: Option<bool>,
+    pub author: Option<String>,
+    pub base_ref: Option<String>,
+    pub milestone_id: Option<i64>,
+    /// Only pull requests updated at or after this time
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
 /// Organization record
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct OrganizationRow {
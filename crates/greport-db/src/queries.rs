@@ -3,6 +3,7 @@
 use crate::models::*;
 use crate::DbPool;
 use chrono::Utc;
+use sqlx::{Postgres, QueryBuilder};
 use uuid::Uuid;
 
 // =============================================================================
@@ -229,8 +230,8 @@ pub async fn upsert_issue(pool: &DbPool, input: &IssueInput) -> sqlx::Result<()>
         r#"
         INSERT INTO issues (id, repository_id, number, title, body, state, milestone_id,
                            author_login, author_id, comments_count, created_at, updated_at,
-                           closed_at, closed_by_login, synced_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, NOW())
+                           closed_at, closed_by_login, author_is_bot, synced_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, NOW())
         ON CONFLICT (repository_id, number) DO UPDATE SET
             title = EXCLUDED.title,
             body = EXCLUDED.body,
@@ -240,6 +241,7 @@ pub async fn upsert_issue(pool: &DbPool, input: &IssueInput) -> sqlx::Result<()>
             updated_at = EXCLUDED.updated_at,
             closed_at = EXCLUDED.closed_at,
             closed_by_login = EXCLUDED.closed_by_login,
+            author_is_bot = EXCLUDED.author_is_bot,
             synced_at = NOW()
         "#,
     )
@@ -257,6 +259,7 @@ pub async fn upsert_issue(pool: &DbPool, input: &IssueInput) -> sqlx::Result<()>
     .bind(input.updated_at)
     .bind(input.closed_at)
     .bind(&input.closed_by_login)
+    .bind(input.author_is_bot)
     .execute(pool)
     .await?;
 
@@ -352,6 +355,79 @@ pub async fn count_issues_by_state(
     Ok(rows)
 }
 
+/// Append the `WHERE` clause selecting a repository's issues matching `filter`
+fn push_issue_filter(
+    query: &mut QueryBuilder<'_, Postgres>,
+    repository_id: i64,
+    filter: &IssueFilter,
+) {
+    query
+        .push(" WHERE i.repository_id = ")
+        .push_bind(repository_id);
+    if let Some(state) = &filter.state {
+        query.push(" AND i.state = ").push_bind(state.clone());
+    }
+    if let Some(milestone_id) = filter.milestone_id {
+        query.push(" AND i.milestone_id = ").push_bind(milestone_id);
+    }
+    for label in &filter.labels {
+        query
+            .push(
+                " AND EXISTS (SELECT 1 FROM issue_labels l \
+                 WHERE l.issue_id = i.id AND LOWER(l.label_name) = LOWER(",
+            )
+            .push_bind(label.clone())
+            .push("))");
+    }
+    if let Some(assignee) = &filter.assignee {
+        query
+            .push(
+                " AND EXISTS (SELECT 1 FROM issue_assignees a \
+                 WHERE a.issue_id = i.id AND a.user_login = ",
+            )
+            .push_bind(assignee.clone())
+            .push(")");
+    }
+    if let Some(author) = &filter.author {
+        query
+            .push(" AND i.author_login = ")
+            .push_bind(author.clone());
+    }
+    if let Some(since) = filter.updated_since {
+        query.push(" AND i.updated_at >= ").push_bind(since);
+    }
+}
+
+/// List a page of a repository's issues matching `filter`, newest first
+pub async fn search_issues(
+    pool: &DbPool,
+    repository_id: i64,
+    filter: &IssueFilter,
+    limit: i64,
+    offset: i64,
+) -> sqlx::Result<Vec<IssueRow>> {
+    let mut query = QueryBuilder::new("SELECT i.* FROM issues i");
+    push_issue_filter(&mut query, repository_id, filter);
+    query
+        .push(" ORDER BY i.created_at DESC, i.number DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    query.build_query_as().fetch_all(pool).await
+}
+
+/// Count a repository's issues matching `filter`
+pub async fn count_issues(
+    pool: &DbPool,
+    repository_id: i64,
+    filter: &IssueFilter,
+) -> sqlx::Result<i64> {
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM issues i");
+    push_issue_filter(&mut query, repository_id, filter);
+    let (count,): (i64,) = query.build_query_as().fetch_one(pool).await?;
+    Ok(count)
+}
+
 // =============================================================================
 // Pull request queries
 // =============================================================================
@@ -419,8 +495,8 @@ pub async fn upsert_pull_request(pool: &DbPool, input: &PullRequestInput) -> sql
         INSERT INTO pull_requests (id, repository_id, number, title, body, state, draft,
                                    milestone_id, author_login, author_id, head_ref, base_ref,
                                    merged, merged_at, additions, deletions, changed_files,
-                                   created_at, updated_at, closed_at, synced_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, NOW())
+                                   created_at, updated_at, closed_at, author_is_bot, synced_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, NOW())
         ON CONFLICT (repository_id, number) DO UPDATE SET
             title = EXCLUDED.title,
             body = EXCLUDED.body,
@@ -436,6 +512,7 @@ pub async fn upsert_pull_request(pool: &DbPool, input: &PullRequestInput) -> sql
             changed_files = EXCLUDED.changed_files,
             updated_at = EXCLUDED.updated_at,
             closed_at = EXCLUDED.closed_at,
+            author_is_bot = EXCLUDED.author_is_bot,
             synced_at = NOW()
         "#,
     )
@@ -459,12 +536,76 @@ pub async fn upsert_pull_request(pool: &DbPool, input: &PullRequestInput) -> sql
     .bind(input.created_at)
     .bind(input.updated_at)
     .bind(input.closed_at)
+    .bind(input.author_is_bot)
     .execute(pool)
     .await?;
 
     Ok(())
 }
 
+/// Append the `WHERE` clause selecting a repository's pull requests matching
+/// `filter`
+fn push_pull_request_filter(
+    query: &mut QueryBuilder<'_, Postgres>,
+    repository_id: i64,
+    filter: &PullRequestFilter,
+) {
+    query
+        .push(" WHERE repository_id = ")
+        .push_bind(repository_id);
+    if let Some(state) = &filter.state {
+        query.push(" AND state = ").push_bind(state.clone());
+    }
+    if let Some(merged) = filter.merged {
+        query.push(" AND merged = ").push_bind(merged);
+    }
+    if let Some(draft) = filter.draft {
+        query.push(" AND draft = ").push_bind(draft);
+    }
+    if let Some(author) = &filter.author {
+        query.push(" AND author_login = ").push_bind(author.clone());
+    }
+    if let Some(base_ref) = &filter.base_ref {
+        query.push(" AND base_ref = ").push_bind(base_ref.clone());
+    }
+    if let Some(milestone_id) = filter.milestone_id {
+        query.push(" AND milestone_id = ").push_bind(milestone_id);
+    }
+    if let Some(since) = filter.updated_since {
+        query.push(" AND updated_at >= ").push_bind(since);
+    }
+}
+
+/// List a page of a repository's pull requests matching `filter`, newest first
+pub async fn search_pull_requests(
+    pool: &DbPool,
+    repository_id: i64,
+    filter: &PullRequestFilter,
+    limit: i64,
+    offset: i64,
+) -> sqlx::Result<Vec<PullRequestRow>> {
+    let mut query = QueryBuilder::new("SELECT * FROM pull_requests");
+    push_pull_request_filter(&mut query, repository_id, filter);
+    query
+        .push(" ORDER BY created_at DESC, number DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    query.build_query_as().fetch_all(pool).await
+}
+
+/// Count a repository's pull requests matching `filter`
+pub async fn count_pull_requests(
+    pool: &DbPool,
+    repository_id: i64,
+    filter: &PullRequestFilter,
+) -> sqlx::Result<i64> {
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM pull_requests");
+    push_pull_request_filter(&mut query, repository_id, filter);
+    let (count,): (i64,) = query.build_query_as().fetch_one(pool).await?;
+    Ok(count)
+}
+
 // =============================================================================
 // Release queries
 // =============================================================================
@@ -514,19 +655,62 @@ pub async fn list_releases(
         .await
 }
 
+/// List a page of a repository's releases, newest first, optionally
+/// excluding prereleases
+pub async fn search_releases(
+    pool: &DbPool,
+    repository_id: i64,
+    include_prereleases: bool,
+    limit: i64,
+    offset: i64,
+) -> sqlx::Result<Vec<ReleaseRow>> {
+    sqlx::query_as::<_, ReleaseRow>(
+        r#"
+        SELECT * FROM releases
+        WHERE repository_id = $1 AND ($2 OR prerelease = FALSE)
+        ORDER BY created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(repository_id)
+    .bind(include_prereleases)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Count a repository's releases, optionally excluding prereleases
+pub async fn count_releases(
+    pool: &DbPool,
+    repository_id: i64,
+    include_prereleases: bool,
+) -> sqlx::Result<i64> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM releases WHERE repository_id = $1 AND ($2 OR prerelease = FALSE)",
+    )
+    .bind(repository_id)
+    .bind(include_prereleases)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
 /// Upsert release
 pub async fn upsert_release(pool: &DbPool, input: &ReleaseInput) -> sqlx::Result<()> {
     sqlx::query(
         r#"
         INSERT INTO releases (id, repository_id, tag_name, name, body, draft, prerelease,
-                              author_login, author_id, created_at, published_at, synced_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+                              author_login, author_id, created_at, published_at,
+                              author_is_bot, synced_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW())
         ON CONFLICT (repository_id, tag_name) DO UPDATE SET
             name = EXCLUDED.name,
             body = EXCLUDED.body,
             draft = EXCLUDED.draft,
             prerelease = EXCLUDED.prerelease,
             published_at = EXCLUDED.published_at,
+            author_is_bot = EXCLUDED.author_is_bot,
             synced_at = NOW()
         "#,
     )
@@ -541,6 +725,7 @@ pub async fn upsert_release(pool: &DbPool, input: &ReleaseInput) -> sqlx::Result
     .bind(input.author_id)
     .bind(input.created_at)
     .bind(input.published_at)
+    .bind(input.author_is_bot)
     .execute(pool)
     .await?;
 
@@ -576,6 +761,31 @@ pub async fn list_api_keys(pool: &DbPool, owner: &str) -> sqlx::Result<Vec<ApiKe
     .await
 }
 
+/// List API keys for all owners, optionally including revoked keys
+pub async fn list_all_api_keys(
+    pool: &DbPool,
+    include_revoked: bool,
+) -> sqlx::Result<Vec<ApiKeyRow>> {
+    sqlx::query_as::<_, ApiKeyRow>(
+        r#"
+        SELECT * FROM api_keys
+        WHERE ($1 OR revoked = FALSE)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(include_revoked)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get API key by ID
+pub async fn get_api_key(pool: &DbPool, id: Uuid) -> sqlx::Result<Option<ApiKeyRow>> {
+    sqlx::query_as::<_, ApiKeyRow>("SELECT * FROM api_keys WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
 /// Create API key
 pub async fn create_api_key(pool: &DbPool, input: &ApiKeyInput) -> sqlx::Result<ApiKeyRow> {
     sqlx::query_as::<_, ApiKeyRow>(
@@ -664,6 +874,53 @@ pub async fn upsert_sync_status(
     Ok(())
 }
 
+/// Advance the global sync generation, returning the new value.
+///
+/// When `repository_id` is given the repository's sync status records the
+/// new generation, which changes the cache keys of its responses.
+pub async fn bump_sync_generation(pool: &DbPool, repository_id: Option<i64>) -> sqlx::Result<i64> {
+    let (generation,): (i64,) = sqlx::query_as(
+        "UPDATE sync_generation SET generation = generation + 1 RETURNING generation",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if let Some(repository_id) = repository_id {
+        sqlx::query("UPDATE sync_status SET generation = $2 WHERE repository_id = $1")
+            .bind(repository_id)
+            .bind(generation)
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(generation)
+}
+
+/// Get the sync generation for a repository (by full name), or the global
+/// generation when no repository is given
+pub async fn get_sync_generation(pool: &DbPool, full_name: Option<&str>) -> sqlx::Result<i64> {
+    let row: Option<(i64,)> = match full_name {
+        Some(full_name) => {
+            sqlx::query_as(
+                r#"
+                SELECT s.generation FROM sync_status s
+                JOIN repositories r ON r.id = s.repository_id
+                WHERE r.full_name = $1
+                "#,
+            )
+            .bind(full_name)
+            .fetch_optional(pool)
+            .await?
+        }
+        None => {
+            sqlx::query_as("SELECT generation FROM sync_generation")
+                .fetch_optional(pool)
+                .await?
+        }
+    };
+    Ok(row.map(|(g,)| g).unwrap_or(0))
+}
+
 /// Record sync error
 pub async fn record_sync_error(pool: &DbPool, repository_id: i64, error: &str) -> sqlx::Result<()> {
     sqlx::query(
@@ -734,6 +991,39 @@ pub async fn set_cache_entry(
     Ok(())
 }
 
+/// Store a response body in the cache
+pub async fn set_cached_response(
+    pool: &DbPool,
+    key: &str,
+    data_type: &str,
+    ttl_seconds: i64,
+    etag: &str,
+    body: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO cache_metadata (key, data_type, expires_at, etag, body)
+        VALUES ($1, $2, NOW() + make_interval(secs => $3), $4, $5)
+        ON CONFLICT (key) DO UPDATE SET
+            data_type = EXCLUDED.data_type,
+            expires_at = EXCLUDED.expires_at,
+            etag = EXCLUDED.etag,
+            body = EXCLUDED.body,
+            hit_count = 0,
+            created_at = NOW()
+        "#,
+    )
+    .bind(key)
+    .bind(data_type)
+    .bind(ttl_seconds as f64)
+    .bind(etag)
+    .bind(body)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 /// Delete expired cache entries
 pub async fn cleanup_expired_cache(pool: &DbPool) -> sqlx::Result<u64> {
     let result = sqlx::query("DELETE FROM cache_metadata WHERE expires_at < NOW()")
@@ -752,6 +1042,74 @@ pub async fn is_cache_valid(pool: &DbPool, key: &str) -> sqlx::Result<bool> {
     Ok(count.0 > 0)
 }
 
+// =============================================================================
+// Rate limit queries
+// =============================================================================
+
+/// Count a request against a client's current fixed window.
+///
+/// Returns the window's count (including this request), the previous
+/// window's count, the seconds elapsed in the current window and its start.
+pub async fn rate_limit_hit(
+    pool: &DbPool,
+    client_id: &str,
+    window_seconds: u64,
+) -> sqlx::Result<RateLimitWindow> {
+    sqlx::query_as::<_, RateLimitWindow>(
+        r#"
+        WITH bucket AS (
+            INSERT INTO rate_limit_counters (client_id, window_start, count)
+            VALUES ($1, to_timestamp(floor(extract(epoch FROM NOW()) / $2) * $2), 1)
+            ON CONFLICT (client_id, window_start)
+                DO UPDATE SET count = rate_limit_counters.count + 1
+            RETURNING window_start, count
+        )
+        SELECT bucket.window_start,
+               bucket.count AS current_count,
+               COALESCE((SELECT p.count FROM rate_limit_counters p
+                         WHERE p.client_id = $1
+                           AND p.window_start = bucket.window_start - make_interval(secs => $2)),
+                        0) AS previous_count,
+               EXTRACT(EPOCH FROM (NOW() - bucket.window_start))::float8 AS elapsed_seconds
+        FROM bucket
+        "#,
+    )
+    .bind(client_id)
+    .bind(window_seconds as f64)
+    .fetch_one(pool)
+    .await
+}
+
+/// Undo a counted request (used when the request was rejected)
+pub async fn rate_limit_release(
+    pool: &DbPool,
+    client_id: &str,
+    window_start: chrono::DateTime<Utc>,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE rate_limit_counters SET count = GREATEST(count - 1, 0)
+        WHERE client_id = $1 AND window_start = $2
+        "#,
+    )
+    .bind(client_id)
+    .bind(window_start)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Delete counters older than two windows
+pub async fn cleanup_rate_limit_counters(pool: &DbPool, window_seconds: u64) -> sqlx::Result<u64> {
+    let result = sqlx::query(
+        "DELETE FROM rate_limit_counters WHERE window_start < NOW() - make_interval(secs => $1)",
+    )
+    .bind((window_seconds * 2) as f64)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
 // =============================================================================
 // Statistics queries
 // =============================================================================
@@ -1274,6 +1632,29 @@ pub async fn list_project_items(
     q.fetch_all(pool).await
 }
 
+/// Count items for a project with optional filters
+pub async fn count_project_items(
+    pool: &DbPool,
+    project_id: &str,
+    content_type: Option<&str>,
+    content_state: Option<&str>,
+) -> sqlx::Result<i64> {
+    let (count,): (i64,) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FROM project_items
+        WHERE project_id = $1
+          AND ($2::TEXT IS NULL OR content_type = $2)
+          AND ($3::TEXT IS NULL OR content_state = $3)
+        "#,
+    )
+    .bind(project_id)
+    .bind(content_type)
+    .bind(content_state)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
 /// Count project items by status field value
 pub async fn count_project_items_by_status(
     pool: &DbPool,
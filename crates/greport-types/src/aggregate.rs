@@ -0,0 +1,191 @@
+//! Cross-repository aggregate metrics types
+
+use greport_core::models::{Issue, PullRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RepoIssueMetrics {
+    pub repository: String,
+    pub total: usize,
+    pub open: usize,
+    pub closed: usize,
+    pub avg_time_to_close_hours: Option<f64>,
+    pub stale_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IssueMetricsTotals {
+    pub total: usize,
+    pub open: usize,
+    pub closed: usize,
+    pub avg_time_to_close_hours: Option<f64>,
+    pub stale_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AgeBucketResponse {
+    pub label: String,
+    pub min_days: i64,
+    pub max_days: Option<i64>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AggregateIssueMetrics {
+    pub by_repository: Vec<RepoIssueMetrics>,
+    pub totals: IssueMetricsTotals,
+    pub by_label: HashMap<String, usize>,
+    pub by_assignee: HashMap<String, usize>,
+    pub age_distribution: Vec<AgeBucketResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RepoPullMetrics {
+    pub repository: String,
+    pub total: usize,
+    pub open: usize,
+    pub merged: usize,
+    pub avg_time_to_merge_hours: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PullMetricsTotals {
+    pub total: usize,
+    pub open: usize,
+    pub merged: usize,
+    pub avg_time_to_merge_hours: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AggregatePullMetrics {
+    pub by_repository: Vec<RepoPullMetrics>,
+    pub totals: PullMetricsTotals,
+    pub by_size: HashMap<String, usize>,
+    pub by_author: HashMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AggregateContributorStats {
+    pub login: String,
+    pub repositories: Vec<String>,
+    pub total_issues_created: usize,
+    pub total_prs_created: usize,
+    pub total_prs_merged: usize,
+    /// Whether the account is a bot (only listed when bot activity is
+    /// reported separately)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bot: bool,
+    /// Team of the person, from the identities file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    /// Accounts merged into this person
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RepoVelocityEntry {
+    pub repository: String,
+    pub avg_opened: f64,
+    pub avg_closed: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AggregateVelocityMetrics {
+    pub period: String,
+    pub by_repository: Vec<RepoVelocityEntry>,
+    pub combined_avg_opened: f64,
+    pub combined_avg_closed: f64,
+    pub trend: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct IssueMetricsQuery {
+    pub state: Option<String>,
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct PullMetricsQuery {
+    pub state: Option<String>,
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct AggregateListQuery {
+    pub state: Option<String>,
+    pub days: Option<i64>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AggregateIssueItem {
+    pub repository: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AggregatePullItem {
+    pub repository: String,
+    #[serde(flatten)]
+    pub pull: PullRequest,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct VelocityQuery {
+    pub period: Option<String>,
+    pub last: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrgAggregateIssueItem {
+    pub organization: String,
+    pub repository: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrgAggregatePullItem {
+    pub organization: String,
+    pub repository: String,
+    #[serde(flatten)]
+    pub pull: PullRequest,
+}
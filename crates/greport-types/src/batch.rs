@@ -0,0 +1,44 @@
+//! Batch sync types
+
+use serde::{Deserialize, Serialize};
+
+/// Result of syncing a single repository within a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RepoSyncResult {
+    pub repository: String,
+    pub success: bool,
+    pub issues_synced: Option<usize>,
+    pub pulls_synced: Option<usize>,
+    pub releases_synced: Option<usize>,
+    pub milestones_synced: Option<usize>,
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Result of syncing projects for one organization within a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrgProjectSyncResult {
+    pub organization: String,
+    pub success: bool,
+    pub projects_synced: Option<usize>,
+    pub items_synced: Option<usize>,
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Result of a batch sync operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchSyncResult {
+    pub results: Vec<RepoSyncResult>,
+    pub total_repos: usize,
+    pub successful: usize,
+    pub failed: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub project_results: Vec<OrgProjectSyncResult>,
+    pub synced_at: String,
+}
@@ -0,0 +1,15 @@
+//! Calendar types
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct CalendarQuery {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub types: Option<String>,
+}
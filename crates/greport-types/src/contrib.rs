@@ -0,0 +1,35 @@
+//! Contributor statistics types
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ContributorsQuery {
+    pub sort_by: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ContributorStats {
+    /// Login, or the person's name when the login is mapped in the
+    /// identities file
+    pub login: String,
+    pub issues_created: usize,
+    pub prs_created: usize,
+    pub prs_merged: usize,
+    /// Whether the account is a bot (only listed when bot activity is
+    /// reported separately)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bot: bool,
+    /// Team of the person, from the identities file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    /// Accounts merged into this person
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logins: Vec<String>,
+}
@@ -0,0 +1,18 @@
+//! API error response body
+
+use serde::{Deserialize, Serialize};
+
+/// Error response body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorBody {
+    /// Machine-readable error code (e.g. `NOT_FOUND`)
+    pub code: String,
+    pub message: String,
+}
@@ -0,0 +1,72 @@
+//! Issue route query parameters
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ListIssuesQuery {
+    pub state: Option<String>,
+    pub labels: Option<String>,
+    pub assignee: Option<String>,
+    pub milestone: Option<String>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct MetricsQuery {
+    pub state: Option<String>,
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct VelocityQuery {
+    pub period: Option<String>,
+    pub last: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct BurndownQuery {
+    pub milestone: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct StaleQuery {
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct EpicsQuery {
+    pub stale_days: Option<i64>,
+    pub all: Option<bool>,
+}
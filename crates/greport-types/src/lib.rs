@@ -0,0 +1,30 @@
+//! greport-types - Request and response types of the greport REST API
+//!
+//! The server (`greport-api`) and the client (`greport-client`) share these
+//! types, so a client does not have to link the server. Modules are grouped
+//! like the server's route modules. The `openapi` feature derives the
+//! `utoipa` schemas and query parameters the server documents.
+
+mod error;
+mod response;
+mod sync;
+
+pub mod admin;
+pub mod aggregate;
+pub mod batch;
+pub mod calendar;
+pub mod contrib;
+pub mod health;
+pub mod issues;
+pub mod login;
+pub mod orgs;
+pub mod projects;
+pub mod pulls;
+pub mod release_plan;
+pub mod releases;
+pub mod repos;
+pub mod sla;
+
+pub use error::{ErrorBody, ErrorResponse};
+pub use response::{ApiResponse, PaginatedResponse, PaginationMeta};
+pub use sync::{ProjectSyncResult, SyncResult};
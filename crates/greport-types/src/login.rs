@@ -0,0 +1,12 @@
+//! Login types
+
+use serde::{Deserialize, Serialize};
+
+/// The authenticated caller
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CurrentUser {
+    pub login: String,
+    pub scopes: Vec<String>,
+    pub orgs: Vec<String>,
+}
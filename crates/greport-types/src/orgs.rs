@@ -0,0 +1,28 @@
+//! Organization types
+
+use serde::{Deserialize, Serialize};
+
+/// Summary of an organization (no tokens exposed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrgSummary {
+    pub name: String,
+    pub web_url: String,
+    pub repo_count: usize,
+}
+
+/// Response for the orgs list endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrgsListResponse {
+    pub orgs: Vec<OrgSummary>,
+    pub default_web_url: String,
+}
+
+/// Repository entry within an organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrgRepoEntry {
+    pub name: String,
+    pub full_name: String,
+}
@@ -0,0 +1,115 @@
+//! GitHub Projects V2 types
+
+use chrono::{DateTime, Utc};
+use greport_core::metrics::ProjectMetrics;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectSummary {
+    pub number: i64,
+    pub owner: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub closed: bool,
+    pub total_items: i32,
+    pub synced_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectDetail {
+    pub number: i64,
+    pub owner: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub closed: bool,
+    pub total_items: i32,
+    pub synced_at: DateTime<Utc>,
+    pub fields: Vec<ProjectFieldSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectFieldSummary {
+    pub name: String,
+    pub field_type: String,
+    pub config_json: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectItemResponse {
+    pub node_id: String,
+    pub content_type: String,
+    pub content_number: Option<i64>,
+    pub content_title: String,
+    pub content_state: Option<String>,
+    pub content_url: Option<String>,
+    pub content_repository: Option<String>,
+    pub field_values: Option<Value>,
+}
+
+/// Board metrics of a project, alongside which project they describe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectMetricsResponse {
+    pub project_number: i64,
+    pub project_title: String,
+    #[serde(flatten)]
+    pub metrics: ProjectMetrics,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ListProjectsQuery {
+    pub include_closed: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ProjectMetricsQuery {
+    /// Single-select field for the status breakdown (default "Status")
+    pub status_field: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct IterationsQuery {
+    /// Iteration field name (default "Iteration")
+    pub field: Option<String>,
+    /// Numeric field to measure velocity in points
+    pub points_field: Option<String>,
+    /// Single-select status field (default "Status")
+    pub status_field: Option<String>,
+    /// Comma-separated status values that count as done (default "Done")
+    pub done: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ListItemsQuery {
+    pub content_type: Option<String>,
+    pub state: Option<String>,
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
@@ -0,0 +1,14 @@
+//! Release plan query parameters
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct ReleasePlanQuery {
+    pub months_back: Option<u32>,
+    pub months_forward: Option<u32>,
+}
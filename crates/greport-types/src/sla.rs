@@ -0,0 +1,107 @@
+//! SLA (Service Level Agreement) types
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct SlaQuery {
+    /// Custom response time SLA in hours
+    pub response_hours: Option<i64>,
+    /// Custom resolution time SLA in hours
+    pub resolution_hours: Option<i64>,
+    /// Filter by labels (comma-separated)
+    pub labels: Option<String>,
+}
+
+/// SLA compliance report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlaReport {
+    /// Repository identifier
+    pub repository: String,
+    /// SLA configuration used
+    pub config: SlaConfig,
+    /// Summary statistics
+    pub summary: SlaSummary,
+    /// Issues breaching SLA
+    pub breaching_issues: Vec<SlaIssue>,
+    /// Issues at risk of breaching
+    pub at_risk_issues: Vec<SlaIssue>,
+    /// Generated at timestamp
+    pub generated_at: String,
+}
+
+/// SLA configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlaConfig {
+    pub response_time_hours: i64,
+    pub resolution_time_hours: i64,
+}
+
+/// SLA summary statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlaSummary {
+    /// Total open issues
+    pub total_open: usize,
+    /// Issues within SLA
+    pub within_sla: usize,
+    /// Issues breaching response SLA
+    pub response_breached: usize,
+    /// Issues breaching resolution SLA
+    pub resolution_breached: usize,
+    /// Issues at risk (>80% of SLA time elapsed)
+    pub at_risk: usize,
+    /// SLA compliance percentage
+    pub compliance_rate: f64,
+}
+
+/// Issue with SLA status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlaIssue {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    pub created_at: String,
+    pub age_hours: i64,
+    pub sla_status: SlaStatus,
+    pub labels: Vec<String>,
+}
+
+/// SLA status for an issue
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum SlaStatus {
+    /// Within SLA limits
+    Ok,
+    /// At risk (>80% of time elapsed)
+    AtRisk { percent_elapsed: f64 },
+    /// Response time breached
+    ResponseBreached { hours_overdue: i64 },
+    /// Resolution time breached
+    ResolutionBreached { hours_overdue: i64 },
+}
+
+impl Clone for SlaStatus {
+    fn clone(&self) -> Self {
+        match self {
+            SlaStatus::Ok => SlaStatus::Ok,
+            SlaStatus::AtRisk { percent_elapsed } => SlaStatus::AtRisk {
+                percent_elapsed: *percent_elapsed,
+            },
+            SlaStatus::ResponseBreached { hours_overdue } => SlaStatus::ResponseBreached {
+                hours_overdue: *hours_overdue,
+            },
+            SlaStatus::ResolutionBreached { hours_overdue } => SlaStatus::ResolutionBreached {
+                hours_overdue: *hours_overdue,
+            },
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Sync results
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Result of a sync operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SyncResult {
+    pub repository: String,
+    pub issues_synced: usize,
+    pub pulls_synced: usize,
+    pub releases_synced: usize,
+    pub milestones_synced: usize,
+    pub synced_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Result of a project sync operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectSyncResult {
+    pub organization: String,
+    pub projects_synced: usize,
+    pub items_synced: usize,
+    pub synced_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
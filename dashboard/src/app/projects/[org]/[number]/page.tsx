@@ -177,7 +177,7 @@ export default function ProjectDetailPage() {
         <div className="grid grid-cols-1 gap-4 sm:grid-cols-2 lg:grid-cols-4">
           <MetricCard title="Total Items" value={metrics.total_items} />
           {metrics.by_status.slice(0, 3).map((s) => (
-            <MetricCard key={s.status} title={s.status || "No Status"} value={s.count} />
+            <MetricCard key={s.value} title={s.value} value={s.count} />
           ))}
         </div>
       )}
@@ -274,7 +274,7 @@ export default function ProjectDetailPage() {
                 Status Distribution
               </h3>
               <PieChartComponent
-                data={metrics.by_status.map((s) => ({ name: s.status || "No Status", value: s.count }))}
+                data={metrics.by_status.map((s) => ({ name: s.value, value: s.count }))}
               />
             </div>
           )}
@@ -284,7 +284,7 @@ export default function ProjectDetailPage() {
                 Content Type Distribution
               </h3>
               <BarChartComponent
-                data={metrics.by_content_type.map((c) => ({ name: c.content_type, value: c.count }))}
+                data={metrics.by_content_type.map((c) => ({ name: c.value, value: c.count }))}
                 layout="horizontal"
                 color="#8b5cf6"
               />
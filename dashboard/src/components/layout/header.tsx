@@ -1,10 +1,20 @@
 "use client";
 
 import { useState } from "react";
-import { useSWRConfig } from "swr";
+import useSWR, { useSWRConfig } from "swr";
 import { useRepo } from "@/hooks/use-repo";
 import { useRepos } from "@/hooks/use-repos";
-import { syncRepo, batchSync } from "@/lib/api";
+import {
+  syncRepo,
+  batchSync,
+  fetcher,
+  currentUserUrl,
+  loginUrl,
+  logout,
+  ApiError,
+} from "@/lib/api";
+import type { CurrentUser } from "@/lib/api";
+import type { ApiResponse } from "@/types/api";
 
 export function Header() {
   const { owner, repo, mode, setRepo, setMode } = useRepo();
@@ -85,12 +95,58 @@ export function Header() {
             <RefreshIcon className={`h-4 w-4 ${syncing ? "animate-spin" : ""}`} />
             {syncing ? "Syncing..." : "Refresh"}
           </button>
+
+          <UserMenu />
         </div>
       </div>
     </header>
   );
 }
 
+function UserMenu() {
+  const { data, error, mutate } = useSWR<ApiResponse<CurrentUser>>(
+    currentUserUrl(),
+    fetcher,
+    { revalidateOnFocus: false, shouldRetryOnError: false },
+  );
+
+  async function handleLogout() {
+    try {
+      await logout();
+    } finally {
+      await mutate(undefined, { revalidate: true });
+    }
+  }
+
+  if (data) {
+    return (
+      <div className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+        <span className="font-medium">{data.data.login}</span>
+        <button
+          onClick={handleLogout}
+          className="rounded-md px-2 py-1 text-gray-500 hover:bg-gray-100 hover:text-gray-700 dark:hover:bg-gray-800 dark:hover:text-gray-200"
+        >
+          Sign out
+        </button>
+      </div>
+    );
+  }
+
+  // 404 means login is not configured on the API server
+  if (error instanceof ApiError && error.status === 401) {
+    return (
+      <a
+        href={loginUrl()}
+        className="rounded-md bg-blue-600 px-3 py-1.5 text-sm font-medium text-white shadow-sm hover:bg-blue-500"
+      >
+        Sign in
+      </a>
+    );
+  }
+
+  return null;
+}
+
 function MobileMenuButton() {
   return (
     <button
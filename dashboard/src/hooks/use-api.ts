@@ -62,6 +62,7 @@ const API_BASE = process.env.NEXT_PUBLIC_API_URL || "http://localhost:9423";
 
 async function directFetcher<T>(url: string): Promise<T> {
   const res = await fetch(url, {
+    credentials: "include",
     headers: { "Content-Type": "application/json" },
   });
   if (!res.ok) {
@@ -21,8 +21,7 @@ import type {
   ProjectItemResponse,
   ProjectMetrics,
   ProjectFieldSummary,
-  StatusCount,
-  ContentTypeCount,
+  FieldCount,
 } from "@/types/api";
 
 const API_BASE = process.env.NEXT_PUBLIC_API_URL || "http://localhost:9423";
@@ -41,6 +40,7 @@ export class ApiError extends Error {
 async function apiFetch<T>(path: string, init?: RequestInit): Promise<T> {
   const url = `${API_BASE}${path}`;
   const res = await fetch(url, {
+    credentials: "include",
     ...init,
     headers: {
       "Content-Type": "application/json",
@@ -202,6 +202,27 @@ export async function syncRepo(
   });
 }
 
+// Login session
+export interface CurrentUser {
+  login: string;
+  scopes: string[];
+  orgs: string[];
+}
+
+export function currentUserUrl(): string {
+  return "/api/v1/auth/me";
+}
+
+export function loginUrl(): string {
+  return `${API_BASE}/api/v1/auth/login`;
+}
+
+export async function logout(): Promise<void> {
+  await apiFetch<ApiResponse<{ logged_out: boolean }>>("/api/v1/auth/logout", {
+    method: "POST",
+  });
+}
+
 // Repository management
 export function reposUrl(): string {
   return "/api/v1/repos";
@@ -362,6 +383,5 @@ export type {
   ProjectItemResponse,
   ProjectMetrics,
   ProjectFieldSummary,
-  StatusCount,
-  ContentTypeCount,
+  FieldCount,
 };
@@ -481,13 +481,8 @@ export interface ProjectItemResponse {
   field_values?: unknown;
 }
 
-export interface StatusCount {
-  status: string;
-  count: number;
-}
-
-export interface ContentTypeCount {
-  content_type: string;
+export interface FieldCount {
+  value: string;
   count: number;
 }
 
@@ -495,8 +490,11 @@ export interface ProjectMetrics {
   project_number: number;
   project_title: string;
   total_items: number;
-  by_status: StatusCount[];
-  by_content_type: ContentTypeCount[];
+  by_status: FieldCount[];
+  by_content_type: FieldCount[];
+  by_assignee: FieldCount[];
+  by_iteration: FieldCount[];
+  closed_items: number;
 }
 
 // API Response wrappers